@@ -0,0 +1,332 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use anyhow::anyhow;
+use common_ast::ast::format_statement;
+use common_ast::ast::format_statement_tree;
+use common_ast::ast::pretty_statement;
+use common_ast::ast::AstFormatContext;
+use common_ast::ast::FormatTreeNode;
+use common_ast::ast::InsertSource;
+use common_ast::ast::InsertStmt;
+use common_ast::ast::MergeIntoStmt;
+use common_ast::ast::MergeSource;
+use common_ast::ast::ReplaceStmt;
+use common_ast::ast::Statement;
+use common_ast::ast::UpdateStmt;
+use common_ast::parser::parse_sql_with_diagnostic;
+use common_ast::parser::split_statements;
+use common_ast::parser::token::TokenKind;
+use common_ast::parser::token::Tokenizer;
+use common_ast::parser::ScriptStatement;
+use common_ast::line_column;
+use common_ast::Diagnostic;
+use common_ast::ParseOptions;
+use serde_json::json;
+use serde_json::Value;
+
+use crate::input::Script;
+
+pub struct Context {
+    pub options: ParseOptions,
+    /// Whether to color the diagnostics with ANSI escape codes.
+    pub ansi: bool,
+}
+
+/// A statement of a script parsed successfully.
+struct Parsed {
+    statement: ScriptStatement,
+    stmt: Statement,
+    format: Option<String>,
+}
+
+/// Parse the statements of the script, or print the diagnostics to stderr if any
+/// of them fails.
+fn parse_script(ctx: &Context, script: &Script) -> Option<Vec<Parsed>> {
+    let mut parsed = vec![];
+    let mut ok = true;
+    for diag in parse_statements(ctx, script, &mut parsed) {
+        let (line, column) = line_column(&script.text, diag.span.start);
+        eprintln!("{}:{}:{}: {}", script.name(), line, column, diag.code);
+        if ctx.ansi {
+            eprintln!("{}", diag.render_ansi(&script.text));
+        } else {
+            eprintln!("{}", diag.render_text(&script.text));
+        }
+        ok = false;
+    }
+    ok.then_some(parsed)
+}
+
+/// Parse the statements of the script into `parsed`, returning the diagnostics with
+/// the spans relative to the script.
+fn parse_statements(ctx: &Context, script: &Script, parsed: &mut Vec<Parsed>) -> Vec<Diagnostic> {
+    let mut diags = vec![];
+    for statement in split_statements(&script.text) {
+        match statement.parse(&script.text, &ctx.options) {
+            Ok((stmt, format)) => parsed.push(Parsed {
+                statement,
+                stmt,
+                format,
+            }),
+            Err(mut diag) => {
+                diag.shift(statement.span.start);
+                diags.push(diag);
+            }
+        }
+    }
+    diags
+}
+
+/// Print a header to tell the scripts apart in the output of multiple scripts.
+fn print_header(out: &mut impl Write, scripts: &[Script], script: &Script) -> anyhow::Result<()> {
+    if scripts.len() > 1 {
+        writeln!(out, "-- {}", script.name())?;
+    }
+    Ok(())
+}
+
+pub fn parse(
+    ctx: &Context,
+    out: &mut impl Write,
+    scripts: &[Script],
+    json: bool,
+) -> anyhow::Result<bool> {
+    if !json {
+        let mut ok = true;
+        for script in scripts {
+            ok &= parse_script(ctx, script).is_some();
+        }
+        return Ok(ok);
+    }
+
+    let mut diags = vec![];
+    for script in scripts {
+        for diag in parse_statements(ctx, script, &mut vec![]) {
+            let mut object = serde_json::Map::new();
+            object.insert("file".to_string(), json!(script.name()));
+            if let Value::Object(fields) = diag.to_json(&script.text) {
+                object.extend(fields);
+            }
+            diags.push(Value::Object(object));
+        }
+    }
+    writeln!(out, "{}", serde_json::to_string_pretty(&diags)?)?;
+    Ok(diags.is_empty())
+}
+
+pub fn lint(ctx: &Context, scripts: &[Script]) -> anyhow::Result<bool> {
+    let mut ok = true;
+    for script in scripts {
+        let Some(parsed) = parse_script(ctx, script) else {
+            ok = false;
+            continue;
+        };
+        for Parsed {
+            statement, stmt, ..
+        } in parsed
+        {
+            if let Some(warning) = lint_statement(&stmt) {
+                let (line, column) = line_column(&script.text, statement.span.start);
+                eprintln!("{}:{}:{}: warning: {}", script.name(), line, column, warning);
+                ok = false;
+            }
+        }
+    }
+    Ok(ok)
+}
+
+/// The warning about the statement if it changes all the rows of a table, which is
+/// rarely intended in a script.
+fn lint_statement(stmt: &Statement) -> Option<&'static str> {
+    match stmt {
+        Statement::Delete {
+            selection: None, ..
+        } => Some("`DELETE` without `WHERE` deletes all the rows"),
+        Statement::Update(UpdateStmt {
+            selection: None, ..
+        }) => Some("`UPDATE` without `WHERE` updates all the rows"),
+        _ => None,
+    }
+}
+
+pub fn fmt(
+    ctx: &Context,
+    out: &mut impl Write,
+    scripts: &[Script],
+    check: bool,
+    max_width: usize,
+) -> anyhow::Result<bool> {
+    let mut ok = true;
+    for script in scripts {
+        let Some(parsed) = parse_script(ctx, script) else {
+            ok = false;
+            continue;
+        };
+        let mut formatted = String::with_capacity(script.text.len());
+        let mut pos = 0;
+        for Parsed {
+            statement,
+            stmt,
+            format,
+        } in parsed
+        {
+            // The pretty printer drops the comments, the `FORMAT` clause and the
+            // data left in the source, like the rows of `INSERT ... VALUES`.
+            if statement.has_comments || format.is_some() || has_inline_data(&stmt) {
+                continue;
+            }
+            let display = stmt.to_string();
+            let pretty = pretty_statement(stmt, max_width).map_err(|err| anyhow!(err.message()))?;
+            // It also drops some syntax, like hints, so the statement is left as it
+            // is unless the pretty one is parsed back to the same statement.
+            if !is_same_statement(ctx, &display, &pretty) {
+                continue;
+            }
+            formatted += &script.text[pos..statement.span.start];
+            formatted += &pretty;
+            pos = statement.span.end;
+        }
+        formatted += &script.text[pos..];
+
+        match &script.path {
+            _ if check => {
+                if formatted != script.text {
+                    eprintln!("{}: not formatted", script.name());
+                    ok = false;
+                }
+            }
+            Some(path) => {
+                if formatted != script.text {
+                    std::fs::write(path, formatted)?;
+                }
+            }
+            None => write!(out, "{}", formatted)?,
+        }
+    }
+    Ok(ok)
+}
+
+/// Whether `sql` is parsed to a statement displayed as `display`.
+fn is_same_statement(ctx: &Context, display: &str, sql: &str) -> bool {
+    parse_sql_with_diagnostic(sql, &ctx.options)
+        .is_ok_and(|(stmt, format)| format.is_none() && stmt.to_string() == display)
+}
+
+/// Whether the data of the statement is left in the source after the statement.
+fn has_inline_data(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Insert(InsertStmt { source, .. })
+        | Statement::Replace(ReplaceStmt { source, .. }) => {
+            !matches!(source, InsertSource::Select { .. })
+        }
+        Statement::MergeInto(MergeIntoStmt { source, .. }) => {
+            matches!(source, MergeSource::StreamingV2 { .. })
+        }
+        _ => false,
+    }
+}
+
+pub fn ast(
+    ctx: &Context,
+    out: &mut impl Write,
+    scripts: &[Script],
+    json: bool,
+) -> anyhow::Result<bool> {
+    let mut ok = true;
+    let mut trees = vec![];
+    for script in scripts {
+        let Some(parsed) = parse_script(ctx, script) else {
+            ok = false;
+            continue;
+        };
+        if !json {
+            print_header(out, scripts, script)?;
+        }
+        for Parsed {
+            statement, stmt, ..
+        } in parsed
+        {
+            if json {
+                trees.push(json!({
+                    "file": script.name(),
+                    "span": {
+                        "start": statement.span.start,
+                        "end": statement.span.end,
+                    },
+                    "ast": tree_to_json(&format_statement_tree(&stmt)),
+                }));
+            } else {
+                let tree = format_statement(stmt).map_err(|err| anyhow!(err.message()))?;
+                writeln!(out, "{}", tree)?;
+            }
+        }
+    }
+    if json {
+        writeln!(out, "{}", serde_json::to_string_pretty(&trees)?)?;
+    }
+    Ok(ok)
+}
+
+fn tree_to_json(node: &FormatTreeNode<AstFormatContext>) -> Value {
+    let mut object = serde_json::Map::new();
+    object.insert("name".to_string(), json!(node.payload.name()));
+    if let Some(alias) = node.payload.alias() {
+        object.insert("alias".to_string(), json!(alias));
+    }
+    if !node.children.is_empty() {
+        let children = node.children.iter().map(tree_to_json).collect();
+        object.insert("children".to_string(), Value::Array(children));
+    }
+    Value::Object(object)
+}
+
+pub fn tokens(out: &mut impl Write, scripts: &[Script]) -> anyhow::Result<bool> {
+    let mut ok = true;
+    for script in scripts {
+        print_header(out, scripts, script)?;
+        for token in Tokenizer::new(&script.text) {
+            match token {
+                Ok(token) if token.kind == TokenKind::EOI => break,
+                Ok(token) => writeln!(out, "{:?} {:?}", token, token.text())?,
+                Err(err) => {
+                    let start = err.span().map_or(0, |span| span.start);
+                    let (line, column) = line_column(&script.text, start);
+                    eprintln!("{}:{}:{}", script.name(), line, column);
+                    eprintln!("{}", err.display_with_sql(&script.text).message());
+                    ok = false;
+                    break;
+                }
+            }
+        }
+    }
+    Ok(ok)
+}
+
+pub fn mask(ctx: &Context, out: &mut impl Write, scripts: &[Script]) -> anyhow::Result<bool> {
+    let mut ok = true;
+    for script in scripts {
+        let Some(parsed) = parse_script(ctx, script) else {
+            ok = false;
+            continue;
+        };
+        print_header(out, scripts, script)?;
+        for Parsed { stmt, .. } in parsed {
+            writeln!(out, "{};", stmt.to_mask_sql())?;
+        }
+    }
+    Ok(ok)
+}
@@ -0,0 +1,91 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+/// A script read from a file or stdin.
+pub struct Script {
+    /// The path of the file, or `None` for stdin.
+    pub path: Option<PathBuf>,
+    pub text: String,
+}
+
+impl Script {
+    /// The name of the script in the messages.
+    pub fn name(&self) -> String {
+        match &self.path {
+            Some(path) => path.display().to_string(),
+            None => "<stdin>".to_string(),
+        }
+    }
+}
+
+/// Read the scripts at the paths, searching the directories for `*.sql` files in
+/// the order of their paths. Read stdin if there is no path or for `-`.
+pub fn read_scripts(paths: &[PathBuf]) -> anyhow::Result<Vec<Script>> {
+    if paths.is_empty() {
+        return Ok(vec![read_stdin()?]);
+    }
+    let mut scripts = vec![];
+    for path in paths {
+        if path.as_os_str() == "-" {
+            scripts.push(read_stdin()?);
+        } else if path.is_dir() {
+            let mut files = vec![];
+            find_sql_files(path, &mut files)?;
+            files.sort();
+            for file in files {
+                scripts.push(read_file(file)?);
+            }
+        } else {
+            scripts.push(read_file(path.clone())?);
+        }
+    }
+    Ok(scripts)
+}
+
+fn read_stdin() -> anyhow::Result<Script> {
+    let mut text = String::new();
+    std::io::stdin()
+        .read_to_string(&mut text)
+        .context("failed to read stdin")?;
+    Ok(Script { path: None, text })
+}
+
+fn read_file(path: PathBuf) -> anyhow::Result<Script> {
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(Script {
+        path: Some(path),
+        text,
+    })
+}
+
+fn find_sql_files(dir: &Path, files: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    let entries = fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_sql_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "sql") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
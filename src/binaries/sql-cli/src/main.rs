@@ -0,0 +1,181 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A command-line tool to check, format and inspect Databend SQL scripts.
+
+#![allow(clippy::uninlined_format_args)]
+
+mod command;
+mod input;
+
+use std::io::ErrorKind;
+use std::io::IsTerminal;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::ColorChoice;
+use clap::Parser;
+use clap::Subcommand;
+use clap::ValueEnum;
+use common_ast::Dialect;
+use common_ast::ParseOptions;
+use common_ast::ParserLimits;
+
+use crate::command::Context;
+use crate::input::read_scripts;
+
+#[derive(Parser)]
+#[command(name = "databend-sql", version, about)]
+struct Cli {
+    /// The SQL dialect of the scripts.
+    #[arg(long, global = true, value_enum, default_value_t = DialectArg::PostgreSQL)]
+    dialect: DialectArg,
+    /// Whether to color the diagnostics.
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check the scripts for syntax errors, exiting with 1 if there are any.
+    Parse {
+        /// Print the diagnostics to stdout as JSON.
+        #[arg(long)]
+        json: bool,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Check the scripts for syntax errors and for statements changing all the rows
+    /// of a table, like `DELETE` without `WHERE`, exiting with 1 if there are any.
+    Lint {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Format the scripts in place with the pretty printer, or print them to stdout
+    /// if read from stdin. The statements with comments, or with syntax the pretty
+    /// printer drops, like hints, are left as they are.
+    Fmt {
+        /// Don't write the scripts, but exit with 1 if any of them is not formatted.
+        #[arg(long)]
+        check: bool,
+        /// The max width of the formatted statements.
+        #[arg(long, default_value_t = 80)]
+        max_width: usize,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Print the AST of the statements.
+    Ast {
+        /// Print the tree of `EXPLAIN AST` as JSON, which has the name of each node
+        /// rather than all of its fields.
+        #[arg(long)]
+        json: bool,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Print the tokens of the scripts.
+    Tokens {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Print the statements with the secrets, like the credentials of stages, masked.
+    Mask {
+        #[command(flatten)]
+        input: InputArgs,
+    },
+}
+
+#[derive(clap::Args)]
+struct InputArgs {
+    /// The script files, or the directories to search for `*.sql` files. Read
+    /// stdin if there is no path or for `-`.
+    paths: Vec<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DialectArg {
+    #[value(name = "postgresql")]
+    PostgreSQL,
+    #[value(name = "mysql")]
+    MySQL,
+    #[value(name = "hive")]
+    Hive,
+}
+
+impl From<DialectArg> for Dialect {
+    fn from(dialect: DialectArg) -> Self {
+        match dialect {
+            DialectArg::PostgreSQL => Dialect::PostgreSQL,
+            DialectArg::MySQL => Dialect::MySQL,
+            DialectArg::Hive => Dialect::Hive,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        // The reader of the output, e.g. `head`, has exited.
+        Err(err)
+            if err
+                .downcast_ref::<std::io::Error>()
+                .is_some_and(|err| err.kind() == ErrorKind::BrokenPipe) =>
+        {
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {:#}", err);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Run the command, returning whether the scripts pass it.
+fn run(cli: Cli) -> anyhow::Result<bool> {
+    let ctx = Context {
+        options: ParseOptions {
+            dialect: cli.dialect.into(),
+            limits: ParserLimits::recommended(),
+            ..Default::default()
+        },
+        ansi: match cli.color {
+            ColorChoice::Auto => std::io::stderr().is_terminal(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        },
+    };
+    let mut out = std::io::stdout().lock();
+    let ok = match cli.command {
+        Command::Parse { json, input } => {
+            command::parse(&ctx, &mut out, &read_scripts(&input.paths)?, json)
+        }
+        Command::Lint { input } => command::lint(&ctx, &read_scripts(&input.paths)?),
+        Command::Fmt {
+            check,
+            max_width,
+            input,
+        } => command::fmt(&ctx, &mut out, &read_scripts(&input.paths)?, check, max_width),
+        Command::Ast { json, input } => {
+            command::ast(&ctx, &mut out, &read_scripts(&input.paths)?, json)
+        }
+        Command::Tokens { input } => command::tokens(&mut out, &read_scripts(&input.paths)?),
+        Command::Mask { input } => command::mask(&ctx, &mut out, &read_scripts(&input.paths)?),
+    }?;
+    out.flush()?;
+    Ok(ok)
+}
@@ -0,0 +1,132 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::process::Stdio;
+
+use goldenfile::Mint;
+
+/// Run the command in `dir` with the stdin, returning the exit code, stdout and
+/// stderr.
+fn run(dir: &Path, args: &[&str], stdin: &str) -> (i32, String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_databend-sql"))
+        .current_dir(dir)
+        .arg("--color=never")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn test_cli() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("cli.txt").unwrap();
+
+    let scripts = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/it/testdata/scripts");
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR")).join("databend-sql");
+    let _ = fs::remove_dir_all(&tmp);
+    fs::create_dir_all(&tmp).unwrap();
+    fs::copy(
+        scripts.join("migrations/002_query.sql"),
+        tmp.join("query.sql"),
+    )
+    .unwrap();
+
+    let cases: Vec<(&Path, &[&str], &str)> = vec![
+        (&scripts, &["parse", "migrations"], ""),
+        (&scripts, &["lint", "broken.sql"], ""),
+        (&scripts, &["lint", "migrations", "cleanup.sql"], ""),
+        (&scripts, &["parse", "--json", "broken.sql"], ""),
+        (&scripts, &["parse"], "SELECT 1;\nSELEC 2;\n"),
+        (&scripts, &["fmt", "--check", "migrations"], ""),
+        (
+            &scripts,
+            &["fmt", "--max-width", "20", "-"],
+            "select a, b from t where a > 1;\n",
+        ),
+        (
+            &scripts,
+            &["fmt", "-"],
+            "insert into t values (1, 'a');\nselect a from t;\n",
+        ),
+        (
+            &scripts,
+            &["fmt", "-"],
+            "select /*+ SET_VAR(timezone='UTC') */ a from t;\nselect array_transform(a, x -> x + 1) from t;\nselect a from t;\n",
+        ),
+        (&tmp, &["fmt", "query.sql"], ""),
+        (&tmp, &["fmt", "--check", "query.sql"], ""),
+        (&scripts, &["fmt", "broken.sql"], ""),
+        (&scripts, &["ast"], "SELECT \"a\" FROM t"),
+        (
+            &scripts,
+            &["ast", "--dialect", "mysql"],
+            "SELECT \"a\" FROM t",
+        ),
+        (&scripts, &["ast", "--json", "-"], "SELECT a AS x FROM t"),
+        (&scripts, &["tokens"], "SELECT a, 'b' FROM t; -- the end\n"),
+        (&scripts, &["tokens"], "SELECT 'unterminated"),
+        (&scripts, &["mask", "migrations"], ""),
+        (&scripts, &["parse", "missing.sql"], ""),
+        (&scripts, &["parse", "--dialect", "oracle"], ""),
+    ];
+
+    for (dir, args, stdin) in cases {
+        let (code, stdout, stderr) = run(dir, args, stdin);
+        writeln!(file, "---------- Command ----------").unwrap();
+        writeln!(file, "databend-sql {}", args.join(" ")).unwrap();
+        if !stdin.is_empty() {
+            writeln!(file, "---------- Stdin ------------").unwrap();
+            writeln!(file, "{}", stdin.trim_end()).unwrap();
+        }
+        writeln!(file, "---------- Exit code --------").unwrap();
+        writeln!(file, "{}", code).unwrap();
+        if !stdout.is_empty() {
+            writeln!(file, "---------- Stdout -----------").unwrap();
+            writeln!(file, "{}", stdout.trim_end()).unwrap();
+        }
+        if !stderr.is_empty() {
+            writeln!(file, "---------- Stderr -----------").unwrap();
+            writeln!(file, "{}", stderr.trim_end()).unwrap();
+        }
+        if dir == tmp {
+            writeln!(file, "---------- query.sql --------").unwrap();
+            writeln!(
+                file,
+                "{}",
+                fs::read_to_string(tmp.join("query.sql")).unwrap()
+            )
+            .unwrap();
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
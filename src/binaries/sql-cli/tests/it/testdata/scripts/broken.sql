@@ -0,0 +1,3 @@
+SELECT 1;
+SELECT * FORM t;
+SELECT 'unterminated
@@ -0,0 +1,3 @@
+DELETE FROM t WHERE a < 0;
+DELETE FROM t;
+UPDATE t SET a = 0;
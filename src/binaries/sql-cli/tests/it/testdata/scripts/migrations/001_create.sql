@@ -0,0 +1,2 @@
+create table t (a int, b string);
+create stage s url = 's3://bucket/path/' connection = (access_key_id = 'ak' secret_access_key = 'sk');
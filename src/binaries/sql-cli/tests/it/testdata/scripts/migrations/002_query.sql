@@ -0,0 +1,4 @@
+-- The first rows.
+select a, b from t where a > 1 order by a limit 10;
+
+select count(*) from t
@@ -0,0 +1,324 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::ControlFlow;
+use std::ops::Range;
+
+use common_ast::ast::*;
+use common_ast::parser::complete;
+use common_ast::parser::split_statements;
+use common_ast::parser::ScriptStatement;
+use common_ast::traverse_statement;
+use common_ast::AstNode;
+use common_ast::Children;
+use common_ast::Diagnostic;
+use common_ast::ParseOptions;
+use common_ast::VisitResult;
+use common_ast::Visitor;
+
+/// The max width of the statements formatted by the pretty printer.
+const MAX_WIDTH: usize = 80;
+
+/// The statement at the byte offset `offset`, including its ends.
+pub fn statement_at(script: &str, offset: usize) -> Option<ScriptStatement> {
+    split_statements(script)
+        .into_iter()
+        .find(|statement| statement.span.start <= offset && offset <= statement.span.end)
+}
+
+/// The diagnostics of the statements that fail to parse, with the spans relative to
+/// the script.
+pub fn diagnostics(script: &str, options: &ParseOptions) -> Vec<Diagnostic> {
+    split_statements(script)
+        .iter()
+        .filter_map(|statement| {
+            let mut diag = statement.parse(script, options).err()?;
+            diag.shift(statement.span.start);
+            Some(diag)
+        })
+        .collect()
+}
+
+/// Format each statement with the pretty printer, returning the edits to the
+/// script. The statements that fail to parse, or have comments or a `FORMAT`
+/// clause that the pretty printer would drop, are left as they are.
+pub fn format(script: &str, options: &ParseOptions) -> Vec<(Range<usize>, String)> {
+    split_statements(script)
+        .into_iter()
+        .filter(|statement| !statement.has_comments)
+        .filter_map(|statement| {
+            let (stmt, format) = statement.parse(script, options).ok()?;
+            if format.is_some() {
+                return None;
+            }
+            let formatted = pretty_statement(stmt, MAX_WIDTH).ok()?;
+            (formatted != script[statement.span.clone()]).then_some((statement.span, formatted))
+        })
+        .collect()
+}
+
+/// The AST of the statement at `offset` formatted as a tree, and the span of the
+/// statement.
+pub fn hover(
+    script: &str,
+    offset: usize,
+    options: &ParseOptions,
+) -> Option<(Range<usize>, String)> {
+    let statement = statement_at(script, offset)?;
+    let (stmt, _) = statement.parse(script, options).ok()?;
+    let tree = format_statement(stmt).ok()?;
+    Some((statement.span, tree))
+}
+
+/// The keywords that can be typed at `offset`, and the span of the word they
+/// replace.
+pub fn complete_keywords(
+    script: &str,
+    offset: usize,
+    options: &ParseOptions,
+) -> (Range<usize>, Vec<String>) {
+    let completions = complete(script, offset, options.dialect);
+    (
+        completions.span.start..completions.span.end,
+        completions.keywords,
+    )
+}
+
+/// A relation defined in a script, like a table in `CREATE TABLE` or a CTE.
+pub struct Symbol {
+    pub name: String,
+    /// The kind of the relation or the type of the column.
+    pub detail: String,
+    /// The span of the definition.
+    pub span: Range<usize>,
+    /// The span of the name.
+    pub name_span: Range<usize>,
+    pub columns: Vec<Symbol>,
+}
+
+/// The tables and views created by the statements, and the CTEs of their queries.
+pub fn symbols(script: &str, options: &ParseOptions) -> Vec<Symbol> {
+    let mut symbols = vec![];
+    for statement in split_statements(script) {
+        let Ok((stmt, _)) = statement.parse(script, options) else {
+            continue;
+        };
+        let offset = statement.span.start;
+        let relation = |name: &Identifier, detail: &str, columns: Vec<Symbol>| {
+            let name_span = span_of(name, offset)?;
+            Some(Symbol {
+                name: name.name.clone(),
+                detail: detail.to_string(),
+                span: statement.span.clone(),
+                name_span,
+                columns,
+            })
+        };
+        match &stmt {
+            Statement::CreateTable(create) => {
+                let columns = match &create.source {
+                    Some(CreateTableSource::Columns(columns)) => columns
+                        .iter()
+                        .filter_map(|column| column_symbol(&column.name, &column.data_type, offset))
+                        .collect(),
+                    _ => vec![],
+                };
+                symbols.extend(relation(&create.table, "table", columns));
+            }
+            Statement::CreateView(create) => {
+                let columns = create
+                    .columns
+                    .iter()
+                    .filter_map(|column| column_symbol(column, "", offset))
+                    .collect();
+                symbols.extend(relation(&create.view, "view", columns));
+            }
+            _ => {}
+        }
+
+        let mut names = Names::default();
+        let _ = traverse_statement(&mut names, &stmt);
+        for cte in names.ctes {
+            let columns = cte
+                .alias
+                .columns
+                .iter()
+                .filter_map(|column| column_symbol(column, "", offset))
+                .collect();
+            if let (Some(span), Some(name_span)) = (
+                cte.span.map(|span| span.start + offset..span.end + offset),
+                span_of(&cte.alias.name, offset),
+            ) {
+                symbols.push(Symbol {
+                    name: cte.alias.name.name.clone(),
+                    detail: "CTE".to_string(),
+                    span,
+                    name_span,
+                    columns,
+                });
+            }
+        }
+    }
+    symbols
+}
+
+fn column_symbol(name: &Identifier, data_type: impl ToString, offset: usize) -> Option<Symbol> {
+    let span = span_of(name, offset)?;
+    Some(Symbol {
+        name: name.name.clone(),
+        detail: data_type.to_string(),
+        span: span.clone(),
+        name_span: span,
+        columns: vec![],
+    })
+}
+
+/// The span of the definition of the CTE or alias referenced at `offset`.
+pub fn definition(script: &str, offset: usize, options: &ParseOptions) -> Option<Range<usize>> {
+    let statement = statement_at(script, offset)?;
+    let (stmt, _) = statement.parse(script, options).ok()?;
+    let mut names = Names::default();
+    let _ = traverse_statement(&mut names, &stmt);
+
+    let start = statement.span.start;
+    let at_offset = |idents| ident_at(idents, offset - start);
+    let find = |defs: Vec<&Identifier>, name: &Identifier| {
+        let def = defs.into_iter().find(|def| same_name(def, name))?;
+        span_of(def, start)
+    };
+    let ctes = names.ctes.iter().map(|cte| &cte.alias.name);
+    if let Some(table) = at_offset(&names.tables) {
+        return find(ctes.collect(), table);
+    }
+    if let Some(qualifier) = at_offset(&names.qualifiers) {
+        let defs = names.table_aliases.iter().copied().chain(ctes).collect();
+        return find(defs, qualifier);
+    }
+    if let Some(column) = at_offset(&names.columns) {
+        return find(names.select_aliases.clone(), column);
+    }
+    None
+}
+
+/// The identifier whose span contains `offset`, including its ends.
+fn ident_at<'a>(idents: &[&'a Identifier], offset: usize) -> Option<&'a Identifier> {
+    idents.iter().copied().find(|ident| {
+        ident
+            .span
+            .is_some_and(|span| span.start <= offset && offset <= span.end)
+    })
+}
+
+fn span_of(ident: &Identifier, offset: usize) -> Option<Range<usize>> {
+    ident
+        .span
+        .map(|span| span.start + offset..span.end + offset)
+}
+
+/// Unquoted names are case insensitive, and quoted names are case sensitive.
+fn same_name(a: &Identifier, b: &Identifier) -> bool {
+    if a.quote.is_some() || b.quote.is_some() {
+        a.name == b.name
+    } else {
+        a.name.eq_ignore_ascii_case(&b.name)
+    }
+}
+
+/// The definitions of and references to CTEs and aliases in a statement.
+#[derive(Default)]
+struct Names<'ast> {
+    ctes: Vec<&'ast CTE>,
+    table_aliases: Vec<&'ast Identifier>,
+    select_aliases: Vec<&'ast Identifier>,
+    /// The tables without a database, which may reference CTEs.
+    tables: Vec<&'ast Identifier>,
+    /// The tables or aliases that qualify the columns, like `t` in `t.a`.
+    qualifiers: Vec<&'ast Identifier>,
+    /// The columns without a qualifier, which may reference aliases of select
+    /// targets, like `a` in `ORDER BY a`.
+    columns: Vec<&'ast Identifier>,
+}
+
+impl<'ast> Names<'ast> {
+    fn enter_query(&mut self, query: &'ast Query) {
+        if let Some(with) = &query.with {
+            self.ctes.extend(&with.ctes);
+        }
+    }
+
+    fn enter_select_target(&mut self, target: &'ast SelectTarget) {
+        match target {
+            SelectTarget::AliasedExpr {
+                alias: Some(alias), ..
+            } => self.select_aliases.push(alias),
+            SelectTarget::AliasedExpr { .. } => {}
+            SelectTarget::QualifiedName { qualified, .. } => {
+                if let [Indirection::Identifier(table), Indirection::Star(_)] = &qualified[..] {
+                    self.qualifiers.push(table);
+                }
+            }
+        }
+    }
+
+    fn enter_table_reference(&mut self, table_ref: &'ast TableReference) {
+        let alias = match table_ref {
+            TableReference::Table {
+                catalog: None,
+                database: None,
+                table,
+                alias,
+                ..
+            } => {
+                self.tables.push(table);
+                alias
+            }
+            TableReference::Table { alias, .. }
+            | TableReference::TableFunction { alias, .. }
+            | TableReference::Subquery { alias, .. }
+            | TableReference::Location { alias, .. } => alias,
+            TableReference::Join { .. } => &None,
+        };
+        if let Some(alias) = alias {
+            self.table_aliases.push(&alias.name);
+        }
+    }
+
+    fn enter_expr(&mut self, expr: &'ast Expr) {
+        if let Expr::ColumnRef {
+            database: None,
+            table,
+            column: ColumnID::Name(column),
+            ..
+        } = expr
+        {
+            match table {
+                Some(table) => self.qualifiers.push(table),
+                None => self.columns.push(column),
+            }
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for Names<'ast> {
+    fn enter_node(&mut self, node: AstNode<'ast>) -> VisitResult {
+        match node {
+            AstNode::Query(query) => self.enter_query(query),
+            AstNode::SelectTarget(target) => self.enter_select_target(target),
+            AstNode::TableReference(table_ref) => self.enter_table_reference(table_ref),
+            AstNode::Expr(expr) => self.enter_expr(expr),
+            _ => {}
+        }
+        ControlFlow::Continue(Children::Visit)
+    }
+}
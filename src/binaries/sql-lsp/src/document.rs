@@ -0,0 +1,64 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use lsp_types::Position;
+
+/// The text of an open document, with the start of each line to convert between
+/// byte offsets and LSP positions, whose characters are counted in UTF-16 code
+/// units.
+pub struct Document {
+    pub text: String,
+    line_starts: Vec<usize>,
+}
+
+impl Document {
+    pub fn new(text: String) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Document { text, line_starts }
+    }
+
+    pub fn position(&self, offset: usize) -> Position {
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let character = self.text[line_start..offset].encode_utf16().count();
+        Position::new(line as u32, character as u32)
+    }
+
+    /// The byte offset of `position`, clamped to the end of its line or the text.
+    pub fn offset(&self, position: Position) -> usize {
+        let line = position.line as usize;
+        if line >= self.line_starts.len() {
+            return self.text.len();
+        }
+        let line_start = self.line_starts[line];
+        let line_end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.text.len(), |next| next - 1);
+        let mut character = 0;
+        for (i, c) in self.text[line_start..line_end].char_indices() {
+            if character >= position.character as usize {
+                return line_start + i;
+            }
+            character += c.len_utf16();
+        }
+        line_end
+    }
+
+    pub fn range(&self, span: std::ops::Range<usize>) -> lsp_types::Range {
+        lsp_types::Range::new(self.position(span.start), self.position(span.end))
+    }
+}
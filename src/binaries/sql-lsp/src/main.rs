@@ -0,0 +1,32 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A language server of Databend SQL over stdio, see [`Server`].
+
+#![allow(clippy::uninlined_format_args)]
+
+mod analysis;
+mod document;
+mod server;
+
+use lsp_server::Connection;
+
+use crate::server::Server;
+
+fn main() -> anyhow::Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+    Server::new(connection).run()?;
+    io_threads.join()?;
+    Ok(())
+}
@@ -0,0 +1,325 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use common_ast::Dialect;
+use common_ast::ParseOptions;
+use common_ast::ParserLimits;
+use lsp_server::Connection;
+use lsp_server::ErrorCode;
+use lsp_server::Message;
+use lsp_server::Notification;
+use lsp_server::Request;
+use lsp_server::Response;
+use lsp_types::notification::DidChangeTextDocument;
+use lsp_types::notification::DidCloseTextDocument;
+use lsp_types::notification::DidOpenTextDocument;
+use lsp_types::notification::Notification as _;
+use lsp_types::notification::PublishDiagnostics;
+use lsp_types::request::Completion;
+use lsp_types::request::DocumentSymbolRequest;
+use lsp_types::request::Formatting;
+use lsp_types::request::GotoDefinition;
+use lsp_types::request::HoverRequest;
+use lsp_types::request::Request as _;
+use lsp_types::CompletionItem;
+use lsp_types::CompletionItemKind;
+use lsp_types::CompletionOptions;
+use lsp_types::CompletionParams;
+use lsp_types::CompletionTextEdit;
+use lsp_types::DiagnosticRelatedInformation;
+use lsp_types::DiagnosticSeverity;
+use lsp_types::DidChangeTextDocumentParams;
+use lsp_types::DidCloseTextDocumentParams;
+use lsp_types::DidOpenTextDocumentParams;
+use lsp_types::DocumentFormattingParams;
+use lsp_types::DocumentSymbol;
+use lsp_types::DocumentSymbolParams;
+use lsp_types::GotoDefinitionParams;
+use lsp_types::Hover;
+use lsp_types::HoverContents;
+use lsp_types::HoverParams;
+use lsp_types::HoverProviderCapability;
+use lsp_types::InitializeParams;
+use lsp_types::Location;
+use lsp_types::MarkupContent;
+use lsp_types::MarkupKind;
+use lsp_types::NumberOrString;
+use lsp_types::OneOf;
+use lsp_types::PublishDiagnosticsParams;
+use lsp_types::ServerCapabilities;
+use lsp_types::SymbolKind;
+use lsp_types::TextDocumentSyncCapability;
+use lsp_types::TextDocumentSyncKind;
+use lsp_types::TextEdit;
+use lsp_types::Url;
+use serde_json::json;
+use serde_json::Value;
+
+use crate::analysis;
+use crate::analysis::Symbol;
+use crate::document::Document;
+
+/// A language server of Databend SQL. The documents are synchronized in full, and
+/// the dialect is set by the `dialect` of the initialization options, which is
+/// `postgresql` by default.
+pub struct Server {
+    connection: Connection,
+    options: ParseOptions,
+    documents: HashMap<Url, Document>,
+}
+
+impl Server {
+    pub fn new(connection: Connection) -> Self {
+        Server {
+            connection,
+            options: ParseOptions {
+                limits: ParserLimits::recommended(),
+                ..Default::default()
+            },
+            documents: HashMap::new(),
+        }
+    }
+
+    pub fn run(mut self) -> Result<()> {
+        let capabilities = ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            hover_provider: Some(HoverProviderCapability::Simple(true)),
+            completion_provider: Some(CompletionOptions::default()),
+            definition_provider: Some(OneOf::Left(true)),
+            document_symbol_provider: Some(OneOf::Left(true)),
+            document_formatting_provider: Some(OneOf::Left(true)),
+            ..Default::default()
+        };
+        let (id, params) = self.connection.initialize_start()?;
+        let params: InitializeParams = serde_json::from_value(params)?;
+        if let Some(dialect) = params
+            .initialization_options
+            .as_ref()
+            .and_then(|options| options.get("dialect"))
+            .and_then(Value::as_str)
+        {
+            self.options.dialect = parse_dialect(dialect)?;
+        }
+        self.connection.initialize_finish(
+            id,
+            json!({
+                "capabilities": capabilities,
+                "serverInfo": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }),
+        )?;
+
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    self.connection.sender.send(response.into())?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            HoverRequest::METHOD => self.handle(request, Self::hover),
+            Completion::METHOD => self.handle(request, Self::completion),
+            GotoDefinition::METHOD => self.handle(request, Self::definition),
+            DocumentSymbolRequest::METHOD => self.handle(request, Self::document_symbols),
+            Formatting::METHOD => self.handle(request, Self::formatting),
+            method => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("unsupported request: {method}"),
+                );
+            }
+        };
+        match result {
+            Ok(result) => Response::new_ok(id, result),
+            Err(err) => Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string()),
+        }
+    }
+
+    fn handle<P, R>(&self, request: Request, handler: fn(&Self, P) -> Option<R>) -> Result<Value>
+    where
+        P: serde::de::DeserializeOwned,
+        R: serde::Serialize,
+    {
+        let params = serde_json::from_value(request.params)?;
+        Ok(serde_json::to_value(handler(self, params))?)
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.update(params.text_document.uri, params.text_document.text)?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.update(params.text_document.uri, change.text)?;
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                self.publish_diagnostics(params.text_document.uri, vec![])?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, uri: Url, text: String) -> Result<()> {
+        let document = Document::new(text);
+        let diagnostics = analysis::diagnostics(&document.text, &self.options)
+            .into_iter()
+            .map(|diag| lsp_types::Diagnostic {
+                range: document.range(diag.span.start..diag.span.end),
+                severity: Some(DiagnosticSeverity::ERROR),
+                code: Some(NumberOrString::String(diag.code.to_string())),
+                source: Some("databend".to_string()),
+                message: diag.message,
+                related_information: Some(
+                    diag.contexts
+                        .iter()
+                        .map(|(span, context)| DiagnosticRelatedInformation {
+                            location: Location::new(
+                                uri.clone(),
+                                document.range(span.start..span.end),
+                            ),
+                            message: format!("while parsing {context}"),
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .filter(|related| !related.is_empty()),
+                ..Default::default()
+            })
+            .collect();
+        self.documents.insert(uri.clone(), document);
+        self.publish_diagnostics(uri, diagnostics)
+    }
+
+    fn publish_diagnostics(&self, uri: Url, diagnostics: Vec<lsp_types::Diagnostic>) -> Result<()> {
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(notification.into())?;
+        Ok(())
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let offset = document.offset(position.position);
+        let (span, tree) = analysis::hover(&document.text, offset, &self.options)?;
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```text\n{tree}\n```"),
+            }),
+            range: Some(document.range(span)),
+        })
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<Vec<CompletionItem>> {
+        let position = params.text_document_position;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let offset = document.offset(position.position);
+        let (span, keywords) = analysis::complete_keywords(&document.text, offset, &self.options);
+        let range = document.range(span);
+        let items = keywords
+            .into_iter()
+            .map(|keyword| CompletionItem {
+                label: keyword.clone(),
+                kind: Some(CompletionItemKind::KEYWORD),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(range, keyword))),
+                ..Default::default()
+            })
+            .collect();
+        Some(items)
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<Location> {
+        let position = params.text_document_position_params;
+        let document = self.documents.get(&position.text_document.uri)?;
+        let offset = document.offset(position.position);
+        let span = analysis::definition(&document.text, offset, &self.options)?;
+        Some(Location::new(
+            position.text_document.uri,
+            document.range(span),
+        ))
+    }
+
+    fn document_symbols(&self, params: DocumentSymbolParams) -> Option<Vec<DocumentSymbol>> {
+        let document = self.documents.get(&params.text_document.uri)?;
+        let symbols = analysis::symbols(&document.text, &self.options)
+            .into_iter()
+            .map(|symbol| document_symbol(document, symbol, SymbolKind::STRUCT))
+            .collect();
+        Some(symbols)
+    }
+
+    fn formatting(&self, params: DocumentFormattingParams) -> Option<Vec<TextEdit>> {
+        let document = self.documents.get(&params.text_document.uri)?;
+        let edits = analysis::format(&document.text, &self.options)
+            .into_iter()
+            .map(|(span, text)| TextEdit::new(document.range(span), text))
+            .collect();
+        Some(edits)
+    }
+}
+
+#[allow(deprecated)]
+fn document_symbol(document: &Document, symbol: Symbol, kind: SymbolKind) -> DocumentSymbol {
+    let children = symbol
+        .columns
+        .into_iter()
+        .map(|column| document_symbol(document, column, SymbolKind::FIELD))
+        .collect::<Vec<_>>();
+    DocumentSymbol {
+        name: symbol.name,
+        detail: Some(symbol.detail).filter(|detail| !detail.is_empty()),
+        kind,
+        tags: None,
+        deprecated: None,
+        range: document.range(symbol.span),
+        selection_range: document.range(symbol.name_span),
+        children: Some(children).filter(|children| !children.is_empty()),
+    }
+}
+
+fn parse_dialect(dialect: &str) -> Result<Dialect> {
+    match dialect.to_lowercase().as_str() {
+        "postgresql" => Ok(Dialect::PostgreSQL),
+        "mysql" => Ok(Dialect::MySQL),
+        "hive" => Ok(Dialect::Hive),
+        _ => Err(anyhow::anyhow!("unknown dialect: {dialect}")),
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write;
+use std::process::Child;
+use std::process::ChildStdin;
+use std::process::ChildStdout;
+use std::process::Command;
+use std::process::Stdio;
+
+use goldenfile::Mint;
+use serde_json::json;
+use serde_json::Value;
+
+const URI: &str = "file:///tmp/query.sql";
+
+/// A client of the language server, talking to the binary over pipes.
+struct Client {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: i64,
+}
+
+impl Client {
+    fn spawn(initialization_options: Value) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_databend-sql-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        let mut client = Client {
+            child,
+            stdin,
+            stdout,
+            next_id: 0,
+        };
+        client.request(
+            "initialize",
+            json!({
+                "capabilities": {},
+                "initializationOptions": initialization_options,
+            }),
+        );
+        client.notify("initialized", json!({}));
+        client
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn recv(&mut self) -> Value {
+        let mut len = 0;
+        loop {
+            let mut header = String::new();
+            self.stdout.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length: ") {
+                len = value.parse().unwrap();
+            }
+        }
+        let mut body = vec![0; len];
+        self.stdout.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    /// Send a request and wait for its response, skipping the notifications.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}));
+        loop {
+            let message = self.recv();
+            if message["id"] == json!(id) {
+                return message;
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({"jsonrpc": "2.0", "method": method, "params": params}));
+    }
+
+    /// Wait for the next diagnostics published by the server.
+    fn diagnostics(&mut self) -> Value {
+        loop {
+            let message = self.recv();
+            if message["method"] == "textDocument/publishDiagnostics" {
+                return message["params"]["diagnostics"].clone();
+            }
+        }
+    }
+
+    fn shutdown(mut self) {
+        let response = self.request("shutdown", Value::Null);
+        assert_eq!(response["result"], Value::Null);
+        self.notify("exit", Value::Null);
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+fn position(line: u32, character: u32) -> Value {
+    json!({
+        "textDocument": {"uri": URI},
+        "position": {"line": line, "character": character},
+    })
+}
+
+#[test]
+fn test_lsp() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("lsp.txt").unwrap();
+    let mut write = |title: &str, value: &Value| {
+        writeln!(file, "---------- {} ----------", title).unwrap();
+        writeln!(file, "{}", serde_json::to_string_pretty(value).unwrap()).unwrap();
+        writeln!(file, "\n").unwrap();
+    };
+
+    let mut client = Client::spawn(json!({"dialect": "postgresql"}));
+
+    let text = "WITH top AS (SELECT a, b FROM t ORDER BY b DESC LIMIT 10)\n\
+                SELECT x.a AS c FROM top AS x ORDER BY c;\n\
+                -- The table of the query.\n\
+                CREATE TABLE t (a INT, b STRING);\n\
+                SELEC 1;\n";
+    client.notify(
+        "textDocument/didOpen",
+        json!({
+            "textDocument": {"uri": URI, "languageId": "sql", "version": 1, "text": text},
+        }),
+    );
+    write("diagnostics", &client.diagnostics());
+
+    let hover = client.request("textDocument/hover", position(3, 15));
+    write("hover CREATE TABLE", &hover["result"]);
+
+    for (name, line, character) in [("top", 1, 22), ("x", 1, 7), ("c", 1, 39)] {
+        let definition = client.request("textDocument/definition", position(line, character));
+        write(&format!("definition of {}", name), &definition["result"]);
+    }
+
+    let symbols = client.request(
+        "textDocument/documentSymbol",
+        json!({"textDocument": {"uri": URI}}),
+    );
+    write("document symbols", &symbols["result"]);
+
+    let completion = client.request("textDocument/completion", position(4, 5));
+    let items = completion["result"].as_array().unwrap();
+    assert!(items.iter().any(|item| item["label"] == "SELECT"));
+    write("completion of SELEC", &items[0]);
+
+    let text = text.replace("SELEC 1", "SELECT 1");
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": {"uri": URI, "version": 2},
+            "contentChanges": [{"text": text}],
+        }),
+    );
+    write("diagnostics after change", &client.diagnostics());
+
+    let formatting = client.request(
+        "textDocument/formatting",
+        json!({
+            "textDocument": {"uri": URI},
+            "options": {"tabSize": 4, "insertSpaces": true},
+        }),
+    );
+    write("formatting", &formatting["result"]);
+
+    let unsupported = client.request("textDocument/references", position(1, 22));
+    write("unsupported request", &unsupported["error"]);
+
+    client.shutdown();
+}
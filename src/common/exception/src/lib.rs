@@ -25,6 +25,7 @@ pub use exception::Result;
 pub use exception::ToErrorCode;
 pub use exception_into::SerializedError;
 pub use span::pretty_print_error;
+pub use span::pretty_print_error_with_notes;
 pub use span::Range;
 pub use span::Span;
 pub use with_context::ErrorWithContext;
@@ -64,6 +64,17 @@ impl From<std::ops::Range<usize>> for Range {
 }
 
 pub fn pretty_print_error(source: &str, labels: Vec<(Range, String)>) -> String {
+    pretty_print_error_with_notes(source, labels, vec![], false)
+}
+
+/// Same as [`pretty_print_error`], but followed by notes like `help: ...`, and
+/// colored with ANSI escape codes for terminals if `ansi` is set.
+pub fn pretty_print_error_with_notes(
+    source: &str,
+    labels: Vec<(Range, String)>,
+    notes: Vec<String>,
+    ansi: bool,
+) -> String {
     use codespan_reporting::diagnostic::Diagnostic;
     use codespan_reporting::diagnostic::Label;
     use codespan_reporting::files::SimpleFile;
@@ -72,7 +83,11 @@ pub fn pretty_print_error(source: &str, labels: Vec<(Range, String)>) -> String
     use codespan_reporting::term::Chars;
     use codespan_reporting::term::Config;
 
-    let mut writer = Buffer::no_color();
+    let mut writer = if ansi {
+        Buffer::ansi()
+    } else {
+        Buffer::no_color()
+    };
     let file = SimpleFile::new("SQL", source);
     let config = Config {
         chars: Chars::ascii(),
@@ -92,7 +107,7 @@ pub fn pretty_print_error(source: &str, labels: Vec<(Range, String)>) -> String
         })
         .collect();
 
-    let diagnostic = Diagnostic::error().with_labels(labels);
+    let diagnostic = Diagnostic::error().with_labels(labels).with_notes(notes);
 
     term::emit(&mut writer, &config, &file, &diagnostic).unwrap();
 
@@ -17,7 +17,9 @@ extern crate criterion;
 
 use common_ast::parser::parse_expr;
 use common_ast::parser::parse_sql;
+use common_ast::parser::parser_values_with_placeholder;
 use common_ast::parser::tokenize_sql;
+use common_ast::parser::ValuesRowReader;
 use common_ast::Dialect;
 use criterion::black_box;
 use criterion::Criterion;
@@ -66,6 +68,27 @@ fn bench(c: &mut Criterion) {
             black_box(expr);
         })
     });
+
+    let rows = (0..1000)
+        .map(|i| format!("({i}, 'name_{i}', {i}.5, ?, now())"))
+        .collect::<Vec<_>>();
+    let values = rows.join(", ");
+    group.bench_function("values_with_placeholder", |b| {
+        b.iter(|| {
+            for row in &rows {
+                let tokens = tokenize_sql(row).unwrap();
+                let values = parser_values_with_placeholder(&tokens, Dialect::PostgreSQL).unwrap();
+                black_box(values);
+            }
+        })
+    });
+    group.bench_function("values_row_reader", |b| {
+        b.iter(|| {
+            for row in ValuesRowReader::new(&values, 0, Dialect::PostgreSQL) {
+                black_box(row.unwrap());
+            }
+        })
+    });
 }
 
 criterion_group!(benches, bench);
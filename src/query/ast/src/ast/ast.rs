@@ -16,6 +16,7 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 
 use common_exception::Span;
+use common_io::escape_string_with_quote;
 
 use crate::parser::quote::quote_ident;
 
@@ -58,6 +59,15 @@ impl Identifier {
         self.quote.is_some()
     }
 
+    /// The name to compare identifiers by, unquoted identifiers are case-insensitive.
+    pub fn normalized_name(&self) -> String {
+        if self.is_quoted() {
+            self.name.clone()
+        } else {
+            self.name.to_lowercase()
+        }
+    }
+
     pub fn from_name(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
@@ -140,3 +150,21 @@ pub(crate) fn write_space_separated_map(
     }
     Ok(())
 }
+
+/// Write input map items into `field_a = 'x', field_b = 'y'`, with the values escaped
+pub(crate) fn write_comma_separated_map(
+    f: &mut Formatter<'_>,
+    items: impl IntoIterator<Item = (impl Display, impl AsRef<str>)>,
+) -> std::fmt::Result {
+    for (i, (k, v)) in items.into_iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(
+            f,
+            "{k} = '{}'",
+            escape_string_with_quote(v.as_ref(), Some('\''))
+        )?;
+    }
+    Ok(())
+}
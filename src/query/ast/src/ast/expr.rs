@@ -167,6 +167,8 @@ pub enum Expr {
     },
     /// A literal value, such as string, number, date or NULL
     Literal { span: Span, lit: Literal },
+    /// A placeholder `?` standing in for a value that is bound later
+    Placeholder { span: Span },
     /// `COUNT(*)` expression
     CountAll { span: Span, window: Option<Window> },
     /// `(foo, bar)`
@@ -439,6 +441,12 @@ impl BinaryOperator {
             BinaryOperator::Lte => Ok(BinaryOperator::Gt),
             BinaryOperator::Eq => Ok(BinaryOperator::NotEq),
             BinaryOperator::NotEq => Ok(BinaryOperator::Eq),
+            BinaryOperator::Like => Ok(BinaryOperator::NotLike),
+            BinaryOperator::NotLike => Ok(BinaryOperator::Like),
+            BinaryOperator::Regexp => Ok(BinaryOperator::NotRegexp),
+            BinaryOperator::NotRegexp => Ok(BinaryOperator::Regexp),
+            BinaryOperator::RLike => Ok(BinaryOperator::NotRLike),
+            BinaryOperator::NotRLike => Ok(BinaryOperator::RLike),
             _ => Err(ErrorCode::Unimplemented(format!(
                 "Converting {self} to its contrary is not currently supported"
             ))),
@@ -507,6 +515,7 @@ impl Expr {
             | Expr::Substring { span, .. }
             | Expr::Trim { span, .. }
             | Expr::Literal { span, .. }
+            | Expr::Placeholder { span }
             | Expr::CountAll { span, .. }
             | Expr::Tuple { span, .. }
             | Expr::FunctionCall { span, .. }
@@ -1058,6 +1067,9 @@ impl Display for Expr {
             Expr::Literal { lit, .. } => {
                 write!(f, "{lit}")?;
             }
+            Expr::Placeholder { .. } => {
+                write!(f, "?")?;
+            }
             Expr::CountAll { window, .. } => {
                 write!(f, "COUNT(*)")?;
                 if let Some(window) = window {
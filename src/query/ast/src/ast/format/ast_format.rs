@@ -23,10 +23,14 @@ use crate::ast::*;
 use crate::visitors::Visitor;
 
 pub fn format_statement(stmt: Statement) -> Result<String> {
+    format_statement_tree(&stmt).format_pretty()
+}
+
+/// The tree of the AST nodes printed by [`format_statement`].
+pub fn format_statement_tree(stmt: &Statement) -> FormatTreeNode<AstFormatContext> {
     let mut visitor = AstFormatVisitor::new();
-    visitor.visit_statement(&stmt);
-    let format_ctx = visitor.children.pop().unwrap();
-    format_ctx.format_pretty()
+    visitor.visit_statement(stmt);
+    visitor.children.pop().unwrap()
 }
 
 #[derive(Clone)]
@@ -60,6 +64,14 @@ impl AstFormatContext {
             alias,
         }
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
 }
 
 impl Display for AstFormatContext {
@@ -404,6 +416,12 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_placeholder(&mut self, _span: Span) {
+        let format_ctx = AstFormatContext::new("Placeholder".to_string());
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
     fn visit_count_all(&mut self, _span: Span, _window: &'ast Option<Window>) {
         let name = "Function CountAll".to_string();
         let format_ctx = AstFormatContext::new(name);
@@ -707,6 +725,15 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         children.push(self.children.pop().unwrap());
         self.visit_copy_unit(&copy.dst);
         children.push(self.children.pop().unwrap());
+        if let Some(partition_by) = &copy.partition_by {
+            self.visit_expr(partition_by);
+            let partition_by_child = self.children.pop().unwrap();
+            let partition_by_name = "PartitionBy".to_string();
+            let partition_by_format_ctx = AstFormatContext::with_children(partition_by_name, 1);
+            let partition_by_node =
+                FormatTreeNode::with_children(partition_by_format_ctx, vec![partition_by_child]);
+            children.push(partition_by_node);
+        }
         if let Some(files) = &copy.files {
             let mut files_children = Vec::with_capacity(files.len());
             for file in files.iter() {
@@ -727,9 +754,10 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             let pattern_node = FormatTreeNode::new(pattern_format_ctx);
             children.push(pattern_node);
         }
-        if !copy.file_format.is_empty() {
-            let mut file_formats_children = Vec::with_capacity(copy.file_format.len());
-            for (k, v) in copy.file_format.iter() {
+        if let Some(file_format) = &copy.file_format {
+            let file_format = file_format.to_options();
+            let mut file_formats_children = Vec::with_capacity(file_format.len());
+            for (k, v) in file_format.iter() {
                 let file_format_name = format!("FileFormat {} = {:?}", k, v);
                 let file_format_format_ctx = AstFormatContext::new(file_format_name);
                 let file_format_node = FormatTreeNode::new(file_format_format_ctx);
@@ -744,8 +772,8 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 FormatTreeNode::with_children(files_formats_format_ctx, file_formats_children);
             children.push(files_formats_node);
         }
-        if !copy.validation_mode.is_empty() {
-            let validation_mode_name = format!("ValidationMode {}", copy.validation_mode);
+        if let Some(validation_mode) = &copy.validation_mode {
+            let validation_mode_name = format!("ValidationMode {}", validation_mode);
             let validation_mode_format_ctx = AstFormatContext::new(validation_mode_name);
             let validation_mode_node = FormatTreeNode::new(validation_mode_format_ctx);
             children.push(validation_mode_node);
@@ -770,6 +798,17 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         let disable_variant_check_node = FormatTreeNode::new(disable_variant_check_ctx);
         children.push(disable_variant_check_node);
 
+        if copy.header {
+            let header_format_ctx = AstFormatContext::new("Header true".to_string());
+            children.push(FormatTreeNode::new(header_format_ctx));
+        }
+
+        if copy.include_query_id {
+            let include_query_id_format_ctx =
+                AstFormatContext::new("IncludeQueryId true".to_string());
+            children.push(FormatTreeNode::new(include_query_id_format_ctx));
+        }
+
         let name = "Copy".to_string();
         let format_ctx = AstFormatContext::with_children(name, children.len());
         let node = FormatTreeNode::with_children(format_ctx, children);
@@ -965,6 +1004,7 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 self.children.push(streaming_node);
             }
             InsertSource::StreamingV2 { settings, .. } => {
+                let settings = settings.to_options();
                 let mut file_formats_children = Vec::with_capacity(settings.len());
                 for (k, v) in settings.iter() {
                     let file_format_name = format!("FileFormat {} = {:?}", k, v);
@@ -2098,9 +2138,10 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             let location_format_ctx = AstFormatContext::new(location_name);
             children.push(FormatTreeNode::new(location_format_ctx));
         }
-        if !stmt.file_format_options.is_empty() {
-            let mut file_formats_children = Vec::with_capacity(stmt.file_format_options.len());
-            for (k, v) in stmt.file_format_options.iter() {
+        if let Some(file_format) = &stmt.file_format_options {
+            let file_format = file_format.to_options();
+            let mut file_formats_children = Vec::with_capacity(file_format.len());
+            for (k, v) in file_format.iter() {
                 let file_format_name = format!("FileFormat {} = {:?}", k, v);
                 let file_format_format_ctx = AstFormatContext::new(file_format_name);
                 let file_format_node = FormatTreeNode::new(file_format_format_ctx);
@@ -2115,16 +2156,16 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 FormatTreeNode::with_children(files_formats_format_ctx, file_formats_children);
             children.push(files_formats_node);
         }
-        if !stmt.on_error.is_empty() {
-            let on_error_name = format!("OnError {}", stmt.on_error);
+        if let Some(on_error) = &stmt.on_error {
+            let on_error_name = format!("OnError {}", on_error);
             let on_error_format_ctx = AstFormatContext::new(on_error_name);
             children.push(FormatTreeNode::new(on_error_format_ctx));
         }
         let size_limit_name = format!("SizeLimit {}", stmt.size_limit);
         let size_limit_format_ctx = AstFormatContext::new(size_limit_name);
         children.push(FormatTreeNode::new(size_limit_format_ctx));
-        if !stmt.validation_mode.is_empty() {
-            let validation_mode_name = format!("ValidationMode {}", stmt.validation_mode);
+        if let Some(validation_mode) = &stmt.validation_mode {
+            let validation_mode_name = format!("ValidationMode {}", validation_mode);
             let validation_mode_format_ctx = AstFormatContext::new(validation_mode_name);
             children.push(FormatTreeNode::new(validation_mode_format_ctx));
         }
@@ -20,6 +20,8 @@ mod syntax;
 use std::fmt::Display;
 
 pub use ast_format::format_statement;
+pub use ast_format::format_statement_tree;
+pub use ast_format::AstFormatContext;
 pub use indent_format::*;
 pub use pretty_format::*;
 pub use syntax::pretty_statement;
@@ -92,7 +92,7 @@ fn pretty_source(source: InsertSource) -> RcDoc<'static> {
             RcDoc::line()
                 .append(RcDoc::text("FILE_FORMAT_SETTINGS = "))
                 .append(parenthesized(
-                    interweave_comma(settings.iter().map(|(k, v)| {
+                    interweave_comma(settings.to_options().iter().map(|(k, v)| {
                         RcDoc::text(k.to_string())
                             .append(RcDoc::space())
                             .append(RcDoc::text("="))
@@ -112,11 +112,7 @@ fn pretty_source(source: InsertSource) -> RcDoc<'static> {
                         .append(RcDoc::text(format!("{:?}", on_error_mode))),
                 ),
         ),
-        InsertSource::Values { rest_str } => RcDoc::text("VALUES").append(
-            RcDoc::line()
-                .nest(NEST_FACTOR)
-                .append(RcDoc::text(rest_str)),
-        ),
+        InsertSource::Values { .. } => RcDoc::text("VALUES"),
         InsertSource::Select { query } => pretty_query(*query),
     })
 }
@@ -181,6 +177,13 @@ pub(crate) fn pretty_copy(copy_stmt: CopyStmt) -> RcDoc<'static> {
         .append(pretty_copy_unit(copy_stmt.dst))
         .append(RcDoc::line().append(RcDoc::text("FROM ")))
         .append(pretty_copy_unit(copy_stmt.src))
+        .append(if let Some(partition_by) = copy_stmt.partition_by {
+            RcDoc::line()
+                .append(RcDoc::text("PARTITION BY "))
+                .append(pretty_expr(*partition_by))
+        } else {
+            RcDoc::nil()
+        })
         .append(if let Some(files) = &copy_stmt.files {
             RcDoc::line()
                 .append(RcDoc::text("FILES = "))
@@ -198,11 +201,11 @@ pub(crate) fn pretty_copy(copy_stmt: CopyStmt) -> RcDoc<'static> {
         } else {
             RcDoc::nil()
         })
-        .append(if !copy_stmt.file_format.is_empty() {
+        .append(if let Some(file_format) = &copy_stmt.file_format {
             RcDoc::line()
                 .append(RcDoc::text("FILE_FORMAT = "))
                 .append(parenthesized(
-                    interweave_comma(copy_stmt.file_format.iter().map(|(k, v)| {
+                    interweave_comma(file_format.to_options().iter().map(|(k, v)| {
                         RcDoc::text(k.to_string())
                             .append(RcDoc::space())
                             .append(RcDoc::text("="))
@@ -214,10 +217,10 @@ pub(crate) fn pretty_copy(copy_stmt: CopyStmt) -> RcDoc<'static> {
         } else {
             RcDoc::nil()
         })
-        .append(if !copy_stmt.validation_mode.is_empty() {
+        .append(if let Some(validation_mode) = copy_stmt.validation_mode {
             RcDoc::line()
                 .append(RcDoc::text("VALIDATION_MODE = "))
-                .append(RcDoc::text(copy_stmt.validation_mode))
+                .append(RcDoc::text(validation_mode.to_string()))
         } else {
             RcDoc::nil()
         })
@@ -235,6 +238,13 @@ pub(crate) fn pretty_copy(copy_stmt: CopyStmt) -> RcDoc<'static> {
         } else {
             RcDoc::nil()
         })
+        .append(if copy_stmt.max_file_size != 0 {
+            RcDoc::line()
+                .append(RcDoc::text("MAX_FILE_SIZE = "))
+                .append(RcDoc::text(format!("{}", copy_stmt.max_file_size)))
+        } else {
+            RcDoc::nil()
+        })
         .append(
             RcDoc::line()
                 .append(RcDoc::text("PURGE = "))
@@ -245,6 +255,16 @@ pub(crate) fn pretty_copy(copy_stmt: CopyStmt) -> RcDoc<'static> {
                 .append(RcDoc::text("DISABLE_VARIANT_CHECK = "))
                 .append(RcDoc::text(format!("{}", copy_stmt.disable_variant_check))),
         )
+        .append(if copy_stmt.header {
+            RcDoc::line().append(RcDoc::text("HEADER = true"))
+        } else {
+            RcDoc::nil()
+        })
+        .append(if copy_stmt.include_query_id {
+            RcDoc::line().append(RcDoc::text("INCLUDE_QUERY_ID = true"))
+        } else {
+            RcDoc::nil()
+        })
 }
 
 fn pretty_copy_unit(copy_unit: CopyUnit) -> RcDoc<'static> {
@@ -19,15 +19,22 @@ use std::fmt::Formatter;
 use std::io::Error;
 use std::io::ErrorKind;
 use std::io::Result;
+use std::str::FromStr;
 
 use itertools::Itertools;
 use url::Url;
 
 use crate::ast::write_quoted_comma_separated_list;
 use crate::ast::write_space_separated_map;
+use crate::ast::Expr;
+use crate::ast::FileFormatOptions;
 use crate::ast::Hint;
 use crate::ast::Identifier;
 use crate::ast::Query;
+use crate::ast::SetExpr;
+use crate::ast::StorageParams;
+use crate::ast::StorageParamsError;
+use crate::ast::TableReference;
 
 /// CopyStmt is the parsed statement of `COPY`.
 ///
@@ -43,9 +50,8 @@ pub struct CopyStmt {
     pub dst: CopyUnit,
     pub files: Option<Vec<String>>,
     pub pattern: Option<String>,
-    pub file_format: BTreeMap<String, String>,
-    /// TODO(xuanwo): parse into validation_mode directly.
-    pub validation_mode: String,
+    pub file_format: Option<FileFormatOptions>,
+    pub validation_mode: Option<ValidationMode>,
     pub size_limit: usize,
     pub max_files: usize,
     pub max_file_size: usize,
@@ -54,7 +60,14 @@ pub struct CopyStmt {
     pub purge: bool,
     pub force: bool,
     pub disable_variant_check: bool,
-    pub on_error: String,
+    pub on_error: OnErrorMode,
+    /// `PARTITION BY <expr>` of unloading, the files are written into the
+    /// sub-path evaluated by the expression.
+    pub partition_by: Option<Box<Expr>>,
+    /// `HEADER = true` of unloading, writes the column names into the files.
+    pub header: bool,
+    /// `INCLUDE_QUERY_ID = true` of unloading, adds the query id into the file names.
+    pub include_query_id: bool,
 }
 
 impl CopyStmt {
@@ -62,8 +75,8 @@ impl CopyStmt {
         match opt {
             CopyOption::Files(v) => self.files = Some(v),
             CopyOption::Pattern(v) => self.pattern = Some(v),
-            CopyOption::FileFormat(v) => self.file_format = v,
-            CopyOption::ValidationMode(v) => self.validation_mode = v,
+            CopyOption::FileFormat(v) => self.file_format = Some(v),
+            CopyOption::ValidationMode(v) => self.validation_mode = Some(v),
             CopyOption::SizeLimit(v) => self.size_limit = v,
             CopyOption::MaxFiles(v) => self.max_files = v,
             CopyOption::MaxFileSize(v) => self.max_file_size = v,
@@ -73,8 +86,25 @@ impl CopyStmt {
             CopyOption::Force(v) => self.force = v,
             CopyOption::DisableVariantCheck(v) => self.disable_variant_check = v,
             CopyOption::OnError(v) => self.on_error = v,
+            CopyOption::PartitionBy(v) => self.partition_by = Some(v),
+            CopyOption::Header(v) => self.header = v,
+            CopyOption::IncludeQueryId(v) => self.include_query_id = v,
         }
     }
+
+    /// Return the stage location of a load with transformation, e.g.
+    /// `COPY INTO t FROM (SELECT $1, $3::INT FROM @stage/path)`.
+    pub fn transform_location(&self) -> Option<&FileLocation> {
+        match &self.dst {
+            CopyUnit::Table { .. } => self.src.stage_query_location(),
+            _ => None,
+        }
+    }
+
+    /// Whether it's an unloading into a location.
+    pub fn is_unload(&self) -> bool {
+        matches!(self.dst, CopyUnit::Location(_))
+    }
 }
 
 impl Display for CopyStmt {
@@ -86,6 +116,10 @@ impl Display for CopyStmt {
         write!(f, " INTO {}", self.dst)?;
         write!(f, " FROM {}", self.src)?;
 
+        if let Some(partition_by) = &self.partition_by {
+            write!(f, " PARTITION BY {partition_by}")?;
+        }
+
         if let Some(files) = &self.files {
             write!(f, " FILES = (")?;
             write_quoted_comma_separated_list(f, files)?;
@@ -96,16 +130,12 @@ impl Display for CopyStmt {
             write!(f, " PATTERN = '{}'", pattern)?;
         }
 
-        if !self.file_format.is_empty() {
-            write!(f, " FILE_FORMAT = (")?;
-            for (k, v) in self.file_format.iter() {
-                write!(f, " {} = '{}'", k, v)?;
-            }
-            write!(f, " )")?;
+        if let Some(file_format) = &self.file_format {
+            write!(f, " FILE_FORMAT = ({file_format})")?;
         }
 
-        if !self.validation_mode.is_empty() {
-            write!(f, "VALIDATION_MODE = {}", self.validation_mode)?;
+        if let Some(validation_mode) = &self.validation_mode {
+            write!(f, " VALIDATION_MODE = {validation_mode}")?;
         }
 
         if self.size_limit != 0 {
@@ -130,6 +160,14 @@ impl Display for CopyStmt {
         write!(f, " DISABLE_VARIANT_CHECK = {}", self.disable_variant_check)?;
         write!(f, " ON_ERROR = '{}'", self.on_error)?;
 
+        if self.header {
+            write!(f, " HEADER = true")?;
+        }
+
+        if self.include_query_id {
+            write!(f, " INCLUDE_QUERY_ID = true")?;
+        }
+
         Ok(())
     }
 }
@@ -161,6 +199,22 @@ impl CopyUnit {
             CopyUnit::Query(_) => "Query",
         }
     }
+
+    /// Return the location if the unit is a query that only selects from a
+    /// stage, like `(SELECT $1, $2 FROM @stage)`.
+    pub fn stage_query_location(&self) -> Option<&FileLocation> {
+        let query = match self {
+            CopyUnit::Query(query) if query.with.is_none() => query,
+            _ => return None,
+        };
+        match &query.body {
+            SetExpr::Select(select) => match select.from.as_slice() {
+                [TableReference::Location { location, .. }] => Some(location),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
 
 impl Display for CopyUnit {
@@ -222,6 +276,23 @@ impl Connection {
         }
     }
 
+    /// Rewrite the values of the connection with `f(key, value)`.
+    pub fn map_values(&self, mut f: impl FnMut(&str, &str) -> String) -> Self {
+        let conns = self
+            .conns
+            .iter()
+            .map(|(k, v)| (k.clone(), f(k, v)))
+            .collect();
+        Self {
+            visited_keys: self.visited_keys.clone(),
+            conns,
+        }
+    }
+
+    pub(crate) fn options(&self) -> &BTreeMap<String, String> {
+        &self.conns
+    }
+
     pub fn get(&mut self, key: &str) -> Option<&String> {
         self.visited_keys.insert(key.to_string());
         self.conns.get(key)
@@ -340,6 +411,13 @@ impl UriLocation {
     }
 }
 
+impl UriLocation {
+    /// Validate the connection against the options known by the protocol.
+    pub fn storage_params(&self) -> std::result::Result<StorageParams, StorageParamsError> {
+        StorageParams::try_from_connection(&self.protocol, &self.connection.conns)
+    }
+}
+
 impl Display for UriLocation {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "'{}://{}{}'", self.protocol, self.name, self.path)?;
@@ -383,8 +461,8 @@ impl Display for FileLocation {
 pub enum CopyOption {
     Files(Vec<String>),
     Pattern(String),
-    FileFormat(BTreeMap<String, String>),
-    ValidationMode(String),
+    FileFormat(FileFormatOptions),
+    ValidationMode(ValidationMode),
     SizeLimit(usize),
     MaxFiles(usize),
     MaxFileSize(usize),
@@ -393,5 +471,114 @@ pub enum CopyOption {
     Purge(bool),
     Force(bool),
     DisableVariantCheck(bool),
-    OnError(String),
+    OnError(OnErrorMode),
+    PartitionBy(Box<Expr>),
+    Header(bool),
+    IncludeQueryId(bool),
+}
+
+/// `VALIDATION_MODE = <mode>` of `COPY`, validates the files instead of loading them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// `RETURN_<n>_ROWS`
+    ReturnRows(u64),
+    /// `RETURN_ERRORS`
+    ReturnErrors,
+    /// `RETURN_ALL_ERRORS`
+    ReturnAllErrors,
+}
+
+impl FromStr for ValidationMode {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        const INVALID: &str =
+            "invalid VALIDATION_MODE, expected RETURN_<n>_ROWS, RETURN_ERRORS or RETURN_ALL_ERRORS";
+
+        match s.to_uppercase().as_str() {
+            "RETURN_ERRORS" => Ok(ValidationMode::ReturnErrors),
+            "RETURN_ALL_ERRORS" => Ok(ValidationMode::ReturnAllErrors),
+            s => {
+                let n = s
+                    .strip_prefix("RETURN_")
+                    .and_then(|s| s.strip_suffix("_ROWS"))
+                    .ok_or(INVALID)?;
+                match n.parse::<u64>() {
+                    Ok(n) if n > 0 => Ok(ValidationMode::ReturnRows(n)),
+                    _ => Err(
+                        "invalid VALIDATION_MODE, the number of rows must be a positive integer",
+                    ),
+                }
+            }
+        }
+    }
+}
+
+impl Display for ValidationMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationMode::ReturnRows(n) => write!(f, "RETURN_{n}_ROWS"),
+            ValidationMode::ReturnErrors => write!(f, "RETURN_ERRORS"),
+            ValidationMode::ReturnAllErrors => write!(f, "RETURN_ALL_ERRORS"),
+        }
+    }
+}
+
+/// `ON_ERROR = <mode>`, the action to take when a file fails to load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnErrorMode {
+    /// `CONTINUE`, skip the invalid rows and continue loading the file.
+    Continue,
+    /// `SKIP_FILE`, skip the file on the first error.
+    SkipFile,
+    /// `SKIP_FILE_<n>`, skip the file when the number of errors reaches `n`.
+    SkipFileNum(u64),
+    /// `SKIP_FILE_<n>%`, skip the file when the percentage of error rows reaches `n`.
+    SkipFilePercent(u64),
+    /// `ABORT`, abort the whole statement on the first error.
+    #[default]
+    Abort,
+}
+
+impl FromStr for OnErrorMode {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        const INVALID: &str = "invalid ON_ERROR, expected CONTINUE, SKIP_FILE, SKIP_FILE_<n>, SKIP_FILE_<n>% or ABORT";
+
+        match s.to_uppercase().as_str() {
+            "CONTINUE" => Ok(OnErrorMode::Continue),
+            "SKIP_FILE" => Ok(OnErrorMode::SkipFile),
+            "ABORT" => Ok(OnErrorMode::Abort),
+            s => {
+                let n = s.strip_prefix("SKIP_FILE_").ok_or(INVALID)?;
+                match n.strip_suffix('%') {
+                    Some(n) => match n.parse::<u64>() {
+                        Ok(n) if n > 0 && n <= 100 => Ok(OnErrorMode::SkipFilePercent(n)),
+                        _ => {
+                            Err("invalid ON_ERROR, the percentage must be an integer from 1 to 100")
+                        }
+                    },
+                    None => match n.parse::<u64>() {
+                        Ok(n) if n > 0 => Ok(OnErrorMode::SkipFileNum(n)),
+                        _ => {
+                            Err("invalid ON_ERROR, the number of errors must be a positive integer")
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+impl Display for OnErrorMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OnErrorMode::Continue => write!(f, "CONTINUE"),
+            OnErrorMode::SkipFile => write!(f, "SKIP_FILE"),
+            OnErrorMode::SkipFileNum(n) => write!(f, "SKIP_FILE_{n}"),
+            OnErrorMode::SkipFilePercent(n) => write!(f, "SKIP_FILE_{n}%"),
+            OnErrorMode::Abort => write!(f, "ABORT"),
+        }
+    }
 }
@@ -0,0 +1,640 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use common_exception::ErrorCode;
+use common_io::escape_string_with_quote;
+
+use crate::meta::FileFormatOptionsAst;
+
+/// `TYPE = <type>` of a file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageFileFormatType {
+    Csv,
+    Tsv,
+    NdJson,
+    Json,
+    Parquet,
+    Orc,
+    Xml,
+}
+
+impl FromStr for StageFileFormatType {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "CSV" => Ok(StageFileFormatType::Csv),
+            "TSV" | "TABSEPARATED" => Ok(StageFileFormatType::Tsv),
+            "NDJSON" | "JSONEACHROW" => Ok(StageFileFormatType::NdJson),
+            "JSON" => Ok(StageFileFormatType::Json),
+            "PARQUET" => Ok(StageFileFormatType::Parquet),
+            "ORC" => Ok(StageFileFormatType::Orc),
+            "XML" => Ok(StageFileFormatType::Xml),
+            _ => Err(
+                "unknown file format type, expected CSV, TSV, NDJSON, JSON, PARQUET, ORC or XML",
+            ),
+        }
+    }
+}
+
+impl Display for StageFileFormatType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StageFileFormatType::Csv => write!(f, "CSV"),
+            StageFileFormatType::Tsv => write!(f, "TSV"),
+            StageFileFormatType::NdJson => write!(f, "NDJSON"),
+            StageFileFormatType::Json => write!(f, "JSON"),
+            StageFileFormatType::Parquet => write!(f, "PARQUET"),
+            StageFileFormatType::Orc => write!(f, "ORC"),
+            StageFileFormatType::Xml => write!(f, "XML"),
+        }
+    }
+}
+
+/// `COMPRESSION = <compression>` of a file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageFileCompression {
+    /// Detect the compression by the file extension.
+    Auto,
+    Gzip,
+    Bz2,
+    Brotli,
+    Zstd,
+    Deflate,
+    RawDeflate,
+    Xz,
+    None,
+}
+
+impl FromStr for StageFileCompression {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "AUTO" => Ok(StageFileCompression::Auto),
+            "GZIP" => Ok(StageFileCompression::Gzip),
+            "BZ2" => Ok(StageFileCompression::Bz2),
+            "BROTLI" => Ok(StageFileCompression::Brotli),
+            "ZSTD" => Ok(StageFileCompression::Zstd),
+            "DEFLATE" => Ok(StageFileCompression::Deflate),
+            "RAWDEFLATE" | "RAW_DEFLATE" => Ok(StageFileCompression::RawDeflate),
+            "XZ" => Ok(StageFileCompression::Xz),
+            "NONE" | "" => Ok(StageFileCompression::None),
+            _ => Err(
+                "unknown compression, expected AUTO, GZIP, BZ2, BROTLI, ZSTD, DEFLATE, RAWDEFLATE, XZ or NONE",
+            ),
+        }
+    }
+}
+
+impl Display for StageFileCompression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StageFileCompression::Auto => write!(f, "AUTO"),
+            StageFileCompression::Gzip => write!(f, "GZIP"),
+            StageFileCompression::Bz2 => write!(f, "BZ2"),
+            StageFileCompression::Brotli => write!(f, "BROTLI"),
+            StageFileCompression::Zstd => write!(f, "ZSTD"),
+            StageFileCompression::Deflate => write!(f, "DEFLATE"),
+            StageFileCompression::RawDeflate => write!(f, "RAWDEFLATE"),
+            StageFileCompression::Xz => write!(f, "XZ"),
+            StageFileCompression::None => write!(f, "NONE"),
+        }
+    }
+}
+
+/// Options of `TYPE = CSV`. Options left as `None` take the default value of the engine.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CsvFileFormatParams {
+    pub compression: Option<StageFileCompression>,
+    pub skip_header: Option<u64>,
+    pub field_delimiter: Option<String>,
+    pub record_delimiter: Option<String>,
+    pub quote: Option<String>,
+    pub escape: Option<String>,
+    pub nan_display: Option<String>,
+    pub null_display: Option<String>,
+}
+
+/// Options of `TYPE = TSV`. Options left as `None` take the default value of the engine.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TsvFileFormatParams {
+    pub compression: Option<StageFileCompression>,
+    pub skip_header: Option<u64>,
+    pub field_delimiter: Option<String>,
+    pub record_delimiter: Option<String>,
+    pub quote: Option<String>,
+    pub escape: Option<String>,
+    pub nan_display: Option<String>,
+}
+
+/// Options of `TYPE = NDJSON`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NdJsonFileFormatParams {
+    pub compression: Option<StageFileCompression>,
+}
+
+/// Options of `TYPE = JSON`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct JsonFileFormatParams {
+    pub compression: Option<StageFileCompression>,
+}
+
+/// Options of `TYPE = PARQUET`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ParquetFileFormatParams {}
+
+/// Options of `TYPE = ORC`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OrcFileFormatParams {}
+
+/// Options of `TYPE = XML`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct XmlFileFormatParams {
+    pub compression: Option<StageFileCompression>,
+    pub row_tag: Option<String>,
+}
+
+/// Options without `TYPE`, which are applied on the file format the engine falls
+/// back to, e.g. the file format of the stage.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UntypedFileFormatParams {
+    pub compression: Option<StageFileCompression>,
+    pub skip_header: Option<u64>,
+    pub field_delimiter: Option<String>,
+    pub record_delimiter: Option<String>,
+    pub quote: Option<String>,
+    pub escape: Option<String>,
+    pub nan_display: Option<String>,
+    pub null_display: Option<String>,
+    pub row_tag: Option<String>,
+}
+
+/// The typed options of a file format, e.g. `TYPE = CSV FIELD_DELIMITER = ','`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileFormatParams {
+    Csv(CsvFileFormatParams),
+    Tsv(TsvFileFormatParams),
+    NdJson(NdJsonFileFormatParams),
+    Json(JsonFileFormatParams),
+    Parquet(ParquetFileFormatParams),
+    Orc(OrcFileFormatParams),
+    Xml(XmlFileFormatParams),
+}
+
+/// The options of `FILE_FORMAT = (...)`, which either refers to a named file
+/// format or describes the file format inline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileFormatOptions {
+    /// `FORMAT_NAME = '<name>'`
+    Named(String),
+    Params(FileFormatParams),
+    /// The options without `TYPE`.
+    Untyped(UntypedFileFormatParams),
+}
+
+/// Error of a file format option, with the lowercase option key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileFormatOptionError {
+    UnknownKey(String),
+    /// An invalid value, or an option that is not supported by the file format type.
+    InvalidValue {
+        key: String,
+        reason: &'static str,
+    },
+}
+
+impl FileFormatOptionError {
+    fn invalid(key: &str, reason: &'static str) -> Self {
+        FileFormatOptionError::InvalidValue {
+            key: key.to_string(),
+            reason,
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        match self {
+            FileFormatOptionError::UnknownKey(key) => key,
+            FileFormatOptionError::InvalidValue { key, .. } => key,
+        }
+    }
+}
+
+impl Display for FileFormatOptionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileFormatOptionError::UnknownKey(key) => {
+                write!(f, "unknown file format option `{}`", key.to_uppercase())
+            }
+            FileFormatOptionError::InvalidValue { key, reason } => write!(
+                f,
+                "invalid file format option `{}`: {}",
+                key.to_uppercase(),
+                reason
+            ),
+        }
+    }
+}
+
+impl From<FileFormatOptionError> for ErrorCode {
+    fn from(err: FileFormatOptionError) -> Self {
+        ErrorCode::BadArguments(err.to_string())
+    }
+}
+
+impl FileFormatParams {
+    /// Build the typed options from the `key => value` map, the keys are case-insensitive.
+    pub fn try_from_options(
+        options: &BTreeMap<String, String>,
+    ) -> Result<Self, FileFormatOptionError> {
+        let options = options
+            .iter()
+            .map(|(k, v)| (k.to_lowercase(), v.as_str()))
+            .collect::<BTreeMap<_, _>>();
+        let typ = match options.get("type") {
+            Some(typ) => typ
+                .parse::<StageFileFormatType>()
+                .map_err(|reason| FileFormatOptionError::invalid("type", reason))?,
+            None => return Err(FileFormatOptionError::invalid("type", "the option is required")),
+        };
+        let mut params = FileFormatParams::default_of(typ);
+        for (key, value) in options.iter().filter(|(key, _)| *key != "type") {
+            params.set_option(key, value)?;
+        }
+        Ok(params)
+    }
+
+    /// Convert back to the `key => value` map with lowercase keys.
+    pub fn to_options(&self) -> BTreeMap<String, String> {
+        let mut options = BTreeMap::new();
+        options.insert("type".to_string(), self.format_type().to_string());
+        match self {
+            FileFormatParams::Csv(p) => {
+                insert_option(&mut options, "compression", &p.compression);
+                insert_option(&mut options, "skip_header", &p.skip_header);
+                insert_option(&mut options, "field_delimiter", &p.field_delimiter);
+                insert_option(&mut options, "record_delimiter", &p.record_delimiter);
+                insert_option(&mut options, "quote", &p.quote);
+                insert_option(&mut options, "escape", &p.escape);
+                insert_option(&mut options, "nan_display", &p.nan_display);
+                insert_option(&mut options, "null_display", &p.null_display);
+            }
+            FileFormatParams::Tsv(p) => {
+                insert_option(&mut options, "compression", &p.compression);
+                insert_option(&mut options, "skip_header", &p.skip_header);
+                insert_option(&mut options, "field_delimiter", &p.field_delimiter);
+                insert_option(&mut options, "record_delimiter", &p.record_delimiter);
+                insert_option(&mut options, "quote", &p.quote);
+                insert_option(&mut options, "escape", &p.escape);
+                insert_option(&mut options, "nan_display", &p.nan_display);
+            }
+            FileFormatParams::NdJson(p) => {
+                insert_option(&mut options, "compression", &p.compression);
+            }
+            FileFormatParams::Json(p) => {
+                insert_option(&mut options, "compression", &p.compression);
+            }
+            FileFormatParams::Parquet(_) | FileFormatParams::Orc(_) => {}
+            FileFormatParams::Xml(p) => {
+                insert_option(&mut options, "compression", &p.compression);
+                insert_option(&mut options, "row_tag", &p.row_tag);
+            }
+        }
+        options
+    }
+
+    pub fn format_type(&self) -> StageFileFormatType {
+        match self {
+            FileFormatParams::Csv(_) => StageFileFormatType::Csv,
+            FileFormatParams::Tsv(_) => StageFileFormatType::Tsv,
+            FileFormatParams::NdJson(_) => StageFileFormatType::NdJson,
+            FileFormatParams::Json(_) => StageFileFormatType::Json,
+            FileFormatParams::Parquet(_) => StageFileFormatType::Parquet,
+            FileFormatParams::Orc(_) => StageFileFormatType::Orc,
+            FileFormatParams::Xml(_) => StageFileFormatType::Xml,
+        }
+    }
+
+    pub fn compression(&self) -> Option<StageFileCompression> {
+        match self {
+            FileFormatParams::Csv(p) => p.compression,
+            FileFormatParams::Tsv(p) => p.compression,
+            FileFormatParams::NdJson(p) => p.compression,
+            FileFormatParams::Json(p) => p.compression,
+            FileFormatParams::Xml(p) => p.compression,
+            FileFormatParams::Parquet(_) | FileFormatParams::Orc(_) => None,
+        }
+    }
+
+    fn default_of(typ: StageFileFormatType) -> Self {
+        match typ {
+            StageFileFormatType::Csv => FileFormatParams::Csv(Default::default()),
+            StageFileFormatType::Tsv => FileFormatParams::Tsv(Default::default()),
+            StageFileFormatType::NdJson => FileFormatParams::NdJson(Default::default()),
+            StageFileFormatType::Json => FileFormatParams::Json(Default::default()),
+            StageFileFormatType::Parquet => FileFormatParams::Parquet(Default::default()),
+            StageFileFormatType::Orc => FileFormatParams::Orc(Default::default()),
+            StageFileFormatType::Xml => FileFormatParams::Xml(Default::default()),
+        }
+    }
+
+    fn set_option(&mut self, key: &str, value: &str) -> Result<(), FileFormatOptionError> {
+        let invalid = |reason| FileFormatOptionError::invalid(key, reason);
+        match (self, key) {
+            (FileFormatParams::Csv(p), "compression") => {
+                p.compression = Some(value.parse().map_err(invalid)?)
+            }
+            (FileFormatParams::Csv(p), "skip_header") => {
+                p.skip_header = Some(skip_header(value).map_err(invalid)?)
+            }
+            (FileFormatParams::Csv(p), "field_delimiter") => {
+                p.field_delimiter = Some(field_delimiter(value).map_err(invalid)?)
+            }
+            (FileFormatParams::Csv(p), "record_delimiter") => {
+                p.record_delimiter = Some(record_delimiter(value).map_err(invalid)?)
+            }
+            (FileFormatParams::Csv(p), "quote") => p.quote = Some(quote(value).map_err(invalid)?),
+            (FileFormatParams::Csv(p), "escape") => {
+                p.escape = Some(escape(value).map_err(invalid)?)
+            }
+            (FileFormatParams::Csv(p), "nan_display") => {
+                p.nan_display = Some(nan_display(value).map_err(invalid)?)
+            }
+            (FileFormatParams::Csv(p), "null_display") => p.null_display = Some(value.to_string()),
+            (FileFormatParams::Tsv(p), "compression") => {
+                p.compression = Some(value.parse().map_err(invalid)?)
+            }
+            (FileFormatParams::Tsv(p), "skip_header") => {
+                p.skip_header = Some(skip_header(value).map_err(invalid)?)
+            }
+            (FileFormatParams::Tsv(p), "field_delimiter") => {
+                p.field_delimiter = Some(field_delimiter(value).map_err(invalid)?)
+            }
+            (FileFormatParams::Tsv(p), "record_delimiter") => {
+                p.record_delimiter = Some(record_delimiter(value).map_err(invalid)?)
+            }
+            (FileFormatParams::Tsv(p), "quote") => p.quote = Some(quote(value).map_err(invalid)?),
+            (FileFormatParams::Tsv(p), "escape") => {
+                p.escape = Some(escape(value).map_err(invalid)?)
+            }
+            (FileFormatParams::Tsv(p), "nan_display") => {
+                p.nan_display = Some(nan_display(value).map_err(invalid)?)
+            }
+            (FileFormatParams::NdJson(p), "compression") => {
+                p.compression = Some(value.parse().map_err(invalid)?)
+            }
+            (FileFormatParams::Json(p), "compression") => {
+                p.compression = Some(value.parse().map_err(invalid)?)
+            }
+            (FileFormatParams::Xml(p), "compression") => {
+                p.compression = Some(value.parse().map_err(invalid)?)
+            }
+            (FileFormatParams::Xml(p), "row_tag") => {
+                p.row_tag = Some(row_tag(value).map_err(invalid)?)
+            }
+            (_, key) if FILE_FORMAT_OPTIONS.contains(&key) => {
+                return Err(invalid("option is not supported by this file format type"));
+            }
+            _ => return Err(FileFormatOptionError::UnknownKey(key.to_string())),
+        }
+        Ok(())
+    }
+}
+
+impl Display for FileFormatParams {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TYPE = {}", self.format_type())?;
+        for (k, v) in self.to_options().iter().filter(|(k, _)| *k != "type") {
+            write!(f, " ")?;
+            write_option(f, k, v)?;
+        }
+        Ok(())
+    }
+}
+
+impl UntypedFileFormatParams {
+    fn set_option(&mut self, key: &str, value: &str) -> Result<(), FileFormatOptionError> {
+        let invalid = |reason| FileFormatOptionError::invalid(key, reason);
+        match key {
+            "compression" => self.compression = Some(value.parse().map_err(invalid)?),
+            "skip_header" => self.skip_header = Some(skip_header(value).map_err(invalid)?),
+            "field_delimiter" => {
+                self.field_delimiter = Some(field_delimiter(value).map_err(invalid)?)
+            }
+            "record_delimiter" => {
+                self.record_delimiter = Some(record_delimiter(value).map_err(invalid)?)
+            }
+            "quote" => self.quote = Some(quote(value).map_err(invalid)?),
+            "escape" => self.escape = Some(escape(value).map_err(invalid)?),
+            "nan_display" => self.nan_display = Some(nan_display(value).map_err(invalid)?),
+            "null_display" => self.null_display = Some(value.to_string()),
+            "row_tag" => self.row_tag = Some(row_tag(value).map_err(invalid)?),
+            _ => return Err(FileFormatOptionError::UnknownKey(key.to_string())),
+        }
+        Ok(())
+    }
+
+    /// Convert back to the `key => value` map with lowercase keys.
+    pub fn to_options(&self) -> BTreeMap<String, String> {
+        let mut options = BTreeMap::new();
+        insert_option(&mut options, "compression", &self.compression);
+        insert_option(&mut options, "skip_header", &self.skip_header);
+        insert_option(&mut options, "field_delimiter", &self.field_delimiter);
+        insert_option(&mut options, "record_delimiter", &self.record_delimiter);
+        insert_option(&mut options, "quote", &self.quote);
+        insert_option(&mut options, "escape", &self.escape);
+        insert_option(&mut options, "nan_display", &self.nan_display);
+        insert_option(&mut options, "null_display", &self.null_display);
+        insert_option(&mut options, "row_tag", &self.row_tag);
+        options
+    }
+}
+
+impl Display for UntypedFileFormatParams {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, (k, v)) in self.to_options().iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write_option(f, k, v)?;
+        }
+        Ok(())
+    }
+}
+
+impl FileFormatOptions {
+    /// Build the typed options from the `key => value` map, the keys are case-insensitive.
+    pub fn try_from_options(
+        options: &BTreeMap<String, String>,
+    ) -> Result<Self, FileFormatOptionError> {
+        let has_key = |key: &str| options.keys().any(|k| k.eq_ignore_ascii_case(key));
+        if has_key("format_name") {
+            return match options.iter().next() {
+                Some((_, name)) if options.len() == 1 => Ok(FileFormatOptions::Named(name.clone())),
+                _ => Err(FileFormatOptionError::invalid(
+                    "format_name",
+                    "`FORMAT_NAME` can not be used with other options",
+                )),
+            };
+        }
+        if has_key("type") {
+            return Ok(FileFormatOptions::Params(
+                FileFormatParams::try_from_options(options)?,
+            ));
+        }
+        let mut params = UntypedFileFormatParams::default();
+        for (key, value) in options {
+            params.set_option(&key.to_lowercase(), value)?;
+        }
+        Ok(FileFormatOptions::Untyped(params))
+    }
+
+    /// Convert back to the `key => value` map with lowercase keys.
+    pub fn to_options(&self) -> BTreeMap<String, String> {
+        match self {
+            FileFormatOptions::Named(name) => {
+                BTreeMap::from([("format_name".to_string(), name.clone())])
+            }
+            FileFormatOptions::Params(params) => params.to_options(),
+            FileFormatOptions::Untyped(params) => params.to_options(),
+        }
+    }
+}
+
+impl Display for FileFormatOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileFormatOptions::Named(name) => write!(
+                f,
+                "FORMAT_NAME = '{}'",
+                escape_string_with_quote(name, Some('\''))
+            ),
+            FileFormatOptions::Params(params) => write!(f, "{params}"),
+            FileFormatOptions::Untyped(params) => write!(f, "{params}"),
+        }
+    }
+}
+
+impl From<FileFormatParams> for FileFormatOptionsAst {
+    fn from(params: FileFormatParams) -> Self {
+        FileFormatOptionsAst::new(params.to_options())
+    }
+}
+
+impl TryFrom<FileFormatOptionsAst> for FileFormatParams {
+    type Error = ErrorCode;
+
+    fn try_from(ast: FileFormatOptionsAst) -> Result<Self, Self::Error> {
+        Ok(FileFormatParams::try_from_options(&ast.options)?)
+    }
+}
+
+/// The lowercase keys of all the file format options.
+pub(crate) const FILE_FORMAT_OPTIONS: &[&str] = &[
+    "type",
+    "format_name",
+    "compression",
+    "skip_header",
+    "field_delimiter",
+    "record_delimiter",
+    "quote",
+    "escape",
+    "nan_display",
+    "null_display",
+    "row_tag",
+];
+
+fn insert_option<T: ToString>(options: &mut BTreeMap<String, String>, key: &str, v: &Option<T>) {
+    if let Some(v) = v {
+        options.insert(key.to_string(), v.to_string());
+    }
+}
+
+fn write_option(f: &mut Formatter<'_>, key: &str, value: &str) -> std::fmt::Result {
+    match key {
+        "compression" | "skip_header" => write!(f, "{} = {}", key.to_uppercase(), value),
+        _ => write!(
+            f,
+            "{} = '{}'",
+            key.to_uppercase(),
+            escape_string_with_quote(value, Some('\''))
+        ),
+    }
+}
+
+// `NONE` is accepted as an empty value for the delimiters, quote and escape.
+fn none_as_empty(value: &str) -> &str {
+    if value.eq_ignore_ascii_case("none") {
+        ""
+    } else {
+        value
+    }
+}
+
+fn skip_header(value: &str) -> Result<u64, &'static str> {
+    value
+        .parse::<u64>()
+        .map_err(|_| "`SKIP_HEADER` must be an unsigned integer")
+}
+
+fn field_delimiter(value: &str) -> Result<String, &'static str> {
+    let value = none_as_empty(value);
+    if value.chars().count() > 1 {
+        return Err("`FIELD_DELIMITER` must be a single character");
+    }
+    Ok(value.to_string())
+}
+
+fn record_delimiter(value: &str) -> Result<String, &'static str> {
+    let value = none_as_empty(value);
+    if value.chars().count() > 1 && value != "\r\n" {
+        return Err("`RECORD_DELIMITER` must be a single character or '\\r\\n'");
+    }
+    Ok(value.to_string())
+}
+
+fn quote(value: &str) -> Result<String, &'static str> {
+    let value = none_as_empty(value);
+    if !matches!(value, "" | "'" | "\"" | "`") {
+        return Err("`QUOTE` must be one of ', \" or `");
+    }
+    Ok(value.to_string())
+}
+
+fn escape(value: &str) -> Result<String, &'static str> {
+    let value = none_as_empty(value);
+    if value.chars().count() > 1 {
+        return Err("`ESCAPE` must be a single character");
+    }
+    Ok(value.to_string())
+}
+
+fn row_tag(value: &str) -> Result<String, &'static str> {
+    if value.is_empty() {
+        return Err("`ROW_TAG` must not be empty");
+    }
+    Ok(value.to_string())
+}
+
+fn nan_display(value: &str) -> Result<String, &'static str> {
+    if value.is_empty() {
+        return Err("`NAN_DISPLAY` must not be empty");
+    }
+    Ok(value.to_string())
+}
@@ -12,14 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
 use crate::ast::write_comma_separated_list;
 use crate::ast::write_dot_separated_list;
+use crate::ast::FileFormatOptions;
 use crate::ast::Hint;
 use crate::ast::Identifier;
+use crate::ast::OnErrorMode;
 use crate::ast::Query;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -68,12 +69,15 @@ pub enum InsertSource {
         start: usize,
     },
     StreamingV2 {
-        settings: BTreeMap<String, String>,
-        on_error_mode: Option<String>,
+        settings: FileFormatOptions,
+        on_error_mode: Option<OnErrorMode>,
         start: usize,
     },
+    /// The rows are left in the source from byte offset `start`, right after the
+    /// `VALUES` keyword, and can be read with
+    /// [`ValuesRowReader`](crate::parser::ValuesRowReader).
     Values {
-        rest_str: String,
+        start: usize,
     },
     Select {
         query: Box<Query>,
@@ -93,18 +97,10 @@ impl Display for InsertSource {
                 on_error_mode,
                 start: _,
             } => {
-                write!(f, " FILE_FORMAT = (")?;
-                for (k, v) in settings.iter() {
-                    write!(f, " {} = '{}'", k, v)?;
-                }
-                write!(f, " )")?;
-                write!(
-                    f,
-                    " ON_ERROR = '{}'",
-                    on_error_mode.as_ref().unwrap_or(&"Abort".to_string())
-                )
+                write!(f, " FILE_FORMAT = ({settings})")?;
+                write!(f, " ON_ERROR = '{}'", on_error_mode.unwrap_or_default())
             }
-            InsertSource::Values { rest_str } => write!(f, "VALUES {rest_str}"),
+            InsertSource::Values { .. } => write!(f, "VALUES"),
             InsertSource::Select { query } => write!(f, "{query}"),
         }
     }
@@ -12,7 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
@@ -23,7 +22,9 @@ use super::Hint;
 use crate::ast::write_comma_separated_list;
 use crate::ast::write_dot_separated_list;
 use crate::ast::Expr;
+use crate::ast::FileFormatOptions;
 use crate::ast::Identifier;
+use crate::ast::OnErrorMode;
 use crate::ast::Query;
 use crate::ast::TableAlias;
 use crate::ast::TableReference;
@@ -95,7 +96,11 @@ pub struct MergeIntoStmt {
 
 impl Display for MergeIntoStmt {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        write!(f, "MERGE INTO  ")?;
+        write!(f, "MERGE ")?;
+        if let Some(hints) = &self.hints {
+            write!(f, "{} ", hints)?;
+        }
+        write!(f, "INTO ")?;
         write_dot_separated_list(
             f,
             self.catalog
@@ -103,17 +108,20 @@ impl Display for MergeIntoStmt {
                 .chain(&self.database)
                 .chain(Some(&self.table_ident)),
         )?;
+        if let Some(alias) = &self.alias_target {
+            write!(f, " AS {alias}")?;
+        }
 
         write!(f, " USING {} ON {}", self.source, self.join_expr)?;
 
         for clause in &self.merge_options {
             match clause {
                 MergeOption::Match(match_clause) => {
-                    write!(f, " WHEN MATCHED ")?;
+                    write!(f, " WHEN MATCHED")?;
                     if let Some(e) = &match_clause.selection {
-                        write!(f, " AND {} ", e)?;
+                        write!(f, " AND {}", e)?;
                     }
-                    write!(f, " THEN ")?;
+                    write!(f, " THEN")?;
 
                     match &match_clause.operation {
                         MatchOperation::Update { update_list } => {
@@ -121,16 +129,16 @@ impl Display for MergeIntoStmt {
                             write_comma_separated_list(f, update_list)?;
                         }
                         MatchOperation::Delete => {
-                            write!(f, " DELETE ")?;
+                            write!(f, " DELETE")?;
                         }
                     }
                 }
                 MergeOption::Unmatch(unmatch_clause) => {
-                    write!(f, " WHEN NOT MATCHED ")?;
+                    write!(f, " WHEN NOT MATCHED")?;
                     if let Some(e) = &unmatch_clause.selection {
-                        write!(f, " AND {} ", e)?;
+                        write!(f, " AND {}", e)?;
                     }
-                    write!(f, " THEN INSERT ")?;
+                    write!(f, " THEN INSERT")?;
                     if let Some(columns) = &unmatch_clause.insert_operation.columns {
                         if !columns.is_empty() {
                             write!(f, " (")?;
@@ -138,15 +146,9 @@ impl Display for MergeIntoStmt {
                             write!(f, ")")?;
                         }
                     }
-                    write!(f, "VALUES")?;
-                    for (i, value) in unmatch_clause.insert_operation.values.iter().enumerate() {
-                        if i > 0 {
-                            write!(f, ", ")?;
-                        }
-                        write!(f, "(")?;
-                        write_comma_separated_list(f, vec![value])?;
-                        write!(f, ")")?;
-                    }
+                    write!(f, " VALUES (")?;
+                    write_comma_separated_list(f, &unmatch_clause.insert_operation.values)?;
+                    write!(f, ")")?;
                 }
             }
         }
@@ -199,10 +201,11 @@ impl MergeIntoStmt {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 pub enum MergeSource {
     StreamingV2 {
-        settings: BTreeMap<String, String>,
-        on_error_mode: Option<String>,
+        settings: FileFormatOptions,
+        on_error_mode: Option<OnErrorMode>,
         start: usize,
     },
 
@@ -213,8 +216,8 @@ pub enum MergeSource {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct StreamingSource {
-    settings: BTreeMap<String, String>,
-    on_error_mode: Option<String>,
+    settings: FileFormatOptions,
+    on_error_mode: Option<OnErrorMode>,
     start: usize,
 }
 
@@ -244,19 +247,11 @@ impl Display for MergeSource {
                 on_error_mode,
                 start: _,
             } => {
-                write!(f, " FILE_FORMAT = (")?;
-                for (k, v) in settings.iter() {
-                    write!(f, " {} = '{}'", k, v)?;
-                }
-                write!(f, " )")?;
-                write!(
-                    f,
-                    " ON_ERROR = '{}'",
-                    on_error_mode.as_ref().unwrap_or(&"Abort".to_string())
-                )
+                write!(f, " FILE_FORMAT = ({settings})")?;
+                write!(f, " ON_ERROR = '{}'", on_error_mode.unwrap_or_default())
             }
 
-            MergeSource::Select { query } => write!(f, "{query}"),
+            MergeSource::Select { query } => write!(f, "({query})"),
         }
     }
 }
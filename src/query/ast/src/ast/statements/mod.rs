@@ -19,6 +19,7 @@ mod copy;
 mod data_mask;
 mod database;
 mod explain;
+mod file_format;
 mod hint;
 mod index;
 mod insert;
@@ -31,6 +32,7 @@ mod share;
 mod show;
 mod stage;
 mod statement;
+mod storage;
 mod table;
 mod udf;
 mod unset;
@@ -46,6 +48,7 @@ pub use copy::*;
 pub use data_mask::*;
 pub use database::*;
 pub use explain::*;
+pub use file_format::*;
 pub use hint::*;
 pub use index::*;
 pub use insert::*;
@@ -58,6 +61,7 @@ pub use share::*;
 pub use show::*;
 pub use stage::*;
 pub use statement::*;
+pub use storage::*;
 pub use table::*;
 pub use udf::*;
 pub use unset::*;
@@ -17,7 +17,10 @@ use std::default::Default;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+use crate::ast::FileFormatOptions;
+use crate::ast::OnErrorMode;
 use crate::ast::UriLocation;
+use crate::ast::ValidationMode;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CreateStageStmt {
@@ -26,10 +29,10 @@ pub struct CreateStageStmt {
 
     pub location: Option<UriLocation>,
 
-    pub file_format_options: BTreeMap<String, String>,
-    pub on_error: String,
+    pub file_format_options: Option<FileFormatOptions>,
+    pub on_error: Option<OnErrorMode>,
     pub size_limit: usize,
-    pub validation_mode: String,
+    pub validation_mode: Option<ValidationMode>,
     pub comments: String,
 }
 
@@ -46,24 +49,20 @@ impl Display for CreateStageStmt {
             write!(f, "{ul}")?;
         }
 
-        if !self.file_format_options.is_empty() {
-            write!(f, " FILE_FORMAT = (")?;
-            for (k, v) in self.file_format_options.iter() {
-                write!(f, " {} = '{}'", k, v)?;
-            }
-            write!(f, " )")?;
+        if let Some(file_format_options) = &self.file_format_options {
+            write!(f, " FILE_FORMAT = ({file_format_options})")?;
         }
 
-        if !self.on_error.is_empty() {
-            write!(f, " ON_ERROR = '{}'", self.on_error)?;
+        if let Some(on_error) = &self.on_error {
+            write!(f, " ON_ERROR = '{on_error}'")?;
         }
 
         if self.size_limit != 0 {
             write!(f, " SIZE_LIMIT = {}", self.size_limit)?;
         }
 
-        if !self.validation_mode.is_empty() {
-            write!(f, " VALIDATION_MODE = {}", self.validation_mode)?;
+        if let Some(validation_mode) = &self.validation_mode {
+            write!(f, " VALIDATION_MODE = {validation_mode}")?;
         }
 
         if !self.comments.is_empty() {
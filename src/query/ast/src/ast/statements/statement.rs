@@ -15,16 +15,14 @@
 use std::fmt::Display;
 use std::fmt::Formatter;
 
-use crate::meta::FileFormatOptionsAst;
-use crate::meta::principal_identity::PrincipalIdentity;
-use crate::meta::user_identity::UserIdentity;
-
 use super::merge_into::MergeIntoStmt;
 use super::*;
 use crate::ast::Expr;
 use crate::ast::Identifier;
 use crate::ast::Query;
 use crate::ast::TableReference;
+use crate::meta::principal_identity::PrincipalIdentity;
+use crate::meta::user_identity::UserIdentity;
 
 // SQL statement
 #[allow(clippy::large_enum_variant)]
@@ -193,7 +191,7 @@ pub enum Statement {
     CreateFileFormat {
         if_not_exists: bool,
         name: String,
-        file_format_options: FileFormatOptionsAst,
+        file_format_options: FileFormatParams,
     },
     DropFileFormat {
         if_exists: bool,
@@ -480,7 +478,7 @@ impl Display for Statement {
                 name,
                 file_format_options,
             } => {
-                write!(f, "CREATE FILE_FORMAT")?;
+                write!(f, "CREATE FILE FORMAT")?;
                 if *if_not_exists {
                     write!(f, " IF NOT EXISTS")?;
                 }
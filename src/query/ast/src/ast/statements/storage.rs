@@ -0,0 +1,441 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use common_exception::ErrorCode;
+
+/// The storage protocol of an external location, e.g. `s3` in `'s3://bucket/path'`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageProtocol {
+    S3,
+    Gcs,
+    Azblob,
+    Hdfs,
+    Webhdfs,
+    Oss,
+    Obs,
+    Ipfs,
+    /// `http` or `https`
+    Https,
+    Fs,
+}
+
+/// A connection option that is known by a storage protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionKey {
+    /// The canonical lowercase name of the option.
+    pub name: &'static str,
+    /// Other accepted names of the option, e.g. `aws_key_id` for `access_key_id`.
+    pub aliases: &'static [&'static str],
+    /// Whether the value is a credential that must be masked in logs.
+    pub secret: bool,
+}
+
+const fn key(name: &'static str, aliases: &'static [&'static str], secret: bool) -> ConnectionKey {
+    ConnectionKey {
+        name,
+        aliases,
+        secret,
+    }
+}
+
+const S3_KEYS: &[ConnectionKey] = &[
+    key("endpoint_url", &[], false),
+    key("region", &[], false),
+    key("access_key_id", &["aws_key_id"], true),
+    key("secret_access_key", &["aws_secret_key"], true),
+    key("session_token", &["aws_token", "security_token"], true),
+    key("master_key", &[], true),
+    key("enable_virtual_host_style", &[], false),
+    key("role_arn", &["aws_role_arn"], false),
+    key("external_id", &["aws_external_id"], true),
+    key("allow_anonymous", &[], false),
+];
+
+const GCS_KEYS: &[ConnectionKey] = &[
+    key("endpoint_url", &[], false),
+    key("credential", &[], true),
+];
+
+const AZBLOB_KEYS: &[ConnectionKey] = &[
+    key("endpoint_url", &[], false),
+    key("account_name", &[], false),
+    key("account_key", &[], true),
+];
+
+const HDFS_KEYS: &[ConnectionKey] = &[key("name_node", &[], false)];
+
+const WEBHDFS_KEYS: &[ConnectionKey] = &[key("https", &[], false), key("delegation", &[], true)];
+
+const OSS_KEYS: &[ConnectionKey] = &[
+    key("endpoint_url", &[], false),
+    key("presign_endpoint_url", &[], false),
+    key("access_key_id", &[], true),
+    key("access_key_secret", &[], true),
+];
+
+const OBS_KEYS: &[ConnectionKey] = &[
+    key("endpoint_url", &[], false),
+    key("access_key_id", &[], true),
+    key("secret_access_key", &[], true),
+];
+
+const IPFS_KEYS: &[ConnectionKey] = &[key("endpoint_url", &[], false)];
+
+impl StorageProtocol {
+    /// Return the protocol of the URI scheme, which is case-insensitive.
+    pub fn from_scheme(scheme: &str) -> Option<Self> {
+        match scheme.to_lowercase().as_str() {
+            "s3" => Some(StorageProtocol::S3),
+            "gcs" => Some(StorageProtocol::Gcs),
+            "azblob" => Some(StorageProtocol::Azblob),
+            "hdfs" => Some(StorageProtocol::Hdfs),
+            "webhdfs" => Some(StorageProtocol::Webhdfs),
+            "oss" => Some(StorageProtocol::Oss),
+            "obs" => Some(StorageProtocol::Obs),
+            "ipfs" => Some(StorageProtocol::Ipfs),
+            "http" | "https" => Some(StorageProtocol::Https),
+            "fs" => Some(StorageProtocol::Fs),
+            _ => None,
+        }
+    }
+
+    /// The connection options accepted by the protocol.
+    pub fn connection_keys(&self) -> &'static [ConnectionKey] {
+        match self {
+            StorageProtocol::S3 => S3_KEYS,
+            StorageProtocol::Gcs => GCS_KEYS,
+            StorageProtocol::Azblob => AZBLOB_KEYS,
+            StorageProtocol::Hdfs => HDFS_KEYS,
+            StorageProtocol::Webhdfs => WEBHDFS_KEYS,
+            StorageProtocol::Oss => OSS_KEYS,
+            StorageProtocol::Obs => OBS_KEYS,
+            StorageProtocol::Ipfs => IPFS_KEYS,
+            StorageProtocol::Https | StorageProtocol::Fs => &[],
+        }
+    }
+
+    /// Find the known connection option by its name or alias, case-insensitive.
+    pub fn connection_key(&self, name: &str) -> Option<&'static ConnectionKey> {
+        let name = name.to_lowercase();
+        self.connection_keys()
+            .iter()
+            .find(|key| key.name == name || key.aliases.contains(&name.as_str()))
+    }
+
+    /// Whether the value of the connection option should be masked. Unknown
+    /// options are treated as secrets.
+    pub fn is_secret(&self, name: &str) -> bool {
+        self.connection_key(name)
+            .map(|key| key.secret)
+            .unwrap_or(true)
+    }
+}
+
+impl Display for StorageProtocol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageProtocol::S3 => write!(f, "s3"),
+            StorageProtocol::Gcs => write!(f, "gcs"),
+            StorageProtocol::Azblob => write!(f, "azblob"),
+            StorageProtocol::Hdfs => write!(f, "hdfs"),
+            StorageProtocol::Webhdfs => write!(f, "webhdfs"),
+            StorageProtocol::Oss => write!(f, "oss"),
+            StorageProtocol::Obs => write!(f, "obs"),
+            StorageProtocol::Ipfs => write!(f, "ipfs"),
+            StorageProtocol::Https => write!(f, "https"),
+            StorageProtocol::Fs => write!(f, "fs"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StorageS3Params {
+    pub endpoint_url: Option<String>,
+    pub region: Option<String>,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    pub session_token: Option<String>,
+    pub master_key: Option<String>,
+    pub enable_virtual_host_style: Option<bool>,
+    pub role_arn: Option<String>,
+    pub external_id: Option<String>,
+    pub allow_anonymous: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StorageGcsParams {
+    pub endpoint_url: Option<String>,
+    pub credential: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StorageAzblobParams {
+    pub endpoint_url: Option<String>,
+    pub account_name: Option<String>,
+    pub account_key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StorageHdfsParams {
+    pub name_node: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StorageWebhdfsParams {
+    pub https: Option<bool>,
+    pub delegation: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StorageOssParams {
+    pub endpoint_url: String,
+    pub presign_endpoint_url: Option<String>,
+    pub access_key_id: Option<String>,
+    pub access_key_secret: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StorageObsParams {
+    pub endpoint_url: String,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StorageIpfsParams {
+    pub endpoint_url: Option<String>,
+}
+
+/// The validated connection of an external location, the options are checked
+/// against the keys known by the protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageParams {
+    S3(StorageS3Params),
+    Gcs(StorageGcsParams),
+    Azblob(StorageAzblobParams),
+    Hdfs(StorageHdfsParams),
+    Webhdfs(StorageWebhdfsParams),
+    Oss(StorageOssParams),
+    Obs(StorageObsParams),
+    Ipfs(StorageIpfsParams),
+    Https,
+    Fs,
+}
+
+/// Error of an invalid connection of an external location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageParamsError {
+    /// The connection option that is invalid, or `None` if the error is about
+    /// the location itself, e.g. an unsupported protocol or a missing option.
+    pub key: Option<String>,
+    pub reason: &'static str,
+}
+
+impl StorageParamsError {
+    fn new(key: Option<&str>, reason: &'static str) -> Self {
+        StorageParamsError {
+            key: key.map(|key| key.to_string()),
+            reason,
+        }
+    }
+}
+
+impl Display for StorageParamsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.key {
+            Some(key) => write!(
+                f,
+                "invalid connection option `{}`: {}",
+                key.to_uppercase(),
+                self.reason
+            ),
+            None => write!(f, "invalid location: {}", self.reason),
+        }
+    }
+}
+
+impl From<StorageParamsError> for ErrorCode {
+    fn from(err: StorageParamsError) -> Self {
+        ErrorCode::BadArguments(err.to_string())
+    }
+}
+
+impl StorageParams {
+    /// Validate the connection options of a location with the protocol of `scheme`.
+    pub fn try_from_connection(
+        scheme: &str,
+        conns: &BTreeMap<String, String>,
+    ) -> Result<Self, StorageParamsError> {
+        let protocol = StorageProtocol::from_scheme(scheme)
+            .ok_or_else(|| StorageParamsError::new(None, "unsupported storage protocol"))?;
+
+        // Resolve the aliases into the canonical names.
+        let mut options: BTreeMap<&'static str, String> = BTreeMap::new();
+        for (k, v) in conns {
+            let key = protocol.connection_key(k).ok_or_else(|| {
+                StorageParamsError::new(
+                    Some(k),
+                    "connection option is not supported by the storage protocol",
+                )
+            })?;
+            if options.insert(key.name, v.clone()).is_some() {
+                return Err(StorageParamsError::new(
+                    Some(k),
+                    "duplicate connection option",
+                ));
+            }
+        }
+        let mut take = |name: &str| options.remove(name);
+
+        let params = match protocol {
+            StorageProtocol::S3 => StorageParams::S3(StorageS3Params {
+                endpoint_url: take("endpoint_url"),
+                region: take("region"),
+                access_key_id: take("access_key_id"),
+                secret_access_key: take("secret_access_key"),
+                session_token: take("session_token"),
+                master_key: take("master_key"),
+                enable_virtual_host_style: take("enable_virtual_host_style")
+                    .map(|v| parse_bool(conns, "enable_virtual_host_style", &v))
+                    .transpose()?,
+                role_arn: take("role_arn"),
+                external_id: take("external_id"),
+                allow_anonymous: take("allow_anonymous")
+                    .map(|v| parse_bool(conns, "allow_anonymous", &v))
+                    .transpose()?,
+            }),
+            StorageProtocol::Gcs => StorageParams::Gcs(StorageGcsParams {
+                endpoint_url: take("endpoint_url"),
+                credential: take("credential"),
+            }),
+            StorageProtocol::Azblob => StorageParams::Azblob(StorageAzblobParams {
+                endpoint_url: take("endpoint_url"),
+                account_name: take("account_name"),
+                account_key: take("account_key"),
+            }),
+            StorageProtocol::Hdfs => StorageParams::Hdfs(StorageHdfsParams {
+                name_node: take("name_node"),
+            }),
+            StorageProtocol::Webhdfs => StorageParams::Webhdfs(StorageWebhdfsParams {
+                https: take("https")
+                    .map(|v| parse_bool(conns, "https", &v))
+                    .transpose()?,
+                delegation: take("delegation"),
+            }),
+            StorageProtocol::Oss => StorageParams::Oss(StorageOssParams {
+                endpoint_url: take("endpoint_url").ok_or_else(|| {
+                    StorageParamsError::new(None, "`ENDPOINT_URL` is required by oss")
+                })?,
+                presign_endpoint_url: take("presign_endpoint_url"),
+                access_key_id: take("access_key_id"),
+                access_key_secret: take("access_key_secret"),
+            }),
+            StorageProtocol::Obs => StorageParams::Obs(StorageObsParams {
+                endpoint_url: take("endpoint_url").ok_or_else(|| {
+                    StorageParamsError::new(None, "`ENDPOINT_URL` is required by obs")
+                })?,
+                access_key_id: take("access_key_id"),
+                secret_access_key: take("secret_access_key"),
+            }),
+            StorageProtocol::Ipfs => StorageParams::Ipfs(StorageIpfsParams {
+                endpoint_url: take("endpoint_url"),
+            }),
+            StorageProtocol::Https => StorageParams::Https,
+            StorageProtocol::Fs => StorageParams::Fs,
+        };
+
+        params.check_credentials(conns)?;
+        Ok(params)
+    }
+
+    pub fn protocol(&self) -> StorageProtocol {
+        match self {
+            StorageParams::S3(_) => StorageProtocol::S3,
+            StorageParams::Gcs(_) => StorageProtocol::Gcs,
+            StorageParams::Azblob(_) => StorageProtocol::Azblob,
+            StorageParams::Hdfs(_) => StorageProtocol::Hdfs,
+            StorageParams::Webhdfs(_) => StorageProtocol::Webhdfs,
+            StorageParams::Oss(_) => StorageProtocol::Oss,
+            StorageParams::Obs(_) => StorageProtocol::Obs,
+            StorageParams::Ipfs(_) => StorageProtocol::Ipfs,
+            StorageParams::Https => StorageProtocol::Https,
+            StorageParams::Fs => StorageProtocol::Fs,
+        }
+    }
+
+    // The secret of a key pair can not be used without the id and vice versa.
+    fn check_credentials(
+        &self,
+        conns: &BTreeMap<String, String>,
+    ) -> Result<(), StorageParamsError> {
+        let (id, secret) = match self {
+            StorageParams::S3(p) => (
+                ("access_key_id", p.access_key_id.is_some()),
+                ("secret_access_key", p.secret_access_key.is_some()),
+            ),
+            StorageParams::Azblob(p) => (
+                ("account_name", p.account_name.is_some()),
+                ("account_key", p.account_key.is_some()),
+            ),
+            StorageParams::Oss(p) => (
+                ("access_key_id", p.access_key_id.is_some()),
+                ("access_key_secret", p.access_key_secret.is_some()),
+            ),
+            StorageParams::Obs(p) => (
+                ("access_key_id", p.access_key_id.is_some()),
+                ("secret_access_key", p.secret_access_key.is_some()),
+            ),
+            _ => return Ok(()),
+        };
+        match (id, secret) {
+            ((id, true), (_, false)) => Err(StorageParamsError::new(
+                Some(self.option_name(conns, id)),
+                "the secret of the credential is missing",
+            )),
+            ((_, false), (secret, true)) => Err(StorageParamsError::new(
+                Some(self.option_name(conns, secret)),
+                "the id of the credential is missing",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    // Return the name of the option as written by the user, which may be an alias.
+    fn option_name<'a>(&self, conns: &'a BTreeMap<String, String>, name: &'a str) -> &'a str {
+        let protocol = self.protocol();
+        conns
+            .keys()
+            .find(|k| protocol.connection_key(k).map(|key| key.name) == Some(name))
+            .map(|k| k.as_str())
+            .unwrap_or(name)
+    }
+}
+
+fn parse_bool(
+    conns: &BTreeMap<String, String>,
+    name: &str,
+    value: &str,
+) -> Result<bool, StorageParamsError> {
+    value.to_lowercase().parse::<bool>().map_err(|_| {
+        let key = conns
+            .keys()
+            .find(|k| k.eq_ignore_ascii_case(name))
+            .map(|k| k.as_str());
+        StorageParamsError::new(key.or(Some(name)), "expected `true` or `false`")
+    })
+}
@@ -15,10 +15,12 @@
 use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::fmt::Formatter;
-use std::format;
+
+use common_io::escape_string_with_quote;
 
 use crate::ast::statements::show::ShowLimit;
 use crate::ast::write_comma_separated_list;
+use crate::ast::write_comma_separated_map;
 use crate::ast::write_dot_separated_list;
 use crate::ast::write_space_separated_map;
 use crate::ast::Expr;
@@ -358,8 +360,9 @@ impl Display for AlterTableAction {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
             AlterTableAction::SetOptions { set_options } => {
-                write!(f, "SET OPTIONS: ").expect("Set Options Write Error ");
-                write_space_separated_map(f, set_options.iter())
+                write!(f, "SET OPTIONS (")?;
+                write_comma_separated_map(f, set_options)?;
+                write!(f, ")")
             }
             AlterTableAction::RenameTable { new_table } => {
                 write!(f, "RENAME TO {new_table}")
@@ -381,8 +384,9 @@ impl Display for AlterTableAction {
                 write!(f, "DROP COLUMN {column}")
             }
             AlterTableAction::AlterTableClusterKey { cluster_by } => {
-                write!(f, "CLUSTER BY ")?;
-                write_comma_separated_list(f, cluster_by)
+                write!(f, "CLUSTER BY (")?;
+                write_comma_separated_list(f, cluster_by)?;
+                write!(f, ")")
             }
             AlterTableAction::DropTableClusterKey => {
                 write!(f, "DROP CLUSTER KEY")
@@ -741,7 +745,11 @@ impl Display for ColumnDefinition {
             write!(f, "{expr}")?;
         }
         if let Some(comment) = &self.comment {
-            write!(f, " COMMENT '{comment}'")?;
+            write!(
+                f,
+                " COMMENT '{}'",
+                escape_string_with_quote(comment, Some('\''))
+            )?;
         }
         Ok(())
     }
@@ -769,34 +777,12 @@ impl Display for ModifyColumnAction {
                 write!(f, "{} UNSET MASKING POLICY", column)?
             }
             ModifyColumnAction::SetDataType(column_def_vec) => {
-                let ret = column_def_vec
-                    .iter()
-                    .enumerate()
-                    .map(|(i, column_def)| {
-                        let default_expr_str = match &column_def.expr {
-                            Some(default_expr) => default_expr.to_string(),
-                            None => "".to_string(),
-                        };
-                        let comment = match &column_def.comment {
-                            Some(comment) => format!(" COMMENT {}", comment),
-                            None => "".to_string(),
-                        };
-                        if i > 0 {
-                            format!(
-                                " COLUMN {} {}{}{}",
-                                column_def.name, column_def.data_type, default_expr_str, comment
-                            )
-                        } else {
-                            format!(
-                                "{} {}{}{}",
-                                column_def.name, column_def.data_type, default_expr_str, comment
-                            )
-                        }
-                    })
-                    .collect::<Vec<_>>()
-                    .join(",");
-
-                write!(f, "{}", ret)?
+                for (i, column_def) in column_def_vec.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", COLUMN ")?;
+                    }
+                    write!(f, "{column_def}")?;
+                }
             }
             ModifyColumnAction::ConvertStoredComputedColumn(column) => {
                 write!(f, "{} DROP STORED", column)?
@@ -15,6 +15,8 @@
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+use common_io::escape_string_with_quote;
+
 use crate::ast::write_comma_separated_list;
 use crate::ast::Expr;
 use crate::ast::Identifier;
@@ -72,7 +74,9 @@ impl Display for UDFDefinition {
                 write_comma_separated_list(f, arg_types)?;
                 write!(
                     f,
-                    ") RETURNS {return_type} LANGUAGE {language} HANDLER = {handler} ADDRESS = {address}"
+                    ") RETURNS {return_type} LANGUAGE {language} HANDLER = '{}' ADDRESS = '{}'",
+                    escape_string_with_quote(handler, Some('\'')),
+                    escape_string_with_quote(address, Some('\''))
                 )?;
             }
         }
@@ -88,7 +92,11 @@ impl Display for CreateUDFStmt {
         }
         write!(f, " {} {}", self.udf_name, self.definition)?;
         if let Some(description) = &self.description {
-            write!(f, " DESC = '{description}'")?;
+            write!(
+                f,
+                " DESC = '{}'",
+                escape_string_with_quote(description, Some('\''))
+            )?;
         }
         Ok(())
     }
@@ -99,7 +107,11 @@ impl Display for AlterUDFStmt {
         write!(f, "ALTER FUNCTION")?;
         write!(f, " {} {}", self.udf_name, self.definition)?;
         if let Some(description) = &self.description {
-            write!(f, " DESC = '{description}'")?;
+            write!(
+                f,
+                " DESC = '{}'",
+                escape_string_with_quote(description, Some('\''))
+            )?;
         }
         Ok(())
     }
@@ -0,0 +1,370 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::ast::*;
+use crate::parser::quote::need_quote_ident;
+use crate::parser::token::Tokenizer;
+
+/// Builds an identifier, quoted with `"` if it is not a valid bare identifier or
+/// is a reserved keyword.
+pub fn ident(name: &str) -> Identifier {
+    let quote = if need_quote_ident(name) || is_reserved_keyword(name) {
+        Some('"')
+    } else {
+        None
+    };
+    Identifier::from_name_with_quoted(name, quote)
+}
+
+fn is_reserved_keyword(name: &str) -> bool {
+    match Tokenizer::new(name).next() {
+        Some(Ok(token)) => {
+            token.kind.is_reserved_ident(false)
+                || token.kind.is_reserved_ident(true)
+                || token.kind.is_reserved_function_name()
+        }
+        _ => false,
+    }
+}
+
+/// A column reference, e.g. `col("a")`.
+pub fn col(name: &str) -> Expr {
+    Expr::ColumnRef {
+        span: None,
+        database: None,
+        table: None,
+        column: ColumnID::Name(ident(name)),
+    }
+}
+
+/// A column reference qualified by a table, e.g. `qualified_col("t", "a")`.
+pub fn qualified_col(table: &str, name: &str) -> Expr {
+    Expr::ColumnRef {
+        span: None,
+        database: None,
+        table: Some(ident(table)),
+        column: ColumnID::Name(ident(name)),
+    }
+}
+
+/// A literal, e.g. `lit(5)`, `lit("x")` or `lit(true)`.
+pub fn lit(value: impl IntoLiteral) -> Expr {
+    value.into_literal()
+}
+
+/// A value that can be written as a literal.
+pub trait IntoLiteral {
+    fn into_literal(self) -> Expr;
+}
+
+impl IntoLiteral for u64 {
+    fn into_literal(self) -> Expr {
+        Expr::Literal {
+            span: None,
+            lit: Literal::UInt64(self),
+        }
+    }
+}
+
+impl IntoLiteral for u32 {
+    fn into_literal(self) -> Expr {
+        u64::from(self).into_literal()
+    }
+}
+
+impl IntoLiteral for i64 {
+    fn into_literal(self) -> Expr {
+        let abs = self.unsigned_abs().into_literal();
+        if self < 0 {
+            Expr::UnaryOp {
+                span: None,
+                op: UnaryOperator::Minus,
+                expr: Box::new(abs),
+            }
+        } else {
+            abs
+        }
+    }
+}
+
+impl IntoLiteral for i32 {
+    fn into_literal(self) -> Expr {
+        i64::from(self).into_literal()
+    }
+}
+
+impl IntoLiteral for f64 {
+    fn into_literal(self) -> Expr {
+        Expr::Literal {
+            span: None,
+            lit: Literal::Float64(self),
+        }
+    }
+}
+
+impl IntoLiteral for bool {
+    fn into_literal(self) -> Expr {
+        Expr::Literal {
+            span: None,
+            lit: Literal::Boolean(self),
+        }
+    }
+}
+
+impl IntoLiteral for String {
+    fn into_literal(self) -> Expr {
+        Expr::Literal {
+            span: None,
+            lit: Literal::String(self),
+        }
+    }
+}
+
+impl IntoLiteral for &str {
+    fn into_literal(self) -> Expr {
+        self.to_string().into_literal()
+    }
+}
+
+pub fn null() -> Expr {
+    Expr::Literal {
+        span: None,
+        lit: Literal::Null,
+    }
+}
+
+/// A function call, e.g. `func("sum", [col("a")])`.
+pub fn func(name: &str, args: impl IntoIterator<Item = Expr>) -> Expr {
+    Expr::FunctionCall {
+        span: None,
+        distinct: false,
+        name: ident(name),
+        args: args.into_iter().collect(),
+        params: vec![],
+        window: None,
+        lambda: None,
+    }
+}
+
+/// `COUNT(*)`.
+pub fn count_all() -> Expr {
+    Expr::CountAll {
+        span: None,
+        window: None,
+    }
+}
+
+pub fn not(expr: Expr) -> Expr {
+    Expr::UnaryOp {
+        span: None,
+        op: UnaryOperator::Not,
+        expr: Box::new(expr),
+    }
+}
+
+/// `EXISTS (<query>)`.
+pub fn exists(query: impl Into<Query>) -> Expr {
+    Expr::Exists {
+        span: None,
+        not: false,
+        subquery: Box::new(query.into()),
+    }
+}
+
+/// A scalar subquery.
+pub fn subquery(query: impl Into<Query>) -> Expr {
+    Expr::Subquery {
+        span: None,
+        modifier: None,
+        subquery: Box::new(query.into()),
+    }
+}
+
+/// `*` in a select list.
+pub fn star() -> SelectTarget {
+    SelectTarget::QualifiedName {
+        qualified: vec![Indirection::Star(None)],
+        exclude: None,
+    }
+}
+
+/// `<table>.*` in a select list.
+pub fn qualified_star(table: &str) -> SelectTarget {
+    SelectTarget::QualifiedName {
+        qualified: vec![
+            Indirection::Identifier(ident(table)),
+            Indirection::Star(None),
+        ],
+        exclude: None,
+    }
+}
+
+impl Expr {
+    fn binary(self, op: BinaryOperator, right: Expr) -> Expr {
+        Expr::BinaryOp {
+            span: None,
+            op,
+            left: Box::new(self),
+            right: Box::new(right),
+        }
+    }
+
+    pub fn eq(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::Eq, right)
+    }
+
+    pub fn not_eq(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::NotEq, right)
+    }
+
+    pub fn gt(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::Gt, right)
+    }
+
+    pub fn gte(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::Gte, right)
+    }
+
+    pub fn lt(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::Lt, right)
+    }
+
+    pub fn lte(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::Lte, right)
+    }
+
+    pub fn and(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::And, right)
+    }
+
+    pub fn or(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::Or, right)
+    }
+
+    pub fn plus(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::Plus, right)
+    }
+
+    pub fn minus(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::Minus, right)
+    }
+
+    pub fn multiply(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::Multiply, right)
+    }
+
+    pub fn divide(self, right: Expr) -> Expr {
+        self.binary(BinaryOperator::Divide, right)
+    }
+
+    pub fn like(self, pattern: Expr) -> Expr {
+        self.binary(BinaryOperator::Like, pattern)
+    }
+
+    pub fn is_null(self) -> Expr {
+        Expr::IsNull {
+            span: None,
+            expr: Box::new(self),
+            not: false,
+        }
+    }
+
+    pub fn is_not_null(self) -> Expr {
+        Expr::IsNull {
+            span: None,
+            expr: Box::new(self),
+            not: true,
+        }
+    }
+
+    pub fn between(self, low: Expr, high: Expr) -> Expr {
+        Expr::Between {
+            span: None,
+            expr: Box::new(self),
+            low: Box::new(low),
+            high: Box::new(high),
+            not: false,
+        }
+    }
+
+    pub fn in_list(self, list: impl IntoIterator<Item = Expr>) -> Expr {
+        Expr::InList {
+            span: None,
+            expr: Box::new(self),
+            list: list.into_iter().collect(),
+            not: false,
+        }
+    }
+
+    pub fn in_subquery(self, query: impl Into<Query>) -> Expr {
+        Expr::InSubquery {
+            span: None,
+            expr: Box::new(self),
+            subquery: Box::new(query.into()),
+            not: false,
+        }
+    }
+
+    pub fn cast(self, target_type: TypeName) -> Expr {
+        Expr::Cast {
+            span: None,
+            expr: Box::new(self),
+            target_type,
+            pg_style: false,
+        }
+    }
+
+    /// Turns the expression into a select target with an alias.
+    pub fn alias(self, alias: &str) -> SelectTarget {
+        SelectTarget::AliasedExpr {
+            expr: Box::new(self),
+            alias: Some(ident(alias)),
+        }
+    }
+
+    pub fn asc(self) -> OrderByExpr {
+        OrderByExpr {
+            expr: self,
+            asc: Some(true),
+            nulls_first: None,
+        }
+    }
+
+    pub fn desc(self) -> OrderByExpr {
+        OrderByExpr {
+            expr: self,
+            asc: Some(false),
+            nulls_first: None,
+        }
+    }
+}
+
+impl From<Expr> for SelectTarget {
+    fn from(expr: Expr) -> Self {
+        SelectTarget::AliasedExpr {
+            expr: Box::new(expr),
+            alias: None,
+        }
+    }
+}
+
+impl From<Expr> for OrderByExpr {
+    fn from(expr: Expr) -> Self {
+        OrderByExpr {
+            expr,
+            asc: None,
+            nulls_first: None,
+        }
+    }
+}
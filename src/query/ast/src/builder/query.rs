@@ -0,0 +1,261 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::ident;
+use super::lit;
+use crate::ast::*;
+
+/// The name of a table, `t`, `db.t` or `catalog.db.t`, converted from `"t"`,
+/// `("db", "t")` or `("catalog", "db", "t")`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableName {
+    pub catalog: Option<Identifier>,
+    pub database: Option<Identifier>,
+    pub table: Identifier,
+}
+
+impl From<&str> for TableName {
+    fn from(table: &str) -> Self {
+        TableName {
+            catalog: None,
+            database: None,
+            table: ident(table),
+        }
+    }
+}
+
+impl From<(&str, &str)> for TableName {
+    fn from((database, table): (&str, &str)) -> Self {
+        TableName {
+            catalog: None,
+            database: Some(ident(database)),
+            table: ident(table),
+        }
+    }
+}
+
+impl From<(&str, &str, &str)> for TableName {
+    fn from((catalog, database, table): (&str, &str, &str)) -> Self {
+        TableName {
+            catalog: Some(ident(catalog)),
+            database: Some(ident(database)),
+            table: ident(table),
+        }
+    }
+}
+
+/// A table in `FROM`, e.g. `table(("db", "t")).alias("x")`.
+pub fn table(name: impl Into<TableName>) -> TableReference {
+    let TableName {
+        catalog,
+        database,
+        table,
+    } = name.into();
+    TableReference::Table {
+        span: None,
+        catalog,
+        database,
+        table,
+        alias: None,
+        travel_point: None,
+        pivot: None,
+        unpivot: None,
+    }
+}
+
+/// A subquery in `FROM`, which needs an alias.
+pub fn derived_table(query: impl Into<Query>, alias: &str) -> TableReference {
+    TableReference::Subquery {
+        span: None,
+        subquery: Box::new(query.into()),
+        alias: Some(TableAlias {
+            name: ident(alias),
+            columns: vec![],
+        }),
+    }
+}
+
+impl TableReference {
+    /// Sets the alias of a table, table function or subquery. A join has no
+    /// alias and is returned unchanged.
+    pub fn alias(mut self, name: &str) -> TableReference {
+        match &mut self {
+            TableReference::Table { alias, .. }
+            | TableReference::TableFunction { alias, .. }
+            | TableReference::Subquery { alias, .. }
+            | TableReference::Location { alias, .. } => {
+                *alias = Some(TableAlias {
+                    name: ident(name),
+                    columns: vec![],
+                });
+            }
+            TableReference::Join { .. } => {}
+        }
+        self
+    }
+
+    fn join_with(self, op: JoinOperator, right: TableReference, condition: JoinCondition) -> Self {
+        TableReference::Join {
+            span: None,
+            join: Join {
+                op,
+                condition,
+                left: Box::new(self),
+                right: Box::new(right),
+            },
+        }
+    }
+
+    /// `<self> INNER JOIN <right> ON <on>`.
+    pub fn join(self, right: TableReference, on: Expr) -> Self {
+        self.join_with(JoinOperator::Inner, right, JoinCondition::On(Box::new(on)))
+    }
+
+    /// `<self> LEFT OUTER JOIN <right> ON <on>`.
+    pub fn left_join(self, right: TableReference, on: Expr) -> Self {
+        self.join_with(
+            JoinOperator::LeftOuter,
+            right,
+            JoinCondition::On(Box::new(on)),
+        )
+    }
+
+    /// `<self> CROSS JOIN <right>`.
+    pub fn cross_join(self, right: TableReference) -> Self {
+        self.join_with(JoinOperator::CrossJoin, right, JoinCondition::None)
+    }
+}
+
+/// Starts a `SELECT` query, e.g.
+/// `select([col("a").into(), func("sum", [col("b")]).alias("s")])`.
+pub fn select<T: Into<SelectTarget>>(targets: impl IntoIterator<Item = T>) -> SelectBuilder {
+    SelectBuilder {
+        with: None,
+        select: SelectStmt {
+            span: None,
+            hints: None,
+            distinct: false,
+            select_list: targets.into_iter().map(Into::into).collect(),
+            from: vec![],
+            selection: None,
+            group_by: None,
+            having: None,
+            window_list: None,
+        },
+        order_by: vec![],
+        limit: vec![],
+        offset: None,
+    }
+}
+
+/// Builds a [`Query`] with a single `SELECT`, converted with `Into<Query>` or
+/// `Into<Statement>`.
+#[derive(Debug, Clone)]
+pub struct SelectBuilder {
+    with: Option<With>,
+    select: SelectStmt,
+    order_by: Vec<OrderByExpr>,
+    limit: Vec<Expr>,
+    offset: Option<Expr>,
+}
+
+impl SelectBuilder {
+    /// Adds a common table expression, `WITH <name> AS (<query>)`.
+    pub fn with(mut self, name: &str, query: impl Into<Query>) -> Self {
+        let with = self.with.get_or_insert_with(|| With {
+            span: None,
+            recursive: false,
+            ctes: vec![],
+        });
+        with.ctes.push(CTE {
+            span: None,
+            alias: TableAlias {
+                name: ident(name),
+                columns: vec![],
+            },
+            materialized: false,
+            query: Box::new(query.into()),
+        });
+        self
+    }
+
+    pub fn distinct(mut self) -> Self {
+        self.select.distinct = true;
+        self
+    }
+
+    /// Adds a table to `FROM`. Several tables are separated by commas.
+    pub fn from(mut self, table_ref: TableReference) -> Self {
+        self.select.from.push(table_ref);
+        self
+    }
+
+    /// Sets `WHERE`, or adds the predicate with `AND` if it is already set.
+    pub fn where_(mut self, predicate: Expr) -> Self {
+        self.select.selection = Some(match self.select.selection.take() {
+            Some(selection) => selection.and(predicate),
+            None => predicate,
+        });
+        self
+    }
+
+    pub fn group_by(mut self, exprs: impl IntoIterator<Item = Expr>) -> Self {
+        self.select.group_by = Some(GroupBy::Normal(exprs.into_iter().collect()));
+        self
+    }
+
+    /// Sets `HAVING`, or adds the predicate with `AND` if it is already set.
+    pub fn having(mut self, predicate: Expr) -> Self {
+        self.select.having = Some(match self.select.having.take() {
+            Some(having) => having.and(predicate),
+            None => predicate,
+        });
+        self
+    }
+
+    pub fn order_by<T: Into<OrderByExpr>>(mut self, items: impl IntoIterator<Item = T>) -> Self {
+        self.order_by.extend(items.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = vec![lit(limit)];
+        self
+    }
+
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(lit(offset));
+        self
+    }
+}
+
+impl From<SelectBuilder> for Query {
+    fn from(builder: SelectBuilder) -> Self {
+        Query {
+            span: None,
+            with: builder.with,
+            body: SetExpr::Select(Box::new(builder.select)),
+            order_by: builder.order_by,
+            limit: builder.limit,
+            offset: builder.offset,
+            ignore_result: false,
+        }
+    }
+}
+
+impl From<SelectBuilder> for Statement {
+    fn from(builder: SelectBuilder) -> Self {
+        Statement::Query(Box::new(builder.into()))
+    }
+}
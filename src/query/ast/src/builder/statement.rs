@@ -0,0 +1,386 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use super::ident;
+use super::TableName;
+use crate::ast::*;
+
+/// Starts an `INSERT INTO <name>`, finished by [`InsertBuilder::values`] or
+/// [`InsertBuilder::select`].
+pub fn insert_into(name: impl Into<TableName>) -> InsertBuilder {
+    InsertBuilder {
+        name: name.into(),
+        columns: vec![],
+        overwrite: false,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InsertBuilder {
+    name: TableName,
+    columns: Vec<Identifier>,
+    overwrite: bool,
+}
+
+impl InsertBuilder {
+    pub fn columns<'a>(mut self, columns: impl IntoIterator<Item = &'a str>) -> Self {
+        self.columns = columns.into_iter().map(ident).collect();
+        self
+    }
+
+    /// `INSERT OVERWRITE` instead of `INSERT INTO`.
+    pub fn overwrite(mut self) -> Self {
+        self.overwrite = true;
+        self
+    }
+
+    /// Inserts rows of values as a `VALUES` query, since the rows of a parsed
+    /// `INSERT ... VALUES` are left in the source.
+    pub fn values<R: IntoIterator<Item = Expr>>(
+        self,
+        rows: impl IntoIterator<Item = R>,
+    ) -> Statement {
+        let values = rows
+            .into_iter()
+            .map(|row| row.into_iter().collect())
+            .collect();
+        self.build(InsertSource::Select {
+            query: Box::new(SetExpr::Values { span: None, values }.into_query()),
+        })
+    }
+
+    /// Inserts the result of a query.
+    pub fn select(self, query: impl Into<Query>) -> Statement {
+        self.build(InsertSource::Select {
+            query: Box::new(query.into()),
+        })
+    }
+
+    fn build(self, source: InsertSource) -> Statement {
+        let TableName {
+            catalog,
+            database,
+            table,
+        } = self.name;
+        Statement::Insert(InsertStmt {
+            hints: None,
+            catalog,
+            database,
+            table,
+            columns: self.columns,
+            source,
+            overwrite: self.overwrite,
+        })
+    }
+}
+
+/// Starts an `UPDATE <table> SET ...`.
+pub fn update(table: TableReference) -> UpdateBuilder {
+    UpdateBuilder {
+        stmt: UpdateStmt {
+            hints: None,
+            table,
+            update_list: vec![],
+            selection: None,
+        },
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateBuilder {
+    stmt: UpdateStmt,
+}
+
+impl UpdateBuilder {
+    /// Adds `<column> = <expr>` to `SET`.
+    pub fn set(mut self, column: &str, expr: Expr) -> Self {
+        self.stmt.update_list.push(UpdateExpr {
+            name: ident(column),
+            expr,
+        });
+        self
+    }
+
+    /// Sets `WHERE`, or adds the predicate with `AND` if it is already set.
+    pub fn where_(mut self, predicate: Expr) -> Self {
+        self.stmt.selection = and_predicate(self.stmt.selection.take(), predicate);
+        self
+    }
+}
+
+impl From<UpdateBuilder> for Statement {
+    fn from(builder: UpdateBuilder) -> Self {
+        Statement::Update(builder.stmt)
+    }
+}
+
+/// Starts a `DELETE FROM <table>`.
+pub fn delete_from(table: TableReference) -> DeleteBuilder {
+    DeleteBuilder {
+        table,
+        selection: None,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeleteBuilder {
+    table: TableReference,
+    selection: Option<Expr>,
+}
+
+impl DeleteBuilder {
+    /// Sets `WHERE`, or adds the predicate with `AND` if it is already set.
+    pub fn where_(mut self, predicate: Expr) -> Self {
+        self.selection = and_predicate(self.selection.take(), predicate);
+        self
+    }
+}
+
+impl From<DeleteBuilder> for Statement {
+    fn from(builder: DeleteBuilder) -> Self {
+        Statement::Delete {
+            hints: None,
+            table_reference: builder.table,
+            selection: builder.selection,
+        }
+    }
+}
+
+fn and_predicate(selection: Option<Expr>, predicate: Expr) -> Option<Expr> {
+    Some(match selection {
+        Some(selection) => selection.and(predicate),
+        None => predicate,
+    })
+}
+
+/// Starts a `MERGE INTO <name> USING (<source>) ON <on>`, followed by the
+/// `WHEN [NOT] MATCHED` clauses in the order they are added.
+pub fn merge_into(name: impl Into<TableName>, source: impl Into<Query>, on: Expr) -> MergeBuilder {
+    let TableName {
+        catalog,
+        database,
+        table,
+    } = name.into();
+    MergeBuilder {
+        stmt: MergeIntoStmt {
+            hints: None,
+            catalog,
+            database,
+            table_ident: table,
+            source: MergeSource::Select {
+                query: Box::new(source.into()),
+            },
+            alias_target: None,
+            join_expr: on,
+            merge_options: vec![],
+        },
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MergeBuilder {
+    stmt: MergeIntoStmt,
+}
+
+impl MergeBuilder {
+    /// Sets the alias of the target table.
+    pub fn alias(mut self, name: &str) -> Self {
+        self.stmt.alias_target = Some(TableAlias {
+            name: ident(name),
+            columns: vec![],
+        });
+        self
+    }
+
+    /// `WHEN MATCHED [AND <condition>] THEN UPDATE SET <column> = <expr>, ...`.
+    pub fn when_matched_update<'a>(
+        mut self,
+        condition: Option<Expr>,
+        assignments: impl IntoIterator<Item = (&'a str, Expr)>,
+    ) -> Self {
+        let update_list = assignments
+            .into_iter()
+            .map(|(column, expr)| MergeUpdateExpr {
+                catalog: None,
+                table: None,
+                name: ident(column),
+                expr,
+            })
+            .collect();
+        self.stmt
+            .merge_options
+            .push(MergeOption::Match(MatchedClause {
+                selection: condition,
+                operation: MatchOperation::Update { update_list },
+            }));
+        self
+    }
+
+    /// `WHEN MATCHED [AND <condition>] THEN DELETE`.
+    pub fn when_matched_delete(mut self, condition: Option<Expr>) -> Self {
+        self.stmt
+            .merge_options
+            .push(MergeOption::Match(MatchedClause {
+                selection: condition,
+                operation: MatchOperation::Delete,
+            }));
+        self
+    }
+
+    /// `WHEN NOT MATCHED [AND <condition>] THEN INSERT [(<column>, ...)] VALUES (<value>, ...)`.
+    pub fn when_not_matched_insert<'a>(
+        mut self,
+        condition: Option<Expr>,
+        columns: impl IntoIterator<Item = &'a str>,
+        values: impl IntoIterator<Item = Expr>,
+    ) -> Self {
+        let columns = columns.into_iter().map(ident).collect::<Vec<_>>();
+        self.stmt
+            .merge_options
+            .push(MergeOption::Unmatch(UnmatchedClause {
+                selection: condition,
+                insert_operation: InsertOperation {
+                    columns: if columns.is_empty() {
+                        None
+                    } else {
+                        Some(columns)
+                    },
+                    values: values.into_iter().collect(),
+                },
+            }));
+        self
+    }
+}
+
+impl From<MergeBuilder> for Statement {
+    fn from(builder: MergeBuilder) -> Self {
+        Statement::MergeInto(builder.stmt)
+    }
+}
+
+/// Starts a `CREATE TABLE <name>`.
+pub fn create_table(name: impl Into<TableName>) -> CreateTableBuilder {
+    let TableName {
+        catalog,
+        database,
+        table,
+    } = name.into();
+    CreateTableBuilder {
+        stmt: CreateTableStmt {
+            if_not_exists: false,
+            catalog,
+            database,
+            table,
+            source: None,
+            engine: None,
+            uri_location: None,
+            cluster_by: vec![],
+            table_options: BTreeMap::new(),
+            as_query: None,
+            transient: false,
+        },
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateTableBuilder {
+    stmt: CreateTableStmt,
+}
+
+impl CreateTableBuilder {
+    pub fn if_not_exists(mut self) -> Self {
+        self.stmt.if_not_exists = true;
+        self
+    }
+
+    pub fn transient(mut self) -> Self {
+        self.stmt.transient = true;
+        self
+    }
+
+    /// Adds a column, built with [`column_def`].
+    pub fn column(mut self, column: ColumnDefinition) -> Self {
+        match &mut self.stmt.source {
+            Some(CreateTableSource::Columns(columns)) => columns.push(column),
+            _ => self.stmt.source = Some(CreateTableSource::Columns(vec![column])),
+        }
+        self
+    }
+
+    pub fn engine(mut self, engine: Engine) -> Self {
+        self.stmt.engine = Some(engine);
+        self
+    }
+
+    pub fn cluster_by(mut self, exprs: impl IntoIterator<Item = Expr>) -> Self {
+        self.stmt.cluster_by = exprs.into_iter().collect();
+        self
+    }
+
+    /// Adds a table option, e.g. `option("comment", "events")`.
+    pub fn option(mut self, key: &str, value: &str) -> Self {
+        self.stmt
+            .table_options
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// `AS <query>`, which fills the table with the result of the query.
+    pub fn as_select(mut self, query: impl Into<Query>) -> Self {
+        self.stmt.as_query = Some(Box::new(query.into()));
+        self
+    }
+}
+
+impl From<CreateTableBuilder> for Statement {
+    fn from(builder: CreateTableBuilder) -> Self {
+        Statement::CreateTable(builder.stmt)
+    }
+}
+
+/// A column of `CREATE TABLE`, e.g. `column_def("a", TypeName::Int32).not_null()`.
+pub fn column_def(name: &str, data_type: TypeName) -> ColumnDefinition {
+    ColumnDefinition {
+        name: ident(name),
+        data_type,
+        expr: None,
+        comment: None,
+        nullable_constraint: None,
+    }
+}
+
+impl ColumnDefinition {
+    pub fn not_null(mut self) -> Self {
+        self.nullable_constraint = Some(NullableConstraint::NotNull);
+        self
+    }
+
+    pub fn null(mut self) -> Self {
+        self.nullable_constraint = Some(NullableConstraint::Null);
+        self
+    }
+
+    /// `DEFAULT <expr>`.
+    pub fn default_value(mut self, expr: Expr) -> Self {
+        self.expr = Some(ColumnExpr::Default(Box::new(expr)));
+        self
+    }
+
+    pub fn with_comment(mut self, comment: &str) -> Self {
+        self.comment = Some(comment.to_string());
+        self
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use common_exception::pretty_print_error_with_notes;
+use common_exception::ErrorCode;
+use common_exception::Range;
+use serde_json::json;
+
+use crate::parser::token::TokenKind;
+use crate::DeniedSyntax;
+use crate::LimitError;
+
+/// How severe a [`Diagnostic`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// The kind of a [`Diagnostic`], identified by a stable code like `E0002`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticCode {
+    /// A part of the SQL is not a token.
+    UnrecognizedToken,
+    /// The found token is not one of the expected tokens.
+    UnexpectedToken,
+    /// The SQL ends while more tokens are expected.
+    UnexpectedEnd,
+    /// The syntax is invalid for a reason other than the tokens, e.g. a duplicate
+    /// option.
+    InvalidSyntax,
+    /// A statement is parsed, but is followed by more tokens.
+    TrailingTokens,
+    /// A resource limit of the parser is exceeded, see [`LimitError`].
+    LimitExceeded,
+    /// The syntax is denied by the parse options, see [`DeniedSyntax`].
+    DeniedSyntax,
+}
+
+impl DiagnosticCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticCode::UnrecognizedToken => "E0001",
+            DiagnosticCode::UnexpectedToken => "E0002",
+            DiagnosticCode::UnexpectedEnd => "E0003",
+            DiagnosticCode::InvalidSyntax => "E0004",
+            DiagnosticCode::TrailingTokens => "E0005",
+            DiagnosticCode::LimitExceeded => "E0006",
+            DiagnosticCode::DeniedSyntax => "E0007",
+        }
+    }
+}
+
+impl Display for DiagnosticCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A structured error of parsing SQL, which can be rendered as plain text, ANSI
+/// colored text or JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub code: DiagnosticCode,
+    pub severity: Severity,
+    /// The primary span of the error.
+    pub span: Range,
+    /// The message at the primary span, e.g. "expected `FROM` or `,`".
+    pub message: String,
+    /// The kind of the token found at the primary span, `EOI` at the end of the SQL.
+    pub found: Option<TokenKind>,
+    /// The kinds of tokens expected at the primary span, including the keywords,
+    /// the most similar to the found token first.
+    pub expected_tokens: Vec<TokenKind>,
+    /// The texts expected at the primary span, like `(` or `=`.
+    pub expected_texts: Vec<&'static str>,
+    /// The syntax being parsed at the error and its span, the innermost first,
+    /// e.g. "`SELECT ...`".
+    pub contexts: Vec<(Range, &'static str)>,
+    /// The fixes that may resolve the error, the most likely first.
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// A fix for a [`Diagnostic`], like replacing a misspelled keyword.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The description of the fix, e.g. "did you mean `SELECT`?".
+    pub message: String,
+    /// The edits to the source that apply the fix, which don't overlap.
+    pub edits: Vec<TextEdit>,
+}
+
+/// Replace the text at `span` with `replacement`. An insertion has an empty span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub span: Range,
+    pub replacement: String,
+}
+
+impl Suggestion {
+    pub(crate) fn replace(message: String, span: Range, replacement: String) -> Self {
+        Suggestion {
+            message,
+            edits: vec![TextEdit { span, replacement }],
+        }
+    }
+
+    /// Apply the edits to the source.
+    pub fn apply(&self, source: &str) -> String {
+        let mut edits = self.edits.iter().collect::<Vec<_>>();
+        edits.sort_by_key(|edit| edit.span.start);
+        let mut fixed = String::with_capacity(source.len());
+        let mut pos = 0;
+        for edit in edits {
+            fixed += &source[pos..edit.span.start];
+            fixed += &edit.replacement;
+            pos = edit.span.end;
+        }
+        fixed += &source[pos..];
+        fixed
+    }
+}
+
+impl Diagnostic {
+    pub(crate) fn error(code: DiagnosticCode, span: Range, message: String) -> Self {
+        Diagnostic {
+            code,
+            severity: Severity::Error,
+            span,
+            message,
+            found: None,
+            expected_tokens: vec![],
+            expected_texts: vec![],
+            contexts: vec![],
+            suggestions: vec![],
+        }
+    }
+
+    /// Move all the spans by `offset` bytes, e.g. from a statement parsed alone to
+    /// the script containing it.
+    pub fn shift(&mut self, offset: usize) {
+        let shift = |span: &mut Range| {
+            span.start += offset;
+            span.end += offset;
+        };
+        shift(&mut self.span);
+        for (span, _) in &mut self.contexts {
+            shift(span);
+        }
+        for suggestion in &mut self.suggestions {
+            for edit in &mut suggestion.edits {
+                shift(&mut edit.span);
+            }
+        }
+    }
+
+    /// The message at the primary span followed by the contexts.
+    fn labels(&self) -> Vec<(Range, String)> {
+        let mut labels = vec![(self.span, self.message.clone())];
+        labels.extend(
+            self.contexts
+                .iter()
+                .map(|(span, msg)| (*span, format!("while parsing {}", msg))),
+        );
+        labels
+    }
+
+    fn notes(&self) -> Vec<String> {
+        self.suggestions
+            .iter()
+            .map(|suggestion| format!("help: {}", suggestion.message))
+            .collect()
+    }
+
+    /// Render the diagnostic as plain text with the source, like the message of
+    /// the [`ErrorCode`] returned by [`parse_sql`](crate::parser::parse_sql).
+    pub fn render_text(&self, source: &str) -> String {
+        pretty_print_error_with_notes(source, self.labels(), self.notes(), false)
+    }
+
+    /// Render the diagnostic as text with the source, colored with ANSI escape codes
+    /// for terminals.
+    pub fn render_ansi(&self, source: &str) -> String {
+        pretty_print_error_with_notes(source, self.labels(), self.notes(), true)
+    }
+
+    /// Render the diagnostic as JSON. The spans are byte offsets into `source`,
+    /// along with the line and the column of their start, both 1-based and the
+    /// column counted in characters.
+    pub fn to_json(&self, source: &str) -> serde_json::Value {
+        let span = |span: Range| {
+            let (line, column) = line_column(source, span.start);
+            json!({
+                "start": span.start,
+                "end": span.end,
+                "line": line,
+                "column": column,
+            })
+        };
+        json!({
+            "code": self.code.as_str(),
+            "severity": self.severity.to_string(),
+            "span": span(self.span),
+            "message": self.message,
+            "found": self.found.map(|kind| format!("{:?}", kind)),
+            "expected_tokens": self
+                .expected_tokens
+                .iter()
+                .map(|kind| format!("{:?}", kind))
+                .collect::<Vec<_>>(),
+            "expected_texts": self.expected_texts,
+            "contexts": self
+                .contexts
+                .iter()
+                .map(|(ctx_span, msg)| json!({
+                    "span": span(*ctx_span),
+                    "label": format!("while parsing {}", msg),
+                }))
+                .collect::<Vec<_>>(),
+            "suggestions": self
+                .suggestions
+                .iter()
+                .map(|suggestion| json!({
+                    "message": suggestion.message,
+                    "edits": suggestion
+                        .edits
+                        .iter()
+                        .map(|edit| json!({
+                            "span": span(edit.span),
+                            "replacement": edit.replacement,
+                        }))
+                        .collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    /// Convert the diagnostic into the [`ErrorCode`] returned by the parser: the
+    /// rendered text for a syntax error, or the message with the span otherwise.
+    pub fn into_error_code(self, source: &str) -> ErrorCode {
+        match self.code {
+            DiagnosticCode::UnexpectedToken
+            | DiagnosticCode::UnexpectedEnd
+            | DiagnosticCode::InvalidSyntax => ErrorCode::SyntaxException(self.render_text(source)),
+            DiagnosticCode::UnrecognizedToken
+            | DiagnosticCode::TrailingTokens
+            | DiagnosticCode::LimitExceeded
+            | DiagnosticCode::DeniedSyntax => {
+                ErrorCode::SyntaxException(self.message).set_span(Some(self.span))
+            }
+        }
+    }
+}
+
+/// The line and the column of the byte `offset` in `source`, both 1-based and the
+/// column counted in characters.
+pub fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before[before.rfind('\n').map_or(0, |i| i + 1)..]
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+impl From<LimitError> for Diagnostic {
+    fn from(err: LimitError) -> Self {
+        Diagnostic::error(DiagnosticCode::LimitExceeded, err.span(), err.to_string())
+    }
+}
+
+impl From<DeniedSyntax> for Diagnostic {
+    fn from(err: DeniedSyntax) -> Self {
+        Diagnostic::error(DiagnosticCode::DeniedSyntax, err.span, err.to_string())
+    }
+}
@@ -14,16 +14,25 @@
 
 use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::fmt::Display;
+use std::fmt::Formatter;
 use std::fmt::Write;
 use std::num::IntErrorKind;
 use std::num::ParseIntError;
 
-use common_exception::pretty_print_error;
+use common_exception::ErrorCode;
 use common_exception::Range;
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 
+use crate::diagnostic::Diagnostic;
+use crate::diagnostic::DiagnosticCode;
+use crate::diagnostic::Suggestion;
+use crate::diagnostic::TextEdit;
+use crate::input::Dialect;
 use crate::input::Input;
+use crate::input::SyntaxFeature;
+use crate::parser::quote::quote_ident;
 use crate::parser::token::*;
 use crate::util::transform_span;
 
@@ -42,10 +51,12 @@ pub struct Error<'a> {
     pub contexts: Vec<(Range, &'static str)>,
     /// The extra backtrace of error in optional branches.
     pub backtrace: &'a Backtrace,
+    /// The dialect of the input, used to suggest fixes.
+    pub dialect: Dialect,
 }
 
 /// ErrorKind is the error type returned from parser.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ErrorKind {
     /// Error generated by `match_token` function
     ExpectToken(TokenKind),
@@ -53,6 +64,18 @@ pub enum ErrorKind {
     ExpectText(&'static str),
     /// Plain text description of an error
     Other(&'static str),
+    /// An unknown option key, the closest one in `options` is suggested.
+    UnknownOption {
+        /// The kind of the options, e.g. `file format`.
+        kind: &'static str,
+        /// The lowercase keys of all the valid options.
+        options: &'static [&'static str],
+    },
+    /// A missing option key, all the keys in `options` are listed.
+    ExpectOption {
+        /// The lowercase keys of all the valid options.
+        options: &'static [&'static str],
+    },
 }
 
 /// Record the farthest position in the input before encountering an error.
@@ -78,6 +101,8 @@ impl Backtrace {
 pub struct BacktraceInner {
     /// The span of the next token when encountering an error.
     span: Range,
+    /// The kind of the next token when encountering an error.
+    found: TokenKind,
     /// List of errors tried in various branches that consumed
     /// the same (farthest) length of input.
     errors: Vec<ErrorKind>,
@@ -90,6 +115,7 @@ impl<'a> nom::error::ParseError<Input<'a>> for Error<'a> {
             errors: vec![],
             contexts: vec![],
             backtrace: i.2,
+            dialect: i.1,
         }
     }
 
@@ -125,6 +151,9 @@ impl<'a> nom::error::ContextError<Input<'a>> for Error<'a> {
 
 impl<'a> Error<'a> {
     pub fn from_error_kind(input: Input<'a>, kind: ErrorKind) -> Self {
+        if input[0].kind == EOI {
+            input.3.add_expected_at_end(kind);
+        }
         let mut inner = input.2.inner.borrow_mut();
         if let Some(ref mut inner) = *inner {
             match input.0[0].span.start.cmp(&inner.span.start) {
@@ -135,6 +164,7 @@ impl<'a> Error<'a> {
                 Ordering::Greater => {
                     *inner = BacktraceInner {
                         span: transform_span(&input.0[..1]).unwrap(),
+                        found: input.0[0].kind,
                         errors: vec![kind],
                     };
                 }
@@ -142,6 +172,7 @@ impl<'a> Error<'a> {
         } else {
             *inner = Some(BacktraceInner {
                 span: transform_span(&input.0[..1]).unwrap(),
+                found: input.0[0].kind,
                 errors: vec![kind],
             })
         }
@@ -151,10 +182,129 @@ impl<'a> Error<'a> {
             errors: vec![kind],
             contexts: vec![],
             backtrace: input.2,
+            dialect: input.1,
+        }
+    }
+}
+
+/// A resource limit of [`ParserLimits`](crate::ParserLimits) exceeded by the
+/// statement, with the span where the limit is reached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LimitError {
+    StatementTooLarge {
+        len: usize,
+        limit: usize,
+        span: Range,
+    },
+    TooManyTokens {
+        len: usize,
+        limit: usize,
+        span: Range,
+    },
+    TooDeep {
+        limit: usize,
+        span: Range,
+    },
+    ChainTooLong {
+        limit: usize,
+        span: Range,
+    },
+    InListTooLong {
+        len: usize,
+        limit: usize,
+        span: Range,
+    },
+}
+
+impl LimitError {
+    pub fn span(&self) -> Range {
+        match self {
+            LimitError::StatementTooLarge { span, .. }
+            | LimitError::TooManyTokens { span, .. }
+            | LimitError::TooDeep { span, .. }
+            | LimitError::ChainTooLong { span, .. }
+            | LimitError::InListTooLong { span, .. } => *span,
+        }
+    }
+
+    /// The plain text error reported by the parser.
+    pub(crate) fn reason(&self) -> &'static str {
+        match self {
+            LimitError::StatementTooLarge { .. } => "statement is too large",
+            LimitError::TooManyTokens { .. } => "statement has too many tokens",
+            LimitError::TooDeep { .. } => "nesting is too deep",
+            LimitError::ChainTooLong { .. } => "chain of operators is too long",
+            LimitError::InListTooLong { .. } => "IN list is too long",
+        }
+    }
+}
+
+impl Display for LimitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitError::StatementTooLarge { len, limit, .. } => write!(
+                f,
+                "statement of {len} bytes exceeds the limit of {limit} bytes"
+            ),
+            LimitError::TooManyTokens { len, limit, .. } => write!(
+                f,
+                "statement of {len} tokens exceeds the limit of {limit} tokens"
+            ),
+            LimitError::TooDeep { limit, .. } => {
+                write!(f, "nesting exceeds the depth limit of {limit}")
+            }
+            LimitError::ChainTooLong { limit, .. } => write!(
+                f,
+                "chain of operators exceeds the limit of {limit} operands and operators"
+            ),
+            LimitError::InListTooLong { len, limit, .. } => write!(
+                f,
+                "IN list of {len} items exceeds the limit of {limit} items"
+            ),
+        }
+    }
+}
+
+impl From<LimitError> for ErrorCode {
+    fn from(err: LimitError) -> Self {
+        let span = err.span();
+        ErrorCode::SyntaxException(err.to_string()).set_span(Some(span))
+    }
+}
+
+/// Syntax denied by the [`ParseOptions`](crate::ParseOptions), with its span.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeniedSyntax {
+    pub feature: SyntaxFeature,
+    pub span: Range,
+}
+
+impl DeniedSyntax {
+    /// The plain text error reported by the parser.
+    pub(crate) fn reason(&self) -> &'static str {
+        match self.feature {
+            SyntaxFeature::Hints => "hints are not allowed",
+            SyntaxFeature::StageTable => "reading a stage or location as a table is not allowed",
+            SyntaxFeature::UdfServer => "UDF server definitions are not allowed",
+            SyntaxFeature::Explain => "`EXPLAIN` is not allowed",
+            SyntaxFeature::SetGlobal => "`SET GLOBAL` is not allowed",
         }
     }
 }
 
+impl Display for DeniedSyntax {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.reason())
+    }
+}
+
+impl From<DeniedSyntax> for ErrorCode {
+    fn from(err: DeniedSyntax) -> Self {
+        let span = err.span;
+        ErrorCode::SyntaxException(err.to_string()).set_span(Some(span))
+    }
+}
+
 impl From<fast_float::Error> for ErrorKind {
     fn from(_: fast_float::Error) -> Self {
         ErrorKind::Other("unable to parse float number")
@@ -176,54 +326,133 @@ impl From<ParseIntError> for ErrorKind {
 }
 
 pub fn display_parser_error(error: Error, source: &str) -> String {
-    let inner = &*error.backtrace.inner.borrow();
-    let inner = match inner {
-        Some(inner) => inner,
-        None => return String::new(),
-    };
-    let span_text = &source[inner.span.start..inner.span.end];
+    if error.backtrace.inner.borrow().is_none() {
+        return String::new();
+    }
+    Diagnostic::from_parser_error(error, source).render_text(source)
+}
+
+impl Diagnostic {
+    /// The diagnostic of a syntax error reported by the parser. The expected tokens
+    /// are ordered by their similarity to the found token.
+    pub(crate) fn from_parser_error(error: Error, source: &str) -> Self {
+        let inner = error.backtrace.inner.borrow();
+        let (span, found, errors) = match &*inner {
+            Some(inner) => (inner.span, Some(inner.found), &inner.errors),
+            None => (error.span, None, &error.errors),
+        };
 
-    let mut labels = vec![];
+        // Plain text error has the highest priority. Only display it if exists.
+        if let Some(msg) = errors.iter().find_map(|kind| match kind {
+            ErrorKind::Other(msg) => Some(msg),
+            _ => None,
+        }) {
+            return Diagnostic {
+                found,
+                contexts: error.contexts.clone(),
+                ..Diagnostic::error(DiagnosticCode::InvalidSyntax, span, msg.to_string())
+            };
+        }
 
-    // Plain text error has the highest priority. Only display it if exists.
-    for kind in &inner.errors {
-        if let ErrorKind::Other(msg) = kind {
-            labels = vec![(inner.span, msg.to_string())];
-            break;
+        if let Some((kind, options)) = errors.iter().find_map(|kind| match kind {
+            ErrorKind::UnknownOption { kind, options } => Some((kind, options)),
+            _ => None,
+        }) {
+            let key = &source[span.start..span.end];
+            let lower = key.to_lowercase();
+            let closest = options
+                .iter()
+                .min_by_key(|option| strsim::damerau_levenshtein(&lower, option));
+            let (msg, suggestions) = match closest {
+                Some(option) => {
+                    let option = if key.chars().all(|c| !c.is_ascii_uppercase()) {
+                        option.to_string()
+                    } else {
+                        option.to_uppercase()
+                    };
+                    let msg = format!("unknown {kind} option `{key}`, did you mean `{option}`?");
+                    let suggestion =
+                        Suggestion::replace(format!("did you mean `{option}`?"), span, option);
+                    (msg, vec![suggestion])
+                }
+                None => (format!("unknown {kind} option `{key}`"), vec![]),
+            };
+            return Diagnostic {
+                found,
+                contexts: error.contexts.clone(),
+                suggestions,
+                ..Diagnostic::error(DiagnosticCode::InvalidSyntax, span, msg)
+            };
         }
-    }
 
-    // List all expected tokens in alternative branches.
-    if labels.is_empty() {
-        let mut expected_tokens = error
+        if let Some(options) = errors.iter().find_map(|kind| match kind {
+            ErrorKind::ExpectOption { options } => Some(options),
+            _ => None,
+        }) {
+            // The option keys are listed along with the punctuations expected
+            // after an option, e.g. `)`.
+            let expected = options
+                .iter()
+                .map(|option| option.to_uppercase())
+                .chain(
+                    errors
+                        .iter()
+                        .filter_map(|kind| match kind {
+                            ErrorKind::ExpectText(text) => Some(format!("`{text}`")),
+                            _ => None,
+                        })
+                        .unique(),
+                )
+                .collect::<Vec<_>>();
+            let msg = match expected.split_last() {
+                Some((last, init)) if !init.is_empty() => {
+                    format!("expected one of: {}, or {last}", init.join(", "))
+                }
+                _ => format!("expected one of: {}", expected.join(", ")),
+            };
+            return Diagnostic {
+                found,
+                contexts: error.contexts.clone(),
+                ..Diagnostic::error(DiagnosticCode::InvalidSyntax, span, msg)
+            };
+        }
+
+        // List all expected tokens in alternative branches.
+        let span_text = &source[span.start..span.end];
+        let mut expected = error
             .errors
             .iter()
-            .chain(&inner.errors)
-            .filter_map(|kind| match kind {
-                ErrorKind::ExpectToken(EOI) => None,
-                ErrorKind::ExpectToken(token) if token.is_keyword() => {
-                    Some(format!("`{:?}`", token))
-                }
-                ErrorKind::ExpectToken(token) => Some(format!("<{:?}>", token)),
-                ErrorKind::ExpectText(text) => Some(format!("`{}`", text)),
-                _ => None,
+            .chain(errors)
+            .filter(|kind| {
+                matches!(kind, ErrorKind::ExpectText(_))
+                    || matches!(kind, ErrorKind::ExpectToken(token) if *token != EOI)
             })
             .unique()
+            .map(|kind| {
+                let text = match kind {
+                    ErrorKind::ExpectToken(token) if token.is_keyword() => format!("`{:?}`", token),
+                    ErrorKind::ExpectToken(token) => format!("<{:?}>", token),
+                    ErrorKind::ExpectText(text) => format!("`{}`", text),
+                    ErrorKind::Other(_)
+                    | ErrorKind::UnknownOption { .. }
+                    | ErrorKind::ExpectOption { .. } => unreachable!(),
+                };
+                (kind, text)
+            })
+            .unique_by(|(_, text)| text.clone())
             .collect::<Vec<_>>();
-        expected_tokens.sort_by_cached_key(|token| {
+        expected.sort_by_cached_key(|(_, text)| {
             OrderedFloat::from(-strsim::jaro_winkler(
-                &token.to_lowercase(),
+                &text.to_lowercase(),
                 &span_text.to_lowercase(),
             ))
         });
 
         let mut msg = String::new();
-        let mut iter = expected_tokens.iter().enumerate().peekable();
-        while let Some((i, error)) = iter.next() {
+        let mut iter = expected.iter().enumerate().peekable();
+        while let Some((i, (_, text))) = iter.next() {
             if i == MAX_DISPLAY_ERROR_COUNT {
-                let more = expected_tokens
-                    .len()
-                    .saturating_sub(MAX_DISPLAY_ERROR_COUNT);
+                let more = expected.len().saturating_sub(MAX_DISPLAY_ERROR_COUNT);
                 write!(msg, ", or {} more ...", more).unwrap();
                 break;
             } else if i == 0 {
@@ -235,19 +464,152 @@ pub fn display_parser_error(error: Error, source: &str) -> String {
             } else {
                 msg += ", ";
             }
-            msg += error;
+            msg += text;
         }
 
-        labels = vec![(inner.span, msg)];
+        let suggestions = match found {
+            Some(found) => suggest_fixes(
+                found,
+                span,
+                &expected.iter().map(|(kind, _)| *kind).collect::<Vec<_>>(),
+                source,
+                error.dialect,
+            ),
+            None => vec![],
+        };
+        let code = if found == Some(EOI) {
+            DiagnosticCode::UnexpectedEnd
+        } else {
+            DiagnosticCode::UnexpectedToken
+        };
+        Diagnostic {
+            found,
+            expected_tokens: expected
+                .iter()
+                .filter_map(|(kind, _)| match kind {
+                    ErrorKind::ExpectToken(token) => Some(*token),
+                    _ => None,
+                })
+                .collect(),
+            expected_texts: expected
+                .iter()
+                .filter_map(|(kind, _)| match kind {
+                    ErrorKind::ExpectText(text) => Some(*text),
+                    _ => None,
+                })
+                .collect(),
+            contexts: error.contexts.clone(),
+            suggestions,
+            ..Diagnostic::error(code, span, msg)
+        }
     }
+}
 
-    // Append contexts as secondary labels.
-    labels.extend(
-        error
-            .contexts
+/// Suggest fixes for the token `found` at `span`, given the expected tokens.
+fn suggest_fixes(
+    found: TokenKind,
+    span: Range,
+    expected: &[&ErrorKind],
+    source: &str,
+    dialect: Dialect,
+) -> Vec<Suggestion> {
+    let text = &source[span.start..span.end];
+    let expects_token = |kind| expected.contains(&&ErrorKind::ExpectToken(kind));
+    let expects_text = |text| expected.contains(&&ErrorKind::ExpectText(text));
+
+    // `a = (1, 2)` is valid, but `PIVOT(... FOR a = (...))` is not.
+    if found == Eq && expects_token(IN) {
+        return vec![Suggestion::replace(
+            "use `IN` instead of `=`".to_string(),
+            span,
+            "IN".to_string(),
+        )];
+    }
+
+    // A string is quoted by `"` in MySQL, but `"` quotes an identifier in PostgreSQL.
+    if found == QuotedString
+        && text.starts_with('"')
+        && dialect == Dialect::PostgreSQL
+        && expects_token(QuotedString)
+    {
+        let string = format!("'{}'", text[1..text.len() - 1].replace('\'', "''"));
+        return vec![Suggestion::replace(
+            format!(
+                "`{text}` is an identifier in the PostgreSQL dialect, use single quotes for a string"
+            ),
+            span,
+            string,
+        )];
+    }
+
+    // An identifier may not be expected for the keyword if an expression can end
+    // before it, e.g. `WHERE a IN (1, 2 FROM t`.
+    if found.is_reserved_ident(false)
+        && expects_token(Ident)
+        && !expects_text(",")
+        && !expects_text(")")
+    {
+        let quoted = quote_ident(text, dialect.default_ident_quote(), true);
+        return vec![Suggestion::replace(
+            format!("quote the reserved keyword `{text}` to use it as an identifier"),
+            span,
+            quoted,
+        )];
+    }
+
+    // A misspelled keyword is usually tokenized as an identifier.
+    if found == Ident || found.is_keyword() {
+        let upper = text.to_uppercase();
+        let keyword = expected
             .iter()
-            .map(|(span, msg)| (*span, format!("while parsing {}", msg))),
-    );
+            .filter_map(|kind| match kind {
+                ErrorKind::ExpectToken(token) if token.is_keyword() => Some(format!("{:?}", token)),
+                _ => None,
+            })
+            .filter(|keyword| keyword.chars().all(|c| c.is_ascii_uppercase() || c == '_'))
+            .map(|keyword| {
+                let distance = strsim::damerau_levenshtein(&upper, &keyword);
+                (distance, keyword)
+            })
+            .filter(|(distance, _)| *distance > 0 && *distance <= 2 && distance * 3 <= text.len())
+            .min_by(|(d1, k1), (d2, k2)| {
+                d1.cmp(d2).then_with(|| {
+                    let similarity =
+                        |keyword: &str| OrderedFloat::from(strsim::jaro_winkler(&upper, keyword));
+                    similarity(k2).cmp(&similarity(k1))
+                })
+            });
+        if let Some((_, keyword)) = keyword {
+            let keyword = if text.chars().all(|c| !c.is_ascii_uppercase()) {
+                keyword.to_lowercase()
+            } else {
+                keyword
+            };
+            return vec![Suggestion::replace(
+                format!("did you mean `{keyword}`?"),
+                span,
+                keyword,
+            )];
+        }
+    }
+
+    // `SELECT a b c`, where `b` is the alias of `a`.
+    if expects_text(",") && matches!(found, Ident | QuotedString | LiteralInteger | LiteralFloat) {
+        let prev = Tokenizer::new(&source[..span.start])
+            .filter_map(|token| token.ok())
+            .filter(|token| token.kind != EOI)
+            .last();
+        if let Some(prev) = prev.filter(|prev| !prev.kind.is_keyword()) {
+            let end = prev.span.end;
+            return vec![Suggestion {
+                message: format!("add `,` before `{text}`"),
+                edits: vec![TextEdit {
+                    span: Range { start: end, end },
+                    replacement: ",".to_string(),
+                }],
+            }];
+        }
+    }
 
-    pretty_print_error(source, labels)
+    vec![]
 }
@@ -12,18 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::Cell;
+use std::cell::RefCell;
 use std::ops::Range;
 use std::ops::RangeFrom;
 use std::ops::RangeFull;
 use std::ops::RangeTo;
 
+use enumflags2::bitflags;
+use enumflags2::BitFlags;
+use itertools::Itertools;
+
 use crate::parser::token::Token;
+use crate::parser::CompletionContext;
 use crate::Backtrace;
+use crate::DeniedSyntax;
+use crate::ErrorKind;
+use crate::LimitError;
 
 /// Input tokens slice with a backtrace that records all errors including
-/// the optional branch.
+/// the optional branch, and the state shared by the whole parse.
 #[derive(Debug, Clone, Copy)]
-pub struct Input<'a>(pub &'a [Token<'a>], pub Dialect, pub &'a Backtrace);
+pub struct Input<'a>(
+    pub &'a [Token<'a>],
+    pub Dialect,
+    pub &'a Backtrace,
+    pub &'a ParseState,
+);
 
 impl<'a> std::ops::Deref for Input<'a> {
     type Target = [Token<'a>];
@@ -50,19 +65,19 @@ impl<'a> nom::Offset for Input<'a> {
 
 impl<'a> nom::Slice<Range<usize>> for Input<'a> {
     fn slice(&self, range: Range<usize>) -> Self {
-        Input(&self.0[range], self.1, self.2)
+        Input(&self.0[range], self.1, self.2, self.3)
     }
 }
 
 impl<'a> nom::Slice<RangeTo<usize>> for Input<'a> {
     fn slice(&self, range: RangeTo<usize>) -> Self {
-        Input(&self.0[range], self.1, self.2)
+        Input(&self.0[range], self.1, self.2, self.3)
     }
 }
 
 impl<'a> nom::Slice<RangeFrom<usize>> for Input<'a> {
     fn slice(&self, range: RangeFrom<usize>) -> Self {
-        Input(&self.0[range], self.1, self.2)
+        Input(&self.0[range], self.1, self.2, self.3)
     }
 }
 
@@ -96,6 +111,16 @@ impl Dialect {
         }
     }
 
+    /// The quote used to quote an identifier that is a keyword or contains special
+    /// characters.
+    pub fn default_ident_quote(&self) -> char {
+        match self {
+            Dialect::MySQL => '`',
+            Dialect::Hive => '`',
+            Dialect::PostgreSQL => '"',
+        }
+    }
+
     pub fn is_string_quote(&self, c: char) -> bool {
         match self {
             Dialect::MySQL => c == '\'' || c == '"',
@@ -120,3 +145,230 @@ impl Dialect {
         }
     }
 }
+
+/// Options that control how the tokens are parsed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    pub dialect: Dialect,
+    pub limits: ParserLimits,
+    /// The families of syntax that are rejected with a [`DeniedSyntax`] error,
+    /// e.g. on public endpoints. Nothing is denied by default.
+    pub denied_syntax: BitFlags<SyntaxFeature>,
+}
+
+impl ParseOptions {
+    pub fn is_allowed(&self, feature: SyntaxFeature) -> bool {
+        !self.denied_syntax.contains(feature)
+    }
+
+    pub fn allow(mut self, features: impl Into<BitFlags<SyntaxFeature>>) -> Self {
+        self.denied_syntax.remove(features);
+        self
+    }
+
+    pub fn deny(mut self, features: impl Into<BitFlags<SyntaxFeature>>) -> Self {
+        self.denied_syntax.insert(features);
+        self
+    }
+}
+
+/// A family of syntax that can be denied by the [`ParseOptions`].
+#[bitflags]
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxFeature {
+    /// Optimizer hints like `SELECT /*+ SET_VAR(timezone='UTC') */ ...`. Hint-like
+    /// comments in other positions are skipped by the tokenizer as before.
+    Hints = 1 << 0,
+    /// Reading a stage or a URI as a table, like `SELECT * FROM @stage`.
+    StageTable = 1 << 1,
+    /// UDFs served by an external server, like
+    /// `CREATE FUNCTION ... LANGUAGE python HANDLER = '...' ADDRESS = '...'`.
+    UdfServer = 1 << 2,
+    /// `EXPLAIN` and `EXPLAIN ANALYZE`.
+    Explain = 1 << 3,
+    /// `SET GLOBAL <variable> = <value>`.
+    SetGlobal = 1 << 4,
+}
+
+/// Resource limits that protect the parser and the consumers of the AST from
+/// malicious or generated SQL. Exceeding a limit fails the parse with a
+/// [`LimitError`].
+///
+/// Nothing is limited by default, as [`parse_sql`](crate::parser::parse_sql) has
+/// always done. Untrusted SQL should be parsed with [`ParserLimits::recommended`]
+/// through [`parse_sql_with_options`](crate::parser::parse_sql_with_options).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserLimits {
+    /// The maximum depth of nested expressions, queries and table references,
+    /// e.g. parenthesized expressions, subqueries or function arguments.
+    pub max_depth: usize,
+    /// The maximum number of operands and operators in a chain like
+    /// `a AND b AND c`, `... UNION ALL ...` or `... JOIN ...`, which is built into
+    /// a left-deep tree.
+    pub max_chain_len: usize,
+    /// The maximum number of tokens in the statement.
+    pub max_tokens: usize,
+    /// The maximum number of items in an `IN (...)` list.
+    pub max_in_list_len: usize,
+    /// The maximum length of the statement in bytes.
+    pub max_statement_bytes: usize,
+}
+
+impl Default for ParserLimits {
+    fn default() -> Self {
+        ParserLimits {
+            max_depth: usize::MAX,
+            max_chain_len: usize::MAX,
+            max_tokens: usize::MAX,
+            max_in_list_len: usize::MAX,
+            max_statement_bytes: usize::MAX,
+        }
+    }
+}
+
+impl ParserLimits {
+    /// Limits the nesting and the chains to what the parser and the consumers of
+    /// the AST can handle on a 2 MiB thread stack in a release build, or on the
+    /// 8 MiB main thread stack in a debug build. A debug build on a 2 MiB thread,
+    /// like a test thread, overflows the stack at about a third of these limits, so
+    /// it needs smaller limits or a bigger stack.
+    pub fn recommended() -> Self {
+        ParserLimits {
+            max_depth: 48,
+            max_chain_len: 2048,
+            ..Default::default()
+        }
+    }
+}
+
+/// The state shared by all parsers of one input: the options, the current
+/// nesting depth, the first limit that has been exceeded or syntax that has
+/// been denied, what is expected at the end of the input, and where operands
+/// are expected in the innermost sequence of Pratt parser elements.
+#[derive(Debug, Default)]
+pub struct ParseState {
+    options: ParseOptions,
+    depth: Cell<usize>,
+    limit_error: RefCell<Option<LimitError>>,
+    denied_syntax: RefCell<Option<DeniedSyntax>>,
+    expected_at_end: RefCell<Vec<ErrorKind>>,
+    completion_contexts: RefCell<Vec<CompletionContext>>,
+    operand_positions: RefCell<Vec<usize>>,
+}
+
+impl ParseState {
+    pub fn new(options: ParseOptions) -> Self {
+        ParseState {
+            options,
+            ..Default::default()
+        }
+    }
+
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    pub fn limits(&self) -> &ParserLimits {
+        &self.options.limits
+    }
+
+    /// The limit the parse has been aborted by, if any.
+    pub fn limit_error(&self) -> Option<LimitError> {
+        self.limit_error.borrow().clone()
+    }
+
+    pub(crate) fn set_limit_error(&self, err: LimitError) {
+        self.limit_error.borrow_mut().get_or_insert(err);
+    }
+
+    /// The denied syntax the parse has been aborted by, if any.
+    pub fn denied_syntax(&self) -> Option<DeniedSyntax> {
+        self.denied_syntax.borrow().clone()
+    }
+
+    pub(crate) fn set_denied_syntax(&self, err: DeniedSyntax) {
+        self.denied_syntax.borrow_mut().get_or_insert(err);
+    }
+
+    /// The tokens expected at the end of the input by any branch of the parser,
+    /// even if the errors of the branch are discarded afterwards.
+    pub fn expected_at_end(&self) -> Vec<ErrorKind> {
+        self.expected_at_end
+            .borrow()
+            .iter()
+            .copied()
+            .unique()
+            .collect()
+    }
+
+    pub(crate) fn add_expected_at_end(&self, kind: ErrorKind) {
+        self.expected_at_end.borrow_mut().push(kind);
+    }
+
+    /// The number of times a token has been expected at the end of the input.
+    pub(crate) fn expected_at_end_count(&self) -> usize {
+        self.expected_at_end.borrow().len()
+    }
+
+    /// The kinds of names expected at the end of the input, in the order they are
+    /// tried by the parser.
+    pub fn completion_contexts(&self) -> Vec<CompletionContext> {
+        self.completion_contexts.borrow().clone()
+    }
+
+    pub(crate) fn add_completion_context(&self, context: CompletionContext) {
+        let mut contexts = self.completion_contexts.borrow_mut();
+        if !contexts.contains(&context) {
+            contexts.push(context);
+        }
+    }
+
+    /// Forget the tokens expected after the first `count` times.
+    pub(crate) fn truncate_expected_at_end(&self, count: usize) {
+        self.expected_at_end.borrow_mut().truncate(count);
+    }
+
+    pub(crate) fn completion_context_count(&self) -> usize {
+        self.completion_contexts.borrow().len()
+    }
+
+    /// Forget the contexts recorded after the first `count` ones.
+    pub(crate) fn truncate_completion_contexts(&self, count: usize) {
+        self.completion_contexts.borrow_mut().truncate(count);
+    }
+
+    /// Start a sequence of Pratt parser elements, which expects an operand at
+    /// `position`, returning the operand positions of the enclosing sequence.
+    pub(crate) fn start_sequence(&self, position: usize) -> Vec<usize> {
+        self.operand_positions.replace(vec![position])
+    }
+
+    /// Restore the operand positions of the enclosing sequence after a sequence ends.
+    pub(crate) fn end_sequence(&self, positions: Vec<usize>) {
+        self.operand_positions.replace(positions);
+    }
+
+    /// Record that the current sequence expects an operand at `position`, after a
+    /// prefix or infix operator.
+    pub(crate) fn add_operand_position(&self, position: usize) {
+        self.operand_positions.borrow_mut().push(position);
+    }
+
+    pub(crate) fn is_operand_position(&self, position: usize) -> bool {
+        self.operand_positions.borrow().contains(&position)
+    }
+
+    /// Enter a nested parser, returning false if the nesting depth limit is reached.
+    pub(crate) fn enter(&self) -> bool {
+        if self.depth.get() >= self.options.limits.max_depth {
+            return false;
+        }
+        self.depth.set(self.depth.get() + 1);
+        true
+    }
+
+    pub(crate) fn leave(&self) {
+        self.depth.set(self.depth.get() - 1);
+    }
+}
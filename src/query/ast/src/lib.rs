@@ -15,32 +15,115 @@
 // TODO(xuanwo): Add crate level documents here.
 #![allow(clippy::uninlined_format_args)]
 
+mod diagnostic;
+pub use diagnostic::line_column;
+pub use diagnostic::Diagnostic;
+pub use diagnostic::DiagnosticCode;
+pub use diagnostic::Severity;
+pub use diagnostic::Suggestion;
+pub use diagnostic::TextEdit;
+
 mod error;
 pub use error::display_parser_error;
 pub use error::Backtrace;
+pub use error::DeniedSyntax;
 pub use error::Error;
 pub use error::ErrorKind;
+pub use error::LimitError;
 
-mod visitors;
+pub mod visitors;
+pub use visitors::anonymize;
+pub use visitors::bind_params;
+pub use visitors::column_lineage;
+pub use visitors::diff;
+pub use visitors::diff_with_options;
+pub use visitors::fingerprint;
+pub use visitors::normalize_statement;
+pub use visitors::param_value_to_expr;
+pub use visitors::plan_table_migration;
+pub use visitors::referenced_objects;
+pub use visitors::simplify_expr;
+pub use visitors::traverse_expr;
+pub use visitors::traverse_expr_mut;
+pub use visitors::traverse_query;
+pub use visitors::traverse_query_mut;
+pub use visitors::traverse_statement;
+pub use visitors::traverse_statement_mut;
+pub use visitors::walk_column_id_mut;
+pub use visitors::walk_cte;
+pub use visitors::walk_cte_mut;
 pub use visitors::walk_expr;
 pub use visitors::walk_expr_mut;
+pub use visitors::walk_identifier;
+pub use visitors::walk_identifier_mut;
+pub use visitors::walk_join;
+pub use visitors::walk_join_condition;
+pub use visitors::walk_join_condition_mut;
+pub use visitors::walk_join_mut;
 pub use visitors::walk_query;
 pub use visitors::walk_query_mut;
+pub use visitors::walk_select_stmt;
+pub use visitors::walk_select_stmt_mut;
 pub use visitors::walk_select_target;
 pub use visitors::walk_select_target_mut;
+pub use visitors::walk_set_expr;
+pub use visitors::walk_set_expr_mut;
+pub use visitors::walk_statement;
 pub use visitors::walk_statement_mut;
+pub use visitors::walk_table_reference;
 pub use visitors::walk_table_reference_mut;
+pub use visitors::walk_time_travel_point;
+pub use visitors::walk_time_travel_point_mut;
+pub use visitors::walk_window_definition;
+pub use visitors::walk_with;
+pub use visitors::walk_with_mut;
+pub use visitors::AnonymizationMap;
+pub use visitors::Anonymizer;
+pub use visitors::AstChange;
+pub use visitors::AstNode;
+pub use visitors::AstNodeMut;
+pub use visitors::BindError;
+pub use visitors::ChangeKind;
+pub use visitors::Children;
+pub use visitors::ColumnLineage;
+pub use visitors::DiffOptions;
+pub use visitors::Fingerprint;
+pub use visitors::Fold;
+pub use visitors::LineageError;
+pub use visitors::MigrationError;
+pub use visitors::MigrationOptions;
+pub use visitors::NameKind;
+pub use visitors::NormalForm;
+pub use visitors::ObjectAccess;
+pub use visitors::ObjectName;
+pub use visitors::ParamValue;
+pub use visitors::Params;
+pub use visitors::ReferencedObject;
+pub use visitors::ReferencedObjects;
+pub use visitors::RequiredPrivilege;
+pub use visitors::SchemaProvider;
+pub use visitors::Simplifier;
+pub use visitors::SourceColumn;
+pub use visitors::StatementCategory;
+pub use visitors::StatementClass;
+pub use visitors::StatementLineage;
+pub use visitors::VisitResult;
 pub use visitors::Visitor;
 pub use visitors::VisitorMut;
 
 mod input;
 pub use input::Dialect;
 pub use input::Input;
+pub use input::ParseOptions;
+pub use input::ParseState;
+pub use input::ParserLimits;
+pub use input::SyntaxFeature;
 
 mod util;
 pub use util::match_text;
 pub use util::match_token;
 
 pub mod ast;
+pub mod builder;
 pub mod parser;
 pub mod meta;
@@ -0,0 +1,442 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Range;
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
+
+use crate::input::Dialect;
+use crate::input::Input;
+use crate::parser::highlight::comment_spans;
+use crate::parser::statement::statement;
+use crate::parser::token::*;
+use crate::Backtrace;
+use crate::ErrorKind;
+use crate::ParseState;
+
+/// The kind of name expected at the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompletionContext {
+    TableName,
+    ColumnName,
+    FunctionName,
+    TypeName,
+    /// A stage like `@stage` or a URI like `'s3://bucket/path'`.
+    Stage,
+    /// The key of an option like `key = value`.
+    OptionKey,
+}
+
+/// What can be typed at the cursor, returned by [`complete`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completions {
+    /// The span of the word before the cursor, which is replaced by a completion.
+    /// It's empty if the cursor is not at the end of a word.
+    pub span: Range,
+    /// The word before the cursor, e.g. `SEL` in `SEL|`.
+    pub prefix: String,
+    /// The qualifier of the name being typed, e.g. `["db", "t"]` in `SELECT db.t.|`.
+    pub qualifier: Vec<String>,
+    /// The keywords expected at the cursor, the most similar to the prefix first.
+    pub keywords: Vec<String>,
+    /// The other kinds of tokens expected at the cursor, e.g. `Ident` or
+    /// `LiteralInteger`.
+    pub token_kinds: Vec<TokenKind>,
+    /// The kinds of names expected at the cursor.
+    pub contexts: Vec<CompletionContext>,
+    /// The names of the CTEs visible at the cursor.
+    pub ctes: Vec<String>,
+    /// The tables visible at the cursor, by their alias if they have one, e.g.
+    /// `t` and `u` in `SELECT | FROM db.t, v AS u`.
+    pub aliases: Vec<String>,
+}
+
+impl Completions {
+    fn empty(cursor: usize) -> Self {
+        Completions {
+            span: Range {
+                start: cursor,
+                end: cursor,
+            },
+            prefix: String::new(),
+            qualifier: vec![],
+            keywords: vec![],
+            token_kinds: vec![],
+            contexts: vec![],
+            ctes: vec![],
+            aliases: vec![],
+        }
+    }
+}
+
+/// Complete the statement of `sql` at the byte offset `cursor`, which may be in a
+/// script of statements separated by `;`.
+///
+/// The statement is parsed up to the word before the cursor to find the expected
+/// tokens and kinds of names, while the visible CTEs and tables are found in the
+/// whole statement. Nothing is completed in a comment or a literal, or if the
+/// statement can't be tokenized up to the cursor.
+pub fn complete(sql: &str, cursor: usize, dialect: Dialect) -> Completions {
+    let mut completions = Completions::empty(cursor);
+    if !sql.is_char_boundary(cursor) {
+        return completions;
+    }
+
+    let mut tokens = vec![];
+    for token in Tokenizer::new(sql) {
+        match token {
+            Ok(token) if token.kind == EOI => break,
+            Ok(token) => tokens.push(token),
+            Err(err) => match err.span() {
+                Some(span) if span.start > cursor => break,
+                _ => return completions,
+            },
+        }
+    }
+
+    // The tokens before the word at the cursor, and the tokens after it.
+    let split = tokens.partition_point(|token| token.span.end < cursor);
+    let mut word_start = cursor;
+    let mut after = split;
+    match tokens.get(split) {
+        Some(token) if token.span.start < cursor && is_word(token, dialect) => {
+            word_start = token.span.start;
+            after = split + 1;
+        }
+        Some(token) if token.span.start < cursor && token.span.end > cursor => {
+            return completions;
+        }
+        _ => {
+            let gap_start = split.checked_sub(1).map_or(0, |i| tokens[i].span.end);
+            if in_comment(&sql[gap_start..cursor]) {
+                return completions;
+            }
+        }
+    }
+    let before = tokens.partition_point(|token| token.span.end <= word_start);
+    completions.span.start = word_start;
+    completions.prefix = sql[word_start..cursor].to_string();
+
+    let start = tokens[..before]
+        .iter()
+        .rposition(|token| token.kind == SemiColon)
+        .map_or(0, |i| i + 1);
+    let end = tokens[after..]
+        .iter()
+        .position(|token| token.kind == SemiColon)
+        .map_or(tokens.len(), |i| after + i);
+
+    // The word being typed is not a name in scope yet.
+    let statement = tokens[start..before]
+        .iter()
+        .chain(&tokens[after..end])
+        .cloned()
+        .collect::<Vec<_>>();
+    completions.qualifier = qualifier(&tokens[start..before], dialect);
+    expected(
+        sql,
+        &tokens[start..before],
+        word_start,
+        dialect,
+        &mut completions,
+    );
+    visible_names(&statement, word_start, dialect, &mut completions);
+    completions
+}
+
+/// Whether the kind of token is a keyword, unlike `ColumnPosition` like `$1` or
+/// `AtString` like `@stage`.
+fn is_keyword(kind: TokenKind) -> bool {
+    kind.is_keyword() && !matches!(kind, ColumnPosition | AtString)
+}
+
+/// Whether the token can be a name or a keyword being typed.
+fn is_word(token: &Token, dialect: Dialect) -> bool {
+    match token.kind {
+        Ident => true,
+        QuotedString => token
+            .text()
+            .chars()
+            .next()
+            .is_some_and(|c| dialect.is_ident_quote(c)),
+        kind => {
+            is_keyword(kind)
+                && token
+                    .text()
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+    }
+}
+
+/// Whether the end of `gap`, the whitespaces and comments between two tokens, is
+/// in a comment.
+fn in_comment(gap: &str) -> bool {
+    comment_spans(gap).last().is_some_and(|span| {
+        let comment = &gap[span.clone()];
+        let closed = comment.starts_with("/*") && comment.len() >= 4 && comment.ends_with("*/");
+        span.end == gap.len() && !closed
+    })
+}
+
+/// The names before the dot at the end of `tokens`, e.g. `db` and `t` in `db.t.`.
+fn qualifier(tokens: &[Token], dialect: Dialect) -> Vec<String> {
+    let mut qualifier = vec![];
+    let mut rest = tokens;
+    while let [init @ .., name, dot] = rest {
+        if dot.kind != Dot || !is_word(name, dialect) {
+            break;
+        }
+        qualifier.push(name.text().to_string());
+        rest = init;
+    }
+    qualifier.reverse();
+    qualifier
+}
+
+/// Parse `tokens` of `source`, which end before the word at the cursor, to find the
+/// tokens and kinds of names expected at the word.
+fn expected(
+    source: &str,
+    tokens: &[Token],
+    word_start: usize,
+    dialect: Dialect,
+    completions: &mut Completions,
+) {
+    let mut tokens = tokens.to_vec();
+    tokens.push(Token {
+        source,
+        kind: EOI,
+        span: Range {
+            start: word_start,
+            end: word_start,
+        },
+    });
+
+    let backtrace = Backtrace::new();
+    let state = ParseState::default();
+    let _ = statement(Input(&tokens, dialect, &backtrace, &state));
+    let errors = state.expected_at_end();
+
+    let mut keywords = errors
+        .iter()
+        .flat_map(|kind| match kind {
+            ErrorKind::ExpectToken(kind) if is_keyword(*kind) => vec![format!("{:?}", kind)],
+            ErrorKind::ExpectText(text)
+                if text.chars().all(|c| c.is_ascii_alphabetic() || c == '_') =>
+            {
+                vec![text.to_uppercase()]
+            }
+            ErrorKind::ExpectOption { options } => {
+                options.iter().map(|option| option.to_uppercase()).collect()
+            }
+            _ => vec![],
+        })
+        .unique()
+        .sorted()
+        .collect::<Vec<_>>();
+    if !completions.prefix.is_empty() {
+        let prefix = completions.prefix.to_lowercase();
+        keywords.sort_by_cached_key(|keyword| {
+            OrderedFloat::from(-strsim::jaro_winkler(&keyword.to_lowercase(), &prefix))
+        });
+    }
+    completions.keywords = keywords;
+    completions.token_kinds = errors
+        .iter()
+        .filter_map(|kind| match kind {
+            ErrorKind::ExpectToken(kind) if !is_keyword(*kind) && *kind != EOI => Some(*kind),
+            _ => None,
+        })
+        .unique()
+        .sorted_by_key(|kind| format!("{:?}", kind))
+        .collect();
+    completions.contexts = state.completion_contexts();
+}
+
+/// Find the CTEs and tables in `tokens`, the whole statement, that are visible at
+/// `cursor`. A name is visible if it's defined in the parentheses enclosing the
+/// cursor or in the parentheses enclosing those.
+fn visible_names(tokens: &[Token], cursor: usize, dialect: Dialect, completions: &mut Completions) {
+    // The parentheses enclosing each token, by the index of `(`, or `None` at the top level.
+    let mut groups = Vec::with_capacity(tokens.len());
+    let mut enclosing = vec![];
+    let mut cursor_groups = None;
+    for (i, token) in tokens.iter().enumerate() {
+        if cursor_groups.is_none() && token.span.start >= cursor {
+            cursor_groups = Some(enclosing.clone());
+        }
+        groups.push(enclosing.last().copied());
+        match token.kind {
+            LParen => enclosing.push(i),
+            RParen => {
+                enclosing.pop();
+            }
+            _ => {}
+        }
+    }
+    let cursor_groups = cursor_groups.unwrap_or(enclosing);
+    let is_visible = |i: usize| match groups[i] {
+        Some(group) => cursor_groups.contains(&group),
+        None => true,
+    };
+
+    let name = |i: usize| {
+        tokens
+            .get(i)
+            .filter(|token| is_word(token, dialect) && !token.kind.is_reserved_ident(false))
+    };
+    let skip_parens = |i: usize| match tokens.get(i) {
+        Some(token) if token.kind == LParen => matching_paren(tokens, i) + 1,
+        _ => i,
+    };
+
+    let mut ctes = vec![];
+    let mut aliases = vec![];
+    for (i, token) in tokens.iter().enumerate() {
+        match token.kind {
+            WITH => {
+                let mut j = i + 1;
+                if tokens.get(j).map(|token| token.kind) == Some(RECURSIVE) {
+                    j += 1;
+                }
+                while let Some(cte) = name(j) {
+                    j = skip_parens(j + 1);
+                    if tokens.get(j).map(|token| token.kind) != Some(AS) {
+                        break;
+                    }
+                    j += 1;
+                    if tokens.get(j).map(|token| token.kind) == Some(MATERIALIZED) {
+                        j += 1;
+                    }
+                    if tokens.get(j).map(|token| token.kind) != Some(LParen) {
+                        break;
+                    }
+                    if is_visible(i) {
+                        ctes.push(cte.text().to_string());
+                    }
+                    j = skip_parens(j);
+                    if tokens.get(j).map(|token| token.kind) != Some(Comma) {
+                        break;
+                    }
+                    j += 1;
+                }
+            }
+            // Not `IS DISTINCT FROM` or `EXTRACT(... FROM ...)`.
+            FROM if i > 0 && tokens[i - 1].kind == DISTINCT => {}
+            FROM if matches!(groups[i], Some(group) if !is_query(tokens, group + 1)) => {}
+            FROM | JOIN | UPDATE => {
+                let mut j = i + 1;
+                while let Some((next, alias)) = table_reference(tokens, j, dialect) {
+                    if is_visible(i) {
+                        aliases.extend(alias);
+                    }
+                    if tokens.get(next).map(|token| token.kind) != Some(Comma) {
+                        break;
+                    }
+                    j = next + 1;
+                }
+            }
+            INTO | USING if tokens[0].kind == MERGE => {
+                if let Some((_, alias)) = table_reference(tokens, i + 1, dialect) {
+                    aliases.extend(alias);
+                }
+            }
+            _ => {}
+        }
+    }
+    completions.ctes = ctes.into_iter().unique().collect();
+    completions.aliases = aliases.into_iter().unique().collect();
+}
+
+/// Scan a table reference starting at `tokens[i]`, returning the index after it
+/// and the alias or the name it's referred to by.
+fn table_reference(
+    tokens: &[Token],
+    i: usize,
+    dialect: Dialect,
+) -> Option<(usize, Option<String>)> {
+    let kind = |i: usize| tokens.get(i).map(|token| token.kind);
+    let is_name = |i: usize, after_as: bool| {
+        tokens
+            .get(i)
+            .is_some_and(|token| is_word(token, dialect) && !token.kind.is_reserved_ident(after_as))
+    };
+
+    let mut j = i;
+    let mut name = None;
+    match kind(j) {
+        Some(LParen) => j = matching_paren(tokens, j) + 1,
+        Some(AtString) => j += 1,
+        _ if is_name(j, false) => {
+            while kind(j + 1) == Some(Dot) {
+                // An incomplete name like `db.`.
+                if !is_name(j + 2, false) {
+                    return None;
+                }
+                j += 2;
+            }
+            name = Some(tokens[j].text().to_string());
+            j += 1;
+            // A table function like `numbers(10)`.
+            if kind(j) == Some(LParen) {
+                j = matching_paren(tokens, j) + 1;
+            }
+        }
+        _ => return None,
+    }
+    // A time travel point like `AT (SNAPSHOT => '...')`.
+    if kind(j) == Some(AT) && kind(j + 1) == Some(LParen) {
+        j = matching_paren(tokens, j + 1) + 1;
+    }
+
+    let after_as = kind(j) == Some(AS);
+    if after_as {
+        j += 1;
+    }
+    // Not an option like `FILE_FORMAT = (...)` after a stage.
+    if is_name(j, after_as) && kind(j + 1) != Some(Eq) {
+        name = Some(tokens[j].text().to_string());
+        j += 1;
+    }
+    Some((j, name))
+}
+
+/// Whether a query starts at `tokens[i]`, possibly in parentheses.
+fn is_query(tokens: &[Token], i: usize) -> bool {
+    match tokens.get(i).map(|token| token.kind) {
+        Some(SELECT | WITH | VALUES) => true,
+        Some(LParen) => is_query(tokens, i + 1),
+        _ => false,
+    }
+}
+
+/// The index of the `)` matching the `(` at `tokens[i]`, or the last index if it's
+/// not closed.
+fn matching_paren(tokens: &[Token], i: usize) -> usize {
+    let mut depth = 0;
+    for (j, token) in tokens.iter().enumerate().skip(i) {
+        match token.kind {
+            LParen => depth += 1,
+            RParen => {
+                depth -= 1;
+                if depth == 0 {
+                    return j;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len().saturating_sub(1)
+}
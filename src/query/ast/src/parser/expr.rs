@@ -31,10 +31,12 @@ use crate::parser::query::*;
 use crate::parser::token::*;
 use crate::parser::unescape::unescape_at_string;
 use crate::parser::unescape::unescape_string;
+use crate::parser::CompletionContext;
 use crate::rule;
 use crate::util::*;
 use crate::Error;
 use crate::ErrorKind;
+use crate::LimitError;
 
 pub const BETWEEN_PREC: u32 = 20;
 pub const NOT_PREC: u32 = 15;
@@ -54,8 +56,8 @@ pub fn values_with_placeholder(i: Input) -> IResult<Vec<Option<Expr>>> {
 
 pub fn subexpr(min_precedence: u32) -> impl FnMut(Input) -> IResult<Expr> {
     move |i| {
-        let higher_prec_expr_element =
-            |i| {
+        nested(i, |i| {
+            let higher_prec_expr_element = |i| {
                 expr_element(i).and_then(|(rest, elem)| {
                     match PrattParser::<std::iter::Once<_>>::query(&mut ExprParser, &elem).unwrap()
                     {
@@ -67,83 +69,87 @@ pub fn subexpr(min_precedence: u32) -> impl FnMut(Input) -> IResult<Expr> {
                                 ErrorKind::Other("expected more tokens for expression"),
                             )))
                         }
-                        _ => Ok((rest, elem)),
+                        _ => {
+                            expect_operand_after(&mut ExprParser, &elem, rest);
+                            Ok((rest, elem))
+                        }
                     }
                 })
             };
 
-        let (rest, mut expr_elements) = rule! { #higher_prec_expr_element+ }(i)?;
+            let (rest, mut expr_elements) = rule! { #higher_prec_expr_element+ }(i)?;
 
-        for (prev, curr) in (-1..(expr_elements.len() as isize)).tuple_windows() {
-            // Replace binary Plus and Minus to the unary one, if it's following another op
-            // or it's the first element.
-            if prev == -1
-                || matches!(
-                    expr_elements[prev as usize].elem,
-                    ExprElement::UnaryOp { .. } | ExprElement::BinaryOp { .. }
-                )
-            {
-                match &mut expr_elements[curr as usize].elem {
-                    elem @ ExprElement::BinaryOp {
-                        op: BinaryOperator::Plus,
-                    } => {
-                        *elem = ExprElement::UnaryOp {
-                            op: UnaryOperator::Plus,
-                        };
-                    }
-                    elem @ ExprElement::BinaryOp {
-                        op: BinaryOperator::Minus,
-                    } => {
-                        *elem = ExprElement::UnaryOp {
-                            op: UnaryOperator::Minus,
-                        };
+            for (prev, curr) in (-1..(expr_elements.len() as isize)).tuple_windows() {
+                // Replace binary Plus and Minus to the unary one, if it's following another op
+                // or it's the first element.
+                if prev == -1
+                    || matches!(
+                        expr_elements[prev as usize].elem,
+                        ExprElement::UnaryOp { .. } | ExprElement::BinaryOp { .. }
+                    )
+                {
+                    match &mut expr_elements[curr as usize].elem {
+                        elem @ ExprElement::BinaryOp {
+                            op: BinaryOperator::Plus,
+                        } => {
+                            *elem = ExprElement::UnaryOp {
+                                op: UnaryOperator::Plus,
+                            };
+                        }
+                        elem @ ExprElement::BinaryOp {
+                            op: BinaryOperator::Minus,
+                        } => {
+                            *elem = ExprElement::UnaryOp {
+                                op: UnaryOperator::Minus,
+                            };
+                        }
+                        _ => {}
                     }
-                    _ => {}
                 }
-            }
 
-            // If it's following a prefix or infix element or it's the first element, ...
-            if prev == -1
-                || matches!(
-                    PrattParser::<std::iter::Once<_>>::query(
-                        &mut ExprParser,
-                        &expr_elements[prev as usize]
+                // If it's following a prefix or infix element or it's the first element, ...
+                if prev == -1
+                    || matches!(
+                        PrattParser::<std::iter::Once<_>>::query(
+                            &mut ExprParser,
+                            &expr_elements[prev as usize]
+                        )
+                        .unwrap(),
+                        Affix::Prefix(_) | Affix::Infix(_, _)
                     )
-                    .unwrap(),
-                    Affix::Prefix(_) | Affix::Infix(_, _)
-                )
-            {
-                // replace bracket map access to an array, ...
-                if let ExprElement::MapAccess {
-                    accessor: MapAccessor::Bracket { key },
-                } = &expr_elements[curr as usize].elem
                 {
-                    let span = expr_elements[curr as usize].span;
-                    expr_elements[curr as usize] = WithSpan {
-                        span,
-                        elem: ExprElement::Array {
-                            exprs: vec![(**key).clone()],
-                        },
-                    };
-                }
+                    // replace bracket map access to an array, ...
+                    if let ExprElement::MapAccess {
+                        accessor: MapAccessor::Bracket { key },
+                    } = &expr_elements[curr as usize].elem
+                    {
+                        let span = expr_elements[curr as usize].span;
+                        expr_elements[curr as usize] = WithSpan {
+                            span,
+                            elem: ExprElement::Array {
+                                exprs: vec![(**key).clone()],
+                            },
+                        };
+                    }
 
-                // and replace `.<number>` map access to floating point literal.
-                if let ExprElement::MapAccess {
-                    accessor: MapAccessor::DotNumber { .. },
-                } = &expr_elements[curr as usize].elem
-                {
-                    let span = expr_elements[curr as usize].span;
-                    expr_elements[curr as usize] = WithSpan {
-                        span,
-                        elem: ExprElement::Literal {
-                            lit: literal(span)?.1,
-                        },
-                    };
+                    // and replace `.<number>` map access to floating point literal.
+                    if let ExprElement::MapAccess {
+                        accessor: MapAccessor::DotNumber { .. },
+                    } = &expr_elements[curr as usize].elem
+                    {
+                        let span = expr_elements[curr as usize].span;
+                        expr_elements[curr as usize] = WithSpan {
+                            span,
+                            elem: ExprElement::Literal {
+                                lit: literal(span)?.1,
+                            },
+                        };
+                    }
                 }
             }
-        }
 
-        run_pratt_parser(ExprParser, &expr_elements.into_iter(), rest, i)
+            run_pratt_parser(ExprParser, &expr_elements.into_iter(), rest, i)
+        })
     }
 }
 
@@ -173,7 +179,7 @@ pub fn column_id<'a>(i: Input<'a>) -> IResult<'a, ColumnID> {
 pub fn column_ref<'a>(
     i: Input<'a>,
 ) -> IResult<'a, (Option<Identifier>, Option<Identifier>, ColumnID)> {
-    alt((
+    let column_ref = alt((
         map(
             rule! { #ident ~ "." ~ #ident ~ "." ~ #column_id },
             |(ident1, _, ident2, _, ident3)| (Some(ident1), Some(ident2), ident3),
@@ -183,7 +189,8 @@ pub fn column_ref<'a>(
             |(ident2, _, ident3)| (None, Some(ident2), ident3),
         ),
         map(rule! {  #column_id }, |ident3| (None, None, ident3)),
-    ))(i)
+    ));
+    completion_context(CompletionContext::ColumnName, column_ref)(i)
 }
 
 /// A 'flattened' AST of expressions.
@@ -277,6 +284,8 @@ pub enum ExprElement {
     Literal {
         lit: Literal,
     },
+    /// A placeholder `?`
+    Placeholder,
     /// `Count(*)` expression
     CountAll {
         window: Option<Window>,
@@ -479,6 +488,9 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement>>> PrattParser<I> for ExprP
                 span: transform_span(elem.span.0),
                 lit,
             },
+            ExprElement::Placeholder => Expr::Placeholder {
+                span: transform_span(elem.span.0),
+            },
             ExprElement::CountAll { window } => Expr::CountAll {
                 span: transform_span(elem.span.0),
                 window,
@@ -673,15 +685,23 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             not: opt_not.is_some(),
         },
     );
-    let in_list = map(
-        rule! {
+    let in_list = |i| {
+        let (rest, (span, (opt_not, _, _, list, _))) = consumed(rule! {
             NOT? ~ IN ~ "(" ~ #comma_separated_list1(subexpr(0)) ~ ^")"
-        },
-        |(opt_not, _, _, list, _)| ExprElement::InList {
+        })(i)?;
+        let limit = i.3.limits().max_in_list_len;
+        if list.len() > limit {
+            return limit_exceeded(i, LimitError::InListTooLong {
+                len: list.len(),
+                limit,
+                span: transform_span(&span).unwrap(),
+            });
+        }
+        Ok((rest, ExprElement::InList {
             list,
             not: opt_not.is_some(),
-        },
-    );
+        }))
+    };
     let in_subquery = map(
         rule! {
             NOT? ~ IN ~ "(" ~ #query  ~ ^")"
@@ -939,6 +959,7 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
     // and then will be converted back to a floating point literal if the map access
     // is not following a primary element nor a postfix element.
     let literal = map(literal, |lit| ExprElement::Literal { lit });
+    let placeholder = map(rule! { "?" }, |_| ExprElement::Placeholder);
     let array = map(
         // Array that contains a single literal item will be parsed as a bracket map access,
         // and then will be converted back to an array if the map access is not following
@@ -1029,42 +1050,43 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
     let (rest, (span, elem)) = consumed(alt((
         // Note: each `alt` call supports maximum of 21 parsers
         rule!(
-            #is_null : "`... IS [NOT] NULL`"
-            | #in_list : "`[NOT] IN (<expr>, ...)`"
-            | #in_subquery : "`[NOT] IN (SELECT ...)`"
-            | #exists : "`[NOT] EXISTS (SELECT ...)`"
-            | #between : "`[NOT] BETWEEN ... AND ...`"
-            | #binary_op : "<operator>"
+            #pratt_operator(is_null) : "`... IS [NOT] NULL`"
+            | #pratt_operator(in_list) : "`[NOT] IN (<expr>, ...)`"
+            | #pratt_operator(in_subquery) : "`[NOT] IN (SELECT ...)`"
+            | #pratt_operand(exists) : "`[NOT] EXISTS (SELECT ...)`"
+            | #pratt_operator(between) : "`[NOT] BETWEEN ... AND ...`"
+            | #pratt_operator(binary_op) : "<operator>"
             | #unary_op : "<operator>"
-            | #cast : "`CAST(... AS ...)`"
-            | #date_add: "`DATE_ADD(..., ..., (YEAR | QUARTER | MONTH | DAY | HOUR | MINUTE | SECOND | DOY | DOW))`"
-            | #date_sub: "`DATE_SUB(..., ..., (YEAR | QUARTER | MONTH | DAY | HOUR | MINUTE | SECOND | DOY | DOW))`"
-            | #date_trunc: "`DATE_TRUNC((YEAR | QUARTER | MONTH | DAY | HOUR | MINUTE | SECOND), ...)`"
-            | #date_expr: "`DATE <str_literal>`"
-            | #timestamp_expr: "`TIMESTAMP <str_literal>`"
-            | #interval: "`INTERVAL ... (YEAR | QUARTER | MONTH | DAY | HOUR | MINUTE | SECOND | DOY | DOW)`"
-            | #pg_cast : "`::<type_name>`"
-            | #extract : "`EXTRACT((YEAR | QUARTER | MONTH | DAY | HOUR | MINUTE | SECOND) FROM ...)`"
+            | #pratt_operand(cast) : "`CAST(... AS ...)`"
+            | #pratt_operand(date_add): "`DATE_ADD(..., ..., (YEAR | QUARTER | MONTH | DAY | HOUR | MINUTE | SECOND | DOY | DOW))`"
+            | #pratt_operand(date_sub): "`DATE_SUB(..., ..., (YEAR | QUARTER | MONTH | DAY | HOUR | MINUTE | SECOND | DOY | DOW))`"
+            | #pratt_operand(date_trunc): "`DATE_TRUNC((YEAR | QUARTER | MONTH | DAY | HOUR | MINUTE | SECOND), ...)`"
+            | #pratt_operand(date_expr): "`DATE <str_literal>`"
+            | #pratt_operand(timestamp_expr): "`TIMESTAMP <str_literal>`"
+            | #pratt_operand(interval): "`INTERVAL ... (YEAR | QUARTER | MONTH | DAY | HOUR | MINUTE | SECOND | DOY | DOW)`"
+            | #pratt_operator(pg_cast) : "`::<type_name>`"
+            | #pratt_operand(extract) : "`EXTRACT((YEAR | QUARTER | MONTH | DAY | HOUR | MINUTE | SECOND) FROM ...)`"
         ),
         rule!(
-            #position : "`POSITION(... IN ...)`"
-            | #substring : "`SUBSTRING(... [FROM ...] [FOR ...])`"
-            | #trim : "`TRIM(...)`"
-            | #trim_from : "`TRIM([(BOTH | LEADEING | TRAILING) ... FROM ...)`"
-            | #is_distinct_from: "`... IS [NOT] DISTINCT FROM ...`"
-            | #count_all_with_window : "`COUNT(*) OVER ...`"
-            | #function_call_with_lambda : "<function>"
-            | #function_call_with_window : "<function>"
-            | #function_call_with_params : "<function>"
-            | #function_call : "<function>"
-            | #case : "`CASE ... END`"
-            | #subquery : "`(SELECT ...)`"
-            | #tuple : "`(<expr> [, ...])`"
-            | #column_ref : "<column>"
+            #pratt_operand(position) : "`POSITION(... IN ...)`"
+            | #pratt_operand(substring) : "`SUBSTRING(... [FROM ...] [FOR ...])`"
+            | #pratt_operand(trim) : "`TRIM(...)`"
+            | #pratt_operand(trim_from) : "`TRIM([(BOTH | LEADEING | TRAILING) ... FROM ...)`"
+            | #pratt_operator(is_distinct_from): "`... IS [NOT] DISTINCT FROM ...`"
+            | #pratt_operand(count_all_with_window) : "`COUNT(*) OVER ...`"
+            | #pratt_operand(function_call_with_lambda) : "<function>"
+            | #pratt_operand(function_call_with_window) : "<function>"
+            | #pratt_operand(function_call_with_params) : "<function>"
+            | #pratt_operand(function_call) : "<function>"
+            | #pratt_operand(case) : "`CASE ... END`"
+            | #pratt_operand(subquery) : "`(SELECT ...)`"
+            | #pratt_operand(tuple) : "`(<expr> [, ...])`"
+            | #pratt_operand(column_ref) : "<column>"
             | #map_access : "[<key>] | .<key> | :<key>"
-            | #literal : "<literal>"
-            | #array : "`[...]`"
-            | #map_expr : "`{...}`"
+            | #pratt_operand(literal) : "<literal>"
+            | #pratt_operand(placeholder) : "`?`"
+            | #pratt_operand(array) : "`[...]`"
+            | #pratt_operand(map_expr) : "`{...}`"
         ),
     )))(i)?;
 
@@ -1347,7 +1369,7 @@ pub fn type_name(i: Input) -> IResult<TypeName> {
         rule! { ( STRING | VARCHAR | CHAR | CHARACTER | TEXT | BINARY | VARBINARY ) ~ ( "(" ~ #literal_u64 ~ ")" )? },
     );
     let ty_variant = value(TypeName::Variant, rule! { VARIANT | JSON });
-    map(
+    let type_name = map(
         alt((
             rule! {
             ( #ty_boolean
@@ -1384,7 +1406,8 @@ pub fn type_name(i: Input) -> IResult<TypeName> {
                 ty
             }
         },
-    )(i)
+    );
+    completion_context(CompletionContext::TypeName, type_name)(i)
 }
 
 pub fn interval_kind(i: Input) -> IResult<IntervalKind> {
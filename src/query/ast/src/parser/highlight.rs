@@ -0,0 +1,332 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use common_exception::Range;
+
+use crate::ast::*;
+use crate::input::Dialect;
+use crate::parser::parse_sql;
+use crate::parser::token::*;
+use crate::traverse_statement;
+use crate::AstNode;
+use crate::Children;
+use crate::VisitResult;
+use crate::Visitor;
+
+/// The class of a span of SQL for syntax highlighting, see [`highlight`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenClass {
+    Keyword,
+    Identifier,
+    QuotedIdentifier,
+    String,
+    Number,
+    /// An operator or a punctuation like `+`, `(` or `,`.
+    Operator,
+    Comment,
+    /// An optimizer hint, including the `/*+` and `*/` around it.
+    Hint,
+    /// A placeholder like `?` or `$1`.
+    Placeholder,
+    /// A variable or a stage like `@x`.
+    Variable,
+    Table,
+    Column,
+    Function,
+    /// The alias of a table or a select target, where it's defined or referred to.
+    Alias,
+}
+
+/// Classify the tokens and comments of `sql` for syntax highlighting, in the order
+/// they appear.
+///
+/// The names of the statements that parse are classified by their role, as tables,
+/// columns, functions or aliases, and the other names as identifiers. A statement
+/// with a syntax error is classified by its tokens only, and the classification
+/// stops where the SQL can't be tokenized, e.g. at an unterminated string.
+pub fn highlight(sql: &str, dialect: Dialect) -> Vec<(Range, TokenClass)> {
+    let mut tokens = vec![];
+    let mut tokenized = false;
+    for token in Tokenizer::new(sql) {
+        match token {
+            Ok(token) if token.kind == EOI => tokenized = true,
+            Ok(token) => tokens.push(token),
+            Err(_) => break,
+        }
+    }
+
+    let mut roles = HashMap::new();
+    for statement in tokens.split(|token| token.kind == SemiColon) {
+        if let Some(last) = statement.last() {
+            let mut statement = statement.to_vec();
+            statement.push(Token {
+                source: sql,
+                kind: EOI,
+                span: Range {
+                    start: last.span.end,
+                    end: last.span.end,
+                },
+            });
+            if let Ok((stmt, _)) = parse_sql(&statement, dialect) {
+                roles.extend(name_roles(&stmt));
+            }
+        }
+    }
+
+    let mut classes = vec![];
+    let comments = |classes: &mut Vec<_>, start: usize, end: usize| {
+        for comment in comment_spans(&sql[start..end]) {
+            classes.push((
+                Range {
+                    start: start + comment.start,
+                    end: start + comment.end,
+                },
+                TokenClass::Comment,
+            ));
+        }
+    };
+    let mut in_hint = false;
+    let mut end = 0;
+    for token in &tokens {
+        comments(&mut classes, end, token.span.start);
+        end = token.span.end;
+
+        let class = match token.kind {
+            HintPrefix => {
+                in_hint = true;
+                TokenClass::Hint
+            }
+            HintSuffix => {
+                in_hint = false;
+                TokenClass::Hint
+            }
+            _ if in_hint => TokenClass::Hint,
+            _ => match roles.get(&token.span.start) {
+                Some(class) => *class,
+                None => lexical_class(token, dialect),
+            },
+        };
+        classes.push((token.span, class));
+    }
+    if tokenized {
+        comments(&mut classes, end, sql.len());
+    }
+    classes
+}
+
+fn lexical_class(token: &Token, dialect: Dialect) -> TokenClass {
+    match token.kind {
+        Ident => TokenClass::Identifier,
+        QuotedString => match token.text().chars().next() {
+            Some(c) if dialect.is_ident_quote(c) => TokenClass::QuotedIdentifier,
+            _ => TokenClass::String,
+        },
+        PGLiteralHex => TokenClass::String,
+        LiteralInteger | LiteralFloat | MySQLLiteralHex => TokenClass::Number,
+        Placeholder | ColumnPosition => TokenClass::Placeholder,
+        AtString => TokenClass::Variable,
+        kind if kind.is_keyword() => TokenClass::Keyword,
+        _ => TokenClass::Operator,
+    }
+}
+
+/// The spans of the comments in `gap`, the whitespaces and comments between two
+/// tokens. A line comment ends before the line break, and a block comment that is
+/// not closed ends with the gap.
+pub(crate) fn comment_spans(gap: &str) -> Vec<std::ops::Range<usize>> {
+    let mut spans = vec![];
+    let mut pos = 0;
+    while let Some(start) = gap[pos..].find(['-', '/']).map(|start| pos + start) {
+        let rest = &gap[start..];
+        let end = if rest.starts_with("--") {
+            rest.find('\n').map_or(gap.len(), |end| start + end)
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            comment.find("*/").map_or(gap.len(), |end| start + end + 4)
+        } else {
+            pos = start + 1;
+            continue;
+        };
+        spans.push(start..end);
+        pos = end;
+    }
+    spans
+}
+
+/// The classes of the names in `stmt` by the start of their spans.
+fn name_roles(stmt: &Statement) -> HashMap<usize, TokenClass> {
+    let mut collector = NameRoles::default();
+    let _ = traverse_statement(&mut collector, stmt);
+
+    for qualifier in std::mem::take(&mut collector.qualifiers) {
+        let class = if collector.aliases.contains(&qualifier.name.to_lowercase()) {
+            TokenClass::Alias
+        } else {
+            TokenClass::Table
+        };
+        collector.add(qualifier, class);
+    }
+    collector.roles
+}
+
+#[derive(Default)]
+struct NameRoles<'ast> {
+    roles: HashMap<usize, TokenClass>,
+    /// The tables or aliases that qualify the columns, like `t` in `t.a`.
+    qualifiers: Vec<&'ast Identifier>,
+    /// The aliases of tables in lowercase.
+    aliases: HashSet<String>,
+}
+
+impl<'ast> NameRoles<'ast> {
+    fn add(&mut self, ident: &Identifier, class: TokenClass) {
+        if let Some(span) = ident.span {
+            self.roles.insert(span.start, class);
+        }
+    }
+
+    fn add_table(&mut self, table: &Identifier, columns: &[Identifier]) {
+        self.add(table, TokenClass::Table);
+        for column in columns {
+            self.add(column, TokenClass::Column);
+        }
+    }
+
+    fn add_table_alias(&mut self, alias: &'ast Option<TableAlias>) {
+        if let Some(alias) = alias {
+            self.aliases.insert(alias.name.name.to_lowercase());
+            self.add(&alias.name, TokenClass::Alias);
+            for column in &alias.columns {
+                self.add(column, TokenClass::Column);
+            }
+        }
+    }
+
+    fn enter_statement(&mut self, stmt: &'ast Statement) {
+        match stmt {
+            Statement::Insert(insert) => self.add_table(&insert.table, &insert.columns),
+            Statement::Replace(replace) => self.add_table(&replace.table, &replace.columns),
+            Statement::Copy(copy) => {
+                for unit in [&copy.src, &copy.dst] {
+                    if let CopyUnit::Table { table, columns, .. } = unit {
+                        self.add_table(table, columns.as_deref().unwrap_or_default());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn enter_query(&mut self, query: &'ast Query) {
+        if let Some(with) = &query.with {
+            for cte in &with.ctes {
+                self.add_table(&cte.alias.name, &cte.alias.columns);
+            }
+        }
+    }
+
+    fn enter_select_target(&mut self, target: &'ast SelectTarget) {
+        match target {
+            SelectTarget::AliasedExpr {
+                alias: Some(alias), ..
+            } => self.add(alias, TokenClass::Alias),
+            SelectTarget::AliasedExpr { .. } => {}
+            SelectTarget::QualifiedName { qualified, .. } => {
+                let idents = qualified
+                    .iter()
+                    .filter_map(|indirection| match indirection {
+                        Indirection::Identifier(ident) => Some(ident),
+                        Indirection::Star(_) => None,
+                    })
+                    .collect::<Vec<_>>();
+                match qualified.last() {
+                    Some(Indirection::Identifier(_)) => {
+                        if let [.., table, column] = idents[..] {
+                            self.qualifiers.push(table);
+                            self.add(column, TokenClass::Column);
+                        } else if let [column] = idents[..] {
+                            self.add(column, TokenClass::Column);
+                        }
+                    }
+                    _ => {
+                        if let Some(table) = idents.last() {
+                            self.qualifiers.push(table);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn enter_table_reference(&mut self, table_ref: &'ast TableReference) {
+        match table_ref {
+            TableReference::Table { table, alias, .. } => {
+                self.add(table, TokenClass::Table);
+                self.add_table_alias(alias);
+            }
+            TableReference::TableFunction { name, alias, .. } => {
+                self.add(name, TokenClass::Function);
+                self.add_table_alias(alias);
+            }
+            TableReference::Subquery { alias, .. } | TableReference::Location { alias, .. } => {
+                self.add_table_alias(alias);
+            }
+            TableReference::Join { join, .. } => {
+                if let JoinCondition::Using(columns) = &join.condition {
+                    for column in columns {
+                        self.add(column, TokenClass::Column);
+                    }
+                }
+            }
+        }
+    }
+
+    fn enter_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::ColumnRef { table, column, .. } => {
+                if let Some(table) = table {
+                    self.qualifiers.push(table);
+                }
+                if let ColumnID::Name(column) = column {
+                    self.add(column, TokenClass::Column);
+                }
+            }
+            Expr::FunctionCall { name, .. } => self.add(name, TokenClass::Function),
+            // The span of `COUNT(*)` starts with `COUNT`.
+            Expr::CountAll {
+                span: Some(span), ..
+            } => {
+                self.roles.insert(span.start, TokenClass::Function);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for NameRoles<'ast> {
+    fn enter_node(&mut self, node: AstNode<'ast>) -> VisitResult {
+        match node {
+            AstNode::Statement(stmt) => self.enter_statement(stmt),
+            AstNode::Query(query) => self.enter_query(query),
+            AstNode::SelectTarget(target) => self.enter_select_target(target),
+            AstNode::TableReference(table_ref) => self.enter_table_reference(table_ref),
+            AstNode::Expr(expr) => self.enter_expr(expr),
+            _ => {}
+        }
+        ControlFlow::Continue(Children::Visit)
+    }
+}
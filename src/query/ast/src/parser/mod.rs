@@ -12,22 +12,38 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod completion;
 mod data_mask;
 pub mod expr;
+mod highlight;
 #[allow(clippy::module_inception)]
 mod parser;
 pub mod query;
 pub mod quote;
+mod script;
 mod share;
 mod stage;
 pub mod statement;
 pub mod token;
 pub mod unescape;
+mod values;
 
+pub use completion::complete;
+pub use completion::CompletionContext;
+pub use completion::Completions;
+pub use highlight::highlight;
+pub use highlight::TokenClass;
 pub use parser::parse_comma_separated_exprs;
 pub use parser::parse_comma_separated_idents;
 pub use parser::parse_expr;
+pub use parser::parse_expr_with_options;
 pub use parser::parse_sql;
+pub use parser::parse_sql_with_diagnostic;
+pub use parser::parse_sql_with_options;
 pub use parser::parser_values_with_placeholder;
 pub use parser::tokenize_sql;
+pub use script::split_statements;
+pub use script::ScriptStatement;
 pub use token::all_reserved_keywords;
+pub use values::ValuesRow;
+pub use values::ValuesRowReader;
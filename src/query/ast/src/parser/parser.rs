@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use common_exception::ErrorCode;
+use common_exception::Range;
 use common_exception::Result;
 
 use crate::ast::Expr;
@@ -21,6 +22,9 @@ use crate::ast::Statement;
 use crate::error::display_parser_error;
 use crate::input::Dialect;
 use crate::input::Input;
+use crate::input::ParseOptions;
+use crate::input::ParseState;
+use crate::input::ParserLimits;
 use crate::parser::expr;
 use crate::parser::expr::subexpr;
 use crate::parser::expr::values_with_placeholder;
@@ -33,9 +37,42 @@ use crate::util::comma_separated_list1;
 use crate::util::ident;
 use crate::util::transform_span;
 use crate::Backtrace;
+use crate::Diagnostic;
+use crate::DiagnosticCode;
+use crate::LimitError;
 
+/// Tokenize a SQL string. The rows of `INSERT ... VALUES` and `REPLACE ... VALUES`
+/// are left untokenized, the tokens end with the `VALUES` keyword and the EOI, so
+/// that the rows can be streamed from the source by
+/// [`ValuesRowReader`](crate::parser::ValuesRowReader).
 pub fn tokenize_sql(sql: &str) -> Result<Vec<Token>> {
-    Tokenizer::new(sql).collect::<Result<Vec<_>>>()
+    let mut tokens = vec![];
+    let mut values = false;
+    let mut depth = 0usize;
+    for token in Tokenizer::new(sql) {
+        let token = token?;
+        if tokens.is_empty() {
+            values = matches!(token.kind, TokenKind::INSERT | TokenKind::REPLACE);
+        }
+        match token.kind {
+            TokenKind::LParen => depth += 1,
+            TokenKind::RParen => depth = depth.saturating_sub(1),
+            // The source of the insert is a query or a file format.
+            TokenKind::SELECT | TokenKind::WITH | TokenKind::FORMAT | TokenKind::FILE_FORMAT
+                if depth == 0 =>
+            {
+                values = false
+            }
+            TokenKind::VALUES if values && depth == 0 => {
+                tokens.push(token);
+                tokens.push(Token::new_eoi(sql));
+                break;
+            }
+            _ => {}
+        }
+        tokens.push(token);
+    }
+    Ok(tokens)
 }
 
 /// Parse a SQL string into `Statement`s.
@@ -43,18 +80,61 @@ pub fn parse_sql<'a>(
     sql_tokens: &'a [Token<'a>],
     dialect: Dialect,
 ) -> Result<(Statement, Option<String>)> {
+    parse_sql_with_options(sql_tokens, &ParseOptions {
+        dialect,
+        ..Default::default()
+    })
+}
+
+/// Parse a SQL string into `Statement`s, failing with a [`LimitError`] if the
+/// statement exceeds the limits of the options, or a
+/// [`DeniedSyntax`](crate::DeniedSyntax) if it uses syntax denied by the options.
+pub fn parse_sql_with_options<'a>(
+    sql_tokens: &'a [Token<'a>],
+    options: &ParseOptions,
+) -> Result<(Statement, Option<String>)> {
+    parse_statement(sql_tokens, options).map_err(|diag| diag.into_error_code(sql_tokens[0].source))
+}
+
+/// Tokenize and parse a SQL string into `Statement`s, failing with a structured
+/// [`Diagnostic`] instead of a rendered error.
+#[allow(clippy::result_large_err)]
+pub fn parse_sql_with_diagnostic(
+    sql: &str,
+    options: &ParseOptions,
+) -> std::result::Result<(Statement, Option<String>), Diagnostic> {
+    let tokens = tokenize_sql(sql).map_err(|err| {
+        let span = err.span().unwrap_or(Range { start: 0, end: 0 });
+        Diagnostic::error(DiagnosticCode::UnrecognizedToken, span, err.message())
+    })?;
+    parse_statement(&tokens, options)
+}
+
+#[allow(clippy::result_large_err)]
+fn parse_statement<'a>(
+    sql_tokens: &'a [Token<'a>],
+    options: &ParseOptions,
+) -> std::result::Result<(Statement, Option<String>), Diagnostic> {
+    check_statement_size(sql_tokens, &options.limits)?;
     let backtrace = Backtrace::new();
-    match statement(Input(sql_tokens, dialect, &backtrace)) {
+    let state = ParseState::new(options.clone());
+    let res = statement(Input(sql_tokens, options.dialect, &backtrace, &state));
+    if let Some(err) = state.limit_error() {
+        return Err(err.into());
+    }
+    if let Some(err) = state.denied_syntax() {
+        return Err(err.into());
+    }
+    match res {
         Ok((rest, stmts)) if rest[0].kind == TokenKind::EOI => Ok((stmts.stmt, stmts.format)),
-        Ok((rest, _)) => Err(ErrorCode::SyntaxException(
+        Ok((rest, _)) => Err(Diagnostic::error(
+            DiagnosticCode::TrailingTokens,
+            rest[0].span,
             "unable to parse rest of the sql".to_string(),
-        )
-        .set_span(transform_span(&rest[..1]))),
+        )),
         Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
             let source = sql_tokens[0].source;
-            Err(ErrorCode::SyntaxException(display_parser_error(
-                err, source,
-            )))
+            Err(Diagnostic::from_parser_error(err, source))
         }
         Err(nom::Err::Incomplete(_)) => unreachable!(),
     }
@@ -62,8 +142,30 @@ pub fn parse_sql<'a>(
 
 /// Parse udf function into Expr
 pub fn parse_expr<'a>(sql_tokens: &'a [Token<'a>], dialect: Dialect) -> Result<Expr> {
+    parse_expr_with_options(sql_tokens, &ParseOptions {
+        dialect,
+        ..Default::default()
+    })
+}
+
+/// Parse udf function into Expr, failing with a [`LimitError`] if the expression
+/// exceeds the limits of the options, or a [`DeniedSyntax`](crate::DeniedSyntax)
+/// if it uses syntax denied by the options.
+pub fn parse_expr_with_options<'a>(
+    sql_tokens: &'a [Token<'a>],
+    options: &ParseOptions,
+) -> Result<Expr> {
+    check_statement_size(sql_tokens, &options.limits)?;
     let backtrace = Backtrace::new();
-    match expr::expr(Input(sql_tokens, dialect, &backtrace)) {
+    let state = ParseState::new(options.clone());
+    let res = expr::expr(Input(sql_tokens, options.dialect, &backtrace, &state));
+    if let Some(err) = state.limit_error() {
+        return Err(err.into());
+    }
+    if let Some(err) = state.denied_syntax() {
+        return Err(err.into());
+    }
+    match res {
         Ok((rest, expr)) if rest[0].kind == TokenKind::EOI => Ok(expr),
         Ok((rest, _)) => Err(ErrorCode::SyntaxException(
             "unable to parse rest of the sql".to_string(),
@@ -79,13 +181,43 @@ pub fn parse_expr<'a>(sql_tokens: &'a [Token<'a>], dialect: Dialect) -> Result<E
     }
 }
 
+/// Check the length of the source and the number of tokens, excluding the EOI,
+/// before parsing.
+fn check_statement_size(sql_tokens: &[Token], limits: &ParserLimits) -> Result<(), LimitError> {
+    let source = sql_tokens[0].source;
+    if source.len() > limits.max_statement_bytes {
+        let token = sql_tokens
+            .iter()
+            .find(|token| token.span.end > limits.max_statement_bytes)
+            .unwrap_or(&sql_tokens[sql_tokens.len() - 1]);
+        return Err(LimitError::StatementTooLarge {
+            len: source.len(),
+            limit: limits.max_statement_bytes,
+            span: token.span,
+        });
+    }
+    let len = sql_tokens.len() - 1;
+    if len > limits.max_tokens {
+        return Err(LimitError::TooManyTokens {
+            len,
+            limit: limits.max_tokens,
+            span: sql_tokens[limits.max_tokens].span,
+        });
+    }
+    Ok(())
+}
+
 pub fn parse_comma_separated_exprs<'a>(
     sql_tokens: &'a [Token<'a>],
     dialect: Dialect,
 ) -> Result<Vec<Expr>> {
     let backtrace = Backtrace::new();
+    let state = ParseState::new(ParseOptions {
+        dialect,
+        ..Default::default()
+    });
     let mut comma_separated_exprs_parser = comma_separated_list0(subexpr(0));
-    match comma_separated_exprs_parser(Input(sql_tokens, dialect, &backtrace)) {
+    match comma_separated_exprs_parser(Input(sql_tokens, dialect, &backtrace, &state)) {
         Ok((_rest, exprs)) => Ok(exprs),
         Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
             let source = sql_tokens[0].source;
@@ -102,8 +234,12 @@ pub fn parse_comma_separated_idents<'a>(
     dialect: Dialect,
 ) -> Result<Vec<Identifier>> {
     let backtrace = Backtrace::new();
+    let state = ParseState::new(ParseOptions {
+        dialect,
+        ..Default::default()
+    });
     let mut comma_separated_idents_parser = comma_separated_list1(ident);
-    match comma_separated_idents_parser(Input(sql_tokens, dialect, &backtrace)) {
+    match comma_separated_idents_parser(Input(sql_tokens, dialect, &backtrace, &state)) {
         Ok((_rest, idents)) => Ok(idents),
         Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
             let source = sql_tokens[0].source;
@@ -120,7 +256,11 @@ pub fn parser_values_with_placeholder<'a>(
     dialect: Dialect,
 ) -> Result<Vec<Option<Expr>>> {
     let backtrace = Backtrace::new();
-    match values_with_placeholder(Input(sql_tokens, dialect, &backtrace)) {
+    let state = ParseState::new(ParseOptions {
+        dialect,
+        ..Default::default()
+    });
+    match values_with_placeholder(Input(sql_tokens, dialect, &backtrace, &state)) {
         Ok((rest, exprs)) if rest[0].kind == TokenKind::EOI => Ok(exprs),
         Ok((rest, _)) => Err(ErrorCode::SyntaxException(
             "unable to parse rest of the sql".to_string(),
@@ -22,10 +22,11 @@ use pratt::Associativity;
 use pratt::PrattParser;
 use pratt::Precedence;
 
-use super::stage::file_location;
 use super::stage::select_stage_option;
+use super::stage::unchecked_file_location;
 use crate::ast::*;
 use crate::input::Input;
+use crate::input::SyntaxFeature;
 use crate::input::WithSpan;
 use crate::parser::expr::*;
 use crate::parser::statement::hint;
@@ -41,9 +42,11 @@ pub fn query(i: Input) -> IResult<Query> {
 }
 
 pub fn set_operation(i: Input) -> IResult<SetExpr> {
-    let (rest, set_operation_elements) = rule!(#set_operation_element+)(i)?;
-    let iter = &mut set_operation_elements.into_iter();
-    run_pratt_parser(SetOperationParser, iter, rest, i)
+    nested(i, |i| {
+        let (rest, set_operation_elements) = rule!(#set_operation_element+)(i)?;
+        let iter = &mut set_operation_elements.into_iter();
+        run_pratt_parser(SetOperationParser, iter, rest, i)
+    })
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -170,17 +173,19 @@ pub fn set_operation_element(i: Input) -> IResult<WithSpan<SetOperationElement>>
     );
 
     let (rest, (span, elem)) = consumed(rule! {
-        #group
-        | #with
-        | #set_operator
-        | #select_stmt
-        | #values
-        | #order_by
-        | #limit
-        | #offset
-        | #ignore_result
+        #pratt_operand(group)
+        | #pratt_operand(with)
+        | #pratt_operator(set_operator)
+        | #pratt_operand(select_stmt)
+        | #pratt_operand(values)
+        | #pratt_operator(order_by)
+        | #pratt_operator(limit)
+        | #pratt_operator(offset)
+        | #pratt_operator(ignore_result)
     })(i)?;
-    Ok((rest, WithSpan { span, elem }))
+    let elem = WithSpan { span, elem };
+    expect_operand_after(&mut SetOperationParser, &elem, rest);
+    Ok((rest, elem))
 }
 
 struct SetOperationParser;
@@ -489,9 +494,11 @@ pub fn order_by_expr(i: Input) -> IResult<OrderByExpr> {
 }
 
 pub fn table_reference(i: Input) -> IResult<TableReference> {
-    let (rest, table_reference_elements) = rule!(#table_reference_element+)(i)?;
-    let iter = &mut table_reference_elements.into_iter();
-    run_pratt_parser(TableReferenceParser, iter, rest, i)
+    nested(i, |i| {
+        let (rest, table_reference_elements) = rule!(#table_reference_element+)(i)?;
+        let iter = &mut table_reference_elements.into_iter();
+        run_pratt_parser(TableReferenceParser, iter, rest, i)
+    })
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -578,7 +585,7 @@ pub fn table_reference_element(i: Input) -> IResult<WithSpan<TableReferenceEleme
     );
     let aliased_table = map(
         rule! {
-            #dot_separated_idents_1_to_3 ~ (AT ~ #travel_point)? ~ #table_alias? ~ #pivot? ~ #unpivot?
+            #table_name ~ (AT ~ #travel_point)? ~ #table_alias? ~ #pivot? ~ #unpivot?
         },
         |((catalog, database, table), travel_point_opt, alias, pivot, unpivot)| {
             TableReferenceElement::Table {
@@ -639,33 +646,44 @@ pub fn table_reference_element(i: Input) -> IResult<WithSpan<TableReferenceEleme
         },
         |(_, table_ref, _)| TableReferenceElement::Group(table_ref),
     );
-    let aliased_stage = map(
-        rule! {
-            (#file_location) ~  ( "(" ~ (#select_stage_option ~ ","?)* ~ ")" )? ~ #table_alias?
-        },
-        |(location, options, alias)| {
-            let options = options
-                .map(|(_, options, _)| options.into_iter().map(|(option, _)| option).collect())
-                .unwrap_or_default();
-            TableReferenceElement::Stage {
-                location,
-                alias,
-                options,
-            }
-        },
-    );
+    let stage_location = |i| syntax_feature(SyntaxFeature::StageTable, unchecked_file_location)(i);
+    // The connection of the location is checked along with the connection
+    // options of the stage table, e.g. `ENDPOINT_URL => '...'`.
+    let aliased_stage = |i| {
+        let (rest, (location, options, alias)) = rule! {
+            (#stage_location) ~  ( "(" ~ (#consumed(select_stage_option) ~ ","?)* ~ ")" )? ~ #table_alias?
+        }(i)?;
+        let options: Vec<_> = options
+            .map(|(_, options, _)| options.into_iter().map(|(option, _)| option).collect())
+            .unwrap_or_default();
+        let connection: Vec<_> = options
+            .iter()
+            .filter_map(|(input, option)| match option {
+                SelectStageOption::Connection(opt) => Some((*input, opt.clone())),
+                _ => None,
+            })
+            .collect();
+        let location = location.check(&connection)?;
+        Ok((rest, TableReferenceElement::Stage {
+            location,
+            alias,
+            options: options.into_iter().map(|(_, option)| option).collect(),
+        }))
+    };
 
     let (rest, (span, elem)) = consumed(rule! {
-        #aliased_stage
-        | #table_function
-        | #aliased_table
-        | #subquery
-        | #group
-        | #join
-        | #join_condition_on
-        | #join_condition_using
+        #pratt_operand(aliased_stage)
+        | #pratt_operand(table_function)
+        | #pratt_operand(aliased_table)
+        | #pratt_operand(subquery)
+        | #pratt_operand(group)
+        | #pratt_operator(join)
+        | #pratt_operator(join_condition_on)
+        | #pratt_operator(join_condition_using)
     })(i)?;
-    Ok((rest, WithSpan { span, elem }))
+    let elem = WithSpan { span, elem };
+    expect_operand_after(&mut TableReferenceParser, &elem, rest);
+    Ok((rest, elem))
 }
 
 struct TableReferenceParser;
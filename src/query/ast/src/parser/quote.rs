@@ -60,7 +60,7 @@ pub fn unquote_ident(s: &str, quote: char) -> String {
 // There're also two known special cases in Databend which do not requires quoting:
 // - "~" is a valid stage name
 // - '$' is a valid character in some system functions
-fn need_quote_ident(ident: &str) -> bool {
+pub(crate) fn need_quote_ident(ident: &str) -> bool {
     if ident.is_empty() {
         return true;
     }
@@ -0,0 +1,86 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Range;
+
+use crate::ast::Statement;
+use crate::input::ParseOptions;
+use crate::parser::parse_sql_with_diagnostic;
+use crate::parser::token::TokenKind;
+use crate::parser::token::Tokenizer;
+use crate::Diagnostic;
+
+/// A statement of a script, separated from the others by `;`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptStatement {
+    /// The span of the statement from its first token to its last token, or to
+    /// the end of the script if the statement can't be tokenized.
+    pub span: Range<usize>,
+    /// Whether there are comments between the tokens of the statement.
+    pub has_comments: bool,
+}
+
+impl ScriptStatement {
+    /// Parse the statement. The spans in the AST or the diagnostic are relative to
+    /// the start of the statement.
+    #[allow(clippy::result_large_err)]
+    pub fn parse(
+        &self,
+        script: &str,
+        options: &ParseOptions,
+    ) -> Result<(Statement, Option<String>), Diagnostic> {
+        parse_sql_with_diagnostic(&script[self.span.clone()], options)
+    }
+}
+
+/// Split a script into statements, skipping the empty ones.
+pub fn split_statements(script: &str) -> Vec<ScriptStatement> {
+    let mut statements = vec![];
+    let mut current: Option<ScriptStatement> = None;
+    for token in Tokenizer::new(script) {
+        match token {
+            Ok(token) if token.kind == TokenKind::EOI => break,
+            Ok(token) if token.kind == TokenKind::SemiColon => {
+                statements.extend(current.take());
+            }
+            Ok(token) => match &mut current {
+                Some(statement) => {
+                    let gap = &script[statement.span.end..token.span.start];
+                    statement.has_comments |= !gap.trim().is_empty();
+                    statement.span.end = token.span.end;
+                }
+                None => {
+                    current = Some(ScriptStatement {
+                        span: token.span.start..token.span.end,
+                        has_comments: false,
+                    })
+                }
+            },
+            Err(err) => {
+                let start = match (&current, err.span()) {
+                    (Some(statement), _) => statement.span.start,
+                    (None, Some(span)) => span.start,
+                    (None, None) => 0,
+                };
+                current = Some(ScriptStatement {
+                    span: start..script.trim_end().len(),
+                    has_comments: false,
+                });
+                break;
+            }
+        }
+    }
+    statements.extend(current);
+    statements
+}
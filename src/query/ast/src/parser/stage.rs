@@ -13,18 +13,30 @@
 // limitations under the License.
 
 use std::collections::BTreeMap;
+use std::str::FromStr;
 
 use nom::branch::alt;
+use nom::combinator::consumed;
 use nom::combinator::map;
 
+use crate::ast::FileFormatOptionError;
+use crate::ast::FileFormatOptions;
+use crate::ast::FileFormatParams;
 use crate::ast::FileLocation;
+use crate::ast::Identifier;
+use crate::ast::OnErrorMode;
 use crate::ast::SelectStageOption;
+use crate::ast::StorageParams;
 use crate::ast::UriLocation;
+use crate::ast::ValidationMode;
+use crate::ast::FILE_FORMAT_OPTIONS;
 use crate::input::Input;
 use crate::parser::expr::*;
 use crate::parser::token::*;
+use crate::parser::CompletionContext;
 use crate::rule;
 use crate::util::*;
+use crate::Error;
 use crate::ErrorKind;
 
 pub fn parameter_to_string(i: Input) -> IResult<String> {
@@ -41,7 +53,7 @@ fn connection_opt(sep: &'static str) -> impl FnMut(Input) -> IResult<(String, St
     move |i| {
         let string_options = map(
             rule! {
-                ( #ident ) ~ #match_text(sep) ~ #literal_string
+                ( #option_key ) ~ #match_text(sep) ~ #literal_string
             },
             |(k, _, v)| (k.to_string().to_lowercase(), v),
         );
@@ -57,76 +69,131 @@ fn connection_opt(sep: &'static str) -> impl FnMut(Input) -> IResult<(String, St
     }
 }
 
-pub fn connection_options(i: Input) -> IResult<BTreeMap<String, String>> {
-    let connection_opt = connection_opt("=");
-    map(rule! { "(" ~ (#connection_opt)* ~ ")"}, |(_, opts, _)| {
-        BTreeMap::from_iter(opts.iter().map(|(k, v)| (k.to_lowercase(), v.clone())))
-    })(i)
+// Connection options along with their positions, used to report invalid options.
+pub fn connection_options(i: Input) -> IResult<Vec<(Input, (String, String))>> {
+    let connection_opt = consumed(connection_opt("="));
+    map(rule! { "(" ~ (#connection_opt)* ~ ")"}, |(_, opts, _)| opts)(i)
 }
 
-pub fn format_options(i: Input) -> IResult<BTreeMap<String, String>> {
-    let option_type = map(
-        rule! {
-            TYPE ~ "=" ~ (TSV | CSV | NDJSON | PARQUET | JSON | XML)
-        },
-        |(_, _, v)| ("type".to_string(), v.text().to_string()),
-    );
+// Any identifier is accepted as an option key, the keys are checked after all
+// the options are parsed. Otherwise the valid keys are listed in the error.
+fn format_option_key(i: Input) -> IResult<Identifier> {
+    match option_key(i) {
+        Err(nom::Err::Error(_)) => Err(nom::Err::Error(Error::from_error_kind(
+            i,
+            ErrorKind::ExpectOption {
+                options: FILE_FORMAT_OPTIONS,
+            },
+        ))),
+        res => res,
+    }
+}
 
-    let option_compression = map(
-        rule! {
-            COMPRESSION ~ "=" ~ (AUTO | NONE | GZIP | BZ2 | BROTLI | ZSTD | DEFLATE | RAWDEFLATE | XZ)
-        },
-        |(_, _, v)| ("COMPRESSION".to_string(), v.text().to_string()),
-    );
+fn format_option(i: Input) -> IResult<(Input, String, String)> {
+    let ident_to_string = |i| map(ident, |ident| ident.name)(i);
+    let integer_to_string = |i| map(rule! { LiteralInteger }, |token| token.text().to_string())(i);
 
-    let string_options = map(
+    map(
         rule! {
-            (TYPE
-                | FORMAT_NAME
-                | COMPRESSION
-                | RECORD_DELIMITER
-                | FIELD_DELIMITER
-                | QUOTE
-                | NAN_DISPLAY
-                | NULL_DISPLAY
-                | ESCAPE
-                | ROW_TAG) ~ "=" ~ #literal_string
+            #format_option_key ~ "=" ~ ( #literal_string | #integer_to_string | #ident_to_string )
         },
-        |(k, _, v)| (k.text().to_string(), v),
-    );
+        move |(k, _, v)| (i, k.name.to_lowercase(), v),
+    )(i)
+}
 
-    let int_options = map(
-        rule! {
-            SKIP_HEADER ~ "=" ~ #literal_u64
-        },
-        |(k, _, v)| (k.text().to_string(), v.to_string()),
-    );
+fn format_option_list(i: Input) -> IResult<Vec<(Input, String, String)>> {
+    map(rule! { (#format_option ~ ","?)* }, |opts| {
+        opts.into_iter().map(|(opt, _)| opt).collect()
+    })(i)
+}
 
-    let none_options = map(
-        rule! {
-            (RECORD_DELIMITER | FIELD_DELIMITER | QUOTE | SKIP_HEADER | NON_DISPLAY | ESCAPE ) ~ "=" ~ NONE
-        },
-        |(k, _, v)| (k.text().to_string(), v.text().to_string()),
-    );
+/// Check the options after they are all parsed, so that an incomplete option
+/// is still reported as a syntax error.
+fn check_format_options<'a>(
+    rest: Input<'a>,
+    opts: &[(Input<'a>, String, String)],
+) -> IResult<'a, FileFormatOptions> {
+    let mut options = BTreeMap::new();
+    for (input, k, v) in opts {
+        if options.insert(k.clone(), v.clone()).is_some() {
+            return failure_at(*input, "duplicate file format option");
+        }
+    }
+    match FileFormatOptions::try_from_options(&options) {
+        Ok(options) => Ok((rest, options)),
+        Err(err) => {
+            let input = opts
+                .iter()
+                .find(|(_, k, _)| k == err.key())
+                .map(|(input, _, _)| *input)
+                .unwrap_or(rest);
+            match err {
+                FileFormatOptionError::UnknownKey(_) => {
+                    failure_with(input, ErrorKind::UnknownOption {
+                        kind: "file format",
+                        options: FILE_FORMAT_OPTIONS,
+                    })
+                }
+                FileFormatOptionError::InvalidValue { reason, .. } => failure_at(input, reason),
+            }
+        }
+    }
+}
 
-    map(
-        rule! { ((#option_type | #option_compression | #string_options | #int_options | #none_options) ~ ","?)* },
-        |opts| BTreeMap::from_iter(opts.iter().map(|((k, v), _)| (k.to_lowercase(), v.clone()))),
-    )(i)
+/// Options of `CREATE FILE FORMAT`, which end with the statement and require `TYPE`.
+pub fn format_options(i: Input) -> IResult<FileFormatParams> {
+    let (rest, (opts, _)) = rule! { #format_option_list ~ &(";" | EOI) }(i)?;
+    match check_format_options(rest, &opts)? {
+        (rest, FileFormatOptions::Params(params)) => Ok((rest, params)),
+        (_, FileFormatOptions::Named(_)) => failure_at(
+            opts[0].0,
+            "`FORMAT_NAME` can not be used in `CREATE FILE FORMAT`",
+        ),
+        (_, FileFormatOptions::Untyped(_)) => failure_at(rest, "missing file format option `TYPE`"),
+    }
 }
 
-pub fn file_format_clause(i: Input) -> IResult<BTreeMap<String, String>> {
-    map(
-        rule! { FILE_FORMAT ~ "=" ~ "(" ~ #format_options ~ ")" },
-        |(_, _, _, opts, _)| opts,
+pub fn file_format_clause(i: Input) -> IResult<FileFormatOptions> {
+    let (rest, (_, _, _, opts, _)) =
+        rule! { FILE_FORMAT ~ "=" ~ "(" ~ #format_option_list ~ &")" }(i)?;
+    let (rest, options) = check_format_options(rest, &opts)?;
+    let (rest, _) = match_text(")")(rest)?;
+    Ok((rest, options))
+}
+
+// The value of `ON_ERROR` and `VALIDATION_MODE`, e.g. `'SKIP_FILE_10%'` or `SKIP_FILE_10%`.
+fn copy_mode_value(i: Input) -> IResult<String> {
+    let ident_to_string = map(rule! { #ident ~ "%"? }, |(ident, percent)| {
+        format!("{}{}", ident.name, if percent.is_some() { "%" } else { "" })
+    });
+
+    rule!(
+        #literal_string
+        | #ident_to_string
     )(i)
 }
 
+fn copy_mode<T: FromStr<Err = &'static str>>(i: Input) -> IResult<T> {
+    let (rest, value) = copy_mode_value(i)?;
+    match value.parse() {
+        Ok(mode) => Ok((rest, mode)),
+        Err(reason) => failure_at(i, reason),
+    }
+}
+
+pub fn on_error_mode(i: Input) -> IResult<OnErrorMode> {
+    copy_mode(i)
+}
+
+pub fn validation_mode(i: Input) -> IResult<ValidationMode> {
+    copy_mode(i)
+}
+
 // parse: (k = v ...)* into a map
 pub fn options(i: Input) -> IResult<BTreeMap<String, String>> {
     map(
         rule! {
-        "(" ~ ( #ident ~ "=" ~ #parameter_to_string )* ~ ")"
+        "(" ~ ( #option_key ~ "=" ~ #parameter_to_string )* ~ ")"
         },
         |(_, opts, _)| {
             BTreeMap::from_iter(
@@ -138,10 +205,24 @@ pub fn options(i: Input) -> IResult<BTreeMap<String, String>> {
 }
 
 pub fn file_location(i: Input) -> IResult<FileLocation> {
-    alt((
-        string_location,
-        map_res(at_string, |location| Ok(FileLocation::Stage(location))),
-    ))(i)
+    let (rest, location) = unchecked_file_location(i)?;
+    Ok((rest, location.check(&[])?))
+}
+
+/// Same as `file_location`, but the connection of the location is validated
+/// later by [`UncheckedLocation::check`], e.g. along with the options of a stage table.
+pub fn unchecked_file_location(i: Input) -> IResult<UncheckedLocation> {
+    let file_location = alt((
+        unchecked_string_location,
+        map_res(at_string, |location| {
+            Ok(UncheckedLocation {
+                location: FileLocation::Stage(location),
+                input: i,
+                opts: vec![],
+            })
+        }),
+    ));
+    completion_context(CompletionContext::Stage, file_location)(i)
 }
 
 pub fn stage_location(i: Input) -> IResult<String> {
@@ -159,41 +240,98 @@ pub fn uri_location(i: Input) -> IResult<UriLocation> {
 }
 
 pub fn string_location(i: Input) -> IResult<FileLocation> {
-    map_res(
-        rule! {
-            #literal_string
-            ~ (CONNECTION ~ "=" ~ #connection_options ~ ","?)?
-            ~ (CREDENTIALS ~ "=" ~ #connection_options ~ ","?)?
-            ~ (LOCATION_PREFIX ~ "=" ~ #literal_string ~ ","?)?
-        },
-        |(location, connection_opts, credentials_opts, location_prefix)| {
-            if let Some(stripped) = location.strip_prefix('@') {
-                if location_prefix.is_none()
-                    && connection_opts.is_none()
-                    && credentials_opts.is_none()
-                {
-                    Ok(FileLocation::Stage(stripped.to_string()))
-                } else {
-                    Err(ErrorKind::Other("uri location should not start with '@'"))
-                }
-            } else {
-                let part_prefix = if let Some((_, _, p, _)) = location_prefix {
-                    p
-                } else {
-                    "".to_string()
-                };
-                // fs location is not a valid url, let's check it in advance.
-
-                // TODO: We will use `CONNECTION` to replace `CREDENTIALS`.
-                let mut conns = connection_opts.map(|v| v.2).unwrap_or_default();
-                conns.extend(credentials_opts.map(|v| v.2).unwrap_or_default());
-
-                let uri = UriLocation::from_uri(location, part_prefix, conns)
-                    .map_err(|_| ErrorKind::Other("invalid uri"))?;
-                Ok(FileLocation::Uri(uri))
-            }
-        },
-    )(i)
+    let (rest, location) = unchecked_string_location(i)?;
+    Ok((rest, location.check(&[])?))
+}
+
+/// A file location whose connection is not validated yet.
+pub struct UncheckedLocation<'a> {
+    location: FileLocation,
+    /// The quoted location, where the errors of the location itself are reported.
+    input: Input<'a>,
+    /// The connection options along with their positions.
+    opts: Vec<(Input<'a>, (String, String))>,
+}
+
+impl<'a> UncheckedLocation<'a> {
+    /// Validate the connection of the location against its protocol, with the
+    /// connection options in `extra` given after the location.
+    pub fn check(
+        self,
+        extra: &[(Input<'a>, (String, String))],
+    ) -> Result<FileLocation, nom::Err<Error<'a>>> {
+        let FileLocation::Uri(uri) = &self.location else {
+            return Ok(self.location);
+        };
+        let mut conns = uri.connection.options().clone();
+        conns.extend(extra.iter().map(|(_, opt)| opt.clone()));
+        if let Err(err) = StorageParams::try_from_connection(&uri.protocol, &conns) {
+            let input = err
+                .key
+                .as_ref()
+                .and_then(|key| {
+                    self.opts
+                        .iter()
+                        .chain(extra)
+                        .rev()
+                        .find(|(_, (k, _))| k == key)
+                })
+                .map(|(input, _)| *input)
+                .unwrap_or(self.input);
+            return failure_at(input, err.reason).map(|(_, location)| location);
+        }
+        Ok(self.location)
+    }
+}
+
+fn unchecked_string_location(i: Input) -> IResult<UncheckedLocation> {
+    let (rest, (location, connection_opts, credentials_opts, location_prefix)) = rule!(
+        #consumed(literal_string)
+        ~ (CONNECTION ~ "=" ~ #connection_options ~ ","?)?
+        ~ (CREDENTIALS ~ "=" ~ #connection_options ~ ","?)?
+        ~ (LOCATION_PREFIX ~ "=" ~ #literal_string ~ ","?)?
+    )(i)?;
+    let (location_input, location) = location;
+    let error = |reason| {
+        Err(nom::Err::Error(crate::Error::from_error_kind(
+            i,
+            ErrorKind::Other(reason),
+        )))
+    };
+
+    if let Some(stripped) = location.strip_prefix('@') {
+        return if location_prefix.is_none()
+            && connection_opts.is_none()
+            && credentials_opts.is_none()
+        {
+            Ok((rest, UncheckedLocation {
+                location: FileLocation::Stage(stripped.to_string()),
+                input: location_input,
+                opts: vec![],
+            }))
+        } else {
+            error("uri location should not start with '@'")
+        };
+    }
+
+    let part_prefix = location_prefix.map(|(_, _, p, _)| p).unwrap_or_default();
+
+    // TODO: We will use `CONNECTION` to replace `CREDENTIALS`.
+    let opts: Vec<_> = connection_opts
+        .into_iter()
+        .chain(credentials_opts)
+        .flat_map(|(_, _, opts, _)| opts)
+        .collect();
+    let conns = opts.iter().map(|(_, opt)| opt.clone()).collect();
+
+    match UriLocation::from_uri(location, part_prefix, conns) {
+        Ok(uri) => Ok((rest, UncheckedLocation {
+            location: FileLocation::Uri(uri),
+            input: location_input,
+            opts,
+        })),
+        Err(_) => error("invalid uri"),
+    }
 }
 
 pub fn select_stage_option(i: Input) -> IResult<SelectStageOption> {
@@ -17,7 +17,6 @@ use std::collections::BTreeMap;
 use std::time::Duration;
 
 use crate::meta::AuthType;
-use crate::meta::FileFormatOptionsAst;
 use crate::meta::principal_identity::PrincipalIdentity;
 use crate::meta::user_identity::UserIdentity;
 use crate::meta::UserPrivilegeType;
@@ -33,6 +32,7 @@ use nom::Slice;
 
 use crate::ast::*;
 use crate::input::Input;
+use crate::input::SyntaxFeature;
 use crate::parser::data_mask::data_mask_policy;
 use crate::parser::expr::subexpr;
 use crate::parser::expr::*;
@@ -60,49 +60,55 @@ pub enum CreateDatabaseOption {
 }
 
 pub fn statement(i: Input) -> IResult<StatementMsg> {
-    let explain = map_res(
-        rule! {
-            EXPLAIN ~ ( AST | SYNTAX | PIPELINE | JOIN | GRAPH | FRAGMENTS | RAW | MEMO )? ~ #statement
-        },
-        |(_, opt_kind, statement)| {
-            Ok(Statement::Explain {
-                kind: match opt_kind.map(|token| token.kind) {
-                    Some(TokenKind::AST) => {
-                        let formatted_stmt = format_statement(statement.stmt.clone())
-                            .map_err(|_| ErrorKind::Other("invalid statement"))?;
-                        ExplainKind::Ast(formatted_stmt)
-                    }
-                    Some(TokenKind::SYNTAX) => {
-                        let pretty_stmt = pretty_statement(statement.stmt.clone(), 10)
-                            .map_err(|_| ErrorKind::Other("invalid statement"))?;
-                        ExplainKind::Syntax(pretty_stmt)
-                    }
-                    Some(TokenKind::PIPELINE) => ExplainKind::Pipeline,
-                    Some(TokenKind::JOIN) => ExplainKind::JOIN,
-                    Some(TokenKind::GRAPH) => ExplainKind::Graph,
-                    Some(TokenKind::FRAGMENTS) => ExplainKind::Fragments,
-                    Some(TokenKind::RAW) => ExplainKind::Raw,
-                    Some(TokenKind::MEMO) => ExplainKind::Memo("".to_string()),
-                    None => ExplainKind::Plan,
-                    _ => unreachable!(),
-                },
-                query: Box::new(statement.stmt),
-            })
-        },
+    let explain = syntax_feature(
+        SyntaxFeature::Explain,
+        map_res(
+            rule! {
+                EXPLAIN ~ ( AST | SYNTAX | PIPELINE | JOIN | GRAPH | FRAGMENTS | RAW | MEMO )? ~ #statement
+            },
+            |(_, opt_kind, statement)| {
+                Ok(Statement::Explain {
+                    kind: match opt_kind.map(|token| token.kind) {
+                        Some(TokenKind::AST) => {
+                            let formatted_stmt = format_statement(statement.stmt.clone())
+                                .map_err(|_| ErrorKind::Other("invalid statement"))?;
+                            ExplainKind::Ast(formatted_stmt)
+                        }
+                        Some(TokenKind::SYNTAX) => {
+                            let pretty_stmt = pretty_statement(statement.stmt.clone(), 10)
+                                .map_err(|_| ErrorKind::Other("invalid statement"))?;
+                            ExplainKind::Syntax(pretty_stmt)
+                        }
+                        Some(TokenKind::PIPELINE) => ExplainKind::Pipeline,
+                        Some(TokenKind::JOIN) => ExplainKind::JOIN,
+                        Some(TokenKind::GRAPH) => ExplainKind::Graph,
+                        Some(TokenKind::FRAGMENTS) => ExplainKind::Fragments,
+                        Some(TokenKind::RAW) => ExplainKind::Raw,
+                        Some(TokenKind::MEMO) => ExplainKind::Memo("".to_string()),
+                        None => ExplainKind::Plan,
+                        _ => unreachable!(),
+                    },
+                    query: Box::new(statement.stmt),
+                })
+            },
+        ),
     );
-    let explain_analyze = map(
-        rule! {
-            EXPLAIN ~ ANALYZE ~ #statement
-        },
-        |(_, _, statement)| Statement::ExplainAnalyze {
-            query: Box::new(statement.stmt),
-        },
+    let explain_analyze = syntax_feature(
+        SyntaxFeature::Explain,
+        map(
+            rule! {
+                EXPLAIN ~ ANALYZE ~ #statement
+            },
+            |(_, _, statement)| Statement::ExplainAnalyze {
+                query: Box::new(statement.stmt),
+            },
+        ),
     );
 
     let insert = map(
         rule! {
             INSERT ~ #hint? ~ ( INTO | OVERWRITE ) ~ TABLE?
-            ~ #dot_separated_idents_1_to_3
+            ~ #table_name
             ~ ( "(" ~ #comma_separated_list1(ident) ~ ")" )?
             ~ #insert_source
         },
@@ -124,7 +130,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     let replace = map(
         rule! {
             REPLACE ~ #hint? ~ INTO?
-            ~ #dot_separated_idents_1_to_3
+            ~ #table_name
             ~ ( "(" ~ #comma_separated_list1(ident) ~ ")" )?
             ~ (ON ~ CONFLICT? ~ "(" ~ #comma_separated_list1(ident) ~ ")")
             ~ #insert_source
@@ -154,7 +160,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
 
     let merge = map(
         rule! {
-            MERGE ~ #hint? ~ INTO ~ #dot_separated_idents_1_to_3  ~ #table_alias? ~ USING
+            MERGE ~ #hint? ~ INTO ~ #table_name  ~ #table_alias? ~ USING
             ~ #merge_source  ~ ON ~ #expr ~ (#match_clause | #unmatch_clause)*
         },
         |(
@@ -247,9 +253,10 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         },
     );
 
+    let global = syntax_feature(SyntaxFeature::SetGlobal, match_token(GLOBAL));
     let set_variable = map(
         rule! {
-            SET ~ (GLOBAL)? ~ #ident ~ "=" ~ #subexpr(0)
+            SET ~ #global? ~ #ident ~ "=" ~ #subexpr(0)
         },
         |(_, opt_is_global, variable, _, value)| Statement::SetVariable {
             is_global: opt_is_global.is_some(),
@@ -460,7 +467,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     );
     let show_create_table = map(
         rule! {
-            SHOW ~ CREATE ~ TABLE ~ #dot_separated_idents_1_to_3
+            SHOW ~ CREATE ~ TABLE ~ #table_name
         },
         |(_, _, _, (catalog, database, table))| {
             Statement::ShowCreateTable(ShowCreateTableStmt {
@@ -472,7 +479,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     );
     let describe_table = map(
         rule! {
-            ( DESC | DESCRIBE ) ~ #dot_separated_idents_1_to_3
+            ( DESC | DESCRIBE ) ~ #table_name
         },
         |(_, (catalog, database, table))| {
             Statement::DescribeTable(DescribeTableStmt {
@@ -486,7 +493,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     // parse `show fields from` statement
     let show_fields = map(
         rule! {
-            SHOW ~ FIELDS ~ FROM ~ #dot_separated_idents_1_to_3
+            SHOW ~ FIELDS ~ FROM ~ #table_name
         },
         |(_, _, _, (catalog, database, table))| {
             Statement::DescribeTable(DescribeTableStmt {
@@ -521,7 +528,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
 
     let attach_table = map(
         rule! {
-            ATTACH ~ TABLE ~ #dot_separated_idents_1_to_3 ~ #uri_location
+            ATTACH ~ TABLE ~ #table_name ~ #uri_location
         },
         |(_, _, (catalog, database, table), uri_location)| {
             Statement::AttachTable(AttachTableStmt {
@@ -575,7 +582,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     );
     let drop_table = map(
         rule! {
-            DROP ~ TABLE ~ ( IF ~ EXISTS )? ~ #dot_separated_idents_1_to_3 ~ ( ALL )?
+            DROP ~ TABLE ~ ( IF ~ EXISTS )? ~ #table_name ~ ( ALL )?
         },
         |(_, _, opt_if_exists, (catalog, database, table), opt_all)| {
             Statement::DropTable(DropTableStmt {
@@ -589,7 +596,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     );
     let undrop_table = map(
         rule! {
-            UNDROP ~ TABLE ~ #dot_separated_idents_1_to_3
+            UNDROP ~ TABLE ~ #table_name
         },
         |(_, _, (catalog, database, table))| {
             Statement::UndropTable(UndropTableStmt {
@@ -613,7 +620,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     );
     let rename_table = map(
         rule! {
-            RENAME ~ TABLE ~ ( IF ~ EXISTS )? ~ #dot_separated_idents_1_to_3 ~ TO ~ #dot_separated_idents_1_to_3
+            RENAME ~ TABLE ~ ( IF ~ EXISTS )? ~ #table_name ~ TO ~ #dot_separated_idents_1_to_3
         },
         |(
             _,
@@ -636,7 +643,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     );
     let truncate_table = map(
         rule! {
-            TRUNCATE ~ TABLE ~ #dot_separated_idents_1_to_3 ~ PURGE?
+            TRUNCATE ~ TABLE ~ #table_name ~ PURGE?
         },
         |(_, _, (catalog, database, table), opt_purge)| {
             Statement::TruncateTable(TruncateTableStmt {
@@ -649,7 +656,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     );
     let optimize_table = map(
         rule! {
-            OPTIMIZE ~ TABLE ~ #dot_separated_idents_1_to_3 ~ #optimize_table_action ~ ( LIMIT ~ #literal_u64 )?
+            OPTIMIZE ~ TABLE ~ #table_name ~ #optimize_table_action ~ ( LIMIT ~ #literal_u64 )?
         },
         |(_, _, (catalog, database, table), action, opt_limit)| {
             Statement::OptimizeTable(OptimizeTableStmt {
@@ -663,7 +670,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     );
     let vacuum_table = map(
         rule! {
-            VACUUM ~ TABLE ~ #dot_separated_idents_1_to_3 ~ #vacuum_table_option
+            VACUUM ~ TABLE ~ #table_name ~ #vacuum_table_option
         },
         |(_, _, (catalog, database, table), option)| {
             Statement::VacuumTable(VacuumTableStmt {
@@ -692,7 +699,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     );
     let analyze_table = map(
         rule! {
-            ANALYZE ~ TABLE ~ #dot_separated_idents_1_to_3
+            ANALYZE ~ TABLE ~ #table_name
         },
         |(_, _, (catalog, database, table))| {
             Statement::AnalyzeTable(AnalyzeTableStmt {
@@ -704,7 +711,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     );
     let exists_table = map(
         rule! {
-            EXISTS ~ TABLE ~ #dot_separated_idents_1_to_3
+            EXISTS ~ TABLE ~ #table_name
         },
         |(_, _, (catalog, database, table))| {
             Statement::ExistsTable(ExistsTableStmt {
@@ -810,7 +817,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
 
     let create_virtual_column = map(
         rule! {
-            CREATE ~ VIRTUAL ~ COLUMN ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")" ~ FOR ~ #dot_separated_idents_1_to_3
+            CREATE ~ VIRTUAL ~ COLUMN ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")" ~ FOR ~ #table_name
         },
         |(_, _, _, _, virtual_columns, _, _, (catalog, database, table))| {
             Statement::CreateVirtualColumn(CreateVirtualColumnStmt {
@@ -824,7 +831,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
 
     let alter_virtual_column = map(
         rule! {
-            ALTER ~ VIRTUAL ~ COLUMN ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")" ~ FOR ~ #dot_separated_idents_1_to_3
+            ALTER ~ VIRTUAL ~ COLUMN ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")" ~ FOR ~ #table_name
         },
         |(_, _, _, _, virtual_columns, _, _, (catalog, database, table))| {
             Statement::AlterVirtualColumn(AlterVirtualColumnStmt {
@@ -838,7 +845,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
 
     let drop_virtual_column = map(
         rule! {
-            DROP ~ VIRTUAL ~ COLUMN ~ FOR ~ #dot_separated_idents_1_to_3
+            DROP ~ VIRTUAL ~ COLUMN ~ FOR ~ #table_name
         },
         |(_, _, _, _, (catalog, database, table))| {
             Statement::DropVirtualColumn(DropVirtualColumnStmt {
@@ -851,7 +858,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
 
     let refresh_virtual_column = map(
         rule! {
-            REFRESH ~ VIRTUAL ~ COLUMN ~ FOR ~ #dot_separated_idents_1_to_3
+            REFRESH ~ VIRTUAL ~ COLUMN ~ FOR ~ #table_name
         },
         |(_, _, _, _, (catalog, database, table))| {
             Statement::RefreshVirtualColumn(RefreshVirtualColumnStmt {
@@ -1016,9 +1023,9 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             ~ ( #stage_name )
             ~ ( URL ~ "=" ~ #uri_location)?
             ~ ( #file_format_clause )?
-            ~ ( ON_ERROR ~ "=" ~ #ident)?
+            ~ ( ON_ERROR ~ "=" ~ #on_error_mode)?
             ~ ( SIZE_LIMIT ~ "=" ~ #literal_u64)?
-            ~ ( VALIDATION_MODE ~ "=" ~ #ident)?
+            ~ ( VALIDATION_MODE ~ "=" ~ #validation_mode)?
             ~ ( (COMMENT | COMMENTS) ~ "=" ~ #literal_string)?
         },
         |(
@@ -1037,12 +1044,10 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
                 if_not_exists: opt_if_not_exists.is_some(),
                 stage_name: stage.to_string(),
                 location: url_opt.map(|v| v.2),
-                file_format_options: file_format_opt.unwrap_or_default(),
-                on_error: on_error_opt.map(|v| v.2.to_string()).unwrap_or_default(),
+                file_format_options: file_format_opt,
+                on_error: on_error_opt.map(|v| v.2),
                 size_limit: size_limit_opt.map(|v| v.2 as usize).unwrap_or_default(),
-                validation_mode: validation_mode_opt
-                    .map(|v| v.2.to_string())
-                    .unwrap_or_default(),
+                validation_mode: validation_mode_opt.map(|v| v.2),
                 comments: comment_opt.map(|v| v.2).unwrap_or_default(),
             }))
         },
@@ -1087,39 +1092,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         },
     );
 
-    let copy_into = map(
-        rule! {
-            COPY
-            ~ #hint?
-            ~ INTO ~ #copy_unit
-            ~ FROM ~ #copy_unit
-            ~ ( #copy_option ~ ","? )*
-        },
-        |(_, opt_hints, _, dst, _, src, opts)| {
-            let mut copy_stmt = CopyStmt {
-                hints: opt_hints,
-                src,
-                dst,
-                files: Default::default(),
-                pattern: Default::default(),
-                file_format: Default::default(),
-                validation_mode: Default::default(),
-                size_limit: Default::default(),
-                max_files: Default::default(),
-                max_file_size: Default::default(),
-                split_size: Default::default(),
-                single: Default::default(),
-                purge: Default::default(),
-                force: Default::default(),
-                disable_variant_check: Default::default(),
-                on_error: "abort".to_string(),
-            };
-            for (opt, _) in opts {
-                copy_stmt.apply_option(opt);
-            }
-            Statement::Copy(copy_stmt)
-        },
-    );
+    let copy_into = map(copy_into, Statement::Copy);
 
     let call = map(
         rule! {
@@ -1273,18 +1246,15 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         |(_, _)| Statement::ShowShares(ShowSharesStmt {}),
     );
 
-    let create_file_format = map_res(
+    let create_file_format = map(
         rule! {
             CREATE ~ FILE ~ FORMAT ~ ( IF ~ NOT ~ EXISTS )?
             ~ #ident ~ #format_options
         },
-        |(_, _, _, opt_if_not_exists, name, options)| {
-            let file_format_options = FileFormatOptionsAst { options };
-            Ok(Statement::CreateFileFormat {
-                if_not_exists: opt_if_not_exists.is_some(),
-                name: name.to_string(),
-                file_format_options,
-            })
+        |(_, _, _, opt_if_not_exists, name, file_format_options)| Statement::CreateFileFormat {
+            if_not_exists: opt_if_not_exists.is_some(),
+            name: name.to_string(),
+            file_format_options,
         },
     );
 
@@ -1551,7 +1521,8 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
                 [ FILE_FORMAT = ( { TYPE = { CSV | JSON | PARQUET } [ formatTypeOptions ] } ) ]
                 [ FILES = ( '<file_name>' [ , '<file_name>' ] [ , ... ] ) ]
                 [ PATTERN = '<regex_pattern>' ]
-                [ VALIDATION_MODE = RETURN_ROWS ]
+                [ VALIDATION_MODE = { RETURN_<n>_ROWS | RETURN_ERRORS | RETURN_ALL_ERRORS } ]
+                [ PARTITION BY <expr> ]
                 [ copyOptions ]`"
         ),
         rule!(
@@ -1608,6 +1579,8 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
 // stop the parser immediately and return the rest tokens by `InsertSource`.
 //
 // This is a hack to make it able to parse a large streaming insert statement.
+// The rows of `VALUES` are not even tokenized by `tokenize_sql`, only their offset
+// is kept, and they are read lazily from the source by `ValuesRowReader`.
 pub fn insert_source(i: Input) -> IResult<InsertSource> {
     let streaming = map(
         rule! {
@@ -1621,19 +1594,21 @@ pub fn insert_source(i: Input) -> IResult<InsertSource> {
     );
     let streaming_v2 = map(
         rule! {
-           #file_format_clause  ~ ( ON_ERROR ~ "=" ~ #ident)? ~  #rest_str
+           #file_format_clause  ~ ( ON_ERROR ~ "=" ~ #on_error_mode)? ~  #rest_str
         },
         |(options, on_error_opt, (_, start))| InsertSource::StreamingV2 {
             settings: options,
-            on_error_mode: on_error_opt.map(|v| v.2.to_string()),
+            on_error_mode: on_error_opt.map(|v| v.2),
             start,
         },
     );
     let values = map(
         rule! {
-            VALUES ~ #rest_str
+            VALUES ~ #skip_rest
+        },
+        |(values, _)| InsertSource::Values {
+            start: values.span.end,
         },
-        |(_, (rest_str, _))| InsertSource::Values { rest_str },
     );
     let query = map(query, |query| InsertSource::Select {
         query: Box::new(query),
@@ -1650,11 +1625,11 @@ pub fn insert_source(i: Input) -> IResult<InsertSource> {
 pub fn merge_source(i: Input) -> IResult<MergeSource> {
     let streaming_v2 = map(
         rule! {
-           #file_format_clause  ~ ( ON_ERROR ~ "=" ~ #ident)? ~  #rest_str
+           #file_format_clause  ~ ( ON_ERROR ~ "=" ~ #on_error_mode)? ~  #rest_str
         },
         |(options, on_error_opt, (_, start))| MergeSource::StreamingV2 {
             settings: options,
-            on_error_mode: on_error_opt.map(|v| v.2.to_string()),
+            on_error_mode: on_error_opt.map(|v| v.2),
             start,
         },
     );
@@ -1712,7 +1687,7 @@ pub fn hint(i: Input) -> IResult<Hint> {
         },
         |_| Hint { hints_list: vec![] },
     );
-    rule!(#hint|#invalid_hint)(i)
+    syntax_feature(SyntaxFeature::Hints, rule!(#hint|#invalid_hint))(i)
 }
 
 pub fn rest_str(i: Input) -> IResult<(String, usize)> {
@@ -1728,6 +1703,11 @@ pub fn rest_str(i: Input) -> IResult<(String, usize)> {
     ))
 }
 
+/// Skip the rest tokens, e.g. the rows of `VALUES` if they are tokenized.
+fn skip_rest(i: Input) -> IResult<()> {
+    Ok((i.slice((i.len() - 1)..), ()))
+}
+
 pub fn column_def(i: Input) -> IResult<ColumnDefinition> {
     #[derive(Clone)]
     enum ColumnConstraint {
@@ -2016,7 +1996,7 @@ pub fn create_table_source(i: Input) -> IResult<CreateTableSource> {
     );
     let like = map(
         rule! {
-            LIKE ~ #dot_separated_idents_1_to_3
+            LIKE ~ #table_name
         },
         |(_, (catalog, database, table))| CreateTableSource::Like {
             catalog,
@@ -2358,6 +2338,54 @@ pub fn kill_target(i: Input) -> IResult<KillTarget> {
     ))(i)
 }
 
+/// Parse `COPY INTO <dst> FROM <src> [ <copy_option> ... ]`, the options can
+/// be given in any order.
+pub fn copy_into(i: Input) -> IResult<CopyStmt> {
+    let (rest, (_, opt_hints, _, (dst_input, dst), _, src, opts)) = rule! {
+        COPY
+        ~ #hint?
+        ~ INTO ~ #consumed(copy_unit)
+        ~ FROM ~ #copy_unit
+        ~ ( #copy_option ~ ","? )*
+    }(i)?;
+
+    let mut copy_stmt = CopyStmt {
+        hints: opt_hints,
+        src,
+        dst,
+        files: Default::default(),
+        pattern: Default::default(),
+        file_format: Default::default(),
+        validation_mode: Default::default(),
+        size_limit: Default::default(),
+        max_files: Default::default(),
+        max_file_size: Default::default(),
+        split_size: Default::default(),
+        single: Default::default(),
+        purge: Default::default(),
+        force: Default::default(),
+        disable_variant_check: Default::default(),
+        on_error: OnErrorMode::Abort,
+        partition_by: Default::default(),
+        header: Default::default(),
+        include_query_id: Default::default(),
+    };
+    for (opt, _) in opts {
+        copy_stmt.apply_option(opt);
+    }
+
+    if !copy_stmt.is_unload()
+        && (copy_stmt.partition_by.is_some() || copy_stmt.header || copy_stmt.include_query_id)
+    {
+        return failure_at(
+            dst_input,
+            "`PARTITION BY`, `HEADER` and `INCLUDE_QUERY_ID` can only be used when unloading into a location",
+        );
+    }
+
+    Ok((rest, copy_stmt))
+}
+
 /// Parse input into `CopyUnit`
 ///
 /// # Notes
@@ -2368,7 +2396,7 @@ pub fn copy_unit(i: Input) -> IResult<CopyUnit> {
     let table = |i| {
         map(
             rule! {
-            #dot_separated_idents_1_to_3
+            #table_name
             ~ ( "(" ~ #comma_separated_list1(ident) ~ ")" )?
             },
             |((catalog, database, table), opt_columns)| CopyUnit::Table {
@@ -2421,7 +2449,7 @@ pub fn show_limit(i: Input) -> IResult<ShowLimit> {
 pub fn table_option(i: Input) -> IResult<BTreeMap<String, String>> {
     map(
         rule! {
-           ( #ident ~ "=" ~ #parameter_to_string )*
+           ( #option_key ~ "=" ~ #parameter_to_string )*
         },
         |opts| {
             BTreeMap::from_iter(
@@ -2435,7 +2463,7 @@ pub fn table_option(i: Input) -> IResult<BTreeMap<String, String>> {
 pub fn set_table_option(i: Input) -> IResult<BTreeMap<String, String>> {
     map(
         rule! {
-           ( #ident ~ "=" ~ #parameter_to_string ) ~ ("," ~ #ident ~ "=" ~ #parameter_to_string )*
+           ( #option_key ~ "=" ~ #parameter_to_string ) ~ ("," ~ #option_key ~ "=" ~ #parameter_to_string )*
         },
         |(key, _, value, opts)| {
             let mut options = BTreeMap::from_iter(
@@ -2580,7 +2608,7 @@ pub fn copy_option(i: Input) -> IResult<CopyOption> {
             CopyOption::FileFormat(options)
         }),
         map(
-            rule! { VALIDATION_MODE ~ "=" ~ #literal_string },
+            rule! { VALIDATION_MODE ~ "=" ~ #validation_mode },
             |(_, _, validation_mode)| CopyOption::ValidationMode(validation_mode),
         ),
         map(
@@ -2608,13 +2636,24 @@ pub fn copy_option(i: Input) -> IResult<CopyOption> {
         map(rule! { FORCE ~ "=" ~ #literal_bool }, |(_, _, force)| {
             CopyOption::Force(force)
         }),
-        map(rule! { ON_ERROR ~ "=" ~ #ident }, |(_, _, on_error)| {
-            CopyOption::OnError(on_error.to_string())
-        }),
+        map(
+            rule! { ON_ERROR ~ "=" ~ #on_error_mode },
+            |(_, _, on_error)| CopyOption::OnError(on_error),
+        ),
         map(
             rule! { DISABLE_VARIANT_CHECK ~ "=" ~ #literal_bool },
             |(_, _, disable_variant_check)| CopyOption::DisableVariantCheck(disable_variant_check),
         ),
+        map(rule! { PARTITION ~ BY ~ #expr }, |(_, _, partition_by)| {
+            CopyOption::PartitionBy(Box::new(partition_by))
+        }),
+        map(rule! { HEADER ~ "=" ~ #literal_bool }, |(_, _, header)| {
+            CopyOption::Header(header)
+        }),
+        map(
+            rule! { INCLUDE_QUERY_ID ~ "=" ~ #literal_bool },
+            |(_, _, include_query_id)| CopyOption::IncludeQueryId(include_query_id),
+        ),
     ))(i)
 }
 
@@ -2649,7 +2688,7 @@ pub fn presign_option(i: Input) -> IResult<PresignOption> {
 pub fn table_reference_only(i: Input) -> IResult<TableReference> {
     map(
         consumed(rule! {
-            #dot_separated_idents_1_to_3
+            #table_name
         }),
         |(span, (catalog, database, table))| TableReference::Table {
             span: transform_span(span.0),
@@ -2698,23 +2737,26 @@ pub fn udf_definition(i: Input) -> IResult<UDFDefinition> {
         },
     );
 
-    let udf_server = map(
-        rule! {
-            "(" ~ #comma_separated_list0(udf_arg_type) ~ ")"
-            ~ RETURNS ~ #udf_arg_type
-            ~ LANGUAGE ~ #ident
-            ~ HANDLER ~ ^"=" ~ ^#literal_string
-            ~ ADDRESS ~ ^"=" ~ ^#literal_string
-        },
-        |(_, arg_types, _, _, return_type, _, language, _, _, handler, _, _, address)| {
-            UDFDefinition::UDFServer {
-                arg_types,
-                return_type,
-                address,
-                handler,
-                language: language.to_string(),
-            }
-        },
+    let udf_server = syntax_feature(
+        SyntaxFeature::UdfServer,
+        map(
+            rule! {
+                "(" ~ #comma_separated_list0(udf_arg_type) ~ ")"
+                ~ RETURNS ~ #udf_arg_type
+                ~ LANGUAGE ~ #ident
+                ~ HANDLER ~ ^"=" ~ ^#literal_string
+                ~ ADDRESS ~ ^"=" ~ ^#literal_string
+            },
+            |(_, arg_types, _, _, return_type, _, language, _, _, handler, _, _, address)| {
+                UDFDefinition::UDFServer {
+                    arg_types,
+                    return_type,
+                    address,
+                    handler,
+                    language: language.to_string(),
+                }
+            },
+        ),
     );
 
     rule!(
@@ -0,0 +1,225 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Range;
+use common_exception::Result;
+use common_exception::Span;
+
+use crate::ast::Expr;
+use crate::error::display_parser_error;
+use crate::input::Dialect;
+use crate::input::Input;
+use crate::input::ParseOptions;
+use crate::input::ParseState;
+use crate::parser::expr::values_with_placeholder;
+use crate::parser::token::Token;
+use crate::parser::token::TokenKind;
+use crate::parser::token::Tokenizer;
+use crate::Backtrace;
+
+/// A single tuple of a `VALUES` clause.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValuesRow {
+    /// Zero-based index of the row in the `VALUES` clause.
+    pub row: usize,
+    /// The span of the row, from the opening to the closing parenthesis.
+    pub span: Span,
+    /// The values of the row, `None` stands for a placeholder `?`.
+    pub values: Vec<Option<Expr>>,
+}
+
+/// Lexes and parses the tuples of a `VALUES` clause one row at a time.
+///
+/// The reader pulls tokens lazily from the source, so only the tokens of the
+/// current row are kept in memory no matter how many rows there are. The spans
+/// of the returned rows and errors are offsets into `source`.
+///
+/// ```text
+/// INSERT INTO t VALUES (1, 'a'), (2, 'b');
+///                      ^ start
+/// ```
+pub struct ValuesRowReader<'a> {
+    source: &'a str,
+    dialect: Dialect,
+    tokenizer: Tokenizer<'a>,
+    start: usize,
+    tokens: Vec<Token<'a>>,
+    row: usize,
+    finished: bool,
+}
+
+impl<'a> ValuesRowReader<'a> {
+    /// Create a reader over the rows that begin at byte offset `start` of `source`,
+    /// e.g. `InsertSource::Values::start` with the original SQL text.
+    pub fn new(source: &'a str, start: usize, dialect: Dialect) -> Self {
+        ValuesRowReader {
+            source,
+            dialect,
+            tokenizer: Tokenizer::new(source),
+            start,
+            tokens: Vec::new(),
+            row: 0,
+            finished: false,
+        }
+    }
+
+    /// Return the one-based line and column of the byte offset in the source.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let prefix = &self.source[..offset.min(self.source.len())];
+        let line = prefix.matches('\n').count() + 1;
+        let line_start = prefix.rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+        let column = prefix[line_start..].chars().count() + 1;
+        (line, column)
+    }
+
+    fn next_token(&mut self) -> Result<Token<'a>> {
+        loop {
+            // The tokenizer always ends with an EOI token.
+            let token = match self.tokenizer.next() {
+                Some(token) => token?,
+                None => return Ok(Token::new_eoi(self.source)),
+            };
+            if token.kind == TokenKind::EOI || token.span.start >= self.start {
+                return Ok(token);
+            }
+        }
+    }
+
+    fn location(&self, span: Range) -> String {
+        let (line, column) = self.position(span.start);
+        format!("row {}, line {}, column {}", self.row, line, column)
+    }
+
+    fn error(&self, span: Range, msg: &str) -> ErrorCode {
+        ErrorCode::SyntaxException(format!("invalid value at {}: {}", self.location(span), msg))
+            .set_span(Some(span))
+    }
+
+    /// Collect the tokens of the next parenthesized row into `self.tokens`,
+    /// or return `false` if there are no more rows.
+    fn read_row_tokens(&mut self) -> Result<bool> {
+        self.tokens.clear();
+
+        let mut token = self.next_token()?;
+        if self.row > 0 {
+            match token.kind {
+                TokenKind::Comma => token = self.next_token()?,
+                TokenKind::SemiColon | TokenKind::EOI => {}
+                _ => return Err(self.error(token.span, "expected `,` or `;`")),
+            }
+        }
+        match token.kind {
+            TokenKind::LParen => {}
+            TokenKind::SemiColon if self.row > 0 => {
+                let token = self.next_token()?;
+                if token.kind != TokenKind::EOI {
+                    return Err(self.error(token.span, "unexpected tokens after `;`"));
+                }
+                return Ok(false);
+            }
+            TokenKind::EOI if self.row > 0 => return Ok(false),
+            _ => return Err(self.error(token.span, "expected `(`")),
+        }
+
+        let mut depth = 0usize;
+        loop {
+            match token.kind {
+                TokenKind::LParen => depth += 1,
+                TokenKind::RParen => depth -= 1,
+                TokenKind::EOI => {
+                    return Err(self.error(token.span, "unclosed `(` at the end of input"));
+                }
+                _ => {}
+            }
+            self.tokens.push(token);
+            if depth == 0 {
+                return Ok(true);
+            }
+            token = self.next_token()?;
+        }
+    }
+
+    fn parse_row(&mut self) -> Result<ValuesRow> {
+        let first = self.tokens.first().unwrap().span;
+        let last = self.tokens.last().unwrap().span;
+        let span = Range {
+            start: first.start,
+            end: last.end,
+        };
+        self.tokens.push(Token {
+            source: self.source,
+            kind: TokenKind::EOI,
+            span: (last.end..last.end).into(),
+        });
+
+        let backtrace = Backtrace::new();
+        let state = ParseState::new(ParseOptions {
+            dialect: self.dialect,
+            ..Default::default()
+        });
+        let input = Input(&self.tokens, self.dialect, &backtrace, &state);
+        let values = match values_with_placeholder(input) {
+            Ok((rest, values)) if rest[0].kind == TokenKind::EOI => values,
+            Ok((rest, _)) => {
+                return Err(self.error(rest[0].span, "unable to parse rest of the row"));
+            }
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+                let err_span = err.span;
+                return Err(ErrorCode::SyntaxException(format!(
+                    "invalid value at {}\n{}",
+                    self.location(err_span),
+                    display_parser_error(err, self.source)
+                ))
+                .set_span(Some(err_span)));
+            }
+            Err(nom::Err::Incomplete(_)) => unreachable!(),
+        };
+
+        Ok(ValuesRow {
+            row: self.row,
+            span: Some(span),
+            values,
+        })
+    }
+}
+
+impl<'a> Iterator for ValuesRowReader<'a> {
+    type Item = Result<ValuesRow>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let res = match self.read_row_tokens() {
+            Ok(true) => self.parse_row(),
+            Ok(false) => {
+                self.finished = true;
+                return None;
+            }
+            Err(err) => Err(err),
+        };
+        match res {
+            Ok(row) => {
+                self.row += 1;
+                Some(Ok(row))
+            }
+            Err(err) => {
+                // Stop at the first error since the rest can not be located reliably.
+                self.finished = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -18,18 +18,23 @@ use nom::branch::alt;
 use nom::combinator::map;
 use nom::Offset;
 use nom::Slice;
+use pratt::Affix;
 use pratt::PrattError;
 use pratt::PrattParser;
 use pratt::Precedence;
 
 use crate::ast::Identifier;
 use crate::input::Input;
+use crate::input::SyntaxFeature;
 use crate::input::WithSpan;
 use crate::parser::quote::unquote_ident;
 use crate::parser::token::*;
+use crate::parser::CompletionContext;
 use crate::rule;
+use crate::DeniedSyntax;
 use crate::Error;
 use crate::ErrorKind;
+use crate::LimitError;
 
 pub type IResult<'a, Output> = nom::IResult<Input<'a>, Output, Error<'a>>;
 
@@ -81,7 +86,23 @@ pub fn ident_after_as(i: Input) -> IResult<Identifier> {
 }
 
 pub fn function_name(i: Input) -> IResult<Identifier> {
-    non_reserved_identifier(|token| token.is_reserved_function_name())(i)
+    completion_context(
+        CompletionContext::FunctionName,
+        non_reserved_identifier(|token| token.is_reserved_function_name()),
+    )(i)
+}
+
+/// Parse a table name of one to three idents, e.g. `catalog.db.table`.
+#[allow(clippy::needless_lifetimes)]
+pub fn table_name<'a>(
+    i: Input<'a>,
+) -> IResult<'a, (Option<Identifier>, Option<Identifier>, Identifier)> {
+    completion_context(CompletionContext::TableName, dot_separated_idents_1_to_3)(i)
+}
+
+/// Parse the key of an option like `key = value`.
+pub fn option_key(i: Input) -> IResult<Identifier> {
+    completion_context(CompletionContext::OptionKey, ident)(i)
 }
 
 /// Parse input into stage name.
@@ -341,6 +362,147 @@ where
     }
 }
 
+/// Fail at `input` with a plain text error, discarding the errors of the tokens
+/// that have been tried after it, so that the error is reported at `input`.
+pub fn failure_at<'a, T>(input: Input<'a>, reason: &'static str) -> IResult<'a, T> {
+    failure_with(input, ErrorKind::Other(reason))
+}
+
+/// Same as `failure_at`, but fail with an arbitrary error kind.
+pub fn failure_with<'a, T>(input: Input<'a>, kind: ErrorKind) -> IResult<'a, T> {
+    input.2.clear();
+    Err(nom::Err::Failure(Error::from_error_kind(input, kind)))
+}
+
+/// Fail at `input` because a resource limit is exceeded. The limit is recorded in
+/// the parse state, so that it's reported even if the failure is discarded.
+pub fn limit_exceeded<'a, T>(input: Input<'a>, err: LimitError) -> IResult<'a, T> {
+    let reason = err.reason();
+    input.3.set_limit_error(err);
+    failure_at(input, reason)
+}
+
+/// Run `parser` one level deeper in the nesting of expressions, queries and table
+/// references, failing at `input` if it's nested too deep. The parser reads a
+/// sequence of Pratt parser elements, which starts with an operand at `input`.
+pub fn nested<'a, O>(
+    input: Input<'a>,
+    parser: impl FnOnce(Input<'a>) -> IResult<'a, O>,
+) -> IResult<'a, O> {
+    if !input.3.enter() {
+        let limit = input.3.limits().max_depth;
+        return limit_exceeded(input, LimitError::TooDeep {
+            limit,
+            span: transform_span(&input[..1]).unwrap(),
+        });
+    }
+    let enclosing = input.3.start_sequence(input.len());
+    let res = parser(input);
+    input.3.end_sequence(enclosing);
+    input.3.leave();
+    res
+}
+
+/// Record that an operand is expected at `rest`, after `element` in the current
+/// sequence of Pratt parser elements, if it's a prefix or infix operator.
+pub fn expect_operand_after<P, T>(pratt: &mut P, element: &T, rest: Input)
+where P: PrattParser<std::iter::Once<T>, Input = T> {
+    if let Ok(Affix::Prefix(_) | Affix::Infix(..)) = pratt.query(element) {
+        rest.3.add_operand_position(rest.len());
+    }
+}
+
+/// Run `parser` of an infix or postfix operator in a sequence of Pratt parser
+/// elements, discarding what it expects at the end of the input if it's tried
+/// where an operand is expected, e.g. `JOIN` in `SELECT * FROM |`.
+pub fn pratt_operator<'a, O>(
+    parser: impl FnMut(Input<'a>) -> IResult<'a, O>,
+) -> impl FnMut(Input<'a>) -> IResult<'a, O> {
+    discard_expected_at_end_if(true, parser)
+}
+
+/// Run `parser` of an operand in a sequence of Pratt parser elements, discarding
+/// what it expects at the end of the input if it's tried after an operand, e.g.
+/// a table name in `SELECT * FROM t |`.
+pub fn pratt_operand<'a, O>(
+    parser: impl FnMut(Input<'a>) -> IResult<'a, O>,
+) -> impl FnMut(Input<'a>) -> IResult<'a, O> {
+    discard_expected_at_end_if(false, parser)
+}
+
+/// Run `parser`, discarding the tokens and completion contexts it expects at the
+/// end of the input if whether an operand is expected at `input` is `at_operand`.
+fn discard_expected_at_end_if<'a, O>(
+    at_operand: bool,
+    mut parser: impl FnMut(Input<'a>) -> IResult<'a, O>,
+) -> impl FnMut(Input<'a>) -> IResult<'a, O> {
+    move |input| {
+        let discard = input.3.is_operand_position(input.len()) == at_operand;
+        let expected = input.3.expected_at_end_count();
+        let contexts = input.3.completion_context_count();
+        let res = parser(input);
+        if discard {
+            input.3.truncate_expected_at_end(expected);
+            input.3.truncate_completion_contexts(contexts);
+        }
+        res
+    }
+}
+
+/// Run `parser`, failing at `input` if the syntax it matches is denied by the parse
+/// options.
+pub fn syntax_feature<'a, O>(
+    feature: SyntaxFeature,
+    mut parser: impl FnMut(Input<'a>) -> IResult<'a, O>,
+) -> impl FnMut(Input<'a>) -> IResult<'a, O> {
+    move |input| {
+        let (rest, output) = parser(input)?;
+        if input.3.options().is_allowed(feature) {
+            return Ok((rest, output));
+        }
+        let err = DeniedSyntax {
+            feature,
+            span: transform_span(&input[..input.offset(&rest)]).unwrap(),
+        };
+        let reason = err.reason();
+        input.3.set_denied_syntax(err);
+        failure_at(input, reason)
+    }
+}
+
+/// Run `parser`, recording `context` in the parse state if it expects a token at
+/// the end of the input, either right away or after a qualified name like `db.`.
+/// This tells what kind of name is completed at the cursor, see
+/// [`complete`](crate::parser::complete).
+pub fn completion_context<'a, O>(
+    context: CompletionContext,
+    mut parser: impl FnMut(Input<'a>) -> IResult<'a, O>,
+) -> impl FnMut(Input<'a>) -> IResult<'a, O> {
+    move |input| {
+        let expected = input.3.expected_at_end_count();
+        let res = parser(input);
+        if input.3.expected_at_end_count() > expected
+            && is_name_prefix(&input[..input.len().saturating_sub(1)])
+        {
+            input.3.add_completion_context(context);
+        }
+        res
+    }
+}
+
+/// Whether the tokens are empty or a qualifier like `db.t.`.
+fn is_name_prefix(tokens: &[Token]) -> bool {
+    match tokens.last() {
+        Some(last) => {
+            last.kind == Dot
+                && tokens.iter().all(|token| {
+                    matches!(token.kind, Ident | QuotedString | Dot) || token.kind.is_keyword()
+                })
+        }
+        None => true,
+    }
+}
+
 pub fn transform_span(tokens: &[Token]) -> Span {
     Some(Range {
         start: tokens.first().unwrap().span.start,
@@ -362,6 +524,14 @@ where
     let mut iter_cloned = iter.clone();
     let mut iter = iter.clone().peekable();
     let len = iter.len();
+    let max_chain_len = input.3.limits().max_chain_len;
+    if len > max_chain_len {
+        let elem = iter_cloned.nth(max_chain_len).unwrap();
+        return limit_exceeded(elem.span, LimitError::ChainTooLong {
+            limit: max_chain_len,
+            span: transform_span(&elem.span).unwrap(),
+        });
+    }
     let expr = parser
         .parse_input(&mut iter, Precedence(0))
         .map_err(|err| {
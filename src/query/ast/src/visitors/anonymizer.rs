@@ -0,0 +1,1109 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use common_exception::Span;
+use ethnum::i256;
+
+use super::walk_expr_children_mut;
+use super::walk_query_mut;
+use super::walk_table_reference_mut;
+use super::VisitorMut;
+use crate::ast::*;
+use crate::meta::principal_identity::PrincipalIdentity;
+use crate::meta::user_identity::UserIdentity;
+use crate::meta::ShareGrantObjectName;
+use crate::meta::ShareNameIdent;
+
+/// The kind of a name that is replaced by a pseudonym. Pseudonyms are assigned
+/// per kind, e.g. `n1` for the first table, column or database name and `user1`
+/// for the first user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NameKind {
+    /// Catalogs, databases, tables, columns, aliases and other named objects.
+    Name,
+    Stage,
+    User,
+    /// The host or bucket of a URI.
+    Host,
+    Role,
+    /// A segment of a file path.
+    Path,
+}
+
+impl NameKind {
+    fn prefix(&self) -> &'static str {
+        match self {
+            NameKind::Name => "n",
+            NameKind::Stage => "stage",
+            NameKind::User => "user",
+            NameKind::Host => "host",
+            NameKind::Role => "role",
+            NameKind::Path => "path",
+        }
+    }
+}
+
+/// The mapping between the original names and their pseudonyms, which is kept
+/// by [`Anonymizer`] to replace a name consistently and can be used to reverse
+/// the anonymization.
+///
+/// Unquoted identifiers are case-insensitive, so they are recorded in lowercase.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnonymizationMap {
+    /// `(kind, original, pseudonym)` in the order of assignment.
+    entries: Vec<(NameKind, String, String)>,
+    pseudonyms: BTreeMap<(NameKind, String), usize>,
+    originals: BTreeMap<String, usize>,
+    counters: BTreeMap<NameKind, usize>,
+}
+
+impl AnonymizationMap {
+    pub fn pseudonym(&self, kind: NameKind, original: &str) -> Option<&str> {
+        self.pseudonyms
+            .get(&(kind, original.to_string()))
+            .map(|i| self.entries[*i].2.as_str())
+    }
+
+    pub fn original(&self, pseudonym: &str) -> Option<(NameKind, &str)> {
+        self.originals.get(pseudonym).map(|i| {
+            let (kind, original, _) = &self.entries[*i];
+            (*kind, original.as_str())
+        })
+    }
+
+    /// Iterate over `(kind, original, pseudonym)` in the order of assignment.
+    pub fn iter(&self) -> impl Iterator<Item = (NameKind, &str, &str)> {
+        self.entries
+            .iter()
+            .map(|(kind, original, pseudonym)| (*kind, original.as_str(), pseudonym.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn get_or_insert(&mut self, kind: NameKind, original: String) -> String {
+        if let Some(i) = self.pseudonyms.get(&(kind, original.clone())) {
+            return self.entries[*i].2.clone();
+        }
+        let counter = self.counters.entry(kind).or_default();
+        *counter += 1;
+        let pseudonym = format!("{}{}", kind.prefix(), counter);
+        let i = self.entries.len();
+        self.pseudonyms.insert((kind, original.clone()), i);
+        self.originals.insert(pseudonym.clone(), i);
+        self.entries.push((kind, original, pseudonym.clone()));
+        pseudonym
+    }
+}
+
+impl Display for AnonymizationMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (_, original, pseudonym) in &self.entries {
+            writeln!(f, "{pseudonym} = {original}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Anonymize a statement, see [`Anonymizer`].
+pub fn anonymize(stmt: &Statement) -> (Statement, AnonymizationMap) {
+    let mut stmt = stmt.clone();
+    let mut anonymizer = Anonymizer::new();
+    anonymizer.anonymize(&mut stmt);
+    (stmt, anonymizer.into_map())
+}
+
+/// Rewrite statements so that they can be shared without revealing the names
+/// and values in them, while keeping them parseable:
+///
+/// - Names of databases, tables, columns, stages, users, roles and other objects
+///   are replaced with pseudonyms. The same name always gets the same pseudonym,
+///   also across statements anonymized by the same `Anonymizer`.
+/// - Literals are replaced with fakes of the same type and a similar shape, e.g.
+///   `'2023-06-01'` becomes `'1970-01-01'` and `12345` becomes `10000`.
+/// - Passwords, connection options, comments and UDF server addresses are faked.
+///
+/// Function names are kept, because user-defined functions can not be told apart
+/// from the builtin functions in a call.
+#[derive(Debug, Clone, Default)]
+pub struct Anonymizer {
+    map: AnonymizationMap,
+}
+
+impl Anonymizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Continue with the pseudonyms assigned by a previous anonymization.
+    pub fn with_map(map: AnonymizationMap) -> Self {
+        Anonymizer { map }
+    }
+
+    pub fn anonymize(&mut self, stmt: &mut Statement) {
+        self.visit_statement(stmt);
+    }
+
+    pub fn map(&self) -> &AnonymizationMap {
+        &self.map
+    }
+
+    pub fn into_map(self) -> AnonymizationMap {
+        self.map
+    }
+
+    fn name(&mut self, kind: NameKind, name: &mut String) {
+        if name.is_empty() {
+            return;
+        }
+        *name = self.map.get_or_insert(kind, name.to_lowercase());
+    }
+
+    fn opt_name(&mut self, kind: NameKind, name: &mut Option<String>) {
+        if let Some(name) = name {
+            self.name(kind, name);
+        }
+    }
+
+    fn opt_identifier(&mut self, ident: &mut Option<Identifier>) {
+        if let Some(ident) = ident {
+            self.visit_identifier(ident);
+        }
+    }
+
+    fn identifiers(&mut self, idents: &mut [Identifier]) {
+        for ident in idents {
+            self.visit_identifier(ident);
+        }
+    }
+
+    fn object(
+        &mut self,
+        catalog: &mut Option<Identifier>,
+        database: &mut Option<Identifier>,
+        name: &mut Identifier,
+    ) {
+        self.opt_identifier(catalog);
+        self.opt_identifier(database);
+        self.visit_identifier(name);
+    }
+
+    fn user(&mut self, user: &mut UserIdentity) {
+        // The hostname can only be `%`.
+        self.name(NameKind::User, &mut user.username);
+    }
+
+    fn principal(&mut self, principal: &mut PrincipalIdentity) {
+        match principal {
+            PrincipalIdentity::User(user) => self.user(user),
+            PrincipalIdentity::Role(role) => self.name(NameKind::Role, role),
+        }
+    }
+
+    fn auth_option(&mut self, auth_option: &mut AuthOption) {
+        if let Some(password) = &mut auth_option.password {
+            *password = "********".to_string();
+        }
+    }
+
+    fn user_options(&mut self, options: &mut [UserOptionItem]) {
+        for option in options {
+            match option {
+                UserOptionItem::DefaultRole(role) => self.name(NameKind::Role, role),
+                UserOptionItem::SetNetworkPolicy(policy) => self.name(NameKind::Name, policy),
+                UserOptionItem::TenantSetting(_) | UserOptionItem::UnsetNetworkPolicy => {}
+            }
+        }
+    }
+
+    fn account_mgr_source(&mut self, source: &mut AccountMgrSource) {
+        match source {
+            AccountMgrSource::Role { role } => self.name(NameKind::Role, role),
+            AccountMgrSource::Privs { level, .. } | AccountMgrSource::ALL { level } => {
+                match level {
+                    AccountMgrLevel::Global => {}
+                    AccountMgrLevel::Database(database) => self.opt_name(NameKind::Name, database),
+                    AccountMgrLevel::Table(database, table) => {
+                        self.opt_name(NameKind::Name, database);
+                        self.name(NameKind::Name, table);
+                    }
+                }
+            }
+        }
+    }
+
+    fn share_object(&mut self, object: &mut ShareGrantObjectName) {
+        match object {
+            ShareGrantObjectName::Database(database) => self.name(NameKind::Name, database),
+            ShareGrantObjectName::Table(database, table) => {
+                self.name(NameKind::Name, database);
+                self.name(NameKind::Name, table);
+            }
+        }
+    }
+
+    fn show_limit(&mut self, limit: &mut Option<ShowLimit>) {
+        match limit {
+            Some(ShowLimit::Like { pattern }) => *pattern = fake_string(pattern),
+            Some(ShowLimit::Where { selection }) => self.visit_expr(selection),
+            None => {}
+        }
+    }
+
+    fn comment(&mut self, comment: &mut Option<String>) {
+        if let Some(comment) = comment {
+            *comment = fake_string(comment);
+        }
+    }
+
+    fn options(&mut self, options: &mut BTreeMap<String, String>) {
+        for value in options.values_mut() {
+            *value = fake_string(value);
+        }
+    }
+
+    fn column_definition(&mut self, column: &mut ColumnDefinition) {
+        self.visit_identifier(&mut column.name);
+        match &mut column.expr {
+            Some(ColumnExpr::Default(expr))
+            | Some(ColumnExpr::Virtual(expr))
+            | Some(ColumnExpr::Stored(expr)) => self.visit_expr(expr),
+            None => {}
+        }
+        self.comment(&mut column.comment);
+    }
+
+    fn udf_definition(&mut self, definition: &mut UDFDefinition) {
+        match definition {
+            UDFDefinition::LambdaUDF {
+                parameters,
+                definition,
+            } => {
+                self.identifiers(parameters);
+                self.visit_expr(definition);
+            }
+            UDFDefinition::UDFServer {
+                address, handler, ..
+            } => {
+                *address = fake_string(address);
+                self.name(NameKind::Name, handler);
+            }
+        }
+    }
+
+    fn file_format(&mut self, options: &mut Option<FileFormatOptions>) {
+        if let Some(FileFormatOptions::Named(name)) = options {
+            self.name(NameKind::Name, name);
+        }
+    }
+
+    /// `<stage>/<path>` of `@<stage>/<path>`.
+    fn stage_location(&mut self, location: &mut String) {
+        let (stage, path) = match location.find('/') {
+            Some(pos) => location.split_at(pos),
+            None => (location.as_str(), ""),
+        };
+        let mut stage = stage.to_string();
+        if stage != "~" {
+            self.name(NameKind::Stage, &mut stage);
+        }
+        let path = self.path(path);
+        *location = format!("{stage}{path}");
+    }
+
+    fn path(&mut self, path: &str) -> String {
+        path.split('/')
+            .map(|segment| {
+                if segment.is_empty() {
+                    return String::new();
+                }
+                // Keep the file extension, it decides the format of the file.
+                let (stem, extension) = match segment.rfind('.') {
+                    Some(pos) if pos > 0 => segment.split_at(pos),
+                    _ => (segment, ""),
+                };
+                let pseudonym = self.map.get_or_insert(NameKind::Path, stem.to_string());
+                format!("{pseudonym}{extension}")
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn uri_location(&mut self, location: &mut UriLocation) {
+        self.name(NameKind::Host, &mut location.name);
+        location.path = self.path(&location.path);
+        if !location.part_prefix.is_empty() {
+            location.part_prefix = self.path(&location.part_prefix);
+        }
+        let protocol = StorageProtocol::from_scheme(&location.protocol);
+        location.connection = location.connection.map_values(|key, value| {
+            if protocol.map(|p| p.is_secret(key)).unwrap_or(true) {
+                "********".to_string()
+            } else {
+                fake_string(value)
+            }
+        });
+    }
+
+    fn file_location(&mut self, location: &mut FileLocation) {
+        match location {
+            FileLocation::Stage(location) => self.stage_location(location),
+            FileLocation::Uri(location) => self.uri_location(location),
+        }
+    }
+
+    fn insert_source(&mut self, source: &mut InsertSource) {
+        match source {
+            InsertSource::Streaming { rest_str, .. } => rest_str.clear(),
+            InsertSource::StreamingV2 { settings, .. } => {
+                if let FileFormatOptions::Named(name) = settings {
+                    self.name(NameKind::Name, name);
+                }
+            }
+            InsertSource::Values { .. } => {}
+            InsertSource::Select { query } => self.visit_query(query),
+        }
+    }
+
+    fn select_window(&mut self, window: &mut WindowDefinition) {
+        self.visit_identifier(&mut window.name);
+        self.window_spec(&mut window.spec);
+    }
+
+    fn window_spec(&mut self, spec: &mut WindowSpec) {
+        self.opt_identifier(&mut spec.existing_window_name);
+        for expr in spec.partition_by.iter_mut() {
+            self.visit_expr(expr);
+        }
+        for order_by in spec.order_by.iter_mut() {
+            self.visit_order_by(order_by);
+        }
+        if let Some(frame) = &mut spec.window_frame {
+            self.visit_frame_bound(&mut frame.start_bound);
+            self.visit_frame_bound(&mut frame.end_bound);
+        }
+    }
+}
+
+impl VisitorMut for Anonymizer {
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::FunctionCall {
+                lambda: Some(lambda),
+                ..
+            } => self.identifiers(&mut lambda.params),
+            Expr::MapAccess { accessor, .. } => match accessor {
+                MapAccessor::Bracket { key } => self.visit_expr(key),
+                MapAccessor::Dot { key } | MapAccessor::Colon { key } => self.visit_identifier(key),
+                MapAccessor::DotNumber { .. } => {}
+            },
+            _ => {}
+        }
+        walk_expr_children_mut(self, expr);
+    }
+
+    fn visit_identifier(&mut self, ident: &mut Identifier) {
+        let original = if ident.quote.is_some() {
+            ident.name.clone()
+        } else {
+            ident.name.to_lowercase()
+        };
+        ident.name = self.map.get_or_insert(NameKind::Name, original);
+        ident.quote = None;
+    }
+
+    fn visit_literal(&mut self, _span: Span, lit: &mut Literal) {
+        *lit = fake_literal(lit);
+    }
+
+    fn visit_query(&mut self, query: &mut Query) {
+        walk_query_mut(self, query);
+    }
+
+    fn visit_select_stmt(&mut self, stmt: &mut SelectStmt) {
+        let SelectStmt {
+            select_list,
+            from,
+            selection,
+            group_by,
+            having,
+            window_list,
+            ..
+        } = stmt;
+
+        for target in select_list.iter_mut() {
+            self.visit_select_target(target);
+        }
+        for table_ref in from.iter_mut() {
+            self.visit_table_reference(table_ref);
+        }
+        if let Some(selection) = selection {
+            self.visit_expr(selection);
+        }
+        match group_by {
+            Some(GroupBy::Normal(exprs) | GroupBy::Cube(exprs) | GroupBy::Rollup(exprs)) => {
+                for expr in exprs.iter_mut().filter(|expr| !is_position(expr)) {
+                    self.visit_expr(expr);
+                }
+            }
+            Some(GroupBy::GroupingSets(sets)) => {
+                for expr in sets.iter_mut().flatten().filter(|expr| !is_position(expr)) {
+                    self.visit_expr(expr);
+                }
+            }
+            Some(GroupBy::All) | None => {}
+        }
+        if let Some(having) = having {
+            self.visit_expr(having);
+        }
+        for window in window_list.iter_mut().flatten() {
+            self.select_window(window);
+        }
+    }
+
+    fn visit_order_by(&mut self, order_by: &mut OrderByExpr) {
+        if !is_position(&order_by.expr) {
+            self.visit_expr(&mut order_by.expr);
+        }
+    }
+
+    fn visit_table_reference(&mut self, table: &mut TableReference) {
+        match table {
+            TableReference::TableFunction { params, alias, .. } => {
+                for param in params {
+                    self.visit_expr(param);
+                }
+                if let Some(alias) = alias {
+                    self.visit_identifier(&mut alias.name);
+                    self.identifiers(&mut alias.columns);
+                }
+            }
+            TableReference::Location {
+                location,
+                options,
+                alias,
+                ..
+            } => {
+                self.file_location(location);
+                for file in options.files.iter_mut().flatten() {
+                    *file = self.path(file);
+                }
+                if let Some(pattern) = &mut options.pattern {
+                    *pattern = fake_string(pattern);
+                }
+                self.opt_name(NameKind::Name, &mut options.file_format);
+                self.options(&mut options.connection);
+                if let Some(alias) = alias {
+                    self.visit_identifier(&mut alias.name);
+                    self.identifiers(&mut alias.columns);
+                }
+            }
+            _ => walk_table_reference_mut(self, table),
+        }
+    }
+
+    fn visit_time_travel_point(&mut self, time: &mut TimeTravelPoint) {
+        match time {
+            TimeTravelPoint::Snapshot(snapshot) => *snapshot = fake_string(snapshot),
+            TimeTravelPoint::Timestamp(expr) => self.visit_expr(expr),
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        match stmt {
+            Statement::Query(query) => self.visit_query(query),
+            Statement::Explain { query, .. } | Statement::ExplainAnalyze { query } => {
+                self.visit_statement(query)
+            }
+            Statement::Copy(copy) => {
+                for unit in [&mut copy.src, &mut copy.dst] {
+                    match unit {
+                        CopyUnit::Table {
+                            catalog,
+                            database,
+                            table,
+                            columns,
+                        } => {
+                            self.object(catalog, database, table);
+                            for columns in columns.iter_mut() {
+                                self.identifiers(columns);
+                            }
+                        }
+                        CopyUnit::Location(location) => self.file_location(location),
+                        CopyUnit::Query(query) => self.visit_query(query),
+                    }
+                }
+                for file in copy.files.iter_mut().flatten() {
+                    *file = self.path(file);
+                }
+                if let Some(pattern) = &mut copy.pattern {
+                    *pattern = fake_string(pattern);
+                }
+                self.file_format(&mut copy.file_format);
+            }
+            Statement::Call(call) => {
+                for arg in call.args.iter_mut() {
+                    if arg.parse::<u64>().is_err() {
+                        self.name(NameKind::Name, arg);
+                    }
+                }
+            }
+            Statement::ShowSettings { like } => {
+                if let Some(like) = like {
+                    *like = fake_string(like);
+                }
+            }
+            Statement::ShowProcessList
+            | Statement::ShowMetrics
+            | Statement::ShowEngines
+            | Statement::ShowIndexes
+            | Statement::ShowUsers
+            | Statement::ShowRoles
+            | Statement::ShowStages
+            | Statement::ShowFileFormats
+            | Statement::ShowNetworkPolicies
+            | Statement::ShowShares(_)
+            | Statement::ShowShareEndpoint(_)
+            | Statement::UnSetVariable(_) => {}
+            Statement::ShowFunctions { limit } | Statement::ShowTableFunctions { limit } => {
+                self.show_limit(limit)
+            }
+            Statement::KillStmt { object_id, .. } => *object_id = fake_string(object_id),
+            Statement::SetVariable { value, .. } => self.visit_expr(value),
+            Statement::SetRole { role_name, .. } => self.name(NameKind::Role, role_name),
+            Statement::Insert(insert) => {
+                self.object(&mut insert.catalog, &mut insert.database, &mut insert.table);
+                self.identifiers(&mut insert.columns);
+                self.insert_source(&mut insert.source);
+            }
+            Statement::Replace(replace) => {
+                self.object(
+                    &mut replace.catalog,
+                    &mut replace.database,
+                    &mut replace.table,
+                );
+                self.identifiers(&mut replace.on_conflict_columns);
+                self.identifiers(&mut replace.columns);
+                self.insert_source(&mut replace.source);
+            }
+            Statement::MergeInto(merge_into) => {
+                self.object(
+                    &mut merge_into.catalog,
+                    &mut merge_into.database,
+                    &mut merge_into.table_ident,
+                );
+                match &mut merge_into.source {
+                    MergeSource::StreamingV2 { settings, .. } => {
+                        if let FileFormatOptions::Named(name) = settings {
+                            self.name(NameKind::Name, name);
+                        }
+                    }
+                    MergeSource::Select { query } => self.visit_query(query),
+                }
+                if let Some(alias) = &mut merge_into.alias_target {
+                    self.visit_identifier(&mut alias.name);
+                    self.identifiers(&mut alias.columns);
+                }
+                self.visit_expr(&mut merge_into.join_expr);
+                for option in merge_into.merge_options.iter_mut() {
+                    match option {
+                        MergeOption::Match(clause) => {
+                            if let Some(selection) = &mut clause.selection {
+                                self.visit_expr(selection);
+                            }
+                            if let MatchOperation::Update { update_list } = &mut clause.operation {
+                                for update in update_list.iter_mut() {
+                                    self.object(
+                                        &mut update.catalog,
+                                        &mut update.table,
+                                        &mut update.name,
+                                    );
+                                    self.visit_expr(&mut update.expr);
+                                }
+                            }
+                        }
+                        MergeOption::Unmatch(clause) => {
+                            if let Some(selection) = &mut clause.selection {
+                                self.visit_expr(selection);
+                            }
+                            let insert = &mut clause.insert_operation;
+                            for columns in insert.columns.iter_mut() {
+                                self.identifiers(columns);
+                            }
+                            for value in insert.values.iter_mut() {
+                                self.visit_expr(value);
+                            }
+                        }
+                    }
+                }
+            }
+            Statement::Delete {
+                table_reference,
+                selection,
+                ..
+            } => {
+                self.visit_table_reference(table_reference);
+                if let Some(selection) = selection {
+                    self.visit_expr(selection);
+                }
+            }
+            Statement::Update(update) => {
+                self.visit_table_reference(&mut update.table);
+                for update_expr in update.update_list.iter_mut() {
+                    self.visit_identifier(&mut update_expr.name);
+                    self.visit_expr(&mut update_expr.expr);
+                }
+                if let Some(selection) = &mut update.selection {
+                    self.visit_expr(selection);
+                }
+            }
+            Statement::ShowCatalogs(stmt) => self.show_limit(&mut stmt.limit),
+            Statement::ShowCreateCatalog(stmt) => self.visit_identifier(&mut stmt.catalog),
+            Statement::CreateCatalog(stmt) => {
+                self.name(NameKind::Name, &mut stmt.catalog_name);
+                self.options(&mut stmt.catalog_options);
+            }
+            Statement::DropCatalog(stmt) => self.visit_identifier(&mut stmt.catalog),
+            Statement::ShowDatabases(stmt) => {
+                self.opt_identifier(&mut stmt.catalog);
+                self.show_limit(&mut stmt.limit);
+            }
+            Statement::ShowCreateDatabase(stmt) => {
+                self.opt_identifier(&mut stmt.catalog);
+                self.visit_identifier(&mut stmt.database);
+            }
+            Statement::CreateDatabase(stmt) => {
+                self.opt_identifier(&mut stmt.catalog);
+                self.visit_identifier(&mut stmt.database);
+                for option in stmt.options.iter_mut() {
+                    option.value = fake_string(&option.value);
+                }
+                if let Some(ShareNameIdent { tenant, share_name }) = &mut stmt.from_share {
+                    self.name(NameKind::Name, tenant);
+                    self.name(NameKind::Name, share_name);
+                }
+            }
+            Statement::DropDatabase(stmt) => {
+                self.opt_identifier(&mut stmt.catalog);
+                self.visit_identifier(&mut stmt.database);
+            }
+            Statement::UndropDatabase(stmt) => {
+                self.opt_identifier(&mut stmt.catalog);
+                self.visit_identifier(&mut stmt.database);
+            }
+            Statement::AlterDatabase(stmt) => {
+                self.opt_identifier(&mut stmt.catalog);
+                self.visit_identifier(&mut stmt.database);
+                match &mut stmt.action {
+                    AlterDatabaseAction::RenameDatabase { new_db } => self.visit_identifier(new_db),
+                }
+            }
+            Statement::UseDatabase { database } => self.visit_identifier(database),
+            Statement::ShowTables(stmt) => {
+                self.opt_identifier(&mut stmt.catalog);
+                self.opt_identifier(&mut stmt.database);
+                self.show_limit(&mut stmt.limit);
+            }
+            Statement::ShowCreateTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table)
+            }
+            Statement::DescribeTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table)
+            }
+            Statement::ShowTablesStatus(stmt) => {
+                self.opt_identifier(&mut stmt.database);
+                self.show_limit(&mut stmt.limit);
+            }
+            Statement::ShowDropTables(stmt) => self.opt_identifier(&mut stmt.database),
+            Statement::AttachTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table);
+                self.uri_location(&mut stmt.uri_location);
+            }
+            Statement::CreateTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table);
+                match &mut stmt.source {
+                    Some(CreateTableSource::Columns(columns)) => {
+                        for column in columns.iter_mut() {
+                            self.column_definition(column);
+                        }
+                    }
+                    Some(CreateTableSource::Like {
+                        catalog,
+                        database,
+                        table,
+                    }) => self.object(catalog, database, table),
+                    None => {}
+                }
+                if let Some(location) = &mut stmt.uri_location {
+                    self.uri_location(location);
+                }
+                for expr in stmt.cluster_by.iter_mut() {
+                    self.visit_expr(expr);
+                }
+                if let Some(comment) = stmt.table_options.get_mut("comment") {
+                    *comment = fake_string(comment);
+                }
+                if let Some(query) = &mut stmt.as_query {
+                    self.visit_query(query);
+                }
+            }
+            Statement::DropTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table)
+            }
+            Statement::UndropTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table)
+            }
+            Statement::AlterTable(stmt) => {
+                self.visit_table_reference(&mut stmt.table_reference);
+                match &mut stmt.action {
+                    AlterTableAction::RenameTable { new_table } => self.visit_identifier(new_table),
+                    AlterTableAction::AddColumn { column, option } => {
+                        self.column_definition(column);
+                        if let AddColumnOption::After(after) = option {
+                            self.visit_identifier(after);
+                        }
+                    }
+                    AlterTableAction::RenameColumn {
+                        old_column,
+                        new_column,
+                    } => {
+                        self.visit_identifier(old_column);
+                        self.visit_identifier(new_column);
+                    }
+                    AlterTableAction::ModifyColumn { action } => match action {
+                        ModifyColumnAction::SetMaskingPolicy(column, policy) => {
+                            self.visit_identifier(column);
+                            self.name(NameKind::Name, policy);
+                        }
+                        ModifyColumnAction::UnsetMaskingPolicy(column)
+                        | ModifyColumnAction::ConvertStoredComputedColumn(column) => {
+                            self.visit_identifier(column)
+                        }
+                        ModifyColumnAction::SetDataType(columns) => {
+                            for column in columns.iter_mut() {
+                                self.column_definition(column);
+                            }
+                        }
+                    },
+                    AlterTableAction::DropColumn { column } => self.visit_identifier(column),
+                    AlterTableAction::AlterTableClusterKey { cluster_by } => {
+                        for expr in cluster_by.iter_mut() {
+                            self.visit_expr(expr);
+                        }
+                    }
+                    AlterTableAction::DropTableClusterKey => {}
+                    AlterTableAction::ReclusterTable { selection, .. } => {
+                        if let Some(selection) = selection {
+                            self.visit_expr(selection);
+                        }
+                    }
+                    AlterTableAction::RevertTo { point } => self.visit_time_travel_point(point),
+                    AlterTableAction::SetOptions { set_options } => {
+                        if let Some(comment) = set_options.get_mut("comment") {
+                            *comment = fake_string(comment);
+                        }
+                    }
+                }
+            }
+            Statement::RenameTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table);
+                self.object(
+                    &mut stmt.new_catalog,
+                    &mut stmt.new_database,
+                    &mut stmt.new_table,
+                );
+            }
+            Statement::TruncateTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table)
+            }
+            Statement::OptimizeTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table);
+                if let OptimizeTableAction::Purge {
+                    before: Some(point),
+                } = &mut stmt.action
+                {
+                    self.visit_time_travel_point(point);
+                }
+            }
+            Statement::VacuumTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table);
+                if let Some(retain_hours) = &mut stmt.option.retain_hours {
+                    self.visit_expr(retain_hours);
+                }
+            }
+            Statement::VacuumDropTable(stmt) => {
+                self.opt_identifier(&mut stmt.catalog);
+                self.opt_identifier(&mut stmt.database);
+                if let Some(retain_hours) = &mut stmt.option.retain_hours {
+                    self.visit_expr(retain_hours);
+                }
+            }
+            Statement::AnalyzeTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table)
+            }
+            Statement::ExistsTable(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table)
+            }
+            Statement::ShowColumns(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table);
+                self.show_limit(&mut stmt.limit);
+            }
+            Statement::CreateView(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.view);
+                self.identifiers(&mut stmt.columns);
+                self.visit_query(&mut stmt.query);
+            }
+            Statement::AlterView(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.view);
+                self.identifiers(&mut stmt.columns);
+                self.visit_query(&mut stmt.query);
+            }
+            Statement::DropView(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.view)
+            }
+            Statement::CreateIndex(stmt) => {
+                self.visit_identifier(&mut stmt.index_name);
+                self.visit_query(&mut stmt.query);
+            }
+            Statement::DropIndex(stmt) => self.visit_identifier(&mut stmt.index),
+            Statement::RefreshIndex(stmt) => self.visit_identifier(&mut stmt.index),
+            Statement::CreateVirtualColumn(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table);
+                for expr in stmt.virtual_columns.iter_mut() {
+                    self.visit_expr(expr);
+                }
+            }
+            Statement::AlterVirtualColumn(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table);
+                for expr in stmt.virtual_columns.iter_mut() {
+                    self.visit_expr(expr);
+                }
+            }
+            Statement::DropVirtualColumn(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table)
+            }
+            Statement::RefreshVirtualColumn(stmt) => {
+                self.object(&mut stmt.catalog, &mut stmt.database, &mut stmt.table)
+            }
+            Statement::CreateUser(stmt) => {
+                self.user(&mut stmt.user);
+                self.auth_option(&mut stmt.auth_option);
+                self.user_options(&mut stmt.user_options);
+            }
+            Statement::AlterUser(stmt) => {
+                if let Some(user) = &mut stmt.user {
+                    self.user(user);
+                }
+                if let Some(auth_option) = &mut stmt.auth_option {
+                    self.auth_option(auth_option);
+                }
+                self.user_options(&mut stmt.user_options);
+            }
+            Statement::DropUser { user, .. } => self.user(user),
+            Statement::CreateRole { role_name, .. } | Statement::DropRole { role_name, .. } => {
+                self.name(NameKind::Role, role_name)
+            }
+            Statement::Grant(stmt) => {
+                self.account_mgr_source(&mut stmt.source);
+                self.principal(&mut stmt.principal);
+            }
+            Statement::ShowGrants { principal } => {
+                if let Some(principal) = principal {
+                    self.principal(principal);
+                }
+            }
+            Statement::Revoke(stmt) => {
+                self.account_mgr_source(&mut stmt.source);
+                self.principal(&mut stmt.principal);
+            }
+            Statement::CreateUDF(stmt) => {
+                self.comment(&mut stmt.description);
+                self.udf_definition(&mut stmt.definition);
+            }
+            Statement::DropUDF { .. } => {}
+            Statement::AlterUDF(stmt) => {
+                self.comment(&mut stmt.description);
+                self.udf_definition(&mut stmt.definition);
+            }
+            Statement::CreateStage(stmt) => {
+                self.name(NameKind::Stage, &mut stmt.stage_name);
+                if let Some(location) = &mut stmt.location {
+                    self.uri_location(location);
+                }
+                self.file_format(&mut stmt.file_format_options);
+                if !stmt.comments.is_empty() {
+                    stmt.comments = fake_string(&stmt.comments);
+                }
+            }
+            Statement::DropStage { stage_name, .. } | Statement::DescribeStage { stage_name } => {
+                self.name(NameKind::Stage, stage_name)
+            }
+            Statement::RemoveStage { location, pattern }
+            | Statement::ListStage { location, pattern } => {
+                self.stage_location(location);
+                *pattern = fake_string(pattern);
+            }
+            Statement::CreateFileFormat { name, .. } | Statement::DropFileFormat { name, .. } => {
+                self.name(NameKind::Name, name)
+            }
+            Statement::Presign(stmt) => match &mut stmt.location {
+                PresignLocation::StageLocation(location) => self.stage_location(location),
+            },
+            Statement::CreateShareEndpoint(stmt) => {
+                self.visit_identifier(&mut stmt.endpoint);
+                self.uri_location(&mut stmt.url);
+                self.visit_identifier(&mut stmt.tenant);
+                self.options(&mut stmt.args);
+                self.comment(&mut stmt.comment);
+            }
+            Statement::DropShareEndpoint(stmt) => self.visit_identifier(&mut stmt.endpoint),
+            Statement::CreateShare(stmt) => {
+                self.visit_identifier(&mut stmt.share);
+                self.comment(&mut stmt.comment);
+            }
+            Statement::DropShare(stmt) => self.visit_identifier(&mut stmt.share),
+            Statement::GrantShareObject(stmt) => {
+                self.visit_identifier(&mut stmt.share);
+                self.share_object(&mut stmt.object);
+            }
+            Statement::RevokeShareObject(stmt) => {
+                self.visit_identifier(&mut stmt.share);
+                self.share_object(&mut stmt.object);
+            }
+            Statement::AlterShareTenants(stmt) => {
+                self.visit_identifier(&mut stmt.share);
+                self.identifiers(&mut stmt.tenants);
+            }
+            Statement::DescShare(stmt) => self.visit_identifier(&mut stmt.share),
+            Statement::ShowObjectGrantPrivileges(stmt) => self.share_object(&mut stmt.object),
+            Statement::ShowGrantsOfShare(stmt) => self.name(NameKind::Name, &mut stmt.share_name),
+            Statement::CreateDatamaskPolicy(stmt) => {
+                self.name(NameKind::Name, &mut stmt.name);
+                for arg in stmt.policy.args.iter_mut() {
+                    self.name(NameKind::Name, &mut arg.arg_name);
+                }
+                self.visit_expr(&mut stmt.policy.body);
+                self.comment(&mut stmt.policy.comment);
+            }
+            Statement::DropDatamaskPolicy(stmt) => self.name(NameKind::Name, &mut stmt.name),
+            Statement::DescDatamaskPolicy(stmt) => self.name(NameKind::Name, &mut stmt.name),
+            Statement::CreateNetworkPolicy(stmt) => {
+                self.name(NameKind::Name, &mut stmt.name);
+                for ip in stmt
+                    .allowed_ip_list
+                    .iter_mut()
+                    .chain(stmt.blocked_ip_list.iter_mut().flatten())
+                {
+                    *ip = fake_string(ip);
+                }
+                self.comment(&mut stmt.comment);
+            }
+            Statement::AlterNetworkPolicy(stmt) => {
+                self.name(NameKind::Name, &mut stmt.name);
+                for ip in stmt
+                    .allowed_ip_list
+                    .iter_mut()
+                    .flatten()
+                    .chain(stmt.blocked_ip_list.iter_mut().flatten())
+                {
+                    *ip = fake_string(ip);
+                }
+                self.comment(&mut stmt.comment);
+            }
+            Statement::DropNetworkPolicy(stmt) => self.name(NameKind::Name, &mut stmt.name),
+            Statement::DescNetworkPolicy(stmt) => self.name(NameKind::Name, &mut stmt.name),
+        }
+    }
+}
+
+/// `ORDER BY 1` and `GROUP BY 1` refer to a column of the select list.
+fn is_position(expr: &Expr) -> bool {
+    matches!(expr, Expr::Literal {
+        lit: Literal::UInt64(_),
+        ..
+    })
+}
+
+fn fake_literal(lit: &Literal) -> Literal {
+    match lit {
+        Literal::UInt64(v) => Literal::UInt64(fake_u64(*v)),
+        Literal::Float64(_) => Literal::Float64(1.5),
+        Literal::Decimal256 {
+            value,
+            precision,
+            scale,
+        } => {
+            let digits = value.to_string().trim_start_matches('-').len() as u32;
+            let mut fake = i256::from(10).pow(digits - 1);
+            if *value < i256::from(0) {
+                fake = -fake;
+            }
+            Literal::Decimal256 {
+                value: fake,
+                precision: *precision,
+                scale: *scale,
+            }
+        }
+        Literal::String(s) => Literal::String(fake_string(s)),
+        Literal::Boolean(_) | Literal::CurrentTimestamp | Literal::Null => lit.clone(),
+    }
+}
+
+/// Keep the number of digits, e.g. `12345` becomes `10000`.
+fn fake_u64(v: u64) -> u64 {
+    if v < 10 {
+        v.min(1)
+    } else {
+        10u64.pow(v.ilog10())
+    }
+}
+
+/// Keep the shape of the string: letters become `x`, digits become `0`, and
+/// the other characters are kept. Booleans are kept and dates become `1970-01-01`
+/// so that they can still be cast.
+fn fake_string(s: &str) -> String {
+    if s.eq_ignore_ascii_case("true") || s.eq_ignore_ascii_case("false") {
+        return s.to_string();
+    }
+    let is_date = s.len() >= 10
+        && s.bytes().take(10).enumerate().all(|(i, b)| match i {
+            4 | 7 => b == b'-',
+            _ => b.is_ascii_digit(),
+        });
+    let (prefix, rest) = if is_date {
+        ("1970-01-01", &s[10..])
+    } else {
+        ("", s)
+    };
+    let rest: String = rest
+        .chars()
+        .map(|c| {
+            if c.is_ascii_digit() {
+                '0'
+            } else if c.is_uppercase() {
+                'X'
+            } else if c.is_alphabetic() {
+                'x'
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("{prefix}{rest}")
+}
@@ -0,0 +1,1170 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A serialization of the AST that only depends on its structure, used to compute
+//! the digests of [`Fingerprint`](super::Fingerprint).
+//!
+//! Structs are written as their fields in parentheses, enums as the name of the
+//! variant followed by its fields, and strings are quoted. Spans and the offsets of
+//! the streaming sources are skipped, so are the rows of `INSERT ... VALUES`.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use common_exception::Range;
+use ethnum::i256;
+
+use crate::ast::*;
+use crate::meta::AuthType;
+use crate::meta::CatalogType;
+use crate::meta::ShareGrantObjectName;
+use crate::meta::ShareGrantObjectPrivilege;
+use crate::meta::ShareNameIdent;
+use crate::meta::UserPrivilegeType;
+use crate::meta::principal_identity::PrincipalIdentity;
+use crate::meta::user_identity::UserIdentity;
+
+pub(crate) trait Canonical {
+    fn write_canonical(&self, out: &mut String);
+}
+
+fn write_token(out: &mut String, token: &str) {
+    if !out.is_empty() {
+        out.push(' ');
+    }
+    out.push_str(token);
+}
+
+macro_rules! canonical_struct {
+    ($ty:ident { $($field:ident),* $(,)? }) => {
+        impl Canonical for $ty {
+            fn write_canonical(&self, out: &mut String) {
+                let $ty { $($field),* } = self;
+                write_token(out, "(");
+                $($field.write_canonical(out);)*
+                write_token(out, ")");
+            }
+        }
+    };
+}
+
+macro_rules! canonical_enum {
+    ($ty:ident { $($variant:ident $(($($arg:ident),*))? $({ $($field:ident),* })?),* $(,)? }) => {
+        impl Canonical for $ty {
+            fn write_canonical(&self, out: &mut String) {
+                match self {
+                    $($ty::$variant $(($($arg),*))? $({ $($field),* })? => {
+                        write_token(out, stringify!($variant));
+                        $(
+                            write_token(out, "(");
+                            $($arg.write_canonical(out);)*
+                            write_token(out, ")");
+                        )?
+                        $(
+                            write_token(out, "(");
+                            $($field.write_canonical(out);)*
+                            write_token(out, ")");
+                        )?
+                    })*
+                }
+            }
+        }
+    };
+}
+
+/// Spans are not part of the structure.
+impl Canonical for Option<Range> {
+    fn write_canonical(&self, _out: &mut String) {}
+}
+
+impl<T: Canonical> Canonical for Option<T> {
+    fn write_canonical(&self, out: &mut String) {
+        match self {
+            Some(value) => value.write_canonical(out),
+            None => write_token(out, "~"),
+        }
+    }
+}
+
+impl<T: Canonical + ?Sized> Canonical for Box<T> {
+    fn write_canonical(&self, out: &mut String) {
+        self.as_ref().write_canonical(out);
+    }
+}
+
+impl<T: Canonical> Canonical for Vec<T> {
+    fn write_canonical(&self, out: &mut String) {
+        write_token(out, "[");
+        for item in self {
+            item.write_canonical(out);
+        }
+        write_token(out, "]");
+    }
+}
+
+impl<K: Canonical, V: Canonical> Canonical for BTreeMap<K, V> {
+    fn write_canonical(&self, out: &mut String) {
+        write_token(out, "{");
+        for (key, value) in self {
+            key.write_canonical(out);
+            value.write_canonical(out);
+        }
+        write_token(out, "}");
+    }
+}
+
+impl<A: Canonical, B: Canonical> Canonical for (A, B) {
+    fn write_canonical(&self, out: &mut String) {
+        write_token(out, "(");
+        self.0.write_canonical(out);
+        self.1.write_canonical(out);
+        write_token(out, ")");
+    }
+}
+
+impl Canonical for () {
+    fn write_canonical(&self, out: &mut String) {
+        write_token(out, "()");
+    }
+}
+
+impl Canonical for String {
+    fn write_canonical(&self, out: &mut String) {
+        let mut quoted = String::with_capacity(self.len() + 2);
+        quoted.push('"');
+        for c in self.chars() {
+            if c == '"' || c == '\\' {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        write_token(out, &quoted);
+    }
+}
+
+impl Canonical for char {
+    fn write_canonical(&self, out: &mut String) {
+        self.to_string().write_canonical(out);
+    }
+}
+
+impl Canonical for bool {
+    fn write_canonical(&self, out: &mut String) {
+        write_token(out, if *self { "true" } else { "false" });
+    }
+}
+
+macro_rules! canonical_integer {
+    ($($ty:ty),*) => {
+        $(impl Canonical for $ty {
+            fn write_canonical(&self, out: &mut String) {
+                write_token(out, &self.to_string());
+            }
+        })*
+    };
+}
+
+canonical_integer!(u8, u64, usize, i256);
+
+impl Canonical for f64 {
+    fn write_canonical(&self, out: &mut String) {
+        write_token(out, &format!("{:016x}", self.to_bits()));
+    }
+}
+
+impl Canonical for Duration {
+    fn write_canonical(&self, out: &mut String) {
+        self.as_secs().write_canonical(out);
+        (self.subsec_nanos() as u64).write_canonical(out);
+    }
+}
+
+impl Canonical for Connection {
+    fn write_canonical(&self, out: &mut String) {
+        self.options().write_canonical(out);
+    }
+}
+
+impl Canonical for InsertSource {
+    fn write_canonical(&self, out: &mut String) {
+        match self {
+            InsertSource::Streaming {
+                format,
+                rest_str: _,
+                start: _,
+            } => {
+                write_token(out, "Streaming");
+                format.write_canonical(out);
+            }
+            InsertSource::StreamingV2 {
+                settings,
+                on_error_mode,
+                start: _,
+            } => {
+                write_token(out, "StreamingV2");
+                settings.write_canonical(out);
+                on_error_mode.write_canonical(out);
+            }
+            InsertSource::Values { start: _ } => write_token(out, "Values"),
+            InsertSource::Select { query } => {
+                write_token(out, "Select");
+                query.write_canonical(out);
+            }
+        }
+    }
+}
+
+impl Canonical for MergeSource {
+    fn write_canonical(&self, out: &mut String) {
+        match self {
+            MergeSource::StreamingV2 {
+                settings,
+                on_error_mode,
+                start: _,
+            } => {
+                write_token(out, "StreamingV2");
+                settings.write_canonical(out);
+                on_error_mode.write_canonical(out);
+            }
+            MergeSource::Select { query } => {
+                write_token(out, "Select");
+                query.write_canonical(out);
+            }
+        }
+    }
+}
+
+// Expressions
+canonical_struct!(Identifier { name, quote, span });
+canonical_struct!(ColumnPosition { pos, name, span });
+canonical_enum!(IntervalKind {
+    Year,
+    Quarter,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Doy,
+    Dow
+});
+canonical_enum!(ColumnID { Name(a0), Position(a0) });
+canonical_enum!(Expr {
+    ColumnRef { span, database, table, column },
+    IsNull { span, expr, not },
+    IsDistinctFrom { span, left, right, not },
+    InList { span, expr, list, not },
+    InSubquery { span, expr, subquery, not },
+    Between { span, expr, low, high, not },
+    BinaryOp { span, op, left, right },
+    UnaryOp { span, op, expr },
+    Cast { span, expr, target_type, pg_style },
+    TryCast { span, expr, target_type },
+    Extract { span, kind, expr },
+    Position { span, substr_expr, str_expr },
+    Substring { span, expr, substring_from, substring_for },
+    Trim { span, expr, trim_where },
+    Literal { span, lit },
+    Placeholder { span },
+    CountAll { span, window },
+    Tuple { span, exprs },
+    FunctionCall { span, distinct, name, args, params, window, lambda },
+    Case { span, operand, conditions, results, else_result },
+    Exists { span, not, subquery },
+    Subquery { span, modifier, subquery },
+    MapAccess { span, expr, accessor },
+    Array { span, exprs },
+    Map { span, kvs },
+    Interval { span, expr, unit },
+    DateAdd { span, unit, interval, date },
+    DateSub { span, unit, interval, date },
+    DateTrunc { span, unit, date },
+});
+canonical_enum!(SubqueryModifier { Any, All, Some });
+canonical_enum!(Literal {
+    UInt64(a0),
+    Float64(a0),
+    Decimal256 { value, precision, scale },
+    String(a0),
+    Boolean(a0),
+    CurrentTimestamp,
+    Null,
+});
+canonical_enum!(MapAccessor { Bracket { key }, Dot { key }, DotNumber { key }, Colon { key } });
+canonical_enum!(TypeName {
+    Boolean,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    Decimal { precision, scale },
+    Date,
+    Timestamp,
+    String,
+    Array(a0),
+    Map { key_type, val_type },
+    Bitmap,
+    Tuple { fields_name, fields_type },
+    Variant,
+    Nullable(a0),
+});
+canonical_enum!(TrimWhere {
+    Both,
+    Leading,
+    Trailing
+});
+canonical_enum!(Window { WindowReference(a0), WindowSpec(a0) });
+canonical_struct!(WindowDefinition { name, spec });
+canonical_struct!(WindowRef { window_name });
+canonical_struct!(WindowSpec {
+    existing_window_name,
+    partition_by,
+    order_by,
+    window_frame
+});
+canonical_struct!(WindowFrame {
+    units,
+    start_bound,
+    end_bound
+});
+canonical_enum!(WindowFrameUnits { Rows, Range });
+canonical_enum!(WindowFrameBound { CurrentRow, Preceding(a0), Following(a0) });
+canonical_struct!(Lambda { params, expr });
+canonical_enum!(BinaryOperator {
+    Plus,
+    Minus,
+    Multiply,
+    Div,
+    Divide,
+    IntDiv,
+    Modulo,
+    StringConcat,
+    Gt,
+    Lt,
+    Gte,
+    Lte,
+    Eq,
+    NotEq,
+    Caret,
+    And,
+    Or,
+    Xor,
+    Like,
+    NotLike,
+    Regexp,
+    RLike,
+    NotRegexp,
+    NotRLike,
+    SoundsLike,
+    BitwiseOr,
+    BitwiseAnd,
+    BitwiseXor,
+    BitwiseShiftLeft,
+    BitwiseShiftRight,
+    L2Distance,
+});
+canonical_enum!(UnaryOperator {
+    Plus,
+    Minus,
+    Not,
+    Factorial,
+    SquareRoot,
+    CubeRoot,
+    Abs,
+    BitwiseNot,
+});
+
+// Queries
+canonical_struct!(Query {
+    span,
+    with,
+    body,
+    order_by,
+    limit,
+    offset,
+    ignore_result
+});
+canonical_struct!(With {
+    span,
+    recursive,
+    ctes
+});
+canonical_struct!(CTE {
+    span,
+    alias,
+    materialized,
+    query
+});
+canonical_struct!(SetOperation {
+    span,
+    op,
+    all,
+    left,
+    right
+});
+canonical_struct!(SelectStmt {
+    span,
+    hints,
+    distinct,
+    select_list,
+    from,
+    selection,
+    group_by,
+    having,
+    window_list,
+});
+canonical_enum!(GroupBy { Normal(a0), All, GroupingSets(a0), Cube(a0), Rollup(a0) });
+canonical_enum!(SetExpr { Select(a0), Query(a0), SetOperation(a0), Values { span, values } });
+canonical_enum!(SetOperator {
+    Union,
+    Except,
+    Intersect
+});
+canonical_struct!(OrderByExpr {
+    expr,
+    asc,
+    nulls_first
+});
+canonical_enum!(SelectTarget { AliasedExpr { expr, alias }, QualifiedName { qualified, exclude } });
+canonical_enum!(Indirection { Identifier(a0), Star(a0) });
+canonical_enum!(TimeTravelPoint { Snapshot(a0), Timestamp(a0) });
+canonical_struct!(Pivot {
+    aggregate,
+    value_column,
+    values
+});
+canonical_struct!(Unpivot {
+    value_column,
+    column_name,
+    names
+});
+canonical_enum!(TableReference {
+    Table { span, catalog, database, table, alias, travel_point, pivot, unpivot },
+    TableFunction { span, name, params, named_params, alias },
+    Subquery { span, subquery, alias },
+    Join { span, join },
+    Location { span, location, options, alias },
+});
+canonical_struct!(TableAlias { name, columns });
+canonical_struct!(Join {
+    op,
+    condition,
+    left,
+    right
+});
+canonical_enum!(JoinOperator {
+    Inner,
+    LeftOuter,
+    RightOuter,
+    FullOuter,
+    LeftSemi,
+    LeftAnti,
+    RightSemi,
+    RightAnti,
+    CrossJoin,
+});
+canonical_enum!(JoinCondition { On(a0), Using(a0), Natural, None });
+
+// Statements
+canonical_struct!(CallStmt { name, args });
+canonical_struct!(ShowCatalogsStmt { limit });
+canonical_struct!(ShowCreateCatalogStmt { catalog });
+canonical_struct!(CreateCatalogStmt {
+    if_not_exists,
+    catalog_name,
+    catalog_type,
+    catalog_options
+});
+canonical_struct!(DropCatalogStmt { if_exists, catalog });
+canonical_struct!(ShowColumnsStmt {
+    catalog,
+    database,
+    table,
+    full,
+    limit
+});
+canonical_struct!(CopyStmt {
+    hints,
+    src,
+    dst,
+    files,
+    pattern,
+    file_format,
+    validation_mode,
+    size_limit,
+    max_files,
+    max_file_size,
+    split_size,
+    single,
+    purge,
+    force,
+    disable_variant_check,
+    on_error,
+    partition_by,
+    header,
+    include_query_id,
+});
+canonical_enum!(CopyUnit { Table { catalog, database, table, columns }, Location(a0), Query(a0) });
+canonical_struct!(UriLocation {
+    protocol,
+    name,
+    path,
+    part_prefix,
+    connection
+});
+canonical_enum!(FileLocation { Stage(a0), Uri(a0) });
+canonical_enum!(ValidationMode { ReturnRows(a0), ReturnErrors, ReturnAllErrors });
+canonical_enum!(OnErrorMode { Continue, SkipFile, SkipFileNum(a0), SkipFilePercent(a0), Abort });
+canonical_struct!(DataMaskArg { arg_name, arg_type });
+canonical_struct!(DataMaskPolicy {
+    args,
+    return_type,
+    body,
+    comment
+});
+canonical_struct!(CreateDatamaskPolicyStmt {
+    if_not_exists,
+    name,
+    policy
+});
+canonical_struct!(DropDatamaskPolicyStmt { if_exists, name });
+canonical_struct!(DescDatamaskPolicyStmt { name });
+canonical_struct!(ShowDatabasesStmt {
+    catalog,
+    full,
+    limit
+});
+canonical_struct!(ShowCreateDatabaseStmt { catalog, database });
+canonical_struct!(CreateDatabaseStmt {
+    if_not_exists,
+    catalog,
+    database,
+    engine,
+    options,
+    from_share,
+});
+canonical_struct!(DropDatabaseStmt {
+    if_exists,
+    catalog,
+    database
+});
+canonical_struct!(UndropDatabaseStmt { catalog, database });
+canonical_struct!(AlterDatabaseStmt {
+    if_exists,
+    catalog,
+    database,
+    action
+});
+canonical_enum!(AlterDatabaseAction { RenameDatabase { new_db } });
+canonical_enum!(DatabaseEngine { Default, Share });
+canonical_struct!(SQLProperty { name, value });
+canonical_enum!(ExplainKind {
+    Ast(a0),
+    Syntax(a0),
+    Memo(a0),
+    Graph,
+    Pipeline,
+    Fragments,
+    Raw,
+    Plan,
+    JOIN,
+    AnalyzePlan,
+});
+canonical_enum!(StageFileCompression {
+    Auto,
+    Gzip,
+    Bz2,
+    Brotli,
+    Zstd,
+    Deflate,
+    RawDeflate,
+    Xz,
+    None,
+});
+canonical_struct!(CsvFileFormatParams {
+    compression,
+    skip_header,
+    field_delimiter,
+    record_delimiter,
+    quote,
+    escape,
+    nan_display,
+    null_display,
+});
+canonical_struct!(TsvFileFormatParams {
+    compression,
+    skip_header,
+    field_delimiter,
+    record_delimiter,
+    quote,
+    escape,
+    nan_display,
+});
+canonical_struct!(NdJsonFileFormatParams { compression });
+canonical_struct!(JsonFileFormatParams { compression });
+canonical_struct!(ParquetFileFormatParams {});
+canonical_struct!(OrcFileFormatParams {});
+canonical_struct!(XmlFileFormatParams {
+    compression,
+    row_tag
+});
+canonical_enum!(FileFormatParams {
+    Csv(a0),
+    Tsv(a0),
+    NdJson(a0),
+    Json(a0),
+    Parquet(a0),
+    Orc(a0),
+    Xml(a0),
+});
+canonical_struct!(UntypedFileFormatParams {
+    compression,
+    skip_header,
+    field_delimiter,
+    record_delimiter,
+    quote,
+    escape,
+    nan_display,
+    null_display,
+    row_tag,
+});
+canonical_enum!(FileFormatOptions {
+    Named(a0),
+    Params(a0),
+    Untyped(a0),
+});
+canonical_struct!(Hint { hints_list });
+canonical_struct!(HintItem { name, expr });
+canonical_struct!(CreateIndexStmt {
+    index_type,
+    if_not_exists,
+    index_name,
+    query,
+    sync_creation
+});
+canonical_enum!(TableIndexType { Aggregating });
+canonical_struct!(DropIndexStmt { if_exists, index });
+canonical_struct!(RefreshIndexStmt { index, limit });
+canonical_struct!(InsertStmt {
+    hints,
+    catalog,
+    database,
+    table,
+    columns,
+    source,
+    overwrite
+});
+canonical_enum!(KillTarget { Query, Connection });
+canonical_struct!(MergeUpdateExpr {
+    catalog,
+    table,
+    name,
+    expr
+});
+canonical_enum!(MatchOperation { Update { update_list }, Delete });
+canonical_struct!(MatchedClause {
+    selection,
+    operation
+});
+canonical_struct!(InsertOperation { columns, values });
+canonical_struct!(UnmatchedClause {
+    selection,
+    insert_operation
+});
+canonical_enum!(MergeOption { Match(a0), Unmatch(a0) });
+canonical_struct!(MergeIntoStmt {
+    hints,
+    catalog,
+    database,
+    table_ident,
+    source,
+    alias_target,
+    join_expr,
+    merge_options,
+});
+canonical_struct!(CreateNetworkPolicyStmt {
+    if_not_exists,
+    name,
+    allowed_ip_list,
+    blocked_ip_list,
+    comment,
+});
+canonical_struct!(AlterNetworkPolicyStmt {
+    if_exists,
+    name,
+    allowed_ip_list,
+    blocked_ip_list,
+    comment,
+});
+canonical_struct!(DropNetworkPolicyStmt { if_exists, name });
+canonical_struct!(DescNetworkPolicyStmt { name });
+canonical_enum!(PresignAction { Download, Upload });
+canonical_enum!(PresignLocation { StageLocation(a0) });
+canonical_struct!(PresignStmt {
+    action,
+    location,
+    expire,
+    content_type
+});
+canonical_struct!(ReplaceStmt {
+    hints,
+    catalog,
+    database,
+    table,
+    on_conflict_columns,
+    columns,
+    source,
+});
+canonical_struct!(CreateShareEndpointStmt {
+    if_not_exists,
+    endpoint,
+    url,
+    tenant,
+    args,
+    comment
+});
+canonical_struct!(CreateShareStmt {
+    if_not_exists,
+    share,
+    comment
+});
+canonical_struct!(DropShareStmt { if_exists, share });
+canonical_struct!(GrantShareObjectStmt {
+    share,
+    object,
+    privilege
+});
+canonical_struct!(RevokeShareObjectStmt {
+    share,
+    object,
+    privilege
+});
+canonical_struct!(AlterShareTenantsStmt {
+    share,
+    if_exists,
+    tenants,
+    is_add
+});
+canonical_struct!(DescShareStmt { share });
+canonical_struct!(ShowSharesStmt {});
+canonical_struct!(ShowShareEndpointStmt {});
+canonical_struct!(DropShareEndpointStmt {
+    if_exists,
+    endpoint
+});
+canonical_struct!(ShowObjectGrantPrivilegesStmt { object });
+canonical_struct!(ShowGrantsOfShareStmt { share_name });
+canonical_struct!(SelectStageOptions {
+    files,
+    pattern,
+    file_format,
+    connection
+});
+canonical_enum!(ShowLimit { Like { pattern }, Where { selection } });
+canonical_struct!(CreateStageStmt {
+    if_not_exists,
+    stage_name,
+    location,
+    file_format_options,
+    on_error,
+    size_limit,
+    validation_mode,
+    comments,
+});
+canonical_enum!(Statement {
+    Query(a0),
+    Explain { kind, query },
+    ExplainAnalyze { query },
+    Copy(a0),
+    Call(a0),
+    ShowSettings { like },
+    ShowProcessList,
+    ShowMetrics,
+    ShowEngines,
+    ShowFunctions { limit },
+    ShowTableFunctions { limit },
+    ShowIndexes,
+    KillStmt { kill_target, object_id },
+    SetVariable { is_global, variable, value },
+    UnSetVariable(a0),
+    SetRole { is_default, role_name },
+    Insert(a0),
+    Replace(a0),
+    MergeInto(a0),
+    Delete { hints, table_reference, selection },
+    Update(a0),
+    ShowCatalogs(a0),
+    ShowCreateCatalog(a0),
+    CreateCatalog(a0),
+    DropCatalog(a0),
+    ShowDatabases(a0),
+    ShowCreateDatabase(a0),
+    CreateDatabase(a0),
+    DropDatabase(a0),
+    UndropDatabase(a0),
+    AlterDatabase(a0),
+    UseDatabase { database },
+    ShowTables(a0),
+    ShowCreateTable(a0),
+    DescribeTable(a0),
+    ShowTablesStatus(a0),
+    ShowDropTables(a0),
+    AttachTable(a0),
+    CreateTable(a0),
+    DropTable(a0),
+    UndropTable(a0),
+    AlterTable(a0),
+    RenameTable(a0),
+    TruncateTable(a0),
+    OptimizeTable(a0),
+    VacuumTable(a0),
+    VacuumDropTable(a0),
+    AnalyzeTable(a0),
+    ExistsTable(a0),
+    ShowColumns(a0),
+    CreateView(a0),
+    AlterView(a0),
+    DropView(a0),
+    CreateIndex(a0),
+    DropIndex(a0),
+    RefreshIndex(a0),
+    CreateVirtualColumn(a0),
+    AlterVirtualColumn(a0),
+    DropVirtualColumn(a0),
+    RefreshVirtualColumn(a0),
+    ShowUsers,
+    CreateUser(a0),
+    AlterUser(a0),
+    DropUser { if_exists, user },
+    ShowRoles,
+    CreateRole { if_not_exists, role_name },
+    DropRole { if_exists, role_name },
+    Grant(a0),
+    ShowGrants { principal },
+    Revoke(a0),
+    CreateUDF(a0),
+    DropUDF { if_exists, udf_name },
+    AlterUDF(a0),
+    CreateStage(a0),
+    ShowStages,
+    DropStage { if_exists, stage_name },
+    DescribeStage { stage_name },
+    RemoveStage { location, pattern },
+    ListStage { location, pattern },
+    CreateFileFormat { if_not_exists, name, file_format_options },
+    DropFileFormat { if_exists, name },
+    ShowFileFormats,
+    Presign(a0),
+    CreateShareEndpoint(a0),
+    ShowShareEndpoint(a0),
+    DropShareEndpoint(a0),
+    CreateShare(a0),
+    DropShare(a0),
+    GrantShareObject(a0),
+    RevokeShareObject(a0),
+    AlterShareTenants(a0),
+    DescShare(a0),
+    ShowShares(a0),
+    ShowObjectGrantPrivileges(a0),
+    ShowGrantsOfShare(a0),
+    CreateDatamaskPolicy(a0),
+    DropDatamaskPolicy(a0),
+    DescDatamaskPolicy(a0),
+    CreateNetworkPolicy(a0),
+    AlterNetworkPolicy(a0),
+    DropNetworkPolicy(a0),
+    DescNetworkPolicy(a0),
+    ShowNetworkPolicies,
+});
+canonical_struct!(ShowTablesStmt {
+    catalog,
+    database,
+    full,
+    limit,
+    with_history
+});
+canonical_struct!(ShowCreateTableStmt {
+    catalog,
+    database,
+    table
+});
+canonical_struct!(ShowTablesStatusStmt { database, limit });
+canonical_struct!(ShowDropTablesStmt { database });
+canonical_struct!(CreateTableStmt {
+    if_not_exists,
+    catalog,
+    database,
+    table,
+    source,
+    engine,
+    uri_location,
+    cluster_by,
+    table_options,
+    as_query,
+    transient,
+});
+canonical_struct!(AttachTableStmt {
+    catalog,
+    database,
+    table,
+    uri_location
+});
+canonical_enum!(CreateTableSource { Columns(a0), Like { catalog, database, table } });
+canonical_struct!(DescribeTableStmt {
+    catalog,
+    database,
+    table
+});
+canonical_struct!(DropTableStmt {
+    if_exists,
+    catalog,
+    database,
+    table,
+    all
+});
+canonical_struct!(UndropTableStmt {
+    catalog,
+    database,
+    table
+});
+canonical_struct!(AlterTableStmt {
+    if_exists,
+    table_reference,
+    action
+});
+canonical_enum!(AlterTableAction {
+    RenameTable { new_table },
+    AddColumn { column, option },
+    RenameColumn { old_column, new_column },
+    ModifyColumn { action },
+    DropColumn { column },
+    AlterTableClusterKey { cluster_by },
+    DropTableClusterKey,
+    ReclusterTable { is_final, selection, limit },
+    RevertTo { point },
+    SetOptions { set_options },
+});
+canonical_enum!(AddColumnOption { End, First, After(a0) });
+canonical_struct!(RenameTableStmt {
+    if_exists,
+    catalog,
+    database,
+    table,
+    new_catalog,
+    new_database,
+    new_table,
+});
+canonical_struct!(TruncateTableStmt {
+    catalog,
+    database,
+    table,
+    purge
+});
+canonical_struct!(VacuumTableStmt {
+    catalog,
+    database,
+    table,
+    option
+});
+canonical_struct!(VacuumDropTableStmt {
+    catalog,
+    database,
+    option
+});
+canonical_struct!(OptimizeTableStmt {
+    catalog,
+    database,
+    table,
+    action,
+    limit
+});
+canonical_struct!(AnalyzeTableStmt {
+    catalog,
+    database,
+    table
+});
+canonical_struct!(ExistsTableStmt {
+    catalog,
+    database,
+    table
+});
+canonical_enum!(Engine {
+    Null,
+    Memory,
+    Fuse,
+    View,
+    Random
+});
+canonical_enum!(CompactTarget { Block, Segment });
+canonical_struct!(VacuumTableOption {
+    retain_hours,
+    dry_run
+});
+canonical_enum!(OptimizeTableAction { All, Purge { before }, Compact { target } });
+canonical_enum!(ColumnExpr { Default(a0), Virtual(a0), Stored(a0) });
+canonical_enum!(NullableConstraint { Null, NotNull });
+canonical_struct!(ColumnDefinition {
+    name,
+    data_type,
+    expr,
+    comment,
+    nullable_constraint
+});
+canonical_enum!(ModifyColumnAction {
+    SetMaskingPolicy(a0, a1),
+    UnsetMaskingPolicy(a0),
+    SetDataType(a0),
+    ConvertStoredComputedColumn(a0),
+});
+canonical_enum!(UDFDefinition {
+    LambdaUDF { parameters, definition },
+    UDFServer { arg_types, return_type, address, handler, language },
+});
+canonical_struct!(CreateUDFStmt {
+    if_not_exists,
+    udf_name,
+    description,
+    definition
+});
+canonical_struct!(AlterUDFStmt {
+    udf_name,
+    description,
+    definition
+});
+canonical_struct!(UnSetStmt { source });
+canonical_enum!(UnSetSource { Var { variable }, Vars { variables } });
+canonical_struct!(UpdateStmt {
+    hints,
+    table,
+    update_list,
+    selection
+});
+canonical_struct!(UpdateExpr { name, expr });
+canonical_struct!(CreateUserStmt {
+    if_not_exists,
+    user,
+    auth_option,
+    user_options
+});
+canonical_struct!(AuthOption {
+    auth_type,
+    password
+});
+canonical_struct!(AlterUserStmt {
+    user,
+    auth_option,
+    user_options
+});
+canonical_struct!(GrantStmt { source, principal });
+canonical_struct!(RevokeStmt { source, principal });
+canonical_enum!(AccountMgrSource { Role { role }, Privs { privileges, level }, ALL { level } });
+canonical_enum!(AccountMgrLevel { Global, Database(a0), Table(a0, a1) });
+canonical_enum!(UserOptionItem {
+    TenantSetting(a0),
+    DefaultRole(a0),
+    SetNetworkPolicy(a0),
+    UnsetNetworkPolicy,
+});
+canonical_struct!(CreateViewStmt {
+    if_not_exists,
+    catalog,
+    database,
+    view,
+    columns,
+    query
+});
+canonical_struct!(AlterViewStmt {
+    catalog,
+    database,
+    view,
+    columns,
+    query
+});
+canonical_struct!(DropViewStmt {
+    if_exists,
+    catalog,
+    database,
+    view
+});
+canonical_struct!(CreateVirtualColumnStmt {
+    catalog,
+    database,
+    table,
+    virtual_columns
+});
+canonical_struct!(AlterVirtualColumnStmt {
+    catalog,
+    database,
+    table,
+    virtual_columns
+});
+canonical_struct!(DropVirtualColumnStmt {
+    catalog,
+    database,
+    table
+});
+canonical_struct!(RefreshVirtualColumnStmt {
+    catalog,
+    database,
+    table
+});
+
+// Meta
+canonical_enum!(CatalogType {
+    Default,
+    Hive,
+    Iceberg
+});
+canonical_struct!(ShareNameIdent { tenant, share_name });
+canonical_enum!(ShareGrantObjectName { Database(a0), Table(a0, a1) });
+canonical_enum!(ShareGrantObjectPrivilege {
+    Usage,
+    ReferenceUsage,
+    Select
+});
+canonical_enum!(AuthType {
+    NoPassword,
+    Sha256Password,
+    DoubleSha1Password,
+    JWT
+});
+canonical_enum!(UserPrivilegeType {
+    Usage,
+    Select,
+    Insert,
+    Update,
+    Delete,
+    Create,
+    Drop,
+    Alter,
+    Super,
+    CreateUser,
+    CreateRole,
+    Grant,
+    CreateStage,
+    DropRole,
+    DropUser,
+    CreateDataMask,
+    Ownership,
+    Set,
+});
+canonical_enum!(PrincipalIdentity { User(a0), Role(a0) });
+canonical_struct!(UserIdentity { username, hostname });
@@ -0,0 +1,519 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use enumflags2::BitFlags;
+
+use super::referenced_objects;
+use super::ObjectName;
+use crate::ast::write_comma_separated_list;
+use crate::ast::*;
+use crate::meta::UserPrivilegeType;
+
+/// The category of a statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StatementCategory {
+    /// `SELECT` and the other queries.
+    Query,
+    /// Statements that modify the data of tables, e.g. `INSERT` or `COPY`.
+    Dml,
+    /// Statements that create, alter or drop objects, e.g. `CREATE TABLE`.
+    Ddl,
+    /// Statements that manage users, roles, grants and shares.
+    Dcl,
+    /// Transaction control statements. The parser has none of them yet.
+    Tcl,
+    /// `SHOW`, `DESC`, `SET`, `USE`, `KILL` and the other session or admin statements.
+    Utility,
+}
+
+impl Display for StatementCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatementCategory::Query => write!(f, "QUERY"),
+            StatementCategory::Dml => write!(f, "DML"),
+            StatementCategory::Ddl => write!(f, "DDL"),
+            StatementCategory::Dcl => write!(f, "DCL"),
+            StatementCategory::Tcl => write!(f, "TCL"),
+            StatementCategory::Utility => write!(f, "UTILITY"),
+        }
+    }
+}
+
+/// The classification of a statement, see [`Statement::classify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatementClass {
+    pub category: StatementCategory,
+    /// The statement doesn't change any data, metadata or global setting.
+    /// Changing the settings of the current session is read-only.
+    pub read_only: bool,
+    /// Running the statement again after a failure or a lost response has the
+    /// same effect as running it once.
+    pub retry_safe: bool,
+}
+
+/// The privileges that are needed on an object, see [`Statement::required_privileges`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredPrivilege {
+    pub level: AccountMgrLevel,
+    pub privileges: BitFlags<UserPrivilegeType>,
+}
+
+impl Display for RequiredPrivilege {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_comma_separated_list(f, self.privileges.iter())?;
+        write!(f, " ON {}", self.level)
+    }
+}
+
+impl Statement {
+    /// Classify the statement. `EXPLAIN` is a read-only utility statement, while
+    /// `EXPLAIN ANALYZE` runs the statement and is classified like it.
+    pub fn classify(&self) -> StatementClass {
+        use StatementCategory::*;
+
+        // The category, whether the statement is read-only and whether it is
+        // idempotent. Read-only statements are always retry safe.
+        let (category, read_only, idempotent) = match self {
+            Statement::Query(_) => (Query, true, true),
+            Statement::Explain { .. } => (Utility, true, true),
+            Statement::ExplainAnalyze { query } => return query.classify(),
+
+            Statement::Copy(copy) => {
+                // The files that are loaded into a table are remembered unless `FORCE`
+                // is set, while unloading always writes new files.
+                let idempotent = matches!(copy.dst, CopyUnit::Table { .. }) && !copy.force;
+                (Dml, false, idempotent)
+            }
+            Statement::Call(_) => (Utility, false, false),
+
+            Statement::ShowSettings { .. }
+            | Statement::ShowProcessList
+            | Statement::ShowMetrics
+            | Statement::ShowEngines
+            | Statement::ShowFunctions { .. }
+            | Statement::ShowTableFunctions { .. }
+            | Statement::ShowIndexes => (Utility, true, true),
+            Statement::KillStmt { .. } => (Utility, false, false),
+            Statement::SetVariable { is_global, .. } => (Utility, !is_global, true),
+            Statement::UnSetVariable(_) => (Utility, true, true),
+            Statement::SetRole { .. } => (Utility, true, true),
+
+            Statement::Insert(insert) => (Dml, false, insert.overwrite),
+            Statement::Replace(_) => (Dml, false, true),
+            Statement::MergeInto(_) => (Dml, false, false),
+            Statement::Delete { .. } => (Dml, false, true),
+            Statement::Update(_) => (Dml, false, false),
+
+            Statement::ShowCatalogs(_) | Statement::ShowCreateCatalog(_) => (Utility, true, true),
+            Statement::CreateCatalog(stmt) => (Ddl, false, stmt.if_not_exists),
+            Statement::DropCatalog(stmt) => (Ddl, false, stmt.if_exists),
+
+            Statement::ShowDatabases(_) | Statement::ShowCreateDatabase(_) => (Utility, true, true),
+            Statement::CreateDatabase(stmt) => (Ddl, false, stmt.if_not_exists),
+            Statement::DropDatabase(stmt) => (Ddl, false, stmt.if_exists),
+            Statement::UndropDatabase(_) => (Ddl, false, false),
+            Statement::AlterDatabase(_) => (Ddl, false, false),
+            Statement::UseDatabase { .. } => (Utility, true, true),
+
+            Statement::ShowTables(_)
+            | Statement::ShowCreateTable(_)
+            | Statement::DescribeTable(_)
+            | Statement::ShowTablesStatus(_)
+            | Statement::ShowDropTables(_)
+            | Statement::ExistsTable(_)
+            | Statement::ShowColumns(_) => (Utility, true, true),
+            Statement::AttachTable(_) => (Ddl, false, false),
+            Statement::CreateTable(stmt) => (Ddl, false, stmt.if_not_exists),
+            Statement::DropTable(stmt) => (Ddl, false, stmt.if_exists),
+            Statement::UndropTable(_) => (Ddl, false, false),
+            Statement::AlterTable(stmt) => {
+                let idempotent = matches!(
+                    stmt.action,
+                    AlterTableAction::AlterTableClusterKey { .. }
+                        | AlterTableAction::ReclusterTable { .. }
+                        | AlterTableAction::RevertTo { .. }
+                        | AlterTableAction::SetOptions { .. }
+                );
+                (Ddl, false, idempotent)
+            }
+            Statement::RenameTable(_) => (Ddl, false, false),
+            Statement::TruncateTable(_)
+            | Statement::OptimizeTable(_)
+            | Statement::VacuumTable(_)
+            | Statement::VacuumDropTable(_)
+            | Statement::AnalyzeTable(_) => (Ddl, false, true),
+
+            Statement::CreateView(stmt) => (Ddl, false, stmt.if_not_exists),
+            Statement::AlterView(_) => (Ddl, false, true),
+            Statement::DropView(stmt) => (Ddl, false, stmt.if_exists),
+
+            Statement::CreateIndex(stmt) => (Ddl, false, stmt.if_not_exists),
+            Statement::DropIndex(stmt) => (Ddl, false, stmt.if_exists),
+            Statement::RefreshIndex(_) => (Ddl, false, true),
+
+            Statement::CreateVirtualColumn(_) => (Ddl, false, false),
+            Statement::AlterVirtualColumn(_) => (Ddl, false, true),
+            Statement::DropVirtualColumn(_) => (Ddl, false, false),
+            Statement::RefreshVirtualColumn(_) => (Ddl, false, true),
+
+            Statement::ShowUsers | Statement::ShowRoles | Statement::ShowGrants { .. } => {
+                (Utility, true, true)
+            }
+            Statement::CreateUser(stmt) => (Dcl, false, stmt.if_not_exists),
+            Statement::AlterUser(_) => (Dcl, false, true),
+            Statement::DropUser { if_exists, .. } => (Dcl, false, *if_exists),
+            Statement::CreateRole { if_not_exists, .. } => (Dcl, false, *if_not_exists),
+            Statement::DropRole { if_exists, .. } => (Dcl, false, *if_exists),
+            Statement::Grant(_) | Statement::Revoke(_) => (Dcl, false, true),
+
+            Statement::CreateUDF(stmt) => (Ddl, false, stmt.if_not_exists),
+            Statement::DropUDF { if_exists, .. } => (Ddl, false, *if_exists),
+            Statement::AlterUDF(_) => (Ddl, false, true),
+
+            Statement::CreateStage(stmt) => (Ddl, false, stmt.if_not_exists),
+            Statement::DropStage { if_exists, .. } => (Ddl, false, *if_exists),
+            Statement::ShowStages
+            | Statement::DescribeStage { .. }
+            | Statement::ListStage { .. } => (Utility, true, true),
+            Statement::RemoveStage { .. } => (Utility, false, true),
+
+            Statement::CreateFileFormat { if_not_exists, .. } => (Ddl, false, *if_not_exists),
+            Statement::DropFileFormat { if_exists, .. } => (Ddl, false, *if_exists),
+            Statement::ShowFileFormats => (Utility, true, true),
+            Statement::Presign(stmt) => match stmt.action {
+                PresignAction::Download => (Utility, true, true),
+                PresignAction::Upload => (Utility, false, true),
+            },
+
+            Statement::CreateShareEndpoint(stmt) => (Ddl, false, stmt.if_not_exists),
+            Statement::DropShareEndpoint(stmt) => (Ddl, false, stmt.if_exists),
+            Statement::CreateShare(stmt) => (Ddl, false, stmt.if_not_exists),
+            Statement::DropShare(stmt) => (Ddl, false, stmt.if_exists),
+            Statement::GrantShareObject(_)
+            | Statement::RevokeShareObject(_)
+            | Statement::AlterShareTenants(_) => (Dcl, false, true),
+            Statement::ShowShareEndpoint(_)
+            | Statement::DescShare(_)
+            | Statement::ShowShares(_)
+            | Statement::ShowObjectGrantPrivileges(_)
+            | Statement::ShowGrantsOfShare(_) => (Utility, true, true),
+
+            Statement::CreateDatamaskPolicy(stmt) => (Ddl, false, stmt.if_not_exists),
+            Statement::DropDatamaskPolicy(stmt) => (Ddl, false, stmt.if_exists),
+            Statement::DescDatamaskPolicy(_) => (Utility, true, true),
+
+            Statement::CreateNetworkPolicy(stmt) => (Ddl, false, stmt.if_not_exists),
+            Statement::AlterNetworkPolicy(_) => (Ddl, false, true),
+            Statement::DropNetworkPolicy(stmt) => (Ddl, false, stmt.if_exists),
+            Statement::DescNetworkPolicy(_) | Statement::ShowNetworkPolicies => {
+                (Utility, true, true)
+            }
+        };
+
+        StatementClass {
+            category,
+            read_only,
+            retry_safe: read_only || idempotent,
+        }
+    }
+
+    /// The privileges that are needed to run the statement, grouped by the object
+    /// they are needed on. `SELECT` is needed on every table and view that is read,
+    /// e.g. the source tables of `INSERT INTO ... SELECT`.
+    ///
+    /// Names are not resolved, so a table without a database is on the current
+    /// database. Statements that only need a session, like `SHOW` or `USE`, need
+    /// no privilege.
+    pub fn required_privileges(&self) -> Vec<RequiredPrivilege> {
+        use UserPrivilegeType::*;
+
+        let mut required = RequiredPrivileges::default();
+        match self {
+            Statement::Query(_) => {}
+            Statement::Explain { query, .. } | Statement::ExplainAnalyze { query } => {
+                return query.required_privileges();
+            }
+
+            Statement::Copy(copy) => {
+                if let CopyUnit::Table {
+                    database, table, ..
+                } = &copy.dst
+                {
+                    required.add(table_level(database, table), Insert);
+                }
+            }
+            Statement::Call(_) => required.add(AccountMgrLevel::Global, Super),
+
+            Statement::ShowSettings { .. }
+            | Statement::ShowProcessList
+            | Statement::ShowMetrics
+            | Statement::ShowEngines
+            | Statement::ShowFunctions { .. }
+            | Statement::ShowTableFunctions { .. }
+            | Statement::ShowIndexes => {}
+            Statement::KillStmt { .. } => required.add(AccountMgrLevel::Global, Super),
+            Statement::SetVariable { is_global, .. } => {
+                if *is_global {
+                    required.add(AccountMgrLevel::Global, Super);
+                }
+            }
+            Statement::UnSetVariable(_) | Statement::SetRole { .. } => {}
+
+            Statement::Insert(insert) => {
+                required.add(table_level(&insert.database, &insert.table), Insert)
+            }
+            Statement::Replace(replace) => required.add(
+                table_level(&replace.database, &replace.table),
+                Insert | Delete,
+            ),
+            Statement::MergeInto(merge) => {
+                let mut privileges = BitFlags::empty();
+                for option in &merge.merge_options {
+                    privileges |= match option {
+                        MergeOption::Match(clause) => match clause.operation {
+                            MatchOperation::Update { .. } => Update,
+                            MatchOperation::Delete => Delete,
+                        },
+                        MergeOption::Unmatch(_) => Insert,
+                    };
+                }
+                required.add(table_level(&merge.database, &merge.table_ident), privileges);
+            }
+            Statement::Delete {
+                table_reference, ..
+            } => {
+                if let Some(level) = table_reference_level(table_reference) {
+                    required.add(level, Delete);
+                }
+            }
+            Statement::Update(update) => {
+                if let Some(level) = table_reference_level(&update.table) {
+                    required.add(level, Update);
+                }
+            }
+
+            Statement::ShowCatalogs(_) | Statement::ShowCreateCatalog(_) => {}
+            Statement::CreateCatalog(_) => required.add(AccountMgrLevel::Global, Create),
+            Statement::DropCatalog(_) => required.add(AccountMgrLevel::Global, Drop),
+
+            Statement::ShowDatabases(_) | Statement::ShowCreateDatabase(_) => {}
+            Statement::CreateDatabase(_) => required.add(AccountMgrLevel::Global, Create),
+            Statement::DropDatabase(stmt) => required.add(database_level(&stmt.database), Drop),
+            Statement::UndropDatabase(stmt) => required.add(database_level(&stmt.database), Drop),
+            Statement::AlterDatabase(stmt) => required.add(database_level(&stmt.database), Alter),
+            Statement::UseDatabase { .. } => {}
+
+            Statement::ShowTables(_)
+            | Statement::ShowCreateTable(_)
+            | Statement::DescribeTable(_)
+            | Statement::ShowTablesStatus(_)
+            | Statement::ShowDropTables(_)
+            | Statement::ExistsTable(_)
+            | Statement::ShowColumns(_) => {}
+            Statement::AttachTable(stmt) => required.add(
+                AccountMgrLevel::Database(ident_name(&stmt.database)),
+                Create,
+            ),
+            Statement::CreateTable(stmt) => required.add(
+                AccountMgrLevel::Database(ident_name(&stmt.database)),
+                Create,
+            ),
+            Statement::DropTable(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.table), Drop)
+            }
+            Statement::UndropTable(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.table), Drop)
+            }
+            Statement::AlterTable(stmt) => {
+                if let Some(level) = table_reference_level(&stmt.table_reference) {
+                    required.add(level, Alter);
+                }
+            }
+            Statement::RenameTable(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.table), Alter);
+                required.add(
+                    AccountMgrLevel::Database(ident_name(&stmt.new_database)),
+                    Create,
+                );
+            }
+            Statement::TruncateTable(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.table), Delete)
+            }
+            Statement::OptimizeTable(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.table), Alter)
+            }
+            Statement::AnalyzeTable(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.table), Alter)
+            }
+            Statement::VacuumTable(_) | Statement::VacuumDropTable(_) => {
+                required.add(AccountMgrLevel::Global, Super)
+            }
+
+            Statement::CreateView(stmt) => required.add(
+                AccountMgrLevel::Database(ident_name(&stmt.database)),
+                Create,
+            ),
+            Statement::AlterView(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.view), Alter)
+            }
+            Statement::DropView(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.view), Drop)
+            }
+
+            Statement::CreateIndex(_) => required.add(AccountMgrLevel::Global, Create),
+            Statement::DropIndex(_) => required.add(AccountMgrLevel::Global, Drop),
+            Statement::RefreshIndex(_) => required.add(AccountMgrLevel::Global, Alter),
+
+            Statement::CreateVirtualColumn(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.table), Alter)
+            }
+            Statement::AlterVirtualColumn(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.table), Alter)
+            }
+            Statement::DropVirtualColumn(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.table), Alter)
+            }
+            Statement::RefreshVirtualColumn(stmt) => {
+                required.add(table_level(&stmt.database, &stmt.table), Alter)
+            }
+
+            Statement::ShowUsers | Statement::ShowRoles | Statement::ShowGrants { .. } => {}
+            Statement::CreateUser(_) => required.add(AccountMgrLevel::Global, CreateUser),
+            Statement::AlterUser(stmt) => {
+                // Users can change their own password without any privilege.
+                if stmt.user.is_some() {
+                    required.add(AccountMgrLevel::Global, CreateUser);
+                }
+            }
+            Statement::DropUser { .. } => required.add(AccountMgrLevel::Global, DropUser),
+            Statement::CreateRole { .. } => required.add(AccountMgrLevel::Global, CreateRole),
+            Statement::DropRole { .. } => required.add(AccountMgrLevel::Global, DropRole),
+            Statement::Grant(GrantStmt { source, .. })
+            | Statement::Revoke(RevokeStmt { source, .. }) => {
+                let level = match source {
+                    AccountMgrSource::Role { .. } => AccountMgrLevel::Global,
+                    AccountMgrSource::Privs { level, .. } | AccountMgrSource::ALL { level } => {
+                        level.clone()
+                    }
+                };
+                required.add(level, Grant);
+            }
+
+            Statement::CreateUDF(_) | Statement::DropUDF { .. } | Statement::AlterUDF(_) => {
+                required.add(AccountMgrLevel::Global, Super)
+            }
+
+            Statement::CreateStage(_) => required.add(AccountMgrLevel::Global, CreateStage),
+            Statement::DropStage { .. } => required.add(AccountMgrLevel::Global, Drop),
+            Statement::RemoveStage { .. } => required.add(AccountMgrLevel::Global, Super),
+            Statement::ShowStages
+            | Statement::DescribeStage { .. }
+            | Statement::ListStage { .. } => {}
+
+            Statement::CreateFileFormat { .. } | Statement::DropFileFormat { .. } => {
+                required.add(AccountMgrLevel::Global, Super)
+            }
+            Statement::ShowFileFormats | Statement::Presign(_) => {}
+
+            Statement::CreateShareEndpoint(_)
+            | Statement::DropShareEndpoint(_)
+            | Statement::CreateShare(_)
+            | Statement::DropShare(_)
+            | Statement::GrantShareObject(_)
+            | Statement::RevokeShareObject(_)
+            | Statement::AlterShareTenants(_) => required.add(AccountMgrLevel::Global, Super),
+            Statement::ShowShareEndpoint(_)
+            | Statement::DescShare(_)
+            | Statement::ShowShares(_)
+            | Statement::ShowObjectGrantPrivileges(_)
+            | Statement::ShowGrantsOfShare(_) => {}
+
+            Statement::CreateDatamaskPolicy(_) | Statement::DropDatamaskPolicy(_) => {
+                required.add(AccountMgrLevel::Global, CreateDataMask)
+            }
+            Statement::DescDatamaskPolicy(_) => {}
+
+            Statement::CreateNetworkPolicy(_)
+            | Statement::AlterNetworkPolicy(_)
+            | Statement::DropNetworkPolicy(_) => required.add(AccountMgrLevel::Global, Super),
+            Statement::DescNetworkPolicy(_) | Statement::ShowNetworkPolicies => {}
+        }
+
+        let objects = referenced_objects(self);
+        for name in objects.reads() {
+            match name {
+                ObjectName::Table {
+                    database, table, ..
+                } => required.add(
+                    AccountMgrLevel::Table(database.clone(), table.clone()),
+                    Select,
+                ),
+                ObjectName::View { database, view, .. } => required.add(
+                    AccountMgrLevel::Table(database.clone(), view.clone()),
+                    Select,
+                ),
+                _ => {}
+            }
+        }
+        required.privileges
+    }
+}
+
+#[derive(Default)]
+struct RequiredPrivileges {
+    privileges: Vec<RequiredPrivilege>,
+}
+
+impl RequiredPrivileges {
+    fn add(&mut self, level: AccountMgrLevel, privileges: impl Into<BitFlags<UserPrivilegeType>>) {
+        let privileges = privileges.into();
+        if privileges.is_empty() {
+            return;
+        }
+        match self
+            .privileges
+            .iter_mut()
+            .find(|required| required.level == level)
+        {
+            Some(required) => required.privileges |= privileges,
+            None => self
+                .privileges
+                .push(RequiredPrivilege { level, privileges }),
+        }
+    }
+}
+
+fn ident_name(ident: &Option<Identifier>) -> Option<String> {
+    ident.as_ref().map(|ident| ident.normalized_name())
+}
+
+fn database_level(database: &Identifier) -> AccountMgrLevel {
+    AccountMgrLevel::Database(Some(database.normalized_name()))
+}
+
+fn table_level(database: &Option<Identifier>, table: &Identifier) -> AccountMgrLevel {
+    AccountMgrLevel::Table(ident_name(database), table.normalized_name())
+}
+
+fn table_reference_level(table_reference: &TableReference) -> Option<AccountMgrLevel> {
+    match table_reference {
+        TableReference::Table {
+            database, table, ..
+        } => Some(table_level(database, table)),
+        _ => None,
+    }
+}
@@ -0,0 +1,266 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::ControlFlow;
+
+use super::Visitor;
+use crate::ast::*;
+
+/// Whether the children of a node are visited after it is entered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Children {
+    Visit,
+    Skip,
+}
+
+/// The result of [`Visitor::enter_node`]: `Break` stops the traversal, `Continue`
+/// visits or skips the children of the node.
+pub type VisitResult = ControlFlow<(), Children>;
+
+/// A node that is entered and left by the walk functions.
+#[derive(Debug, Clone, Copy)]
+pub enum AstNode<'a> {
+    Statement(&'a Statement),
+    Query(&'a Query),
+    Cte(&'a CTE),
+    SetExpr(&'a SetExpr),
+    SelectStmt(&'a SelectStmt),
+    SelectTarget(&'a SelectTarget),
+    TableReference(&'a TableReference),
+    Join(&'a Join),
+    Expr(&'a Expr),
+}
+
+/// Walk a statement with `visitor`, and return `Break` if the traversal is stopped.
+///
+/// Every node is entered before its children (pre-order) and left after them
+/// (post-order). A node whose children are skipped is still left. Once a node
+/// breaks, no more nodes are entered or left. Unlike the default methods of
+/// [`Visitor`], the expressions and queries in DML and DDL statements are visited
+/// too, e.g. the values of `UPDATE` or the query of `CREATE VIEW`.
+pub fn traverse_statement<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    stmt: &'ast Statement,
+) -> ControlFlow<()> {
+    let mut traversal = Traversal::new(visitor);
+    traversal.visit_statement(stmt);
+    traversal.result
+}
+
+pub fn traverse_query<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    query: &'ast Query,
+) -> ControlFlow<()> {
+    let mut traversal = Traversal::new(visitor);
+    traversal.visit_query(query);
+    traversal.result
+}
+
+pub fn traverse_expr<'ast, V: Visitor<'ast>>(visitor: &mut V, expr: &'ast Expr) -> ControlFlow<()> {
+    let mut traversal = Traversal::new(visitor);
+    traversal.visit_expr(expr);
+    traversal.result
+}
+
+/// Forwards the nodes to the inner visitor until it breaks, and visits the
+/// expressions and queries of DML and DDL statements.
+struct Traversal<'v, V> {
+    visitor: &'v mut V,
+    result: ControlFlow<()>,
+}
+
+impl<'v, V> Traversal<'v, V> {
+    fn new(visitor: &'v mut V) -> Self {
+        Self {
+            visitor,
+            result: ControlFlow::Continue(()),
+        }
+    }
+}
+
+impl<'v, 'ast, V: Visitor<'ast>> Traversal<'v, V> {
+    fn visit_column_definition_exprs(&mut self, columns: &'ast [ColumnDefinition]) {
+        for column in columns {
+            match &column.expr {
+                Some(ColumnExpr::Default(expr))
+                | Some(ColumnExpr::Virtual(expr))
+                | Some(ColumnExpr::Stored(expr)) => self.visit_expr(expr),
+                None => {}
+            }
+        }
+    }
+}
+
+impl<'v, 'ast, V: Visitor<'ast>> Visitor<'ast> for Traversal<'v, V> {
+    fn enter_node(&mut self, node: AstNode<'ast>) -> VisitResult {
+        if self.result.is_continue() {
+            let result = self.visitor.enter_node(node);
+            if result.is_break() {
+                self.result = ControlFlow::Break(());
+            }
+            return result;
+        }
+        ControlFlow::Break(())
+    }
+
+    fn leave_node(&mut self, node: AstNode<'ast>) {
+        if self.result.is_continue() {
+            self.visitor.leave_node(node);
+        }
+    }
+
+    fn visit_explain(&mut self, _kind: &'ast ExplainKind, stmt: &'ast Statement) {
+        self.visit_statement(stmt);
+    }
+
+    fn visit_copy(&mut self, copy: &'ast CopyStmt) {
+        for unit in [&copy.src, &copy.dst] {
+            if let CopyUnit::Query(query) = unit {
+                self.visit_query(query);
+            }
+        }
+        if let Some(partition_by) = &copy.partition_by {
+            self.visit_expr(partition_by);
+        }
+    }
+
+    fn visit_set_variable(
+        &mut self,
+        _is_global: bool,
+        _variable: &'ast Identifier,
+        value: &'ast Expr,
+    ) {
+        self.visit_expr(value);
+    }
+
+    fn visit_insert(&mut self, insert: &'ast InsertStmt) {
+        if let InsertSource::Select { query } = &insert.source {
+            self.visit_query(query);
+        }
+    }
+
+    fn visit_replace(&mut self, replace: &'ast ReplaceStmt) {
+        if let InsertSource::Select { query } = &replace.source {
+            self.visit_query(query);
+        }
+    }
+
+    fn visit_merge_into(&mut self, merge_into: &'ast MergeIntoStmt) {
+        if let MergeSource::Select { query } = &merge_into.source {
+            self.visit_query(query);
+        }
+        self.visit_expr(&merge_into.join_expr);
+        for option in &merge_into.merge_options {
+            match option {
+                MergeOption::Match(clause) => {
+                    if let Some(selection) = &clause.selection {
+                        self.visit_expr(selection);
+                    }
+                    if let MatchOperation::Update { update_list } = &clause.operation {
+                        for update in update_list {
+                            self.visit_expr(&update.expr);
+                        }
+                    }
+                }
+                MergeOption::Unmatch(clause) => {
+                    if let Some(selection) = &clause.selection {
+                        self.visit_expr(selection);
+                    }
+                    for value in &clause.insert_operation.values {
+                        self.visit_expr(value);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_delete(
+        &mut self,
+        table_reference: &'ast TableReference,
+        selection: &'ast Option<Expr>,
+    ) {
+        self.visit_table_reference(table_reference);
+        if let Some(selection) = selection {
+            self.visit_expr(selection);
+        }
+    }
+
+    fn visit_update(&mut self, update: &'ast UpdateStmt) {
+        self.visit_table_reference(&update.table);
+        for update_expr in &update.update_list {
+            self.visit_expr(&update_expr.expr);
+        }
+        if let Some(selection) = &update.selection {
+            self.visit_expr(selection);
+        }
+    }
+
+    fn visit_create_table(&mut self, stmt: &'ast CreateTableStmt) {
+        if let Some(CreateTableSource::Columns(columns)) = &stmt.source {
+            self.visit_column_definition_exprs(columns);
+        }
+        for expr in &stmt.cluster_by {
+            self.visit_expr(expr);
+        }
+        if let Some(query) = &stmt.as_query {
+            self.visit_query(query);
+        }
+    }
+
+    fn visit_alter_table(&mut self, stmt: &'ast AlterTableStmt) {
+        self.visit_table_reference(&stmt.table_reference);
+        match &stmt.action {
+            AlterTableAction::AddColumn { column, .. } => {
+                self.visit_column_definition_exprs(std::slice::from_ref(column));
+            }
+            AlterTableAction::ModifyColumn {
+                action: ModifyColumnAction::SetDataType(columns),
+            } => self.visit_column_definition_exprs(columns),
+            AlterTableAction::AlterTableClusterKey { cluster_by } => {
+                for expr in cluster_by {
+                    self.visit_expr(expr);
+                }
+            }
+            AlterTableAction::ReclusterTable {
+                selection: Some(selection),
+                ..
+            } => self.visit_expr(selection),
+            _ => {}
+        }
+    }
+
+    fn visit_create_view(&mut self, stmt: &'ast CreateViewStmt) {
+        self.visit_query(&stmt.query);
+    }
+
+    fn visit_alter_view(&mut self, stmt: &'ast AlterViewStmt) {
+        self.visit_query(&stmt.query);
+    }
+
+    fn visit_create_index(&mut self, stmt: &'ast CreateIndexStmt) {
+        self.visit_query(&stmt.query);
+    }
+
+    fn visit_create_virtual_column(&mut self, stmt: &'ast CreateVirtualColumnStmt) {
+        for expr in &stmt.virtual_columns {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn visit_alter_virtual_column(&mut self, stmt: &'ast AlterVirtualColumnStmt) {
+        for expr in &stmt.virtual_columns {
+            self.visit_expr(expr);
+        }
+    }
+}
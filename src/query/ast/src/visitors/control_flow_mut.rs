@@ -0,0 +1,242 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::ControlFlow;
+
+use super::VisitResult;
+use super::VisitorMut;
+use crate::ast::*;
+
+/// The mutable counterpart of [`AstNode`](super::AstNode), the node can be
+/// rewritten when it is entered or left.
+#[derive(Debug)]
+pub enum AstNodeMut<'a> {
+    Statement(&'a mut Statement),
+    Query(&'a mut Query),
+    Cte(&'a mut CTE),
+    SetExpr(&'a mut SetExpr),
+    SelectStmt(&'a mut SelectStmt),
+    SelectTarget(&'a mut SelectTarget),
+    TableReference(&'a mut TableReference),
+    Join(&'a mut Join),
+    Expr(&'a mut Expr),
+}
+
+/// The mutable counterpart of [`traverse_statement`](super::traverse_statement).
+pub fn traverse_statement_mut<V: VisitorMut>(
+    visitor: &mut V,
+    stmt: &mut Statement,
+) -> ControlFlow<()> {
+    let mut traversal = TraversalMut::new(visitor);
+    traversal.visit_statement(stmt);
+    traversal.result
+}
+
+pub fn traverse_query_mut<V: VisitorMut>(visitor: &mut V, query: &mut Query) -> ControlFlow<()> {
+    let mut traversal = TraversalMut::new(visitor);
+    traversal.visit_query(query);
+    traversal.result
+}
+
+pub fn traverse_expr_mut<V: VisitorMut>(visitor: &mut V, expr: &mut Expr) -> ControlFlow<()> {
+    let mut traversal = TraversalMut::new(visitor);
+    traversal.visit_expr(expr);
+    traversal.result
+}
+
+/// Forwards the nodes to the inner visitor until it breaks, and visits the
+/// expressions and queries of DML and DDL statements.
+struct TraversalMut<'v, V> {
+    visitor: &'v mut V,
+    result: ControlFlow<()>,
+}
+
+impl<'v, V: VisitorMut> TraversalMut<'v, V> {
+    fn new(visitor: &'v mut V) -> Self {
+        Self {
+            visitor,
+            result: ControlFlow::Continue(()),
+        }
+    }
+
+    fn visit_column_definition_exprs(&mut self, columns: &mut [ColumnDefinition]) {
+        for column in columns {
+            match &mut column.expr {
+                Some(ColumnExpr::Default(expr))
+                | Some(ColumnExpr::Virtual(expr))
+                | Some(ColumnExpr::Stored(expr)) => self.visit_expr(expr),
+                None => {}
+            }
+        }
+    }
+}
+
+impl<'v, V: VisitorMut> VisitorMut for TraversalMut<'v, V> {
+    fn enter_node(&mut self, node: AstNodeMut<'_>) -> VisitResult {
+        if self.result.is_continue() {
+            let result = self.visitor.enter_node(node);
+            if result.is_break() {
+                self.result = ControlFlow::Break(());
+            }
+            return result;
+        }
+        ControlFlow::Break(())
+    }
+
+    fn leave_node(&mut self, node: AstNodeMut<'_>) {
+        if self.result.is_continue() {
+            self.visitor.leave_node(node);
+        }
+    }
+
+    fn visit_explain(&mut self, _kind: &mut ExplainKind, stmt: &mut Statement) {
+        self.visit_statement(stmt);
+    }
+
+    fn visit_copy(&mut self, copy: &mut CopyStmt) {
+        for unit in [&mut copy.src, &mut copy.dst] {
+            if let CopyUnit::Query(query) = unit {
+                self.visit_query(query);
+            }
+        }
+        if let Some(partition_by) = &mut copy.partition_by {
+            self.visit_expr(partition_by);
+        }
+    }
+
+    fn visit_set_variable(
+        &mut self,
+        _is_global: bool,
+        _variable: &mut Identifier,
+        value: &mut Box<Expr>,
+    ) {
+        self.visit_expr(value);
+    }
+
+    fn visit_insert(&mut self, insert: &mut InsertStmt) {
+        if let InsertSource::Select { query } = &mut insert.source {
+            self.visit_query(query);
+        }
+    }
+
+    fn visit_replace(&mut self, replace: &mut ReplaceStmt) {
+        if let InsertSource::Select { query } = &mut replace.source {
+            self.visit_query(query);
+        }
+    }
+
+    fn visit_merge_into(&mut self, merge_into: &mut MergeIntoStmt) {
+        if let MergeSource::Select { query } = &mut merge_into.source {
+            self.visit_query(query);
+        }
+        self.visit_expr(&mut merge_into.join_expr);
+        for option in &mut merge_into.merge_options {
+            match option {
+                MergeOption::Match(clause) => {
+                    if let Some(selection) = &mut clause.selection {
+                        self.visit_expr(selection);
+                    }
+                    if let MatchOperation::Update { update_list } = &mut clause.operation {
+                        for update in update_list {
+                            self.visit_expr(&mut update.expr);
+                        }
+                    }
+                }
+                MergeOption::Unmatch(clause) => {
+                    if let Some(selection) = &mut clause.selection {
+                        self.visit_expr(selection);
+                    }
+                    for value in &mut clause.insert_operation.values {
+                        self.visit_expr(value);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_delete(&mut self, table_reference: &mut TableReference, selection: &mut Option<Expr>) {
+        self.visit_table_reference(table_reference);
+        if let Some(selection) = selection {
+            self.visit_expr(selection);
+        }
+    }
+
+    fn visit_update(&mut self, update: &mut UpdateStmt) {
+        self.visit_table_reference(&mut update.table);
+        for update_expr in &mut update.update_list {
+            self.visit_expr(&mut update_expr.expr);
+        }
+        if let Some(selection) = &mut update.selection {
+            self.visit_expr(selection);
+        }
+    }
+
+    fn visit_create_table(&mut self, stmt: &mut CreateTableStmt) {
+        if let Some(CreateTableSource::Columns(columns)) = &mut stmt.source {
+            self.visit_column_definition_exprs(columns);
+        }
+        for expr in &mut stmt.cluster_by {
+            self.visit_expr(expr);
+        }
+        if let Some(query) = &mut stmt.as_query {
+            self.visit_query(query);
+        }
+    }
+
+    fn visit_alter_table(&mut self, stmt: &mut AlterTableStmt) {
+        self.visit_table_reference(&mut stmt.table_reference);
+        match &mut stmt.action {
+            AlterTableAction::AddColumn { column, .. } => {
+                self.visit_column_definition_exprs(std::slice::from_mut(column));
+            }
+            AlterTableAction::ModifyColumn {
+                action: ModifyColumnAction::SetDataType(columns),
+            } => self.visit_column_definition_exprs(columns),
+            AlterTableAction::AlterTableClusterKey { cluster_by } => {
+                for expr in cluster_by {
+                    self.visit_expr(expr);
+                }
+            }
+            AlterTableAction::ReclusterTable {
+                selection: Some(selection),
+                ..
+            } => self.visit_expr(selection),
+            _ => {}
+        }
+    }
+
+    fn visit_create_view(&mut self, stmt: &mut CreateViewStmt) {
+        self.visit_query(&mut stmt.query);
+    }
+
+    fn visit_alter_view(&mut self, stmt: &mut AlterViewStmt) {
+        self.visit_query(&mut stmt.query);
+    }
+
+    fn visit_create_index(&mut self, stmt: &mut CreateIndexStmt) {
+        self.visit_query(&mut stmt.query);
+    }
+
+    fn visit_create_virtual_column(&mut self, stmt: &mut CreateVirtualColumnStmt) {
+        for expr in &mut stmt.virtual_columns {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn visit_alter_virtual_column(&mut self, stmt: &mut AlterVirtualColumnStmt) {
+        for expr in &mut stmt.virtual_columns {
+            self.visit_expr(expr);
+        }
+    }
+}
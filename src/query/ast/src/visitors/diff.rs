@@ -0,0 +1,812 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use common_exception::Span;
+use common_io::escape_string_with_quote;
+
+use super::walk_expr_children_mut;
+use super::VisitorMut;
+use crate::ast::*;
+
+/// Which differences [`diff_with_options`] should not report. Spans are never
+/// compared.
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// Compare unquoted identifiers case-insensitively.
+    pub ignore_identifier_case: bool,
+    /// Ignore differences that only change how the SQL is written, like quoting an
+    /// identifier or `CAST(a AS INT)` versus `a::INT`.
+    pub ignore_formatting: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A node that differs between two statements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AstChange {
+    pub kind: ChangeKind,
+    /// Where the node lives, like `query.body.selection` or `columns[2]`.
+    pub path: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+    pub old_span: Span,
+    pub new_span: Span,
+}
+
+impl Display for AstChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let old = self.old.as_deref().unwrap_or_default();
+        let new = self.new.as_deref().unwrap_or_default();
+        match self.kind {
+            ChangeKind::Added => write!(f, "{}: added {new}", self.path),
+            ChangeKind::Removed => write!(f, "{}: removed {old}", self.path),
+            ChangeKind::Changed => write!(f, "{}: changed from {old} to {new}", self.path),
+        }
+    }
+}
+
+/// Compare two statements node by node, see [`diff_with_options`].
+pub fn diff(old: &Statement, new: &Statement) -> Vec<AstChange> {
+    diff_with_options(old, new, &DiffOptions::default())
+}
+
+/// Compare two statements node by node and report the added, removed and changed
+/// nodes in the order they appear.
+///
+/// Queries, views, UDFs and `CREATE TABLE` are compared clause by clause, with
+/// list items like select targets or column definitions matched up, so that adding
+/// a column is reported as a single addition. Other statements, and nodes of
+/// different kinds, are reported as a whole.
+pub fn diff_with_options(
+    old: &Statement,
+    new: &Statement,
+    options: &DiffOptions,
+) -> Vec<AstChange> {
+    let mut differ = Differ {
+        canonicalizer: Canonicalizer {
+            options: options.clone(),
+        },
+        changes: vec![],
+    };
+    differ.statement(old, new);
+    differ.changes
+}
+
+struct Differ {
+    canonicalizer: Canonicalizer,
+    changes: Vec<AstChange>,
+}
+
+impl Differ {
+    fn statement(&mut self, old: &Statement, new: &Statement) {
+        match (old, new) {
+            (Statement::Query(old), Statement::Query(new)) => self.query("query", old, new),
+            (
+                Statement::Explain { kind, query: old },
+                Statement::Explain {
+                    kind: new_kind,
+                    query: new,
+                },
+            ) if kind == new_kind => {
+                self.statement(old, new);
+                for change in &mut self.changes {
+                    change.path = format!("explain.{}", change.path);
+                }
+            }
+            (Statement::CreateView(old), Statement::CreateView(new)) => {
+                self.flag("if_not_exists", old.if_not_exists, new.if_not_exists);
+                self.option("catalog", &old.catalog, &new.catalog);
+                self.option("database", &old.database, &new.database);
+                self.node("view", &old.view, &new.view);
+                self.list("columns", &old.columns, &new.columns);
+                self.query("query", &old.query, &new.query);
+            }
+            (Statement::AlterView(old), Statement::AlterView(new)) => {
+                self.option("catalog", &old.catalog, &new.catalog);
+                self.option("database", &old.database, &new.database);
+                self.node("view", &old.view, &new.view);
+                self.list("columns", &old.columns, &new.columns);
+                self.query("query", &old.query, &new.query);
+            }
+            (Statement::CreateUDF(old), Statement::CreateUDF(new)) => {
+                self.flag("if_not_exists", old.if_not_exists, new.if_not_exists);
+                self.udf(
+                    (&old.udf_name, &old.description, &old.definition),
+                    (&new.udf_name, &new.description, &new.definition),
+                );
+            }
+            (Statement::AlterUDF(old), Statement::AlterUDF(new)) => {
+                self.udf(
+                    (&old.udf_name, &old.description, &old.definition),
+                    (&new.udf_name, &new.description, &new.definition),
+                );
+            }
+            (Statement::CreateTable(old), Statement::CreateTable(new)) => {
+                self.create_table(old, new)
+            }
+            _ => self.node("statement", old, new),
+        }
+    }
+
+    fn udf(
+        &mut self,
+        old: (&Identifier, &Option<String>, &UDFDefinition),
+        new: (&Identifier, &Option<String>, &UDFDefinition),
+    ) {
+        self.node("udf_name", old.0, new.0);
+        self.text(
+            "description",
+            old.1.as_deref().map(quoted),
+            new.1.as_deref().map(quoted),
+        );
+        match (old.2, new.2) {
+            (
+                UDFDefinition::LambdaUDF {
+                    parameters: old_parameters,
+                    definition: old_definition,
+                },
+                UDFDefinition::LambdaUDF {
+                    parameters: new_parameters,
+                    definition: new_definition,
+                },
+            ) => {
+                self.list("parameters", old_parameters, new_parameters);
+                self.node("definition", old_definition, new_definition);
+            }
+            (old, new) => self.text("definition", Some(old.to_string()), Some(new.to_string())),
+        }
+    }
+
+    fn create_table(&mut self, old: &CreateTableStmt, new: &CreateTableStmt) {
+        self.flag("if_not_exists", old.if_not_exists, new.if_not_exists);
+        self.flag("transient", old.transient, new.transient);
+        self.option("catalog", &old.catalog, &new.catalog);
+        self.option("database", &old.database, &new.database);
+        self.node("table", &old.table, &new.table);
+        match (&old.source, &new.source) {
+            (Some(CreateTableSource::Columns(old)), Some(CreateTableSource::Columns(new))) => {
+                self.columns(old, new)
+            }
+            (old, new) => self.text(
+                "source",
+                old.as_ref().map(ToString::to_string),
+                new.as_ref().map(ToString::to_string),
+            ),
+        }
+        self.text(
+            "engine",
+            old.engine.as_ref().map(ToString::to_string),
+            new.engine.as_ref().map(ToString::to_string),
+        );
+        self.text(
+            "uri_location",
+            old.uri_location.as_ref().map(ToString::to_string),
+            new.uri_location.as_ref().map(ToString::to_string),
+        );
+        self.list("cluster_by", &old.cluster_by, &new.cluster_by);
+        // The table options have no spans of their own, so they point at the table name.
+        for key in old.table_options.keys().chain(
+            new.table_options
+                .keys()
+                .filter(|key| !old.table_options.contains_key(*key)),
+        ) {
+            self.spanned_text(
+                &format!("table_options.{key}"),
+                old.table_options
+                    .get(key)
+                    .map(|v| (quoted(v), old.table.span)),
+                new.table_options
+                    .get(key)
+                    .map(|v| (quoted(v), new.table.span)),
+            );
+        }
+        match (&old.as_query, &new.as_query) {
+            (Some(old), Some(new)) => self.query("as_query", old, new),
+            (old, new) => self.option("as_query", old, new),
+        }
+    }
+
+    /// Columns are matched by name rather than by position.
+    fn columns(&mut self, old: &[ColumnDefinition], new: &[ColumnDefinition]) {
+        let name = |this: &Self, column: &ColumnDefinition| this.key(&column.name);
+        for (i, old_column) in old.iter().enumerate() {
+            let path = format!("columns[{i}]");
+            match new.iter().find(|c| name(self, c) == name(self, old_column)) {
+                Some(new_column) => self.node(&path, old_column, new_column),
+                None => self.removed(&path, old_column),
+            }
+        }
+        for (i, new_column) in new.iter().enumerate() {
+            if !old.iter().any(|c| name(self, c) == name(self, new_column)) {
+                self.added(&format!("columns[{i}]"), new_column);
+            }
+        }
+    }
+
+    fn query(&mut self, path: &str, old: &Query, new: &Query) {
+        match (&old.with, &new.with) {
+            (Some(old_with), Some(new_with)) => {
+                self.flag(
+                    &format!("{path}.with.recursive"),
+                    old_with.recursive,
+                    new_with.recursive,
+                );
+                self.list(&format!("{path}.with.ctes"), &old_with.ctes, &new_with.ctes);
+            }
+            (old, new) => self.option(&format!("{path}.with"), old, new),
+        }
+        self.set_expr(&format!("{path}.body"), &old.body, &new.body);
+        self.list(&format!("{path}.order_by"), &old.order_by, &new.order_by);
+        self.list(&format!("{path}.limit"), &old.limit, &new.limit);
+        self.option(&format!("{path}.offset"), &old.offset, &new.offset);
+    }
+
+    fn set_expr(&mut self, path: &str, old: &SetExpr, new: &SetExpr) {
+        match (old, new) {
+            (SetExpr::Select(old), SetExpr::Select(new)) => self.select(path, old, new),
+            (SetExpr::Query(old), SetExpr::Query(new)) => self.query(path, old, new),
+            (SetExpr::SetOperation(old), SetExpr::SetOperation(new))
+                if old.op == new.op && old.all == new.all =>
+            {
+                self.set_expr(&format!("{path}.left"), &old.left, &new.left);
+                self.set_expr(&format!("{path}.right"), &old.right, &new.right);
+            }
+            (old, new) => self.node(path, old, new),
+        }
+    }
+
+    fn select(&mut self, path: &str, old: &SelectStmt, new: &SelectStmt) {
+        self.option(&format!("{path}.hints"), &old.hints, &new.hints);
+        self.flag(&format!("{path}.distinct"), old.distinct, new.distinct);
+        self.list(
+            &format!("{path}.select_list"),
+            &old.select_list,
+            &new.select_list,
+        );
+        self.list(&format!("{path}.from"), &old.from, &new.from);
+        self.option(&format!("{path}.selection"), &old.selection, &new.selection);
+        match (&old.group_by, &new.group_by) {
+            (Some(GroupBy::Normal(old)), Some(GroupBy::Normal(new))) => {
+                self.list(&format!("{path}.group_by"), old, new)
+            }
+            (old, new) => self.spanned_text(
+                &format!("{path}.group_by"),
+                old.as_ref()
+                    .map(|g| (self.group_by_key(g), group_by_span(g))),
+                new.as_ref()
+                    .map(|g| (self.group_by_key(g), group_by_span(g))),
+            ),
+        }
+        self.option(&format!("{path}.having"), &old.having, &new.having);
+        self.spanned_text(
+            &format!("{path}.window_list"),
+            old.window_list
+                .as_ref()
+                .map(|w| (self.window_list_key(w), window_list_span(w))),
+            new.window_list
+                .as_ref()
+                .map(|w| (self.window_list_key(w), window_list_span(w))),
+        );
+    }
+
+    fn flag(&mut self, path: &str, old: bool, new: bool) {
+        if old != new {
+            self.push(
+                ChangeKind::Changed,
+                path,
+                Some(old.to_string()),
+                Some(new.to_string()),
+                None,
+                None,
+            );
+        }
+    }
+
+    /// Compare nodes that only have a textual form.
+    fn text(&mut self, path: &str, old: Option<String>, new: Option<String>) {
+        self.spanned_text(path, old.map(|old| (old, None)), new.map(|new| (new, None)))
+    }
+
+    /// Same as [`Differ::text`], but for nodes that know where they are.
+    fn spanned_text(
+        &mut self,
+        path: &str,
+        old: Option<(String, Span)>,
+        new: Option<(String, Span)>,
+    ) {
+        match (old, new) {
+            (Some((old, old_span)), Some((new, new_span))) if old != new => self.push(
+                ChangeKind::Changed,
+                path,
+                Some(old),
+                Some(new),
+                old_span,
+                new_span,
+            ),
+            (Some((old, old_span)), None) => {
+                self.push(ChangeKind::Removed, path, Some(old), None, old_span, None)
+            }
+            (None, Some((new, new_span))) => {
+                self.push(ChangeKind::Added, path, None, Some(new), None, new_span)
+            }
+            _ => (),
+        }
+    }
+
+    fn node<T: Node>(&mut self, path: &str, old: &T, new: &T) {
+        if self.key(old) != self.key(new) {
+            self.push(
+                ChangeKind::Changed,
+                path,
+                Some(old.to_string()),
+                Some(new.to_string()),
+                old.span(),
+                new.span(),
+            );
+        }
+    }
+
+    fn option<T: Node>(&mut self, path: &str, old: &Option<T>, new: &Option<T>) {
+        match (old, new) {
+            (Some(old), Some(new)) => self.node(path, old, new),
+            (Some(old), None) => self.removed(path, old),
+            (None, Some(new)) => self.added(path, new),
+            (None, None) => (),
+        }
+    }
+
+    /// Match up the items of two lists with their longest common subsequence, the
+    /// leftovers between two matches are reported as changed pairwise, then as added
+    /// or removed.
+    fn list<T: Node>(&mut self, path: &str, old: &[T], new: &[T]) {
+        let old_keys = old.iter().map(|n| self.key(n)).collect::<Vec<_>>();
+        let new_keys = new.iter().map(|n| self.key(n)).collect::<Vec<_>>();
+
+        // lcs[i][j] is the length of the LCS of old[i..] and new[j..]
+        let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                lcs[i][j] = if old_keys[i] == new_keys[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        let (mut removed, mut added) = (vec![], vec![]);
+        loop {
+            let matched = i < old.len() && j < new.len() && old_keys[i] == new_keys[j];
+            if matched || (i == old.len() && j == new.len()) {
+                self.unmatched(path, old, new, &removed, &added);
+                removed.clear();
+                added.clear();
+                if !matched {
+                    break;
+                }
+                i += 1;
+                j += 1;
+            } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+                removed.push(i);
+                i += 1;
+            } else {
+                added.push(j);
+                j += 1;
+            }
+        }
+    }
+
+    fn unmatched<T: Node>(
+        &mut self,
+        path: &str,
+        old: &[T],
+        new: &[T],
+        removed: &[usize],
+        added: &[usize],
+    ) {
+        let pairs = removed.len().min(added.len());
+        for (i, j) in removed.iter().zip(added) {
+            self.push(
+                ChangeKind::Changed,
+                &format!("{path}[{j}]"),
+                Some(old[*i].to_string()),
+                Some(new[*j].to_string()),
+                old[*i].span(),
+                new[*j].span(),
+            );
+        }
+        for i in &removed[pairs..] {
+            self.removed(&format!("{path}[{i}]"), &old[*i]);
+        }
+        for j in &added[pairs..] {
+            self.added(&format!("{path}[{j}]"), &new[*j]);
+        }
+    }
+
+    fn added<T: Node>(&mut self, path: &str, new: &T) {
+        self.push(
+            ChangeKind::Added,
+            path,
+            None,
+            Some(new.to_string()),
+            None,
+            new.span(),
+        );
+    }
+
+    fn removed<T: Node>(&mut self, path: &str, old: &T) {
+        self.push(
+            ChangeKind::Removed,
+            path,
+            Some(old.to_string()),
+            None,
+            old.span(),
+            None,
+        );
+    }
+
+    fn push(
+        &mut self,
+        kind: ChangeKind,
+        path: &str,
+        old: Option<String>,
+        new: Option<String>,
+        old_span: Span,
+        new_span: Span,
+    ) {
+        self.changes.push(AstChange {
+            kind,
+            path: path.to_string(),
+            old,
+            new,
+            old_span,
+            new_span,
+        });
+    }
+
+    /// The text a node is compared by.
+    fn key<T: Node>(&self, node: &T) -> String {
+        let options = &self.canonicalizer.options;
+        if !options.ignore_identifier_case && !options.ignore_formatting {
+            return node.to_string();
+        }
+        let mut node = node.clone();
+        node.canonicalize(&mut self.canonicalizer.clone());
+        node.to_string()
+    }
+
+    fn group_by_key(&self, group_by: &GroupBy) -> String {
+        let exprs = match group_by {
+            GroupBy::Normal(exprs) | GroupBy::Cube(exprs) | GroupBy::Rollup(exprs) => {
+                exprs.iter().collect()
+            }
+            GroupBy::GroupingSets(sets) => sets.iter().flatten().collect(),
+            GroupBy::All => vec![],
+        };
+        let keys = exprs.iter().map(|e| self.key(*e)).collect::<Vec<_>>();
+        match group_by {
+            GroupBy::Normal(_) => keys.join(", "),
+            GroupBy::All => "ALL".to_string(),
+            GroupBy::GroupingSets(_) => group_by_sets_key(group_by, &keys),
+            GroupBy::Cube(_) => format!("CUBE ({})", keys.join(", ")),
+            GroupBy::Rollup(_) => format!("ROLLUP ({})", keys.join(", ")),
+        }
+    }
+
+    fn window_list_key(&self, windows: &[WindowDefinition]) -> String {
+        let mut windows = windows.to_vec();
+        for window in &mut windows {
+            let mut canonicalizer = self.canonicalizer.clone();
+            canonicalizer.visit_identifier(&mut window.name);
+        }
+        windows
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn quoted(text: &str) -> String {
+    format!("'{}'", escape_string_with_quote(text, Some('\'')))
+}
+
+fn merge_span(first: Span, last: Span) -> Span {
+    match (first, last) {
+        (Some(first), Some(last)) => Some((first.start..last.end).into()),
+        (first, last) => first.or(last),
+    }
+}
+
+/// From the first to the last item, `GROUP BY ALL` has none.
+fn group_by_span(group_by: &GroupBy) -> Span {
+    let exprs = match group_by {
+        GroupBy::Normal(exprs) | GroupBy::Cube(exprs) | GroupBy::Rollup(exprs) => {
+            exprs.iter().collect()
+        }
+        GroupBy::GroupingSets(sets) => sets.iter().flatten().collect::<Vec<_>>(),
+        GroupBy::All => vec![],
+    };
+    let first = exprs.first().and_then(|expr| expr.span());
+    let last = exprs.last().and_then(|expr| expr.span());
+    merge_span(first, last)
+}
+
+/// From the name of the first window to the last expression of the last one.
+fn window_list_span(windows: &[WindowDefinition]) -> Span {
+    let (Some(first), Some(last)) = (windows.first(), windows.last()) else {
+        return None;
+    };
+    let last_span = last
+        .spec
+        .order_by
+        .last()
+        .map(|order_by| order_by.expr.span())
+        .or_else(|| last.spec.partition_by.last().map(|expr| expr.span()))
+        .unwrap_or(last.name.span);
+    merge_span(first.name.span, last_span)
+}
+
+fn group_by_sets_key(group_by: &GroupBy, keys: &[String]) -> String {
+    let GroupBy::GroupingSets(sets) = group_by else {
+        unreachable!()
+    };
+    let mut keys = keys.iter();
+    let sets = sets
+        .iter()
+        .map(|set| {
+            let set = keys.by_ref().take(set.len()).cloned().collect::<Vec<_>>();
+            format!("({})", set.join(", "))
+        })
+        .collect::<Vec<_>>();
+    format!("GROUPING SETS ({})", sets.join(", "))
+}
+
+/// Rewrites a copy of a node so that the differences the [`DiffOptions`] ignore
+/// disappear from its textual form.
+#[derive(Clone)]
+struct Canonicalizer {
+    options: DiffOptions,
+}
+
+impl VisitorMut for Canonicalizer {
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        if self.options.ignore_formatting {
+            if let Expr::Cast { pg_style, .. } = expr {
+                *pg_style = false;
+            }
+        }
+        walk_expr_children_mut(self, expr);
+    }
+
+    fn visit_select_stmt(&mut self, stmt: &mut SelectStmt) {
+        let SelectStmt {
+            select_list,
+            from,
+            selection,
+            group_by,
+            having,
+            ..
+        } = stmt;
+
+        for target in select_list.iter_mut() {
+            self.visit_select_target(target);
+        }
+        for table_ref in from.iter_mut() {
+            self.visit_table_reference(table_ref);
+        }
+        if let Some(selection) = selection {
+            self.visit_expr(selection);
+        }
+        match group_by {
+            Some(GroupBy::Normal(exprs) | GroupBy::Cube(exprs) | GroupBy::Rollup(exprs)) => {
+                exprs.iter_mut().for_each(|expr| self.visit_expr(expr));
+            }
+            Some(GroupBy::GroupingSets(sets)) => {
+                sets.iter_mut()
+                    .flatten()
+                    .for_each(|expr| self.visit_expr(expr));
+            }
+            Some(GroupBy::All) | None => {}
+        }
+        if let Some(having) = having {
+            self.visit_expr(having);
+        }
+    }
+
+    fn visit_order_by(&mut self, order_by: &mut OrderByExpr) {
+        self.visit_expr(&mut order_by.expr);
+    }
+
+    fn visit_identifier(&mut self, ident: &mut Identifier) {
+        if self.options.ignore_identifier_case && ident.quote.is_none() {
+            ident.name = ident.name.to_lowercase();
+        }
+        if self.options.ignore_formatting {
+            ident.quote = None;
+        }
+    }
+}
+
+/// A node that can be compared and reported by [`Differ`].
+trait Node: Display + Clone {
+    fn span(&self) -> Span;
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer);
+}
+
+impl<T: Node> Node for Box<T> {
+    fn span(&self) -> Span {
+        self.as_ref().span()
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        self.as_mut().canonicalize(canonicalizer)
+    }
+}
+
+impl Node for Statement {
+    fn span(&self) -> Span {
+        None
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        canonicalizer.visit_statement(self);
+    }
+}
+
+impl Node for Query {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        canonicalizer.visit_query(self);
+    }
+}
+
+impl Node for SetExpr {
+    fn span(&self) -> Span {
+        match self {
+            SetExpr::Select(select) => select.span,
+            SetExpr::Query(query) => query.span,
+            SetExpr::SetOperation(op) => op.span,
+            SetExpr::Values { span, .. } => *span,
+        }
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        canonicalizer.visit_set_expr(self);
+    }
+}
+
+impl Node for With {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        canonicalizer.visit_with(self);
+    }
+}
+
+impl Node for CTE {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        canonicalizer.visit_identifier(&mut self.alias.name);
+        for column in &mut self.alias.columns {
+            canonicalizer.visit_identifier(column);
+        }
+        canonicalizer.visit_query(&mut self.query);
+    }
+}
+
+impl Node for Expr {
+    fn span(&self) -> Span {
+        Expr::span(self)
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        canonicalizer.visit_expr(self);
+    }
+}
+
+impl Node for Identifier {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        canonicalizer.visit_identifier(self);
+    }
+}
+
+impl Node for SelectTarget {
+    fn span(&self) -> Span {
+        match self {
+            SelectTarget::AliasedExpr { expr, .. } => expr.span(),
+            SelectTarget::QualifiedName { qualified, .. } => match qualified.first() {
+                Some(Indirection::Identifier(ident)) => ident.span,
+                Some(Indirection::Star(span)) => *span,
+                None => None,
+            },
+        }
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        canonicalizer.visit_select_target(self);
+    }
+}
+
+impl Node for TableReference {
+    fn span(&self) -> Span {
+        match self {
+            TableReference::Table { span, .. }
+            | TableReference::TableFunction { span, .. }
+            | TableReference::Subquery { span, .. }
+            | TableReference::Join { span, .. }
+            | TableReference::Location { span, .. } => *span,
+        }
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        canonicalizer.visit_table_reference(self);
+    }
+}
+
+impl Node for OrderByExpr {
+    fn span(&self) -> Span {
+        self.expr.span()
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        canonicalizer.visit_order_by(self);
+    }
+}
+
+impl Node for Hint {
+    fn span(&self) -> Span {
+        None
+    }
+
+    fn canonicalize(&mut self, _canonicalizer: &mut Canonicalizer) {}
+}
+
+impl Node for ColumnDefinition {
+    fn span(&self) -> Span {
+        self.name.span
+    }
+
+    fn canonicalize(&mut self, canonicalizer: &mut Canonicalizer) {
+        canonicalizer.visit_identifier(&mut self.name);
+        match &mut self.expr {
+            Some(ColumnExpr::Default(expr))
+            | Some(ColumnExpr::Virtual(expr))
+            | Some(ColumnExpr::Stored(expr)) => canonicalizer.visit_expr(expr),
+            None => (),
+        }
+    }
+}
@@ -0,0 +1,381 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use common_exception::Span;
+
+use super::canonical::Canonical;
+use super::walk_expr_children_mut;
+use super::walk_statement_mut;
+use super::VisitorMut;
+use crate::ast::*;
+
+/// The shape of a statement with the values stripped, see [`fingerprint`].
+///
+/// The digests are FNV-1a hashes of the canonical form of the normalized
+/// statement, see [`canonical_form`]. Both are fixed and don't depend on how a
+/// statement is displayed, so a digest only changes if the AST does.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fingerprint {
+    pub normalized_sql: String,
+    pub digest64: u64,
+    pub digest128: u128,
+}
+
+impl Display for Fingerprint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:032x}", self.digest128)
+    }
+}
+
+/// Normalize the statement and compute its [`Fingerprint`], so that statements
+/// only differing in values, hints or identifier case share the same fingerprint.
+pub fn fingerprint(stmt: &Statement) -> Fingerprint {
+    let mut stmt = stmt.clone();
+    normalize_statement(&mut stmt);
+    let canonical = canonical_form(&stmt);
+    Fingerprint {
+        normalized_sql: stmt.to_string(),
+        digest64: fnv1a_64(canonical.as_bytes()),
+        digest128: fnv1a_128(canonical.as_bytes()),
+    }
+}
+
+/// Rewrite the statement into its normalized form in place:
+///
+/// - Literals are replaced with placeholders, positional `ORDER BY` and `GROUP BY`
+///   items are kept.
+/// - `IN` lists of constants are collapsed into a single placeholder. The rows of
+///   `INSERT ... VALUES` are left in the source, so they are not part of it either.
+/// - Unquoted identifiers are lowercased.
+/// - Hints are removed.
+///
+/// Comments are already dropped by the tokenizer.
+pub fn normalize_statement(stmt: &mut Statement) {
+    Normalizer.visit_statement(stmt);
+}
+
+struct Normalizer;
+
+impl VisitorMut for Normalizer {
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Literal {
+                lit: Literal::CurrentTimestamp,
+                ..
+            } => {}
+            Expr::Literal { span, .. } => {
+                *expr = Expr::Placeholder { span: *span };
+            }
+            Expr::Map { span, kvs }
+                if kvs
+                    .iter()
+                    .all(|(k, v)| is_constant_literal(k) && is_constant_literal(v)) =>
+            {
+                *expr = Expr::Placeholder { span: *span };
+            }
+            // Fold the sign into the placeholder, so that `-1` and `1` look the same.
+            Expr::UnaryOp {
+                span,
+                op: UnaryOperator::Minus | UnaryOperator::Plus,
+                expr: operand,
+            } if matches!(**operand, Expr::Literal { .. }) => {
+                *expr = Expr::Placeholder { span: *span };
+            }
+            _ => {
+                walk_expr_children_mut(self, expr);
+                if let Expr::InList { list, .. } = expr {
+                    if !list.is_empty() && list.iter().all(is_constant) {
+                        let span = merge_span(list[0].span(), list[list.len() - 1].span());
+                        *list = vec![Expr::Placeholder { span }];
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_identifier(&mut self, ident: &mut Identifier) {
+        if ident.quote.is_none() {
+            ident.name = ident.name.to_lowercase();
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement) {
+        if let Statement::Delete { hints, .. } = stmt {
+            *hints = None;
+        }
+        walk_statement_mut(self, stmt);
+    }
+
+    fn visit_explain(&mut self, _kind: &mut ExplainKind, stmt: &mut Statement) {
+        self.visit_statement(stmt);
+    }
+
+    fn visit_frame_bound(&mut self, bound: &mut WindowFrameBound) {
+        if let WindowFrameBound::Preceding(Some(expr)) | WindowFrameBound::Following(Some(expr)) =
+            bound
+        {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn visit_select_stmt(&mut self, stmt: &mut SelectStmt) {
+        let SelectStmt {
+            hints,
+            select_list,
+            from,
+            selection,
+            group_by,
+            having,
+            window_list,
+            ..
+        } = stmt;
+
+        *hints = None;
+        for target in select_list.iter_mut() {
+            self.visit_select_target(target);
+        }
+        for table_ref in from.iter_mut() {
+            self.visit_table_reference(table_ref);
+        }
+        if let Some(selection) = selection {
+            self.visit_expr(selection);
+        }
+        match group_by {
+            Some(GroupBy::Normal(exprs) | GroupBy::Cube(exprs) | GroupBy::Rollup(exprs)) => {
+                self.visit_group_items(exprs);
+            }
+            Some(GroupBy::GroupingSets(sets)) => {
+                for set in sets {
+                    self.visit_group_items(set);
+                }
+            }
+            Some(GroupBy::All) | None => {}
+        }
+        if let Some(having) = having {
+            self.visit_expr(having);
+        }
+        for window in window_list.iter_mut().flatten() {
+            self.visit_identifier(&mut window.name);
+            for expr in window.spec.partition_by.iter_mut() {
+                self.visit_expr(expr);
+            }
+            for order_by in window.spec.order_by.iter_mut() {
+                self.visit_order_by(order_by);
+            }
+            if let Some(frame) = &mut window.spec.window_frame {
+                self.visit_frame_bound(&mut frame.start_bound);
+                self.visit_frame_bound(&mut frame.end_bound);
+            }
+        }
+    }
+
+    fn visit_order_by(&mut self, order_by: &mut OrderByExpr) {
+        if !is_position(&order_by.expr) {
+            self.visit_expr(&mut order_by.expr);
+        }
+    }
+
+    fn visit_insert(&mut self, insert: &mut InsertStmt) {
+        insert.hints = None;
+        self.visit_table_ref(&mut insert.catalog, &mut insert.database, &mut insert.table);
+        for column in insert.columns.iter_mut() {
+            self.visit_identifier(column);
+        }
+        self.visit_insert_source(&mut insert.source);
+    }
+
+    fn visit_replace(&mut self, replace: &mut ReplaceStmt) {
+        replace.hints = None;
+        self.visit_table_ref(
+            &mut replace.catalog,
+            &mut replace.database,
+            &mut replace.table,
+        );
+        for column in replace
+            .on_conflict_columns
+            .iter_mut()
+            .chain(replace.columns.iter_mut())
+        {
+            self.visit_identifier(column);
+        }
+        self.visit_insert_source(&mut replace.source);
+    }
+
+    fn visit_insert_source(&mut self, insert_source: &mut InsertSource) {
+        match insert_source {
+            InsertSource::Streaming { rest_str, .. } => rest_str.clear(),
+            InsertSource::StreamingV2 { .. } => {}
+            InsertSource::Values { .. } => {}
+            InsertSource::Select { query } => self.visit_query(query),
+        }
+    }
+
+    fn visit_merge_into(&mut self, merge_into: &mut MergeIntoStmt) {
+        merge_into.hints = None;
+        self.visit_table_ref(
+            &mut merge_into.catalog,
+            &mut merge_into.database,
+            &mut merge_into.table_ident,
+        );
+        if let MergeSource::Select { query } = &mut merge_into.source {
+            self.visit_query(query);
+        }
+        if let Some(alias) = &mut merge_into.alias_target {
+            self.visit_identifier(&mut alias.name);
+        }
+        self.visit_expr(&mut merge_into.join_expr);
+        for option in merge_into.merge_options.iter_mut() {
+            match option {
+                MergeOption::Match(clause) => {
+                    if let Some(selection) = &mut clause.selection {
+                        self.visit_expr(selection);
+                    }
+                    if let MatchOperation::Update { update_list } = &mut clause.operation {
+                        for update in update_list.iter_mut() {
+                            self.visit_identifier(&mut update.name);
+                            self.visit_expr(&mut update.expr);
+                        }
+                    }
+                }
+                MergeOption::Unmatch(clause) => {
+                    if let Some(selection) = &mut clause.selection {
+                        self.visit_expr(selection);
+                    }
+                    let insert = &mut clause.insert_operation;
+                    for column in insert.columns.iter_mut().flatten() {
+                        self.visit_identifier(column);
+                    }
+                    for value in insert.values.iter_mut() {
+                        self.visit_expr(value);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_delete(&mut self, table_reference: &mut TableReference, selection: &mut Option<Expr>) {
+        self.visit_table_reference(table_reference);
+        if let Some(selection) = selection {
+            self.visit_expr(selection);
+        }
+    }
+
+    fn visit_update(&mut self, update: &mut UpdateStmt) {
+        update.hints = None;
+        self.visit_table_reference(&mut update.table);
+        for update_expr in update.update_list.iter_mut() {
+            self.visit_identifier(&mut update_expr.name);
+            self.visit_expr(&mut update_expr.expr);
+        }
+        if let Some(selection) = &mut update.selection {
+            self.visit_expr(selection);
+        }
+    }
+
+    fn visit_copy(&mut self, copy: &mut CopyStmt) {
+        copy.hints = None;
+        self.visit_copy_unit(&mut copy.src);
+        self.visit_copy_unit(&mut copy.dst);
+    }
+
+    fn visit_copy_unit(&mut self, copy_unit: &mut CopyUnit) {
+        match copy_unit {
+            CopyUnit::Table {
+                catalog,
+                database,
+                table,
+                columns,
+            } => {
+                self.visit_table_ref(catalog, database, table);
+                for column in columns.iter_mut().flatten() {
+                    self.visit_identifier(column);
+                }
+            }
+            CopyUnit::Location(_) => {}
+            CopyUnit::Query(query) => self.visit_query(query),
+        }
+    }
+}
+
+impl Normalizer {
+    fn visit_group_items(&mut self, exprs: &mut [Expr]) {
+        for expr in exprs.iter_mut() {
+            if !is_position(expr) {
+                self.visit_expr(expr);
+            }
+        }
+    }
+}
+
+/// `ORDER BY 1` and `GROUP BY 1` refer to a column of the select list, they are
+/// part of the shape rather than a value.
+fn is_position(expr: &Expr) -> bool {
+    matches!(expr, Expr::Literal {
+        lit: Literal::UInt64(_),
+        ..
+    })
+}
+
+fn is_constant_literal(lit: &Literal) -> bool {
+    !matches!(lit, Literal::CurrentTimestamp)
+}
+
+fn is_constant(expr: &Expr) -> bool {
+    match expr {
+        Expr::Placeholder { .. } => true,
+        Expr::Tuple { exprs, .. } | Expr::Array { exprs, .. } => exprs.iter().all(is_constant),
+        Expr::Cast { expr, .. } | Expr::TryCast { expr, .. } | Expr::UnaryOp { expr, .. } => {
+            is_constant(expr)
+        }
+        _ => false,
+    }
+}
+
+fn merge_span(first: Span, last: Span) -> Span {
+    match (first, last) {
+        (Some(first), Some(last)) => Some((first.start..last.end).into()),
+        (first, last) => first.or(last),
+    }
+}
+
+/// The canonical form of a normalized statement that the digests are computed
+/// from. It's written from the AST by a serializer of its own rather than by
+/// `Display`, and leaves out the spans, so it doesn't depend on how a statement
+/// is displayed or where its nodes are in the source.
+pub fn canonical_form(stmt: &Statement) -> String {
+    let mut out = String::new();
+    stmt.write_canonical(&mut out);
+    out
+}
+
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x00000100000001b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(PRIME)
+    })
+}
+
+fn fnv1a_128(bytes: &[u8]) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u128).wrapping_mul(PRIME)
+    })
+}
@@ -0,0 +1,1872 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::ast::*;
+
+/// A rewriter that consumes AST nodes and rebuilds them, so a node can be replaced
+/// by a node of another kind, e.g. a column reference by a subquery, and a select
+/// target by any number of select targets.
+///
+/// Every method folds the children of the node by default, with the `fold_*`
+/// function of the same name, in the order they appear in the SQL text. Nodes are
+/// destructured exhaustively, so adding a field or a variant to the AST fails to
+/// compile until it is folded here.
+/// Statements that contain no expression, query or identifier, e.g. of users,
+/// stages and shares, are returned unchanged, and so are the hints.
+pub trait Fold: Sized {
+    fn fold_statement(&mut self, stmt: Statement) -> Statement {
+        fold_statement(self, stmt)
+    }
+
+    fn fold_query(&mut self, query: Query) -> Query {
+        fold_query(self, query)
+    }
+
+    fn fold_with(&mut self, with: With) -> With {
+        fold_with(self, with)
+    }
+
+    fn fold_cte(&mut self, cte: CTE) -> CTE {
+        fold_cte(self, cte)
+    }
+
+    fn fold_set_expr(&mut self, set_expr: SetExpr) -> SetExpr {
+        fold_set_expr(self, set_expr)
+    }
+
+    fn fold_set_operation(&mut self, op: SetOperation) -> SetOperation {
+        fold_set_operation(self, op)
+    }
+
+    fn fold_select_stmt(&mut self, stmt: SelectStmt) -> SelectStmt {
+        fold_select_stmt(self, stmt)
+    }
+
+    /// A select target can be expanded into several ones, or removed.
+    fn fold_select_target(&mut self, target: SelectTarget) -> Vec<SelectTarget> {
+        fold_select_target(self, target)
+    }
+
+    fn fold_group_by(&mut self, group_by: GroupBy) -> GroupBy {
+        fold_group_by(self, group_by)
+    }
+
+    fn fold_order_by(&mut self, order_by: OrderByExpr) -> OrderByExpr {
+        fold_order_by(self, order_by)
+    }
+
+    fn fold_table_reference(&mut self, table_ref: TableReference) -> TableReference {
+        fold_table_reference(self, table_ref)
+    }
+
+    fn fold_table_alias(&mut self, alias: TableAlias) -> TableAlias {
+        fold_table_alias(self, alias)
+    }
+
+    fn fold_join(&mut self, join: Join) -> Join {
+        fold_join(self, join)
+    }
+
+    fn fold_join_condition(&mut self, condition: JoinCondition) -> JoinCondition {
+        fold_join_condition(self, condition)
+    }
+
+    fn fold_time_travel_point(&mut self, point: TimeTravelPoint) -> TimeTravelPoint {
+        fold_time_travel_point(self, point)
+    }
+
+    fn fold_pivot(&mut self, pivot: Pivot) -> Pivot {
+        fold_pivot(self, pivot)
+    }
+
+    fn fold_unpivot(&mut self, unpivot: Unpivot) -> Unpivot {
+        fold_unpivot(self, unpivot)
+    }
+
+    fn fold_window_definition(&mut self, definition: WindowDefinition) -> WindowDefinition {
+        fold_window_definition(self, definition)
+    }
+
+    fn fold_window(&mut self, window: Window) -> Window {
+        fold_window(self, window)
+    }
+
+    fn fold_window_spec(&mut self, spec: WindowSpec) -> WindowSpec {
+        fold_window_spec(self, spec)
+    }
+
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        fold_expr(self, expr)
+    }
+
+    fn fold_column_id(&mut self, column: ColumnID) -> ColumnID {
+        fold_column_id(self, column)
+    }
+
+    fn fold_type_name(&mut self, type_name: TypeName) -> TypeName {
+        type_name
+    }
+
+    fn fold_identifier(&mut self, ident: Identifier) -> Identifier {
+        ident
+    }
+
+    fn fold_insert(&mut self, insert: InsertStmt) -> InsertStmt {
+        fold_insert(self, insert)
+    }
+
+    fn fold_insert_source(&mut self, source: InsertSource) -> InsertSource {
+        fold_insert_source(self, source)
+    }
+
+    fn fold_replace(&mut self, replace: ReplaceStmt) -> ReplaceStmt {
+        fold_replace(self, replace)
+    }
+
+    fn fold_merge_into(&mut self, merge_into: MergeIntoStmt) -> MergeIntoStmt {
+        fold_merge_into(self, merge_into)
+    }
+
+    fn fold_update(&mut self, update: UpdateStmt) -> UpdateStmt {
+        fold_update(self, update)
+    }
+
+    fn fold_copy(&mut self, copy: CopyStmt) -> CopyStmt {
+        fold_copy(self, copy)
+    }
+
+    fn fold_copy_unit(&mut self, unit: CopyUnit) -> CopyUnit {
+        fold_copy_unit(self, unit)
+    }
+
+    fn fold_create_table(&mut self, stmt: CreateTableStmt) -> CreateTableStmt {
+        fold_create_table(self, stmt)
+    }
+
+    fn fold_column_definition(&mut self, column: ColumnDefinition) -> ColumnDefinition {
+        fold_column_definition(self, column)
+    }
+
+    fn fold_alter_table(&mut self, stmt: AlterTableStmt) -> AlterTableStmt {
+        fold_alter_table(self, stmt)
+    }
+
+    fn fold_create_view(&mut self, stmt: CreateViewStmt) -> CreateViewStmt {
+        fold_create_view(self, stmt)
+    }
+
+    fn fold_alter_view(&mut self, stmt: AlterViewStmt) -> AlterViewStmt {
+        fold_alter_view(self, stmt)
+    }
+
+    fn fold_create_index(&mut self, stmt: CreateIndexStmt) -> CreateIndexStmt {
+        fold_create_index(self, stmt)
+    }
+
+    fn fold_create_virtual_column(
+        &mut self,
+        stmt: CreateVirtualColumnStmt,
+    ) -> CreateVirtualColumnStmt {
+        fold_create_virtual_column(self, stmt)
+    }
+
+    fn fold_alter_virtual_column(
+        &mut self,
+        stmt: AlterVirtualColumnStmt,
+    ) -> AlterVirtualColumnStmt {
+        fold_alter_virtual_column(self, stmt)
+    }
+
+    fn fold_show_limit(&mut self, limit: ShowLimit) -> ShowLimit {
+        fold_show_limit(self, limit)
+    }
+
+    fn fold_udf_definition(&mut self, definition: UDFDefinition) -> UDFDefinition {
+        fold_udf_definition(self, definition)
+    }
+
+    fn fold_data_mask_policy(&mut self, policy: DataMaskPolicy) -> DataMaskPolicy {
+        fold_data_mask_policy(self, policy)
+    }
+}
+
+fn fold_boxed_expr<F: Fold>(folder: &mut F, expr: Box<Expr>) -> Box<Expr> {
+    Box::new(folder.fold_expr(*expr))
+}
+
+fn fold_exprs<F: Fold>(folder: &mut F, exprs: Vec<Expr>) -> Vec<Expr> {
+    exprs
+        .into_iter()
+        .map(|expr| folder.fold_expr(expr))
+        .collect()
+}
+
+fn fold_identifiers<F: Fold>(folder: &mut F, idents: Vec<Identifier>) -> Vec<Identifier> {
+    idents
+        .into_iter()
+        .map(|ident| folder.fold_identifier(ident))
+        .collect()
+}
+
+fn fold_optional_identifier<F: Fold>(
+    folder: &mut F,
+    ident: Option<Identifier>,
+) -> Option<Identifier> {
+    ident.map(|ident| folder.fold_identifier(ident))
+}
+
+fn fold_vacuum_table_option<F: Fold>(
+    folder: &mut F,
+    option: VacuumTableOption,
+) -> VacuumTableOption {
+    let VacuumTableOption {
+        retain_hours,
+        dry_run,
+    } = option;
+
+    VacuumTableOption {
+        retain_hours: retain_hours.map(|expr| folder.fold_expr(expr)),
+        dry_run,
+    }
+}
+
+fn fold_optional_show_limit<F: Fold>(
+    folder: &mut F,
+    limit: Option<ShowLimit>,
+) -> Option<ShowLimit> {
+    limit.map(|limit| folder.fold_show_limit(limit))
+}
+
+pub fn fold_statement<F: Fold>(folder: &mut F, stmt: Statement) -> Statement {
+    match stmt {
+        Statement::Query(query) => Statement::Query(Box::new(folder.fold_query(*query))),
+        Statement::Explain { kind, query } => Statement::Explain {
+            kind,
+            query: Box::new(folder.fold_statement(*query)),
+        },
+        Statement::ExplainAnalyze { query } => Statement::ExplainAnalyze {
+            query: Box::new(folder.fold_statement(*query)),
+        },
+        Statement::Copy(copy) => Statement::Copy(folder.fold_copy(copy)),
+        Statement::SetVariable {
+            is_global,
+            variable,
+            value,
+        } => Statement::SetVariable {
+            is_global,
+            variable: folder.fold_identifier(variable),
+            value: fold_boxed_expr(folder, value),
+        },
+        Statement::Insert(insert) => Statement::Insert(folder.fold_insert(insert)),
+        Statement::Replace(replace) => Statement::Replace(folder.fold_replace(replace)),
+        Statement::MergeInto(merge_into) => {
+            Statement::MergeInto(folder.fold_merge_into(merge_into))
+        }
+        Statement::Delete {
+            hints,
+            table_reference,
+            selection,
+        } => Statement::Delete {
+            hints,
+            table_reference: folder.fold_table_reference(table_reference),
+            selection: selection.map(|selection| folder.fold_expr(selection)),
+        },
+        Statement::Update(update) => Statement::Update(folder.fold_update(update)),
+        Statement::UseDatabase { database } => Statement::UseDatabase {
+            database: folder.fold_identifier(database),
+        },
+        Statement::CreateTable(stmt) => Statement::CreateTable(folder.fold_create_table(stmt)),
+        Statement::AlterTable(stmt) => Statement::AlterTable(folder.fold_alter_table(stmt)),
+        Statement::CreateView(stmt) => Statement::CreateView(folder.fold_create_view(stmt)),
+        Statement::AlterView(stmt) => Statement::AlterView(folder.fold_alter_view(stmt)),
+        Statement::CreateIndex(stmt) => Statement::CreateIndex(folder.fold_create_index(stmt)),
+        Statement::CreateVirtualColumn(stmt) => {
+            Statement::CreateVirtualColumn(folder.fold_create_virtual_column(stmt))
+        }
+        Statement::AlterVirtualColumn(stmt) => {
+            Statement::AlterVirtualColumn(folder.fold_alter_virtual_column(stmt))
+        }
+        Statement::ShowFunctions { limit } => Statement::ShowFunctions {
+            limit: fold_optional_show_limit(folder, limit),
+        },
+        Statement::ShowTableFunctions { limit } => Statement::ShowTableFunctions {
+            limit: fold_optional_show_limit(folder, limit),
+        },
+        Statement::UnSetVariable(UnSetStmt { source }) => Statement::UnSetVariable(UnSetStmt {
+            source: match source {
+                UnSetSource::Var { variable } => UnSetSource::Var {
+                    variable: folder.fold_identifier(variable),
+                },
+                UnSetSource::Vars { variables } => UnSetSource::Vars {
+                    variables: fold_identifiers(folder, variables),
+                },
+            },
+        }),
+        Statement::ShowCatalogs(ShowCatalogsStmt { limit }) => {
+            Statement::ShowCatalogs(ShowCatalogsStmt {
+                limit: fold_optional_show_limit(folder, limit),
+            })
+        }
+        Statement::ShowCreateCatalog(ShowCreateCatalogStmt { catalog }) => {
+            Statement::ShowCreateCatalog(ShowCreateCatalogStmt {
+                catalog: folder.fold_identifier(catalog),
+            })
+        }
+        Statement::DropCatalog(DropCatalogStmt { if_exists, catalog }) => {
+            Statement::DropCatalog(DropCatalogStmt {
+                if_exists,
+                catalog: folder.fold_identifier(catalog),
+            })
+        }
+        Statement::ShowDatabases(ShowDatabasesStmt {
+            catalog,
+            full,
+            limit,
+        }) => Statement::ShowDatabases(ShowDatabasesStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            full,
+            limit: fold_optional_show_limit(folder, limit),
+        }),
+        Statement::ShowCreateDatabase(ShowCreateDatabaseStmt { catalog, database }) => {
+            Statement::ShowCreateDatabase(ShowCreateDatabaseStmt {
+                catalog: fold_optional_identifier(folder, catalog),
+                database: folder.fold_identifier(database),
+            })
+        }
+        Statement::CreateDatabase(CreateDatabaseStmt {
+            if_not_exists,
+            catalog,
+            database,
+            engine,
+            options,
+            from_share,
+        }) => Statement::CreateDatabase(CreateDatabaseStmt {
+            if_not_exists,
+            catalog: fold_optional_identifier(folder, catalog),
+            database: folder.fold_identifier(database),
+            engine,
+            options,
+            from_share,
+        }),
+        Statement::DropDatabase(DropDatabaseStmt {
+            if_exists,
+            catalog,
+            database,
+        }) => Statement::DropDatabase(DropDatabaseStmt {
+            if_exists,
+            catalog: fold_optional_identifier(folder, catalog),
+            database: folder.fold_identifier(database),
+        }),
+        Statement::UndropDatabase(UndropDatabaseStmt { catalog, database }) => {
+            Statement::UndropDatabase(UndropDatabaseStmt {
+                catalog: fold_optional_identifier(folder, catalog),
+                database: folder.fold_identifier(database),
+            })
+        }
+        Statement::AlterDatabase(AlterDatabaseStmt {
+            if_exists,
+            catalog,
+            database,
+            action,
+        }) => Statement::AlterDatabase(AlterDatabaseStmt {
+            if_exists,
+            catalog: fold_optional_identifier(folder, catalog),
+            database: folder.fold_identifier(database),
+            action: match action {
+                AlterDatabaseAction::RenameDatabase { new_db } => {
+                    AlterDatabaseAction::RenameDatabase {
+                        new_db: folder.fold_identifier(new_db),
+                    }
+                }
+            },
+        }),
+        Statement::ShowTables(ShowTablesStmt {
+            catalog,
+            database,
+            full,
+            limit,
+            with_history,
+        }) => Statement::ShowTables(ShowTablesStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            full,
+            limit: fold_optional_show_limit(folder, limit),
+            with_history,
+        }),
+        Statement::ShowColumns(ShowColumnsStmt {
+            catalog,
+            database,
+            table,
+            full,
+            limit,
+        }) => Statement::ShowColumns(ShowColumnsStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+            full,
+            limit: fold_optional_show_limit(folder, limit),
+        }),
+        Statement::ShowCreateTable(ShowCreateTableStmt {
+            catalog,
+            database,
+            table,
+        }) => Statement::ShowCreateTable(ShowCreateTableStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+        }),
+        Statement::DescribeTable(DescribeTableStmt {
+            catalog,
+            database,
+            table,
+        }) => Statement::DescribeTable(DescribeTableStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+        }),
+        Statement::ShowTablesStatus(ShowTablesStatusStmt { database, limit }) => {
+            Statement::ShowTablesStatus(ShowTablesStatusStmt {
+                database: fold_optional_identifier(folder, database),
+                limit: fold_optional_show_limit(folder, limit),
+            })
+        }
+        Statement::ShowDropTables(ShowDropTablesStmt { database }) => {
+            Statement::ShowDropTables(ShowDropTablesStmt {
+                database: fold_optional_identifier(folder, database),
+            })
+        }
+        Statement::AttachTable(AttachTableStmt {
+            catalog,
+            database,
+            table,
+            uri_location,
+        }) => Statement::AttachTable(AttachTableStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+            uri_location,
+        }),
+        Statement::DropTable(DropTableStmt {
+            if_exists,
+            catalog,
+            database,
+            table,
+            all,
+        }) => Statement::DropTable(DropTableStmt {
+            if_exists,
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+            all,
+        }),
+        Statement::UndropTable(UndropTableStmt {
+            catalog,
+            database,
+            table,
+        }) => Statement::UndropTable(UndropTableStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+        }),
+        Statement::RenameTable(RenameTableStmt {
+            if_exists,
+            catalog,
+            database,
+            table,
+            new_catalog,
+            new_database,
+            new_table,
+        }) => Statement::RenameTable(RenameTableStmt {
+            if_exists,
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+            new_catalog: fold_optional_identifier(folder, new_catalog),
+            new_database: fold_optional_identifier(folder, new_database),
+            new_table: folder.fold_identifier(new_table),
+        }),
+        Statement::TruncateTable(TruncateTableStmt {
+            catalog,
+            database,
+            table,
+            purge,
+        }) => Statement::TruncateTable(TruncateTableStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+            purge,
+        }),
+        Statement::OptimizeTable(OptimizeTableStmt {
+            catalog,
+            database,
+            table,
+            action,
+            limit,
+        }) => Statement::OptimizeTable(OptimizeTableStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+            action: match action {
+                OptimizeTableAction::Purge { before } => OptimizeTableAction::Purge {
+                    before: before.map(|point| folder.fold_time_travel_point(point)),
+                },
+                action @ (OptimizeTableAction::All | OptimizeTableAction::Compact { .. }) => action,
+            },
+            limit,
+        }),
+        Statement::VacuumTable(VacuumTableStmt {
+            catalog,
+            database,
+            table,
+            option,
+        }) => Statement::VacuumTable(VacuumTableStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+            option: fold_vacuum_table_option(folder, option),
+        }),
+        Statement::VacuumDropTable(VacuumDropTableStmt {
+            catalog,
+            database,
+            option,
+        }) => Statement::VacuumDropTable(VacuumDropTableStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            option: fold_vacuum_table_option(folder, option),
+        }),
+        Statement::AnalyzeTable(AnalyzeTableStmt {
+            catalog,
+            database,
+            table,
+        }) => Statement::AnalyzeTable(AnalyzeTableStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+        }),
+        Statement::ExistsTable(ExistsTableStmt {
+            catalog,
+            database,
+            table,
+        }) => Statement::ExistsTable(ExistsTableStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+        }),
+        Statement::DropView(DropViewStmt {
+            if_exists,
+            catalog,
+            database,
+            view,
+        }) => Statement::DropView(DropViewStmt {
+            if_exists,
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            view: folder.fold_identifier(view),
+        }),
+        Statement::DropIndex(DropIndexStmt { if_exists, index }) => {
+            Statement::DropIndex(DropIndexStmt {
+                if_exists,
+                index: folder.fold_identifier(index),
+            })
+        }
+        Statement::RefreshIndex(RefreshIndexStmt { index, limit }) => {
+            Statement::RefreshIndex(RefreshIndexStmt {
+                index: folder.fold_identifier(index),
+                limit,
+            })
+        }
+        Statement::DropVirtualColumn(DropVirtualColumnStmt {
+            catalog,
+            database,
+            table,
+        }) => Statement::DropVirtualColumn(DropVirtualColumnStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+        }),
+        Statement::RefreshVirtualColumn(RefreshVirtualColumnStmt {
+            catalog,
+            database,
+            table,
+        }) => Statement::RefreshVirtualColumn(RefreshVirtualColumnStmt {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+        }),
+        Statement::CreateUDF(CreateUDFStmt {
+            if_not_exists,
+            udf_name,
+            description,
+            definition,
+        }) => Statement::CreateUDF(CreateUDFStmt {
+            if_not_exists,
+            udf_name: folder.fold_identifier(udf_name),
+            description,
+            definition: folder.fold_udf_definition(definition),
+        }),
+        Statement::AlterUDF(AlterUDFStmt {
+            udf_name,
+            description,
+            definition,
+        }) => Statement::AlterUDF(AlterUDFStmt {
+            udf_name: folder.fold_identifier(udf_name),
+            description,
+            definition: folder.fold_udf_definition(definition),
+        }),
+        Statement::DropUDF {
+            if_exists,
+            udf_name,
+        } => Statement::DropUDF {
+            if_exists,
+            udf_name: folder.fold_identifier(udf_name),
+        },
+        Statement::CreateDatamaskPolicy(CreateDatamaskPolicyStmt {
+            if_not_exists,
+            name,
+            policy,
+        }) => Statement::CreateDatamaskPolicy(CreateDatamaskPolicyStmt {
+            if_not_exists,
+            name,
+            policy: folder.fold_data_mask_policy(policy),
+        }),
+
+        stmt @ (Statement::Call(_)
+        | Statement::ShowSettings { .. }
+        | Statement::ShowProcessList
+        | Statement::ShowMetrics
+        | Statement::ShowEngines
+        | Statement::ShowIndexes
+        | Statement::KillStmt { .. }
+        | Statement::SetRole { .. }
+        | Statement::CreateCatalog(_)
+        | Statement::ShowUsers
+        | Statement::CreateUser(_)
+        | Statement::AlterUser(_)
+        | Statement::DropUser { .. }
+        | Statement::ShowRoles
+        | Statement::CreateRole { .. }
+        | Statement::DropRole { .. }
+        | Statement::Grant(_)
+        | Statement::ShowGrants { .. }
+        | Statement::Revoke(_)
+        | Statement::CreateStage(_)
+        | Statement::ShowStages
+        | Statement::DropStage { .. }
+        | Statement::DescribeStage { .. }
+        | Statement::RemoveStage { .. }
+        | Statement::ListStage { .. }
+        | Statement::CreateFileFormat { .. }
+        | Statement::DropFileFormat { .. }
+        | Statement::ShowFileFormats
+        | Statement::Presign(_)
+        | Statement::CreateShareEndpoint(_)
+        | Statement::ShowShareEndpoint(_)
+        | Statement::DropShareEndpoint(_)
+        | Statement::CreateShare(_)
+        | Statement::DropShare(_)
+        | Statement::GrantShareObject(_)
+        | Statement::RevokeShareObject(_)
+        | Statement::AlterShareTenants(_)
+        | Statement::DescShare(_)
+        | Statement::ShowShares(_)
+        | Statement::ShowObjectGrantPrivileges(_)
+        | Statement::ShowGrantsOfShare(_)
+        | Statement::DropDatamaskPolicy(_)
+        | Statement::DescDatamaskPolicy(_)
+        | Statement::CreateNetworkPolicy(_)
+        | Statement::AlterNetworkPolicy(_)
+        | Statement::DropNetworkPolicy(_)
+        | Statement::DescNetworkPolicy(_)
+        | Statement::ShowNetworkPolicies) => stmt,
+    }
+}
+
+pub fn fold_query<F: Fold>(folder: &mut F, query: Query) -> Query {
+    let Query {
+        span,
+        with,
+        body,
+        order_by,
+        limit,
+        offset,
+        ignore_result,
+    } = query;
+
+    Query {
+        span,
+        with: with.map(|with| folder.fold_with(with)),
+        body: folder.fold_set_expr(body),
+        order_by: order_by
+            .into_iter()
+            .map(|order_by| folder.fold_order_by(order_by))
+            .collect(),
+        limit: fold_exprs(folder, limit),
+        offset: offset.map(|offset| folder.fold_expr(offset)),
+        ignore_result,
+    }
+}
+
+pub fn fold_with<F: Fold>(folder: &mut F, with: With) -> With {
+    let With {
+        span,
+        recursive,
+        ctes,
+    } = with;
+
+    With {
+        span,
+        recursive,
+        ctes: ctes.into_iter().map(|cte| folder.fold_cte(cte)).collect(),
+    }
+}
+
+pub fn fold_cte<F: Fold>(folder: &mut F, cte: CTE) -> CTE {
+    let CTE {
+        span,
+        alias,
+        materialized,
+        query,
+    } = cte;
+
+    CTE {
+        span,
+        alias: folder.fold_table_alias(alias),
+        materialized,
+        query: Box::new(folder.fold_query(*query)),
+    }
+}
+
+pub fn fold_set_expr<F: Fold>(folder: &mut F, set_expr: SetExpr) -> SetExpr {
+    match set_expr {
+        SetExpr::Select(stmt) => SetExpr::Select(Box::new(folder.fold_select_stmt(*stmt))),
+        SetExpr::Query(query) => SetExpr::Query(Box::new(folder.fold_query(*query))),
+        SetExpr::SetOperation(op) => {
+            SetExpr::SetOperation(Box::new(folder.fold_set_operation(*op)))
+        }
+        SetExpr::Values { span, values } => SetExpr::Values {
+            span,
+            values: values
+                .into_iter()
+                .map(|row| fold_exprs(folder, row))
+                .collect(),
+        },
+    }
+}
+
+pub fn fold_set_operation<F: Fold>(folder: &mut F, op: SetOperation) -> SetOperation {
+    let SetOperation {
+        span,
+        op,
+        all,
+        left,
+        right,
+    } = op;
+
+    SetOperation {
+        span,
+        op,
+        all,
+        left: Box::new(folder.fold_set_expr(*left)),
+        right: Box::new(folder.fold_set_expr(*right)),
+    }
+}
+
+pub fn fold_select_stmt<F: Fold>(folder: &mut F, stmt: SelectStmt) -> SelectStmt {
+    let SelectStmt {
+        span,
+        hints,
+        distinct,
+        select_list,
+        from,
+        selection,
+        group_by,
+        having,
+        window_list,
+    } = stmt;
+
+    SelectStmt {
+        span,
+        hints,
+        distinct,
+        select_list: select_list
+            .into_iter()
+            .flat_map(|target| folder.fold_select_target(target))
+            .collect(),
+        from: from
+            .into_iter()
+            .map(|table_ref| folder.fold_table_reference(table_ref))
+            .collect(),
+        selection: selection.map(|selection| folder.fold_expr(selection)),
+        group_by: group_by.map(|group_by| folder.fold_group_by(group_by)),
+        having: having.map(|having| folder.fold_expr(having)),
+        window_list: window_list.map(|window_list| {
+            window_list
+                .into_iter()
+                .map(|definition| folder.fold_window_definition(definition))
+                .collect()
+        }),
+    }
+}
+
+pub fn fold_select_target<F: Fold>(folder: &mut F, target: SelectTarget) -> Vec<SelectTarget> {
+    let target = match target {
+        SelectTarget::AliasedExpr { expr, alias } => SelectTarget::AliasedExpr {
+            expr: fold_boxed_expr(folder, expr),
+            alias: fold_optional_identifier(folder, alias),
+        },
+        SelectTarget::QualifiedName { qualified, exclude } => SelectTarget::QualifiedName {
+            qualified: qualified
+                .into_iter()
+                .map(|indirection| match indirection {
+                    Indirection::Identifier(ident) => {
+                        Indirection::Identifier(folder.fold_identifier(ident))
+                    }
+                    Indirection::Star(span) => Indirection::Star(span),
+                })
+                .collect(),
+            exclude: exclude.map(|exclude| {
+                exclude
+                    .into_iter()
+                    .map(|column| folder.fold_column_id(column))
+                    .collect()
+            }),
+        },
+    };
+    vec![target]
+}
+
+pub fn fold_group_by<F: Fold>(folder: &mut F, group_by: GroupBy) -> GroupBy {
+    match group_by {
+        GroupBy::Normal(exprs) => GroupBy::Normal(fold_exprs(folder, exprs)),
+        GroupBy::All => GroupBy::All,
+        GroupBy::GroupingSets(sets) => GroupBy::GroupingSets(
+            sets.into_iter()
+                .map(|set| fold_exprs(folder, set))
+                .collect(),
+        ),
+        GroupBy::Cube(exprs) => GroupBy::Cube(fold_exprs(folder, exprs)),
+        GroupBy::Rollup(exprs) => GroupBy::Rollup(fold_exprs(folder, exprs)),
+    }
+}
+
+pub fn fold_order_by<F: Fold>(folder: &mut F, order_by: OrderByExpr) -> OrderByExpr {
+    let OrderByExpr {
+        expr,
+        asc,
+        nulls_first,
+    } = order_by;
+
+    OrderByExpr {
+        expr: folder.fold_expr(expr),
+        asc,
+        nulls_first,
+    }
+}
+
+pub fn fold_table_reference<F: Fold>(folder: &mut F, table_ref: TableReference) -> TableReference {
+    match table_ref {
+        TableReference::Table {
+            span,
+            catalog,
+            database,
+            table,
+            alias,
+            travel_point,
+            pivot,
+            unpivot,
+        } => TableReference::Table {
+            span,
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+            alias: alias.map(|alias| folder.fold_table_alias(alias)),
+            travel_point: travel_point.map(|point| folder.fold_time_travel_point(point)),
+            pivot: pivot.map(|pivot| Box::new(folder.fold_pivot(*pivot))),
+            unpivot: unpivot.map(|unpivot| Box::new(folder.fold_unpivot(*unpivot))),
+        },
+        TableReference::TableFunction {
+            span,
+            name,
+            params,
+            named_params,
+            alias,
+        } => TableReference::TableFunction {
+            span,
+            name: folder.fold_identifier(name),
+            params: fold_exprs(folder, params),
+            named_params: named_params
+                .into_iter()
+                .map(|(name, param)| (name, folder.fold_expr(param)))
+                .collect(),
+            alias: alias.map(|alias| folder.fold_table_alias(alias)),
+        },
+        TableReference::Subquery {
+            span,
+            subquery,
+            alias,
+        } => TableReference::Subquery {
+            span,
+            subquery: Box::new(folder.fold_query(*subquery)),
+            alias: alias.map(|alias| folder.fold_table_alias(alias)),
+        },
+        TableReference::Join { span, join } => TableReference::Join {
+            span,
+            join: folder.fold_join(join),
+        },
+        TableReference::Location {
+            span,
+            location,
+            options,
+            alias,
+        } => TableReference::Location {
+            span,
+            location,
+            options,
+            alias: alias.map(|alias| folder.fold_table_alias(alias)),
+        },
+    }
+}
+
+pub fn fold_table_alias<F: Fold>(folder: &mut F, alias: TableAlias) -> TableAlias {
+    let TableAlias { name, columns } = alias;
+
+    TableAlias {
+        name: folder.fold_identifier(name),
+        columns: fold_identifiers(folder, columns),
+    }
+}
+
+pub fn fold_join<F: Fold>(folder: &mut F, join: Join) -> Join {
+    let Join {
+        op,
+        condition,
+        left,
+        right,
+    } = join;
+
+    Join {
+        op,
+        left: Box::new(folder.fold_table_reference(*left)),
+        right: Box::new(folder.fold_table_reference(*right)),
+        condition: folder.fold_join_condition(condition),
+    }
+}
+
+pub fn fold_join_condition<F: Fold>(folder: &mut F, condition: JoinCondition) -> JoinCondition {
+    match condition {
+        JoinCondition::On(expr) => JoinCondition::On(fold_boxed_expr(folder, expr)),
+        JoinCondition::Using(idents) => JoinCondition::Using(fold_identifiers(folder, idents)),
+        JoinCondition::Natural => JoinCondition::Natural,
+        JoinCondition::None => JoinCondition::None,
+    }
+}
+
+pub fn fold_time_travel_point<F: Fold>(folder: &mut F, point: TimeTravelPoint) -> TimeTravelPoint {
+    match point {
+        TimeTravelPoint::Snapshot(snapshot) => TimeTravelPoint::Snapshot(snapshot),
+        TimeTravelPoint::Timestamp(expr) => {
+            TimeTravelPoint::Timestamp(fold_boxed_expr(folder, expr))
+        }
+    }
+}
+
+pub fn fold_pivot<F: Fold>(folder: &mut F, pivot: Pivot) -> Pivot {
+    let Pivot {
+        aggregate,
+        value_column,
+        values,
+    } = pivot;
+
+    Pivot {
+        aggregate: folder.fold_expr(aggregate),
+        value_column: folder.fold_identifier(value_column),
+        values: fold_exprs(folder, values),
+    }
+}
+
+pub fn fold_unpivot<F: Fold>(folder: &mut F, unpivot: Unpivot) -> Unpivot {
+    let Unpivot {
+        value_column,
+        column_name,
+        names,
+    } = unpivot;
+
+    Unpivot {
+        value_column: folder.fold_identifier(value_column),
+        column_name: folder.fold_identifier(column_name),
+        names: fold_identifiers(folder, names),
+    }
+}
+
+pub fn fold_window_definition<F: Fold>(
+    folder: &mut F,
+    definition: WindowDefinition,
+) -> WindowDefinition {
+    let WindowDefinition { name, spec } = definition;
+
+    WindowDefinition {
+        name: folder.fold_identifier(name),
+        spec: folder.fold_window_spec(spec),
+    }
+}
+
+pub fn fold_window<F: Fold>(folder: &mut F, window: Window) -> Window {
+    match window {
+        Window::WindowReference(WindowRef { window_name }) => Window::WindowReference(WindowRef {
+            window_name: folder.fold_identifier(window_name),
+        }),
+        Window::WindowSpec(spec) => Window::WindowSpec(folder.fold_window_spec(spec)),
+    }
+}
+
+pub fn fold_window_spec<F: Fold>(folder: &mut F, spec: WindowSpec) -> WindowSpec {
+    let WindowSpec {
+        existing_window_name,
+        partition_by,
+        order_by,
+        window_frame,
+    } = spec;
+
+    let existing_window_name = fold_optional_identifier(folder, existing_window_name);
+    let partition_by = fold_exprs(folder, partition_by);
+    let order_by = order_by
+        .into_iter()
+        .map(|order_by| folder.fold_order_by(order_by))
+        .collect();
+    let mut fold_bound = |bound| match bound {
+        WindowFrameBound::CurrentRow => WindowFrameBound::CurrentRow,
+        WindowFrameBound::Preceding(expr) => {
+            WindowFrameBound::Preceding(expr.map(|expr| fold_boxed_expr(folder, expr)))
+        }
+        WindowFrameBound::Following(expr) => {
+            WindowFrameBound::Following(expr.map(|expr| fold_boxed_expr(folder, expr)))
+        }
+    };
+    let window_frame = window_frame.map(
+        |WindowFrame {
+             units,
+             start_bound,
+             end_bound,
+         }| WindowFrame {
+            units,
+            start_bound: fold_bound(start_bound),
+            end_bound: fold_bound(end_bound),
+        },
+    );
+
+    WindowSpec {
+        existing_window_name,
+        partition_by,
+        order_by,
+        window_frame,
+    }
+}
+
+pub fn fold_expr<F: Fold>(folder: &mut F, expr: Expr) -> Expr {
+    match expr {
+        Expr::ColumnRef {
+            span,
+            database,
+            table,
+            column,
+        } => Expr::ColumnRef {
+            span,
+            database: fold_optional_identifier(folder, database),
+            table: fold_optional_identifier(folder, table),
+            column: folder.fold_column_id(column),
+        },
+        Expr::IsNull { span, expr, not } => Expr::IsNull {
+            span,
+            expr: fold_boxed_expr(folder, expr),
+            not,
+        },
+        Expr::IsDistinctFrom {
+            span,
+            left,
+            right,
+            not,
+        } => Expr::IsDistinctFrom {
+            span,
+            left: fold_boxed_expr(folder, left),
+            right: fold_boxed_expr(folder, right),
+            not,
+        },
+        Expr::InList {
+            span,
+            expr,
+            list,
+            not,
+        } => Expr::InList {
+            span,
+            expr: fold_boxed_expr(folder, expr),
+            list: fold_exprs(folder, list),
+            not,
+        },
+        Expr::InSubquery {
+            span,
+            expr,
+            subquery,
+            not,
+        } => Expr::InSubquery {
+            span,
+            expr: fold_boxed_expr(folder, expr),
+            subquery: Box::new(folder.fold_query(*subquery)),
+            not,
+        },
+        Expr::Between {
+            span,
+            expr,
+            low,
+            high,
+            not,
+        } => Expr::Between {
+            span,
+            expr: fold_boxed_expr(folder, expr),
+            low: fold_boxed_expr(folder, low),
+            high: fold_boxed_expr(folder, high),
+            not,
+        },
+        Expr::BinaryOp {
+            span,
+            op,
+            left,
+            right,
+        } => Expr::BinaryOp {
+            span,
+            op,
+            left: fold_boxed_expr(folder, left),
+            right: fold_boxed_expr(folder, right),
+        },
+        Expr::UnaryOp { span, op, expr } => Expr::UnaryOp {
+            span,
+            op,
+            expr: fold_boxed_expr(folder, expr),
+        },
+        Expr::Cast {
+            span,
+            expr,
+            target_type,
+            pg_style,
+        } => Expr::Cast {
+            span,
+            expr: fold_boxed_expr(folder, expr),
+            target_type: folder.fold_type_name(target_type),
+            pg_style,
+        },
+        Expr::TryCast {
+            span,
+            expr,
+            target_type,
+        } => Expr::TryCast {
+            span,
+            expr: fold_boxed_expr(folder, expr),
+            target_type: folder.fold_type_name(target_type),
+        },
+        Expr::Extract { span, kind, expr } => Expr::Extract {
+            span,
+            kind,
+            expr: fold_boxed_expr(folder, expr),
+        },
+        Expr::Position {
+            span,
+            substr_expr,
+            str_expr,
+        } => Expr::Position {
+            span,
+            substr_expr: fold_boxed_expr(folder, substr_expr),
+            str_expr: fold_boxed_expr(folder, str_expr),
+        },
+        Expr::Substring {
+            span,
+            expr,
+            substring_from,
+            substring_for,
+        } => Expr::Substring {
+            span,
+            expr: fold_boxed_expr(folder, expr),
+            substring_from: fold_boxed_expr(folder, substring_from),
+            substring_for: substring_for.map(|expr| fold_boxed_expr(folder, expr)),
+        },
+        Expr::Trim {
+            span,
+            expr,
+            trim_where,
+        } => Expr::Trim {
+            span,
+            trim_where: trim_where
+                .map(|(trim_where, trim_str)| (trim_where, fold_boxed_expr(folder, trim_str))),
+            expr: fold_boxed_expr(folder, expr),
+        },
+        Expr::Literal { span, lit } => Expr::Literal { span, lit },
+        Expr::Placeholder { span } => Expr::Placeholder { span },
+        Expr::CountAll { span, window } => Expr::CountAll {
+            span,
+            window: window.map(|window| folder.fold_window(window)),
+        },
+        Expr::Tuple { span, exprs } => Expr::Tuple {
+            span,
+            exprs: fold_exprs(folder, exprs),
+        },
+        Expr::FunctionCall {
+            span,
+            distinct,
+            name,
+            args,
+            params,
+            window,
+            lambda,
+        } => Expr::FunctionCall {
+            span,
+            distinct,
+            name: folder.fold_identifier(name),
+            args: fold_exprs(folder, args),
+            params,
+            window: window.map(|window| folder.fold_window(window)),
+            lambda: lambda.map(|Lambda { params, expr }| Lambda {
+                params: fold_identifiers(folder, params),
+                expr: fold_boxed_expr(folder, expr),
+            }),
+        },
+        Expr::Case {
+            span,
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => {
+            let operand = operand.map(|operand| fold_boxed_expr(folder, operand));
+            let (conditions, results) = conditions
+                .into_iter()
+                .zip(results)
+                .map(|(condition, result)| (folder.fold_expr(condition), folder.fold_expr(result)))
+                .unzip();
+            Expr::Case {
+                span,
+                operand,
+                conditions,
+                results,
+                else_result: else_result.map(|else_result| fold_boxed_expr(folder, else_result)),
+            }
+        }
+        Expr::Exists {
+            span,
+            not,
+            subquery,
+        } => Expr::Exists {
+            span,
+            not,
+            subquery: Box::new(folder.fold_query(*subquery)),
+        },
+        Expr::Subquery {
+            span,
+            modifier,
+            subquery,
+        } => Expr::Subquery {
+            span,
+            modifier,
+            subquery: Box::new(folder.fold_query(*subquery)),
+        },
+        Expr::MapAccess {
+            span,
+            expr,
+            accessor,
+        } => Expr::MapAccess {
+            span,
+            expr: fold_boxed_expr(folder, expr),
+            accessor: match accessor {
+                MapAccessor::Bracket { key } => MapAccessor::Bracket {
+                    key: fold_boxed_expr(folder, key),
+                },
+                MapAccessor::Dot { key } => MapAccessor::Dot {
+                    key: folder.fold_identifier(key),
+                },
+                MapAccessor::DotNumber { key } => MapAccessor::DotNumber { key },
+                MapAccessor::Colon { key } => MapAccessor::Colon {
+                    key: folder.fold_identifier(key),
+                },
+            },
+        },
+        Expr::Array { span, exprs } => Expr::Array {
+            span,
+            exprs: fold_exprs(folder, exprs),
+        },
+        Expr::Map { span, kvs } => Expr::Map { span, kvs },
+        Expr::Interval { span, expr, unit } => Expr::Interval {
+            span,
+            expr: fold_boxed_expr(folder, expr),
+            unit,
+        },
+        Expr::DateAdd {
+            span,
+            unit,
+            interval,
+            date,
+        } => Expr::DateAdd {
+            span,
+            unit,
+            interval: fold_boxed_expr(folder, interval),
+            date: fold_boxed_expr(folder, date),
+        },
+        Expr::DateSub {
+            span,
+            unit,
+            interval,
+            date,
+        } => Expr::DateSub {
+            span,
+            unit,
+            interval: fold_boxed_expr(folder, interval),
+            date: fold_boxed_expr(folder, date),
+        },
+        Expr::DateTrunc { span, unit, date } => Expr::DateTrunc {
+            span,
+            unit,
+            date: fold_boxed_expr(folder, date),
+        },
+    }
+}
+
+pub fn fold_column_id<F: Fold>(folder: &mut F, column: ColumnID) -> ColumnID {
+    match column {
+        ColumnID::Name(ident) => ColumnID::Name(folder.fold_identifier(ident)),
+        ColumnID::Position(position) => ColumnID::Position(position),
+    }
+}
+
+pub fn fold_insert<F: Fold>(folder: &mut F, insert: InsertStmt) -> InsertStmt {
+    let InsertStmt {
+        hints,
+        catalog,
+        database,
+        table,
+        columns,
+        source,
+        overwrite,
+    } = insert;
+
+    InsertStmt {
+        hints,
+        catalog: fold_optional_identifier(folder, catalog),
+        database: fold_optional_identifier(folder, database),
+        table: folder.fold_identifier(table),
+        columns: fold_identifiers(folder, columns),
+        source: folder.fold_insert_source(source),
+        overwrite,
+    }
+}
+
+pub fn fold_insert_source<F: Fold>(folder: &mut F, source: InsertSource) -> InsertSource {
+    match source {
+        InsertSource::Select { query } => InsertSource::Select {
+            query: Box::new(folder.fold_query(*query)),
+        },
+        source @ (InsertSource::Streaming { .. }
+        | InsertSource::StreamingV2 { .. }
+        | InsertSource::Values { .. }) => source,
+    }
+}
+
+pub fn fold_replace<F: Fold>(folder: &mut F, replace: ReplaceStmt) -> ReplaceStmt {
+    let ReplaceStmt {
+        hints,
+        catalog,
+        database,
+        table,
+        on_conflict_columns,
+        columns,
+        source,
+    } = replace;
+
+    ReplaceStmt {
+        hints,
+        catalog: fold_optional_identifier(folder, catalog),
+        database: fold_optional_identifier(folder, database),
+        table: folder.fold_identifier(table),
+        on_conflict_columns: fold_identifiers(folder, on_conflict_columns),
+        columns: fold_identifiers(folder, columns),
+        source: folder.fold_insert_source(source),
+    }
+}
+
+pub fn fold_merge_into<F: Fold>(folder: &mut F, merge_into: MergeIntoStmt) -> MergeIntoStmt {
+    let MergeIntoStmt {
+        hints,
+        catalog,
+        database,
+        table_ident,
+        source,
+        alias_target,
+        join_expr,
+        merge_options,
+    } = merge_into;
+
+    let catalog = fold_optional_identifier(folder, catalog);
+    let database = fold_optional_identifier(folder, database);
+    let table_ident = folder.fold_identifier(table_ident);
+    let alias_target = alias_target.map(|alias| folder.fold_table_alias(alias));
+    let source = match source {
+        MergeSource::Select { query } => MergeSource::Select {
+            query: Box::new(folder.fold_query(*query)),
+        },
+        source @ MergeSource::StreamingV2 { .. } => source,
+    };
+    let join_expr = folder.fold_expr(join_expr);
+    let merge_options = merge_options
+        .into_iter()
+        .map(|option| match option {
+            MergeOption::Match(MatchedClause {
+                selection,
+                operation,
+            }) => MergeOption::Match(MatchedClause {
+                selection: selection.map(|selection| folder.fold_expr(selection)),
+                operation: match operation {
+                    MatchOperation::Update { update_list } => MatchOperation::Update {
+                        update_list: update_list
+                            .into_iter()
+                            .map(|update| fold_merge_update_expr(folder, update))
+                            .collect(),
+                    },
+                    MatchOperation::Delete => MatchOperation::Delete,
+                },
+            }),
+            MergeOption::Unmatch(UnmatchedClause {
+                selection,
+                insert_operation: InsertOperation { columns, values },
+            }) => MergeOption::Unmatch(UnmatchedClause {
+                selection: selection.map(|selection| folder.fold_expr(selection)),
+                insert_operation: InsertOperation {
+                    columns: columns.map(|columns| fold_identifiers(folder, columns)),
+                    values: fold_exprs(folder, values),
+                },
+            }),
+        })
+        .collect();
+
+    MergeIntoStmt {
+        hints,
+        catalog,
+        database,
+        table_ident,
+        source,
+        alias_target,
+        join_expr,
+        merge_options,
+    }
+}
+
+fn fold_merge_update_expr<F: Fold>(folder: &mut F, update: MergeUpdateExpr) -> MergeUpdateExpr {
+    let MergeUpdateExpr {
+        catalog,
+        table,
+        name,
+        expr,
+    } = update;
+
+    MergeUpdateExpr {
+        catalog: fold_optional_identifier(folder, catalog),
+        table: fold_optional_identifier(folder, table),
+        name: folder.fold_identifier(name),
+        expr: folder.fold_expr(expr),
+    }
+}
+
+pub fn fold_update<F: Fold>(folder: &mut F, update: UpdateStmt) -> UpdateStmt {
+    let UpdateStmt {
+        hints,
+        table,
+        update_list,
+        selection,
+    } = update;
+
+    UpdateStmt {
+        hints,
+        table: folder.fold_table_reference(table),
+        update_list: update_list
+            .into_iter()
+            .map(|UpdateExpr { name, expr }| UpdateExpr {
+                name: folder.fold_identifier(name),
+                expr: folder.fold_expr(expr),
+            })
+            .collect(),
+        selection: selection.map(|selection| folder.fold_expr(selection)),
+    }
+}
+
+pub fn fold_copy<F: Fold>(folder: &mut F, copy: CopyStmt) -> CopyStmt {
+    let CopyStmt {
+        hints,
+        src,
+        dst,
+        files,
+        pattern,
+        file_format,
+        validation_mode,
+        size_limit,
+        max_files,
+        max_file_size,
+        split_size,
+        single,
+        purge,
+        force,
+        disable_variant_check,
+        on_error,
+        partition_by,
+        header,
+        include_query_id,
+    } = copy;
+
+    CopyStmt {
+        hints,
+        src: folder.fold_copy_unit(src),
+        dst: folder.fold_copy_unit(dst),
+        files,
+        pattern,
+        file_format,
+        validation_mode,
+        size_limit,
+        max_files,
+        max_file_size,
+        split_size,
+        single,
+        purge,
+        force,
+        disable_variant_check,
+        on_error,
+        partition_by: partition_by.map(|expr| fold_boxed_expr(folder, expr)),
+        header,
+        include_query_id,
+    }
+}
+
+pub fn fold_copy_unit<F: Fold>(folder: &mut F, unit: CopyUnit) -> CopyUnit {
+    match unit {
+        CopyUnit::Table {
+            catalog,
+            database,
+            table,
+            columns,
+        } => CopyUnit::Table {
+            catalog: fold_optional_identifier(folder, catalog),
+            database: fold_optional_identifier(folder, database),
+            table: folder.fold_identifier(table),
+            columns: columns.map(|columns| fold_identifiers(folder, columns)),
+        },
+        CopyUnit::Location(location) => CopyUnit::Location(location),
+        CopyUnit::Query(query) => CopyUnit::Query(Box::new(folder.fold_query(*query))),
+    }
+}
+
+pub fn fold_create_table<F: Fold>(folder: &mut F, stmt: CreateTableStmt) -> CreateTableStmt {
+    let CreateTableStmt {
+        if_not_exists,
+        catalog,
+        database,
+        table,
+        source,
+        engine,
+        uri_location,
+        cluster_by,
+        table_options,
+        as_query,
+        transient,
+    } = stmt;
+
+    CreateTableStmt {
+        if_not_exists,
+        catalog: fold_optional_identifier(folder, catalog),
+        database: fold_optional_identifier(folder, database),
+        table: folder.fold_identifier(table),
+        source: source.map(|source| match source {
+            CreateTableSource::Columns(columns) => CreateTableSource::Columns(
+                columns
+                    .into_iter()
+                    .map(|column| folder.fold_column_definition(column))
+                    .collect(),
+            ),
+            CreateTableSource::Like {
+                catalog,
+                database,
+                table,
+            } => CreateTableSource::Like {
+                catalog: fold_optional_identifier(folder, catalog),
+                database: fold_optional_identifier(folder, database),
+                table: folder.fold_identifier(table),
+            },
+        }),
+        engine,
+        uri_location,
+        cluster_by: fold_exprs(folder, cluster_by),
+        table_options,
+        as_query: as_query.map(|query| Box::new(folder.fold_query(*query))),
+        transient,
+    }
+}
+
+pub fn fold_column_definition<F: Fold>(
+    folder: &mut F,
+    column: ColumnDefinition,
+) -> ColumnDefinition {
+    let ColumnDefinition {
+        name,
+        data_type,
+        expr,
+        comment,
+        nullable_constraint,
+    } = column;
+
+    ColumnDefinition {
+        name: folder.fold_identifier(name),
+        data_type: folder.fold_type_name(data_type),
+        expr: expr.map(|expr| match expr {
+            ColumnExpr::Default(expr) => ColumnExpr::Default(fold_boxed_expr(folder, expr)),
+            ColumnExpr::Virtual(expr) => ColumnExpr::Virtual(fold_boxed_expr(folder, expr)),
+            ColumnExpr::Stored(expr) => ColumnExpr::Stored(fold_boxed_expr(folder, expr)),
+        }),
+        comment,
+        nullable_constraint,
+    }
+}
+
+pub fn fold_alter_table<F: Fold>(folder: &mut F, stmt: AlterTableStmt) -> AlterTableStmt {
+    let AlterTableStmt {
+        if_exists,
+        table_reference,
+        action,
+    } = stmt;
+
+    let table_reference = folder.fold_table_reference(table_reference);
+    let action = match action {
+        AlterTableAction::RenameTable { new_table } => AlterTableAction::RenameTable {
+            new_table: folder.fold_identifier(new_table),
+        },
+        AlterTableAction::AddColumn { column, option } => AlterTableAction::AddColumn {
+            column: folder.fold_column_definition(column),
+            option: match option {
+                AddColumnOption::After(ident) => {
+                    AddColumnOption::After(folder.fold_identifier(ident))
+                }
+                option @ (AddColumnOption::First | AddColumnOption::End) => option,
+            },
+        },
+        AlterTableAction::RenameColumn {
+            old_column,
+            new_column,
+        } => AlterTableAction::RenameColumn {
+            old_column: folder.fold_identifier(old_column),
+            new_column: folder.fold_identifier(new_column),
+        },
+        AlterTableAction::ModifyColumn { action } => AlterTableAction::ModifyColumn {
+            action: match action {
+                ModifyColumnAction::SetMaskingPolicy(column, policy) => {
+                    ModifyColumnAction::SetMaskingPolicy(folder.fold_identifier(column), policy)
+                }
+                ModifyColumnAction::UnsetMaskingPolicy(column) => {
+                    ModifyColumnAction::UnsetMaskingPolicy(folder.fold_identifier(column))
+                }
+                ModifyColumnAction::SetDataType(columns) => ModifyColumnAction::SetDataType(
+                    columns
+                        .into_iter()
+                        .map(|column| folder.fold_column_definition(column))
+                        .collect(),
+                ),
+                ModifyColumnAction::ConvertStoredComputedColumn(column) => {
+                    ModifyColumnAction::ConvertStoredComputedColumn(folder.fold_identifier(column))
+                }
+            },
+        },
+        AlterTableAction::DropColumn { column } => AlterTableAction::DropColumn {
+            column: folder.fold_identifier(column),
+        },
+        AlterTableAction::AlterTableClusterKey { cluster_by } => {
+            AlterTableAction::AlterTableClusterKey {
+                cluster_by: fold_exprs(folder, cluster_by),
+            }
+        }
+        AlterTableAction::DropTableClusterKey => AlterTableAction::DropTableClusterKey,
+        AlterTableAction::ReclusterTable {
+            is_final,
+            selection,
+            limit,
+        } => AlterTableAction::ReclusterTable {
+            is_final,
+            selection: selection.map(|selection| folder.fold_expr(selection)),
+            limit,
+        },
+        AlterTableAction::RevertTo { point } => AlterTableAction::RevertTo {
+            point: folder.fold_time_travel_point(point),
+        },
+        AlterTableAction::SetOptions { set_options } => {
+            AlterTableAction::SetOptions { set_options }
+        }
+    };
+
+    AlterTableStmt {
+        if_exists,
+        table_reference,
+        action,
+    }
+}
+
+pub fn fold_create_view<F: Fold>(folder: &mut F, stmt: CreateViewStmt) -> CreateViewStmt {
+    let CreateViewStmt {
+        if_not_exists,
+        catalog,
+        database,
+        view,
+        columns,
+        query,
+    } = stmt;
+
+    CreateViewStmt {
+        if_not_exists,
+        catalog: fold_optional_identifier(folder, catalog),
+        database: fold_optional_identifier(folder, database),
+        view: folder.fold_identifier(view),
+        columns: fold_identifiers(folder, columns),
+        query: Box::new(folder.fold_query(*query)),
+    }
+}
+
+pub fn fold_alter_view<F: Fold>(folder: &mut F, stmt: AlterViewStmt) -> AlterViewStmt {
+    let AlterViewStmt {
+        catalog,
+        database,
+        view,
+        columns,
+        query,
+    } = stmt;
+
+    AlterViewStmt {
+        catalog: fold_optional_identifier(folder, catalog),
+        database: fold_optional_identifier(folder, database),
+        view: folder.fold_identifier(view),
+        columns: fold_identifiers(folder, columns),
+        query: Box::new(folder.fold_query(*query)),
+    }
+}
+
+pub fn fold_create_index<F: Fold>(folder: &mut F, stmt: CreateIndexStmt) -> CreateIndexStmt {
+    let CreateIndexStmt {
+        index_type,
+        if_not_exists,
+        index_name,
+        query,
+        sync_creation,
+    } = stmt;
+
+    CreateIndexStmt {
+        index_type,
+        if_not_exists,
+        index_name: folder.fold_identifier(index_name),
+        query: Box::new(folder.fold_query(*query)),
+        sync_creation,
+    }
+}
+
+pub fn fold_create_virtual_column<F: Fold>(
+    folder: &mut F,
+    stmt: CreateVirtualColumnStmt,
+) -> CreateVirtualColumnStmt {
+    let CreateVirtualColumnStmt {
+        catalog,
+        database,
+        table,
+        virtual_columns,
+    } = stmt;
+
+    CreateVirtualColumnStmt {
+        catalog: fold_optional_identifier(folder, catalog),
+        database: fold_optional_identifier(folder, database),
+        table: folder.fold_identifier(table),
+        virtual_columns: fold_exprs(folder, virtual_columns),
+    }
+}
+
+pub fn fold_alter_virtual_column<F: Fold>(
+    folder: &mut F,
+    stmt: AlterVirtualColumnStmt,
+) -> AlterVirtualColumnStmt {
+    let AlterVirtualColumnStmt {
+        catalog,
+        database,
+        table,
+        virtual_columns,
+    } = stmt;
+
+    AlterVirtualColumnStmt {
+        catalog: fold_optional_identifier(folder, catalog),
+        database: fold_optional_identifier(folder, database),
+        table: folder.fold_identifier(table),
+        virtual_columns: fold_exprs(folder, virtual_columns),
+    }
+}
+
+pub fn fold_show_limit<F: Fold>(folder: &mut F, limit: ShowLimit) -> ShowLimit {
+    match limit {
+        ShowLimit::Like { pattern } => ShowLimit::Like { pattern },
+        ShowLimit::Where { selection } => ShowLimit::Where {
+            selection: fold_boxed_expr(folder, selection),
+        },
+    }
+}
+
+pub fn fold_udf_definition<F: Fold>(folder: &mut F, definition: UDFDefinition) -> UDFDefinition {
+    match definition {
+        UDFDefinition::LambdaUDF {
+            parameters,
+            definition,
+        } => UDFDefinition::LambdaUDF {
+            parameters: fold_identifiers(folder, parameters),
+            definition: fold_boxed_expr(folder, definition),
+        },
+        UDFDefinition::UDFServer {
+            arg_types,
+            return_type,
+            address,
+            handler,
+            language,
+        } => UDFDefinition::UDFServer {
+            arg_types: arg_types
+                .into_iter()
+                .map(|arg_type| folder.fold_type_name(arg_type))
+                .collect(),
+            return_type: folder.fold_type_name(return_type),
+            address,
+            handler,
+            language,
+        },
+    }
+}
+
+pub fn fold_data_mask_policy<F: Fold>(folder: &mut F, policy: DataMaskPolicy) -> DataMaskPolicy {
+    let DataMaskPolicy {
+        args,
+        return_type,
+        body,
+        comment,
+    } = policy;
+
+    DataMaskPolicy {
+        args: args
+            .into_iter()
+            .map(|DataMaskArg { arg_name, arg_type }| DataMaskArg {
+                arg_name,
+                arg_type: folder.fold_type_name(arg_type),
+            })
+            .collect(),
+        return_type: folder.fold_type_name(return_type),
+        body: folder.fold_expr(body),
+        comment,
+    }
+}
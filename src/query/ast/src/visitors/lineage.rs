@@ -0,0 +1,993 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use common_exception::ErrorCode;
+use common_exception::Span;
+
+use super::ObjectName;
+use super::Visitor;
+use crate::ast::*;
+
+/// Provides the columns of the tables, which are used to expand `*` and to
+/// resolve the unqualified column names.
+pub trait SchemaProvider {
+    /// Return the column names of the table in order, or `None` if the table is unknown.
+    fn table_columns(
+        &self,
+        catalog: Option<&str>,
+        database: Option<&str>,
+        table: &str,
+    ) -> Option<Vec<String>>;
+}
+
+/// A column of a table that a target column is derived from.
+///
+/// `table` is `None` if the column can not be resolved to a single table, and
+/// `column` is `*` if the columns of the table are unknown.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SourceColumn {
+    pub database: Option<String>,
+    pub table: Option<String>,
+    pub column: String,
+}
+
+impl Display for SourceColumn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(database) = &self.database {
+            write!(f, "{database}.")?;
+        }
+        if let Some(table) = &self.table {
+            write!(f, "{table}.")?;
+        }
+        write!(f, "{}", self.column)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnLineage {
+    pub target: String,
+    pub sources: BTreeSet<SourceColumn>,
+}
+
+impl Display for ColumnLineage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} <- {{", self.target)?;
+        for (i, source) in self.sources.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{source}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// The lineage of the columns written by a statement, see [`column_lineage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementLineage {
+    /// The table or view that is written, `None` for a query.
+    pub target: Option<ObjectName>,
+    pub columns: Vec<ColumnLineage>,
+}
+
+impl Display for StatementLineage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(target) = &self.target {
+            writeln!(f, "{target}")?;
+        }
+        for column in &self.columns {
+            writeln!(f, "{column}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineageError {
+    /// The number of the target columns doesn't match the output columns of the query.
+    ColumnCountMismatch { expected: usize, actual: usize },
+    /// The target columns can't be matched by position with a `*` that is not expanded.
+    UnexpandedWildcard,
+}
+
+impl Display for LineageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineageError::ColumnCountMismatch { expected, actual } => write!(
+                f,
+                "expected {expected} columns for the target, but the query returns {actual} columns"
+            ),
+            LineageError::UnexpandedWildcard => write!(
+                f,
+                "the target columns can't be matched with `*` without the schema of its tables"
+            ),
+        }
+    }
+}
+
+impl From<LineageError> for ErrorCode {
+    fn from(err: LineageError) -> Self {
+        ErrorCode::BadArguments(err.to_string())
+    }
+}
+
+/// Return the source columns of each column written by `INSERT ... SELECT`, `REPLACE ... SELECT`,
+/// `CREATE TABLE ... AS SELECT`, `MERGE INTO` and `CREATE VIEW`, or of each output column of a query.
+///
+/// The target columns are matched with the output columns of the query by position, and an error
+/// is returned if their numbers differ or a `*` is not expanded. The `GROUP BY` items are sources
+/// of all the output columns of a grouped query. Without the schema of a table, `*` is not
+/// expanded and the unqualified names are resolved by the tables in `FROM` only if there is no
+/// ambiguity. `None` is returned for other statements.
+pub fn column_lineage(
+    stmt: &Statement,
+    schema: Option<&dyn SchemaProvider>,
+) -> Result<Option<StatementLineage>, LineageError> {
+    let mut analyzer = LineageAnalyzer {
+        schema,
+        scopes: vec![],
+        ctes: vec![],
+    };
+
+    let lineage = match stmt {
+        Statement::Query(query) => StatementLineage {
+            target: None,
+            columns: analyzer.query(query),
+        },
+        Statement::Insert(InsertStmt {
+            catalog,
+            database,
+            table,
+            columns,
+            source: InsertSource::Select { query },
+            ..
+        })
+        | Statement::Replace(ReplaceStmt {
+            catalog,
+            database,
+            table,
+            columns,
+            source: InsertSource::Select { query },
+            ..
+        }) => {
+            let outputs = analyzer.query(query);
+            let targets = analyzer.target_columns(catalog, database, table, columns);
+            StatementLineage {
+                target: Some(table_object(catalog, database, table)),
+                columns: match_targets(targets, outputs)?,
+            }
+        }
+        Statement::CreateTable(CreateTableStmt {
+            catalog,
+            database,
+            table,
+            source,
+            as_query: Some(query),
+            ..
+        }) => {
+            let outputs = analyzer.query(query);
+            let targets = match source {
+                Some(CreateTableSource::Columns(columns)) => columns
+                    .iter()
+                    .map(|column| column.name.name.clone())
+                    .collect(),
+                _ => vec![],
+            };
+            StatementLineage {
+                target: Some(table_object(catalog, database, table)),
+                columns: match_targets(targets, outputs)?,
+            }
+        }
+        Statement::CreateView(CreateViewStmt {
+            catalog,
+            database,
+            view,
+            columns,
+            query,
+            ..
+        })
+        | Statement::AlterView(AlterViewStmt {
+            catalog,
+            database,
+            view,
+            columns,
+            query,
+        }) => {
+            let outputs = analyzer.query(query);
+            let targets = columns.iter().map(|column| column.name.clone()).collect();
+            StatementLineage {
+                target: Some(ObjectName::View {
+                    catalog: catalog.as_ref().map(|ident| ident.name.clone()),
+                    database: database.as_ref().map(|ident| ident.name.clone()),
+                    view: view.name.clone(),
+                }),
+                columns: match_targets(targets, outputs)?,
+            }
+        }
+        Statement::MergeInto(stmt) => analyzer.merge_into(stmt),
+        _ => return Ok(None),
+    };
+    Ok(Some(lineage))
+}
+
+fn table_object(
+    catalog: &Option<Identifier>,
+    database: &Option<Identifier>,
+    table: &Identifier,
+) -> ObjectName {
+    ObjectName::Table {
+        catalog: catalog.as_ref().map(|ident| ident.name.clone()),
+        database: database.as_ref().map(|ident| ident.name.clone()),
+        table: table.name.clone(),
+    }
+}
+
+fn column_name(column: &ColumnID) -> String {
+    match column {
+        ColumnID::Name(ident) => ident.name.clone(),
+        ColumnID::Position(pos) => pos.name(),
+    }
+}
+
+fn source(database: Option<&str>, table: Option<&str>, column: &str) -> BTreeSet<SourceColumn> {
+    BTreeSet::from([SourceColumn {
+        database: database.map(|s| s.to_string()),
+        table: table.map(|s| s.to_string()),
+        column: column.to_string(),
+    }])
+}
+
+// Match the target columns with the output columns by position, the output names are
+// used if the target columns are unknown. The columns can't be matched if a `*` is not
+// expanded.
+fn match_targets(
+    targets: Vec<String>,
+    outputs: Vec<ColumnLineage>,
+) -> Result<Vec<ColumnLineage>, LineageError> {
+    if targets.is_empty() {
+        return Ok(outputs);
+    }
+    if outputs.iter().any(|output| output.target == "*") {
+        return Err(LineageError::UnexpandedWildcard);
+    }
+    if targets.len() != outputs.len() {
+        return Err(LineageError::ColumnCountMismatch {
+            expected: targets.len(),
+            actual: outputs.len(),
+        });
+    }
+    Ok(targets
+        .into_iter()
+        .zip(outputs)
+        .map(|(target, output)| ColumnLineage {
+            target,
+            sources: output.sources,
+        })
+        .collect())
+}
+
+fn rename(mut columns: Vec<ColumnLineage>, names: &[Identifier]) -> Vec<ColumnLineage> {
+    for (column, name) in columns.iter_mut().zip(names) {
+        column.target = name.name.clone();
+    }
+    columns
+}
+
+fn add_lineage(columns: &mut Vec<ColumnLineage>, target: String, sources: BTreeSet<SourceColumn>) {
+    match columns.iter_mut().find(|column| column.target == target) {
+        Some(column) => column.sources.extend(sources),
+        None => columns.push(ColumnLineage { target, sources }),
+    }
+}
+
+#[derive(Debug, Clone)]
+enum RelationColumns {
+    /// The columns of a derived table or a table with known schema.
+    Known(Vec<ColumnLineage>),
+    /// A table with unknown schema.
+    Table {
+        database: Option<String>,
+        table: String,
+    },
+    /// A table function or a stage, whose columns are not derived from any table.
+    Opaque,
+}
+
+#[derive(Debug, Clone)]
+struct Relation {
+    database: Option<String>,
+    // The alias or the name of the table, `None` for an anonymous subquery.
+    name: Option<String>,
+    columns: RelationColumns,
+}
+
+impl Relation {
+    fn matches(&self, database: Option<&str>, table: &str) -> bool {
+        self.name.as_deref() == Some(table)
+            && (database.is_none() || self.database.as_deref() == database)
+    }
+
+    // Whether any column name could be resolved by the relation.
+    fn is_open(&self) -> bool {
+        match &self.columns {
+            RelationColumns::Known(columns) => columns.iter().any(|column| column.target == "*"),
+            RelationColumns::Table { .. } => true,
+            RelationColumns::Opaque => false,
+        }
+    }
+
+    fn resolve_known(&self, column: &str) -> Option<BTreeSet<SourceColumn>> {
+        match &self.columns {
+            RelationColumns::Known(columns) => columns
+                .iter()
+                .find(|c| c.target == column)
+                .map(|c| c.sources.clone()),
+            _ => None,
+        }
+    }
+
+    fn resolve(&self, column: &str) -> BTreeSet<SourceColumn> {
+        match &self.columns {
+            RelationColumns::Known(columns) => {
+                if let Some(sources) = self.resolve_known(column) {
+                    return sources;
+                }
+                // The column may come from a `*` that is not expanded, e.g. `SELECT * FROM t`.
+                let star = columns.iter().find(|c| c.target == "*");
+                match star {
+                    Some(star) => star
+                        .sources
+                        .iter()
+                        .map(|source| SourceColumn {
+                            column: column.to_string(),
+                            ..source.clone()
+                        })
+                        .collect(),
+                    None => source(None, self.name.as_deref(), column),
+                }
+            }
+            RelationColumns::Table { database, table } => {
+                source(database.as_deref(), Some(table), column)
+            }
+            RelationColumns::Opaque => BTreeSet::new(),
+        }
+    }
+
+    fn expand(&self, excluded: &[String], columns: &mut Vec<ColumnLineage>) {
+        match &self.columns {
+            RelationColumns::Known(known) => columns.extend(
+                known
+                    .iter()
+                    .filter(|column| !excluded.contains(&column.target))
+                    .cloned(),
+            ),
+            RelationColumns::Table { database, table } => columns.push(ColumnLineage {
+                target: "*".to_string(),
+                sources: source(database.as_deref(), Some(table), "*"),
+            }),
+            RelationColumns::Opaque => columns.push(ColumnLineage {
+                target: "*".to_string(),
+                sources: BTreeSet::new(),
+            }),
+        }
+    }
+}
+
+struct Scope<'a> {
+    relations: Vec<Relation>,
+    windows: &'a [WindowDefinition],
+}
+
+struct LineageAnalyzer<'a> {
+    schema: Option<&'a dyn SchemaProvider>,
+    // The relations in `FROM` of the enclosing queries, the innermost is the last.
+    scopes: Vec<Scope<'a>>,
+    // The CTEs in scope, one frame for each `WITH` clause.
+    ctes: Vec<Vec<(String, Vec<ColumnLineage>)>>,
+}
+
+impl<'a> LineageAnalyzer<'a> {
+    fn target_columns(
+        &self,
+        catalog: &Option<Identifier>,
+        database: &Option<Identifier>,
+        table: &Identifier,
+        columns: &[Identifier],
+    ) -> Vec<String> {
+        if !columns.is_empty() {
+            return columns.iter().map(|column| column.name.clone()).collect();
+        }
+        self.schema
+            .and_then(|schema| {
+                schema.table_columns(
+                    catalog.as_ref().map(|ident| ident.name.as_str()),
+                    database.as_ref().map(|ident| ident.name.as_str()),
+                    &table.name,
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    // The columns of a table with known schema, each derives from itself.
+    fn table_columns(
+        &self,
+        catalog: &Option<Identifier>,
+        database: Option<&str>,
+        table: &str,
+    ) -> Option<Vec<ColumnLineage>> {
+        let columns = self.schema?.table_columns(
+            catalog.as_ref().map(|ident| ident.name.as_str()),
+            database,
+            table,
+        )?;
+        Some(
+            columns
+                .into_iter()
+                .map(|column| ColumnLineage {
+                    sources: source(database, Some(table), &column),
+                    target: column,
+                })
+                .collect(),
+        )
+    }
+
+    fn cte(&self, name: &str) -> Option<&Vec<ColumnLineage>> {
+        self.ctes
+            .iter()
+            .rev()
+            .flat_map(|frame| frame.iter().rev())
+            .find(|(cte, _)| cte == name)
+            .map(|(_, columns)| columns)
+    }
+
+    fn query(&mut self, query: &'a Query) -> Vec<ColumnLineage> {
+        if let Some(with) = &query.with {
+            self.ctes.push(vec![]);
+            for cte in &with.ctes {
+                let name = cte.alias.name.name.clone();
+                if with.recursive {
+                    // The first pass finds the names of the columns, then the recursive
+                    // references are resolved repeatedly until the sources are stable.
+                    self.ctes.last_mut().unwrap().push((name, vec![]));
+                    let mut columns = rename(self.query(&cte.query), &cte.alias.columns);
+                    for column in columns.iter_mut() {
+                        column.sources.clear();
+                    }
+                    loop {
+                        self.ctes.last_mut().unwrap().last_mut().unwrap().1 = columns.clone();
+                        let next = rename(self.query(&cte.query), &cte.alias.columns);
+                        if next == columns {
+                            break;
+                        }
+                        columns = next;
+                    }
+                } else {
+                    let columns = rename(self.query(&cte.query), &cte.alias.columns);
+                    self.ctes.last_mut().unwrap().push((name, columns));
+                }
+            }
+        }
+
+        let columns = self.set_expr(&query.body);
+
+        if query.with.is_some() {
+            self.ctes.pop();
+        }
+        columns
+    }
+
+    fn set_expr(&mut self, set_expr: &'a SetExpr) -> Vec<ColumnLineage> {
+        match set_expr {
+            SetExpr::Select(select) => self.select(select),
+            SetExpr::Query(query) => self.query(query),
+            SetExpr::SetOperation(op) => {
+                let mut left = self.set_expr(&op.left);
+                let right = self.set_expr(&op.right);
+                for (left, right) in left.iter_mut().zip(right) {
+                    left.sources.extend(right.sources);
+                }
+                left
+            }
+            SetExpr::Values { values, .. } => {
+                let mut columns: Vec<ColumnLineage> = vec![];
+                for row in values {
+                    for (i, value) in row.iter().enumerate() {
+                        let sources = self.expr(value);
+                        add_lineage(&mut columns, format!("col{}", i), sources);
+                    }
+                }
+                columns
+            }
+        }
+    }
+
+    fn select(&mut self, select: &'a SelectStmt) -> Vec<ColumnLineage> {
+        let mut relations = vec![];
+        for table_ref in &select.from {
+            self.table_reference(table_ref, &mut relations);
+        }
+        self.scopes.push(Scope {
+            relations,
+            windows: select.window_list.as_deref().unwrap_or_default(),
+        });
+
+        let mut columns = vec![];
+        let mut aliases = vec![];
+        for target in &select.select_list {
+            match target {
+                SelectTarget::AliasedExpr { expr, alias } => {
+                    if let Some(alias) = alias {
+                        aliases.push(alias.name.as_str());
+                    }
+                    let target = match (alias, expr.as_ref()) {
+                        (Some(alias), _) => alias.name.clone(),
+                        (None, Expr::ColumnRef { column, .. }) => column_name(column),
+                        (None, expr) => expr.to_string(),
+                    };
+                    let sources = self.expr(expr);
+                    columns.push(ColumnLineage { target, sources });
+                }
+                SelectTarget::QualifiedName { qualified, exclude } => {
+                    let excluded: Vec<String> = exclude.iter().flatten().map(column_name).collect();
+                    self.qualified_name(qualified, &excluded, &mut columns);
+                }
+            }
+        }
+
+        if let Some(group_by) = &select.group_by {
+            let sources = self.group_by(group_by, &aliases, &columns);
+            for column in columns.iter_mut() {
+                column.sources.extend(sources.iter().cloned());
+            }
+        }
+
+        self.scopes.pop();
+        columns
+    }
+
+    // The sources of the `GROUP BY` items, which may refer to the output columns by
+    // position or alias.
+    fn group_by(
+        &mut self,
+        group_by: &'a GroupBy,
+        aliases: &[&str],
+        columns: &[ColumnLineage],
+    ) -> BTreeSet<SourceColumn> {
+        let items: Vec<&Expr> = match group_by {
+            GroupBy::Normal(exprs) | GroupBy::Cube(exprs) | GroupBy::Rollup(exprs) => {
+                exprs.iter().collect()
+            }
+            GroupBy::GroupingSets(sets) => sets.iter().flatten().collect(),
+            // The items of `GROUP BY ALL` are the output columns that are not aggregated,
+            // which can't be told apart without knowing the aggregate functions.
+            GroupBy::All => vec![],
+        };
+        let mut sources = BTreeSet::new();
+        for item in items {
+            match item {
+                Expr::Literal {
+                    lit: Literal::UInt64(position),
+                    ..
+                } => {
+                    let column = (*position as usize)
+                        .checked_sub(1)
+                        .and_then(|i| columns.get(i));
+                    if let Some(column) = column {
+                        sources.extend(column.sources.iter().cloned());
+                    }
+                }
+                Expr::ColumnRef {
+                    database: None,
+                    table: None,
+                    column: ColumnID::Name(name),
+                    ..
+                } if aliases.contains(&name.name.as_str()) => {
+                    if let Some(column) = columns.iter().find(|column| column.target == name.name) {
+                        sources.extend(column.sources.iter().cloned());
+                    }
+                }
+                _ => sources.extend(self.expr(item)),
+            }
+        }
+        sources
+    }
+
+    fn qualified_name(
+        &mut self,
+        qualified: &'a QualifiedName,
+        excluded: &[String],
+        columns: &mut Vec<ColumnLineage>,
+    ) {
+        let names: Vec<&str> = qualified
+            .iter()
+            .filter_map(|indirection| match indirection {
+                Indirection::Identifier(ident) => Some(ident.name.as_str()),
+                Indirection::Star(_) => None,
+            })
+            .collect();
+
+        if !matches!(qualified.last(), Some(Indirection::Star(_))) {
+            // A qualified column, e.g. `t.a`.
+            if let [.., column] = names.as_slice() {
+                let (database, table) = match names.as_slice() {
+                    [.., database, table, _] => (Some(*database), Some(*table)),
+                    [.., table, _] => (None, Some(*table)),
+                    _ => (None, None),
+                };
+                columns.push(ColumnLineage {
+                    target: column.to_string(),
+                    sources: self.resolve(database, table, column),
+                });
+            }
+            return;
+        }
+
+        // `*`, `t.*` or `db.t.*`
+        let (database, table) = match names.as_slice() {
+            [] => (None, None),
+            [table] => (None, Some(*table)),
+            [.., database, table] => (Some(*database), Some(*table)),
+        };
+        let Some(scope) = self.scopes.last() else {
+            return;
+        };
+        match table {
+            None => {
+                for relation in &scope.relations {
+                    relation.expand(excluded, columns);
+                }
+            }
+            Some(table) => match scope
+                .relations
+                .iter()
+                .find(|relation| relation.matches(database, table))
+            {
+                Some(relation) => relation.expand(excluded, columns),
+                None => columns.push(ColumnLineage {
+                    target: "*".to_string(),
+                    sources: source(database, Some(table), "*"),
+                }),
+            },
+        }
+    }
+
+    fn table_reference(&mut self, table_ref: &'a TableReference, relations: &mut Vec<Relation>) {
+        match table_ref {
+            TableReference::Table {
+                catalog,
+                database,
+                table,
+                alias,
+                ..
+            } => {
+                let database = database.as_ref().map(|ident| ident.name.clone());
+                let cte = if catalog.is_none() && database.is_none() {
+                    self.cte(&table.name).cloned()
+                } else {
+                    None
+                };
+                let known =
+                    cte.or_else(|| self.table_columns(catalog, database.as_deref(), &table.name));
+                let columns = match known {
+                    Some(known) => RelationColumns::Known(match alias {
+                        Some(alias) => rename(known, &alias.columns),
+                        None => known,
+                    }),
+                    None => RelationColumns::Table {
+                        database: database.clone(),
+                        table: table.name.clone(),
+                    },
+                };
+                relations.push(Relation {
+                    database: if alias.is_some() { None } else { database },
+                    name: Some(match alias {
+                        Some(alias) => alias.name.name.clone(),
+                        None => table.name.clone(),
+                    }),
+                    columns,
+                });
+            }
+            TableReference::Subquery {
+                subquery, alias, ..
+            } => {
+                let columns = self.query(subquery);
+                relations.push(Relation {
+                    database: None,
+                    name: alias.as_ref().map(|alias| alias.name.name.clone()),
+                    columns: RelationColumns::Known(match alias {
+                        Some(alias) => rename(columns, &alias.columns),
+                        None => columns,
+                    }),
+                });
+            }
+            TableReference::TableFunction { name, alias, .. } => relations.push(Relation {
+                database: None,
+                name: Some(match alias {
+                    Some(alias) => alias.name.name.clone(),
+                    None => name.name.clone(),
+                }),
+                columns: RelationColumns::Opaque,
+            }),
+            TableReference::Location { alias, .. } => relations.push(Relation {
+                database: None,
+                name: alias.as_ref().map(|alias| alias.name.name.clone()),
+                columns: RelationColumns::Opaque,
+            }),
+            TableReference::Join { join, .. } => {
+                self.table_reference(&join.left, relations);
+                self.table_reference(&join.right, relations);
+            }
+        }
+    }
+
+    fn resolve(
+        &self,
+        database: Option<&str>,
+        table: Option<&str>,
+        column: &str,
+    ) -> BTreeSet<SourceColumn> {
+        for scope in self.scopes.iter().rev() {
+            if let Some(table) = table {
+                if let Some(relation) = scope
+                    .relations
+                    .iter()
+                    .find(|relation| relation.matches(database, table))
+                {
+                    return relation.resolve(column);
+                }
+                continue;
+            }
+
+            if let Some(sources) = scope
+                .relations
+                .iter()
+                .find_map(|relation| relation.resolve_known(column))
+            {
+                return sources;
+            }
+            let open: Vec<_> = scope
+                .relations
+                .iter()
+                .filter(|relation| relation.is_open())
+                .collect();
+            match open.as_slice() {
+                [relation] => return relation.resolve(column),
+                [] => {}
+                // Ambiguous without the schema.
+                _ => return source(None, None, column),
+            }
+            if scope
+                .relations
+                .iter()
+                .any(|relation| matches!(relation.columns, RelationColumns::Opaque))
+            {
+                return BTreeSet::new();
+            }
+        }
+        source(database, table, column)
+    }
+
+    fn expr(&mut self, expr: &'a Expr) -> BTreeSet<SourceColumn> {
+        let mut refs = ExprRefs::default();
+        refs.visit_expr(expr);
+        self.expr_refs(refs)
+    }
+
+    fn expr_refs(&mut self, mut refs: ExprRefs<'a>) -> BTreeSet<SourceColumn> {
+        // Collect the columns in the named windows, e.g. `OVER w ... WINDOW w AS (...)`.
+        let mut visited = vec![];
+        while let Some(name) = refs.windows.pop() {
+            if visited.contains(&name) {
+                continue;
+            }
+            let windows = self
+                .scopes
+                .last()
+                .map(|scope| scope.windows)
+                .unwrap_or_default();
+            if let Some(window) = windows.iter().find(|window| window.name.name == name) {
+                refs.visit_window_spec(&window.spec);
+            }
+            visited.push(name);
+        }
+
+        let mut sources = BTreeSet::new();
+        for (database, table, column) in &refs.columns {
+            sources.extend(self.resolve(database.as_deref(), table.as_deref(), column));
+        }
+        for subquery in refs.subqueries {
+            for column in self.query(subquery) {
+                sources.extend(column.sources);
+            }
+        }
+        sources
+    }
+
+    fn merge_into(&mut self, stmt: &'a MergeIntoStmt) -> StatementLineage {
+        let database = stmt.database.as_ref().map(|ident| ident.name.clone());
+        let target = Relation {
+            database: database.clone(),
+            name: Some(match &stmt.alias_target {
+                Some(alias) => alias.name.name.clone(),
+                None => stmt.table_ident.name.clone(),
+            }),
+            columns: match self.table_columns(
+                &stmt.catalog,
+                database.as_deref(),
+                &stmt.table_ident.name,
+            ) {
+                Some(columns) => RelationColumns::Known(columns),
+                None => RelationColumns::Table {
+                    database,
+                    table: stmt.table_ident.name.clone(),
+                },
+            },
+        };
+        let mut relations = vec![target];
+        relations.push(Relation {
+            database: None,
+            name: None,
+            columns: match &stmt.source {
+                MergeSource::Select { query } => RelationColumns::Known(self.query(query)),
+                MergeSource::StreamingV2 { .. } => RelationColumns::Opaque,
+            },
+        });
+        self.scopes.push(Scope {
+            relations,
+            windows: &[],
+        });
+
+        let mut columns = vec![];
+        for option in &stmt.merge_options {
+            match option {
+                MergeOption::Match(MatchedClause {
+                    operation: MatchOperation::Update { update_list },
+                    ..
+                }) => {
+                    for update in update_list {
+                        let sources = self.expr(&update.expr);
+                        add_lineage(&mut columns, update.name.name.clone(), sources);
+                    }
+                }
+                MergeOption::Match(_) => {}
+                MergeOption::Unmatch(clause) => {
+                    let insert = &clause.insert_operation;
+                    let targets = self.target_columns(
+                        &stmt.catalog,
+                        &stmt.database,
+                        &stmt.table_ident,
+                        insert.columns.as_deref().unwrap_or_default(),
+                    );
+                    for (i, value) in insert.values.iter().enumerate() {
+                        let sources = self.expr(value);
+                        let target = targets
+                            .get(i)
+                            .cloned()
+                            .unwrap_or_else(|| format!("col{}", i));
+                        add_lineage(&mut columns, target, sources);
+                    }
+                }
+            }
+        }
+
+        self.scopes.pop();
+        StatementLineage {
+            target: Some(table_object(
+                &stmt.catalog,
+                &stmt.database,
+                &stmt.table_ident,
+            )),
+            columns,
+        }
+    }
+}
+
+// The columns, subqueries and named windows referenced by an expression, the
+// subqueries are not visited.
+#[derive(Default)]
+struct ExprRefs<'a> {
+    columns: Vec<(Option<String>, Option<String>, String)>,
+    subqueries: Vec<&'a Query>,
+    windows: Vec<String>,
+    lambda_params: Vec<String>,
+}
+
+impl<'a> ExprRefs<'a> {
+    fn visit_window_spec(&mut self, spec: &'a WindowSpec) {
+        if let Some(name) = &spec.existing_window_name {
+            self.windows.push(name.name.clone());
+        }
+        for expr in &spec.partition_by {
+            self.visit_expr(expr);
+        }
+        for order_by in &spec.order_by {
+            self.visit_expr(&order_by.expr);
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for ExprRefs<'a> {
+    fn visit_column_ref(
+        &mut self,
+        _span: Span,
+        database: &'a Option<Identifier>,
+        table: &'a Option<Identifier>,
+        column: &'a ColumnID,
+    ) {
+        let column = column_name(column);
+        if table.is_none() && self.lambda_params.contains(&column) {
+            return;
+        }
+        self.columns.push((
+            database.as_ref().map(|ident| ident.name.clone()),
+            table.as_ref().map(|ident| ident.name.clone()),
+            column,
+        ));
+    }
+
+    fn visit_function_call(
+        &mut self,
+        _span: Span,
+        _distinct: bool,
+        _name: &'a Identifier,
+        args: &'a [Expr],
+        _params: &'a [Literal],
+        over: &'a Option<Window>,
+        lambda: &'a Option<Lambda>,
+    ) {
+        for arg in args {
+            self.visit_expr(arg);
+        }
+        if let Some(over) = over {
+            self.visit_window(over);
+        }
+        if let Some(lambda) = lambda {
+            let len = self.lambda_params.len();
+            self.lambda_params
+                .extend(lambda.params.iter().map(|param| param.name.clone()));
+            self.visit_expr(&lambda.expr);
+            self.lambda_params.truncate(len);
+        }
+    }
+
+    fn visit_window(&mut self, window: &'a Window) {
+        match window {
+            Window::WindowReference(reference) => {
+                self.windows.push(reference.window_name.name.clone())
+            }
+            Window::WindowSpec(spec) => self.visit_window_spec(spec),
+        }
+    }
+
+    fn visit_in_subquery(&mut self, _span: Span, expr: &'a Expr, subquery: &'a Query, _not: bool) {
+        self.visit_expr(expr);
+        self.subqueries.push(subquery);
+    }
+
+    // The value of `EXISTS` is not derived from the columns of the subquery.
+    fn visit_exists(&mut self, _span: Span, _not: bool, _subquery: &'a Query) {}
+
+    fn visit_subquery(
+        &mut self,
+        _span: Span,
+        _modifier: &'a Option<SubqueryModifier>,
+        subquery: &'a Query,
+    ) {
+        self.subqueries.push(subquery);
+    }
+}
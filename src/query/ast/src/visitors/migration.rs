@@ -0,0 +1,387 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use common_exception::ErrorCode;
+use common_exception::Span;
+
+use super::Visitor;
+use crate::ast::*;
+
+/// Options of [`plan_table_migration`].
+#[derive(Debug, Clone, Default)]
+pub struct MigrationOptions {
+    /// Allow changes that may lose data or fail on existing rows: dropping a column,
+    /// changing the type of a column or making it `NOT NULL`.
+    pub allow_destructive: bool,
+    /// Columns that are renamed, from the old name to the new name, with unquoted
+    /// names in lowercase. Without a hint a renamed column is planned as a drop and
+    /// an add.
+    pub renames: BTreeMap<String, String>,
+}
+
+/// Error of a migration that can't be planned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationError {
+    /// The change may lose data but [`MigrationOptions::allow_destructive`] is not set.
+    Destructive(String),
+    /// A rename hint doesn't match the columns of the tables.
+    InvalidRename { old: String, new: String },
+    /// The change can't be expressed with `ALTER TABLE`.
+    Unsupported(String),
+}
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationError::Destructive(change) => {
+                write!(f, "{change} is destructive, which is not allowed")
+            }
+            MigrationError::InvalidRename { old, new } => write!(
+                f,
+                "invalid rename of column `{old}` to `{new}`: `{old}` must be an old column and `{new}` a new one"
+            ),
+            MigrationError::Unsupported(change) => {
+                write!(f, "{change} is not supported by ALTER TABLE")
+            }
+        }
+    }
+}
+
+impl From<MigrationError> for ErrorCode {
+    fn from(err: MigrationError) -> Self {
+        ErrorCode::BadArguments(err.to_string())
+    }
+}
+
+/// Plan the `ALTER TABLE` statements that migrate a table from the `old` definition
+/// to the `new` one, in the order they should run:
+///
+/// 1. `DROP CLUSTER KEY` if the cluster key is removed, or refers to a column that
+///    is renamed, dropped or modified below.
+/// 2. `RENAME COLUMN` for the rename hints.
+/// 3. `DROP COLUMN` for the columns that are gone.
+/// 4. `MODIFY COLUMN` for the columns with a new type, nullability, default or
+///    comment, or with a stored computed expression that becomes a plain column.
+/// 5. `ADD COLUMN` for the new columns, placed after the column that precedes them.
+/// 6. `CLUSTER BY` if the cluster key changed or was dropped in step 1.
+/// 7. `SET OPTIONS` for the new and changed table options.
+/// 8. `RENAME TO` if the table name changed.
+///
+/// Unquoted column names are compared case-insensitively. The order of existing
+/// columns is not migrated.
+pub fn plan_table_migration(
+    old: &CreateTableStmt,
+    new: &CreateTableStmt,
+    options: &MigrationOptions,
+) -> Result<Vec<AlterTableStmt>, MigrationError> {
+    let (old_columns, new_columns) = match (&old.source, &new.source) {
+        (Some(CreateTableSource::Columns(old)), Some(CreateTableSource::Columns(new))) => {
+            (old, new)
+        }
+        _ => {
+            return Err(MigrationError::Unsupported(
+                "migrating a table without a column list".to_string(),
+            ));
+        }
+    };
+    if old.engine != new.engine {
+        return Err(MigrationError::Unsupported(
+            "changing the engine".to_string(),
+        ));
+    }
+    if old.transient != new.transient {
+        return Err(MigrationError::Unsupported(
+            "changing whether the table is transient".to_string(),
+        ));
+    }
+    if old.uri_location.as_ref().map(ToString::to_string)
+        != new.uri_location.as_ref().map(ToString::to_string)
+    {
+        return Err(MigrationError::Unsupported(
+            "changing the location".to_string(),
+        ));
+    }
+
+    let mut planner = Planner {
+        options,
+        actions: vec![],
+        changed_columns: BTreeSet::new(),
+    };
+    planner.columns(old_columns, new_columns)?;
+    planner.cluster_by(&old.cluster_by, &new.cluster_by);
+    planner.table_options(&old.table_options, &new.table_options)?;
+    if column_name(&old.table) != column_name(&new.table) {
+        planner.actions.push(AlterTableAction::RenameTable {
+            new_table: new.table.clone(),
+        });
+    }
+
+    let table_reference = TableReference::Table {
+        span: None,
+        catalog: old.catalog.clone(),
+        database: old.database.clone(),
+        table: old.table.clone(),
+        alias: None,
+        travel_point: None,
+        pivot: None,
+        unpivot: None,
+    };
+    Ok(planner
+        .actions
+        .into_iter()
+        .map(|action| AlterTableStmt {
+            if_exists: false,
+            table_reference: table_reference.clone(),
+            action,
+        })
+        .collect())
+}
+
+struct Planner<'a> {
+    options: &'a MigrationOptions,
+    actions: Vec<AlterTableAction>,
+    /// The old names of the columns that are renamed, dropped or modified.
+    changed_columns: BTreeSet<String>,
+}
+
+impl Planner<'_> {
+    fn columns(
+        &mut self,
+        old_columns: &[ColumnDefinition],
+        new_columns: &[ColumnDefinition],
+    ) -> Result<(), MigrationError> {
+        // The old name of every column that is kept, by its new name.
+        let mut kept = BTreeMap::new();
+        for column in old_columns {
+            let name = column_name(&column.name);
+            if find_column(new_columns, &name).is_some()
+                && !self.options.renames.contains_key(&name)
+            {
+                kept.insert(name.clone(), name);
+            }
+        }
+        for (old_name, new_name) in &self.options.renames {
+            let invalid = || MigrationError::InvalidRename {
+                old: old_name.clone(),
+                new: new_name.clone(),
+            };
+            let old_column = find_column(old_columns, old_name).ok_or_else(invalid)?;
+            let new_column = find_column(new_columns, new_name).ok_or_else(invalid)?;
+            if kept.contains_key(new_name) {
+                return Err(invalid());
+            }
+            kept.insert(new_name.clone(), old_name.clone());
+            self.changed_columns.insert(old_name.clone());
+            self.actions.push(AlterTableAction::RenameColumn {
+                old_column: old_column.name.clone(),
+                new_column: new_column.name.clone(),
+            });
+        }
+
+        for column in old_columns {
+            let name = column_name(&column.name);
+            if !kept.values().any(|old_name| *old_name == name) {
+                self.destructive(format!("dropping column `{name}`"))?;
+                self.changed_columns.insert(name);
+                self.actions.push(AlterTableAction::DropColumn {
+                    column: column.name.clone(),
+                });
+            }
+        }
+
+        let mut modified = vec![];
+        for new_column in new_columns {
+            let Some(old_name) = kept.get(&column_name(&new_column.name)) else {
+                continue;
+            };
+            let old_column = find_column(old_columns, old_name).unwrap();
+            let modified_len = modified.len();
+            if let Some(action) = self.modify_column(old_column, new_column, &mut modified)? {
+                self.changed_columns.insert(old_name.clone());
+                self.actions.push(action);
+            } else if modified.len() > modified_len {
+                self.changed_columns.insert(old_name.clone());
+            }
+        }
+        if !modified.is_empty() {
+            self.actions.push(AlterTableAction::ModifyColumn {
+                action: ModifyColumnAction::SetDataType(modified),
+            });
+        }
+
+        for (i, column) in new_columns.iter().enumerate() {
+            if kept.contains_key(&column_name(&column.name)) {
+                continue;
+            }
+            let option = if i == 0 {
+                AddColumnOption::First
+            } else if i == new_columns.len() - 1 {
+                AddColumnOption::End
+            } else {
+                AddColumnOption::After(new_columns[i - 1].name.clone())
+            };
+            self.actions.push(AlterTableAction::AddColumn {
+                column: column.clone(),
+                option,
+            });
+        }
+        Ok(())
+    }
+
+    /// Compare a kept column. Changes that `MODIFY COLUMN ... <type>` can apply
+    /// are collected into `modified`, other changes are returned as an action.
+    fn modify_column(
+        &self,
+        old: &ColumnDefinition,
+        new: &ColumnDefinition,
+        modified: &mut Vec<ColumnDefinition>,
+    ) -> Result<Option<AlterTableAction>, MigrationError> {
+        let name = column_name(&new.name);
+        let old_expr = old.expr.as_ref().map(ToString::to_string);
+        let new_expr = new.expr.as_ref().map(ToString::to_string);
+        match (&old.expr, &new.expr) {
+            (Some(ColumnExpr::Stored(_)), None) if same_type(old, new) => {
+                return Ok(Some(AlterTableAction::ModifyColumn {
+                    action: ModifyColumnAction::ConvertStoredComputedColumn(new.name.clone()),
+                }));
+            }
+            (Some(ColumnExpr::Virtual(_) | ColumnExpr::Stored(_)), _)
+            | (_, Some(ColumnExpr::Virtual(_) | ColumnExpr::Stored(_)))
+                if old_expr != new_expr || !same_type(old, new) =>
+            {
+                return Err(MigrationError::Unsupported(format!(
+                    "changing computed column `{name}`"
+                )));
+            }
+            _ => (),
+        }
+
+        if same_type(old, new) && old_expr == new_expr && old.comment == new.comment {
+            return Ok(None);
+        }
+        if !same_type(old, new) && !is_nullable_widening(old, new) {
+            self.destructive(format!(
+                "changing the type of column `{name}` from {} to {}",
+                old.data_type, new.data_type
+            ))?;
+        }
+        modified.push(new.clone());
+        Ok(None)
+    }
+
+    /// The cluster key is planned after the columns, as the new key may refer to the
+    /// new columns, while the old key is dropped before the columns it refers to change.
+    fn cluster_by(&mut self, old: &[Expr], new: &[Expr]) {
+        let old_keys = old.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let new_keys = new.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let changed = old_keys != new_keys;
+        let mut key_columns = KeyColumns::default();
+        for expr in old {
+            key_columns.visit_expr(expr);
+        }
+        let columns_changed = key_columns
+            .names
+            .iter()
+            .any(|name| self.changed_columns.contains(name));
+
+        if !old.is_empty() && (columns_changed || (changed && new.is_empty())) {
+            self.actions
+                .insert(0, AlterTableAction::DropTableClusterKey);
+        }
+        if !new.is_empty() && (changed || columns_changed) {
+            self.actions.push(AlterTableAction::AlterTableClusterKey {
+                cluster_by: new.to_vec(),
+            });
+        }
+    }
+
+    fn table_options(
+        &mut self,
+        old: &BTreeMap<String, String>,
+        new: &BTreeMap<String, String>,
+    ) -> Result<(), MigrationError> {
+        if let Some(key) = old.keys().find(|key| !new.contains_key(*key)) {
+            return Err(MigrationError::Unsupported(format!(
+                "removing table option `{key}`"
+            )));
+        }
+        let set_options = new
+            .iter()
+            .filter(|(key, value)| old.get(*key) != Some(*value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<BTreeMap<_, _>>();
+        if !set_options.is_empty() {
+            self.actions
+                .push(AlterTableAction::SetOptions { set_options });
+        }
+        Ok(())
+    }
+
+    fn destructive(&self, change: String) -> Result<(), MigrationError> {
+        if self.options.allow_destructive {
+            Ok(())
+        } else {
+            Err(MigrationError::Destructive(change))
+        }
+    }
+}
+
+/// The names of the columns an expression refers to.
+#[derive(Default)]
+struct KeyColumns {
+    names: BTreeSet<String>,
+}
+
+impl<'ast> Visitor<'ast> for KeyColumns {
+    fn visit_column_ref(
+        &mut self,
+        _span: Span,
+        _database: &'ast Option<Identifier>,
+        _table: &'ast Option<Identifier>,
+        column: &'ast ColumnID,
+    ) {
+        if let ColumnID::Name(name) = column {
+            self.names.insert(column_name(name));
+        }
+    }
+}
+
+/// Unquoted identifiers are case-insensitive.
+fn column_name(ident: &Identifier) -> String {
+    if ident.quote.is_some() {
+        ident.name.clone()
+    } else {
+        ident.name.to_lowercase()
+    }
+}
+
+fn find_column<'a>(columns: &'a [ColumnDefinition], name: &str) -> Option<&'a ColumnDefinition> {
+    columns.iter().find(|c| column_name(&c.name) == name)
+}
+
+/// Columns are not nullable unless the type is wrapped by `NULL`, so an explicit
+/// `NOT NULL` doesn't change the type.
+fn same_type(old: &ColumnDefinition, new: &ColumnDefinition) -> bool {
+    old.data_type == new.data_type
+}
+
+/// `T` to `T NULL` keeps every existing value.
+fn is_nullable_widening(old: &ColumnDefinition, new: &ColumnDefinition) -> bool {
+    matches!(&new.data_type, TypeName::Nullable(inner) if old.data_type == **inner)
+}
@@ -12,11 +12,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod anonymizer;
+mod canonical;
+mod classification;
+mod control_flow;
+mod control_flow_mut;
+mod diff;
+mod fingerprint;
+mod fold;
+mod lineage;
+mod migration;
+mod params;
+mod referenced_objects;
+mod simplifier;
 mod visitor;
 mod visitor_mut;
 mod walk;
 mod walk_mut;
 
+pub use anonymizer::*;
+pub use classification::*;
+pub use control_flow::*;
+pub use control_flow_mut::*;
+pub use diff::*;
+pub use fingerprint::*;
+pub use fold::*;
+pub use lineage::*;
+pub use migration::*;
+pub use params::*;
+pub use referenced_objects::*;
+pub use simplifier::*;
 pub use visitor::Visitor;
 pub use visitor_mut::VisitorMut;
 pub use walk::*;
@@ -0,0 +1,268 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use common_exception::ErrorCode;
+use ethnum::i256;
+
+use super::fold_expr;
+use super::Fold;
+use crate::ast::*;
+use crate::Dialect;
+
+/// A typed value bound to a placeholder.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamValue {
+    Null,
+    Boolean(bool),
+    Int(i64),
+    UInt(u64),
+    /// A float, cast to `Float64` from its text so that it reads back the same.
+    Float(f64),
+    /// The decimal `value / 10^scale`.
+    Decimal {
+        value: i256,
+        scale: u8,
+    },
+    String(String),
+    /// A date such as `2023-06-01`, kept as text and cast to `DATE`.
+    Date(String),
+    /// A timestamp such as `2023-06-01 12:00:00`, kept as text and cast to `TIMESTAMP`.
+    Timestamp(String),
+    Binary(Vec<u8>),
+}
+
+/// The values of the placeholders of a statement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Params {
+    /// Bound to the `?` placeholders in the order they appear, or to `$n` by the
+    /// position `n`, counted from 1.
+    Positional(Vec<ParamValue>),
+    /// Bound to the `$n` placeholders by the name, e.g. `"$1"`.
+    Named(BTreeMap<String, ParamValue>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindError {
+    /// Both `?` and `$n` placeholders are used.
+    MixedPlaceholders,
+    /// The number of values doesn't match the placeholders.
+    ArityMismatch { expected: usize, actual: usize },
+    /// A `?` placeholder can't be bound by name.
+    UnnamedPlaceholder,
+    /// No value is given for the `$n` placeholder.
+    MissingParam(String),
+    /// The value isn't bound to any placeholder.
+    UnusedParam(String),
+}
+
+impl Display for BindError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BindError::MixedPlaceholders => {
+                write!(f, "`?` and `$n` placeholders can't be used together")
+            }
+            BindError::ArityMismatch { expected, actual } => {
+                write!(f, "expected {expected} parameters, but got {actual}")
+            }
+            BindError::UnnamedPlaceholder => {
+                write!(f, "`?` placeholders can't be bound by name")
+            }
+            BindError::MissingParam(name) => write!(f, "no value for parameter `{name}`"),
+            BindError::UnusedParam(name) => write!(f, "parameter `{name}` is not used"),
+        }
+    }
+}
+
+impl From<BindError> for ErrorCode {
+    fn from(err: BindError) -> Self {
+        ErrorCode::BadArguments(err.to_string())
+    }
+}
+
+/// Replace the placeholders of a statement with literals of the values, for
+/// backends that can't bind parameters on the server side.
+///
+/// The placeholders are `?`, bound in the order they appear, and `$n`, bound by
+/// position or name. Note that `$n` is also the syntax of the columns of a stage,
+/// which are bound as well. The rows of `INSERT ... VALUES` are left in the
+/// source, so their placeholders are read as `None` by
+/// [`ValuesRowReader`](crate::parser::ValuesRowReader) and bound by the caller.
+///
+/// The values are rendered so that the statement displays as valid SQL of the
+/// dialect: strings are escaped when displayed, dates and timestamps are cast from
+/// strings (with `::` for PostgreSQL), binary values are decoded from hex with
+/// `from_hex`, and negative numbers are negated literals.
+pub fn bind_params(
+    stmt: Statement,
+    params: &Params,
+    dialect: Dialect,
+) -> Result<Statement, BindError> {
+    let mut binder = ParamBinder {
+        params,
+        dialect,
+        question_marks: 0,
+        names: BTreeSet::new(),
+        max_position: 0,
+    };
+    let stmt = binder.fold_statement(stmt);
+    binder.check()?;
+    Ok(stmt)
+}
+
+/// Render a value as a literal expression of the dialect.
+pub fn param_value_to_expr(value: &ParamValue, dialect: Dialect) -> Expr {
+    let literal = |lit| Expr::Literal { span: None, lit };
+    let negate = |expr| Expr::UnaryOp {
+        span: None,
+        op: UnaryOperator::Minus,
+        expr: Box::new(expr),
+    };
+    let cast = |text: String, target_type| Expr::Cast {
+        span: None,
+        expr: Box::new(literal(Literal::String(text))),
+        target_type,
+        pg_style: dialect == Dialect::PostgreSQL,
+    };
+
+    match value {
+        ParamValue::Null => literal(Literal::Null),
+        ParamValue::Boolean(value) => literal(Literal::Boolean(*value)),
+        ParamValue::Int(value) => {
+            let abs = literal(Literal::UInt64(value.unsigned_abs()));
+            if *value < 0 { negate(abs) } else { abs }
+        }
+        ParamValue::UInt(value) => literal(Literal::UInt64(*value)),
+        // A float literal would be read back as a decimal or an integer, and loses the
+        // sign of zero, so the value is cast from its shortest round-trip text.
+        ParamValue::Float(value) => cast(format!("{value:?}"), TypeName::Float64),
+        ParamValue::Decimal { value, scale } => {
+            let abs = literal(Literal::Decimal256 {
+                value: value.wrapping_abs(),
+                precision: 76,
+                scale: *scale,
+            });
+            if *value < i256::ZERO {
+                negate(abs)
+            } else {
+                abs
+            }
+        }
+        ParamValue::String(value) => literal(Literal::String(value.clone())),
+        ParamValue::Date(value) => cast(value.clone(), TypeName::Date),
+        ParamValue::Timestamp(value) => cast(value.clone(), TypeName::Timestamp),
+        ParamValue::Binary(value) => {
+            let hex = value.iter().map(|byte| format!("{byte:02x}")).collect();
+            Expr::FunctionCall {
+                span: None,
+                distinct: false,
+                name: Identifier::from_name("from_hex"),
+                args: vec![literal(Literal::String(hex))],
+                params: vec![],
+                window: None,
+                lambda: None,
+            }
+        }
+    }
+}
+
+struct ParamBinder<'a> {
+    params: &'a Params,
+    dialect: Dialect,
+    /// The number of `?` placeholders seen so far.
+    question_marks: usize,
+    /// The names of the `$n` placeholders seen so far.
+    names: BTreeSet<String>,
+    max_position: usize,
+}
+
+impl<'a> ParamBinder<'a> {
+    fn bind_question_mark(&mut self) -> Option<Expr> {
+        self.question_marks += 1;
+        match self.params {
+            Params::Positional(values) => values.get(self.question_marks - 1),
+            Params::Named(_) => None,
+        }
+        .map(|value| param_value_to_expr(value, self.dialect))
+    }
+
+    fn bind_position(&mut self, name: &str, pos: usize) -> Option<Expr> {
+        self.names.insert(name.to_string());
+        self.max_position = self.max_position.max(pos);
+        match self.params {
+            Params::Positional(values) => values.get(pos - 1),
+            Params::Named(values) => values.get(name),
+        }
+        .map(|value| param_value_to_expr(value, self.dialect))
+    }
+
+    fn check(&self) -> Result<(), BindError> {
+        if self.question_marks > 0 && !self.names.is_empty() {
+            return Err(BindError::MixedPlaceholders);
+        }
+        match self.params {
+            Params::Positional(values) => {
+                let expected = self.question_marks.max(self.max_position);
+                if expected != values.len() {
+                    return Err(BindError::ArityMismatch {
+                        expected,
+                        actual: values.len(),
+                    });
+                }
+            }
+            Params::Named(values) => {
+                if self.question_marks > 0 {
+                    return Err(BindError::UnnamedPlaceholder);
+                }
+                if let Some(name) = self.names.iter().find(|name| !values.contains_key(*name)) {
+                    return Err(BindError::MissingParam(name.clone()));
+                }
+                if let Some(name) = values.keys().find(|name| !self.names.contains(*name)) {
+                    return Err(BindError::UnusedParam(name.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Fold for ParamBinder<'a> {
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        match expr {
+            Expr::Placeholder { span } => match self.bind_question_mark() {
+                Some(bound) => bound,
+                None => Expr::Placeholder { span },
+            },
+            Expr::ColumnRef {
+                span,
+                database: None,
+                table: None,
+                column: ColumnID::Position(position),
+            } => match self.bind_position(&position.name, position.pos) {
+                Some(bound) => bound,
+                None => Expr::ColumnRef {
+                    span,
+                    database: None,
+                    table: None,
+                    column: ColumnID::Position(position),
+                },
+            },
+            expr => fold_expr(self, expr),
+        }
+    }
+}
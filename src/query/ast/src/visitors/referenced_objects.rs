@@ -0,0 +1,1161 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use common_exception::Span;
+
+use super::Visitor;
+use crate::ast::*;
+use crate::meta::principal_identity::PrincipalIdentity;
+use crate::meta::user_identity::UserIdentity;
+use crate::meta::ShareGrantObjectName;
+
+/// How a statement accesses an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ObjectAccess {
+    /// The data or the metadata of the object is read, e.g. the tables in `FROM`.
+    Read,
+    /// The data of the object is modified, e.g. the target table of `INSERT`.
+    Write,
+    /// The object itself is created, altered, dropped or granted.
+    Ddl,
+}
+
+impl Display for ObjectAccess {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjectAccess::Read => write!(f, "READ"),
+            ObjectAccess::Write => write!(f, "WRITE"),
+            ObjectAccess::Ddl => write!(f, "DDL"),
+        }
+    }
+}
+
+/// The name of an object referenced by a statement, as it is written in the statement.
+///
+/// Names are not resolved against the current catalog or database, and a table in
+/// a query may also be a view.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ObjectName {
+    Catalog(String),
+    Database {
+        catalog: Option<String>,
+        database: String,
+    },
+    Table {
+        catalog: Option<String>,
+        database: Option<String>,
+        table: String,
+    },
+    View {
+        catalog: Option<String>,
+        database: Option<String>,
+        view: String,
+    },
+    /// A column, `table` is the table name or the alias that qualifies the column.
+    Column {
+        database: Option<String>,
+        table: Option<String>,
+        column: String,
+    },
+    Stage(String),
+    FileFormat(String),
+    /// A scalar, aggregate or table function, including the UDFs.
+    Function(String),
+    Index(String),
+    Share(String),
+    ShareEndpoint(String),
+    User {
+        username: String,
+        hostname: String,
+    },
+    Role(String),
+    MaskingPolicy(String),
+    NetworkPolicy(String),
+}
+
+impl Display for ObjectName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn write_path(
+            f: &mut Formatter<'_>,
+            path: &[&Option<String>],
+            name: &str,
+        ) -> std::fmt::Result {
+            for part in path.iter().filter_map(|part| part.as_ref()) {
+                write!(f, "{part}.")?;
+            }
+            write!(f, "{name}")
+        }
+
+        match self {
+            ObjectName::Catalog(name) => write!(f, "CATALOG {name}"),
+            ObjectName::Database { catalog, database } => {
+                write!(f, "DATABASE ")?;
+                write_path(f, &[catalog], database)
+            }
+            ObjectName::Table {
+                catalog,
+                database,
+                table,
+            } => {
+                write!(f, "TABLE ")?;
+                write_path(f, &[catalog, database], table)
+            }
+            ObjectName::View {
+                catalog,
+                database,
+                view,
+            } => {
+                write!(f, "VIEW ")?;
+                write_path(f, &[catalog, database], view)
+            }
+            ObjectName::Column {
+                database,
+                table,
+                column,
+            } => {
+                write!(f, "COLUMN ")?;
+                write_path(f, &[database, table], column)
+            }
+            ObjectName::Stage(name) => write!(f, "STAGE {name}"),
+            ObjectName::FileFormat(name) => write!(f, "FILE FORMAT {name}"),
+            ObjectName::Function(name) => write!(f, "FUNCTION {name}"),
+            ObjectName::Index(name) => write!(f, "INDEX {name}"),
+            ObjectName::Share(name) => write!(f, "SHARE {name}"),
+            ObjectName::ShareEndpoint(name) => write!(f, "SHARE ENDPOINT {name}"),
+            ObjectName::User { username, hostname } => {
+                write!(f, "USER '{username}'@'{hostname}'")
+            }
+            ObjectName::Role(name) => write!(f, "ROLE {name}"),
+            ObjectName::MaskingPolicy(name) => write!(f, "MASKING POLICY {name}"),
+            ObjectName::NetworkPolicy(name) => write!(f, "NETWORK POLICY {name}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReferencedObject {
+    pub name: ObjectName,
+    pub access: ObjectAccess,
+}
+
+impl Display for ReferencedObject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.access, self.name)
+    }
+}
+
+/// The objects referenced by a statement, see [`referenced_objects`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReferencedObjects {
+    pub objects: BTreeSet<ReferencedObject>,
+}
+
+impl ReferencedObjects {
+    pub fn iter(&self) -> impl Iterator<Item = &ReferencedObject> {
+        self.objects.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// The objects that are accessed in the given way.
+    pub fn with_access(&self, access: ObjectAccess) -> impl Iterator<Item = &ObjectName> {
+        self.objects
+            .iter()
+            .filter(move |object| object.access == access)
+            .map(|object| &object.name)
+    }
+
+    pub fn reads(&self) -> impl Iterator<Item = &ObjectName> {
+        self.with_access(ObjectAccess::Read)
+    }
+
+    pub fn writes(&self) -> impl Iterator<Item = &ObjectName> {
+        self.with_access(ObjectAccess::Write)
+    }
+
+    pub fn ddl_targets(&self) -> impl Iterator<Item = &ObjectName> {
+        self.with_access(ObjectAccess::Ddl)
+    }
+
+    pub fn contains(&self, name: &ObjectName, access: ObjectAccess) -> bool {
+        self.objects.contains(&ReferencedObject {
+            name: name.clone(),
+            access,
+        })
+    }
+}
+
+impl Display for ReferencedObjects {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for object in &self.objects {
+            writeln!(f, "{object}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Collect the catalogs, databases, tables, stages, functions and other objects
+/// that are referenced by the statement.
+///
+/// Names that refer to a CTE in scope are not reported as tables.
+pub fn referenced_objects(stmt: &Statement) -> ReferencedObjects {
+    let mut collector = ReferencedObjectsCollector::default();
+    collector.visit_statement(stmt);
+    ReferencedObjects {
+        objects: collector.objects,
+    }
+}
+
+#[derive(Default)]
+struct ReferencedObjectsCollector {
+    objects: BTreeSet<ReferencedObject>,
+    // The normalized names of the CTEs in scope, one frame for each `WITH` clause.
+    ctes: Vec<Vec<String>>,
+    // The parameters of the lambdas in scope, which are not columns.
+    lambda_params: Vec<String>,
+}
+
+fn ident_name(ident: &Option<Identifier>) -> Option<String> {
+    ident.as_ref().map(|ident| ident.normalized_name())
+}
+
+fn table_name(
+    catalog: &Option<Identifier>,
+    database: &Option<Identifier>,
+    table: &Identifier,
+) -> ObjectName {
+    ObjectName::Table {
+        catalog: ident_name(catalog),
+        database: ident_name(database),
+        table: table.normalized_name(),
+    }
+}
+
+fn database_name(catalog: &Option<Identifier>, database: &Identifier) -> ObjectName {
+    ObjectName::Database {
+        catalog: ident_name(catalog),
+        database: database.normalized_name(),
+    }
+}
+
+fn user_name(user: &UserIdentity) -> ObjectName {
+    ObjectName::User {
+        username: user.username.clone(),
+        hostname: user.hostname.clone(),
+    }
+}
+
+// The stage of a location like `my_stage/path/to/file`, `~` is the user stage.
+fn stage_name(location: &str) -> ObjectName {
+    let name = location.split('/').next().unwrap_or_default();
+    ObjectName::Stage(name.to_string())
+}
+
+impl ReferencedObjectsCollector {
+    fn add(&mut self, name: ObjectName, access: ObjectAccess) {
+        self.objects.insert(ReferencedObject { name, access });
+    }
+
+    fn is_cte(&self, table: &Identifier) -> bool {
+        let name = table.normalized_name();
+        self.ctes.iter().any(|frame| frame.contains(&name))
+    }
+
+    fn add_column(&mut self, table: &ObjectName, column: &Identifier, access: ObjectAccess) {
+        if let ObjectName::Table {
+            database, table, ..
+        } = table
+        {
+            self.add(
+                ObjectName::Column {
+                    database: database.clone(),
+                    table: Some(table.clone()),
+                    column: column.name.clone(),
+                },
+                access,
+            );
+        }
+    }
+
+    fn add_copy_unit(&mut self, unit: &CopyUnit, access: ObjectAccess) {
+        match unit {
+            CopyUnit::Table {
+                catalog,
+                database,
+                table,
+                columns,
+            } => {
+                let name = table_name(catalog, database, table);
+                for column in columns.iter().flatten() {
+                    self.add_column(&name, column, access);
+                }
+                self.add(name, access);
+            }
+            CopyUnit::Location(FileLocation::Stage(location)) => {
+                self.add(stage_name(location), access)
+            }
+            CopyUnit::Location(FileLocation::Uri(_)) => {}
+            CopyUnit::Query(query) => self.visit_query(query),
+        }
+    }
+
+    fn add_file_format(&mut self, options: Option<&FileFormatOptions>) {
+        if let Some(FileFormatOptions::Named(name)) = options {
+            self.add(ObjectName::FileFormat(name.clone()), ObjectAccess::Read);
+        }
+    }
+
+    fn add_insert_source(&mut self, source: &InsertSource) {
+        match source {
+            InsertSource::Select { query } => self.visit_query(query),
+            InsertSource::StreamingV2 { settings, .. } => self.add_file_format(Some(settings)),
+            InsertSource::Streaming { .. } | InsertSource::Values { .. } => {}
+        }
+    }
+
+    fn add_column_definition(&mut self, table: &ObjectName, column: &ColumnDefinition) {
+        self.add_column(table, &column.name, ObjectAccess::Ddl);
+        match &column.expr {
+            Some(ColumnExpr::Default(expr))
+            | Some(ColumnExpr::Virtual(expr))
+            | Some(ColumnExpr::Stored(expr)) => self.visit_expr(expr),
+            None => {}
+        }
+    }
+
+    fn add_alter_table(&mut self, stmt: &AlterTableStmt) {
+        let table = match &stmt.table_reference {
+            TableReference::Table {
+                catalog,
+                database,
+                table,
+                ..
+            } => table_name(catalog, database, table),
+            table_reference => return self.visit_table_reference(table_reference),
+        };
+        match &stmt.action {
+            AlterTableAction::RenameTable { new_table } => {
+                if let ObjectName::Table {
+                    catalog, database, ..
+                } = &table
+                {
+                    self.add(
+                        ObjectName::Table {
+                            catalog: catalog.clone(),
+                            database: database.clone(),
+                            table: new_table.name.clone(),
+                        },
+                        ObjectAccess::Ddl,
+                    );
+                }
+            }
+            AlterTableAction::AddColumn { column, option } => {
+                self.add_column_definition(&table, column);
+                if let AddColumnOption::After(after) = option {
+                    self.add_column(&table, after, ObjectAccess::Read);
+                }
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                self.add_column(&table, old_column, ObjectAccess::Ddl);
+                self.add_column(&table, new_column, ObjectAccess::Ddl);
+            }
+            AlterTableAction::ModifyColumn { action } => match action {
+                ModifyColumnAction::SetMaskingPolicy(column, policy) => {
+                    self.add_column(&table, column, ObjectAccess::Ddl);
+                    self.add(
+                        ObjectName::MaskingPolicy(policy.clone()),
+                        ObjectAccess::Read,
+                    );
+                }
+                ModifyColumnAction::UnsetMaskingPolicy(column)
+                | ModifyColumnAction::ConvertStoredComputedColumn(column) => {
+                    self.add_column(&table, column, ObjectAccess::Ddl);
+                }
+                ModifyColumnAction::SetDataType(columns) => {
+                    for column in columns {
+                        self.add_column_definition(&table, column);
+                    }
+                }
+            },
+            AlterTableAction::DropColumn { column } => {
+                self.add_column(&table, column, ObjectAccess::Ddl);
+            }
+            AlterTableAction::AlterTableClusterKey { cluster_by } => {
+                for expr in cluster_by {
+                    self.visit_expr(expr);
+                }
+            }
+            AlterTableAction::ReclusterTable { selection, .. } => {
+                if let Some(selection) = selection {
+                    self.visit_expr(selection);
+                }
+            }
+            AlterTableAction::RevertTo { point } => self.visit_time_travel_point(point),
+            AlterTableAction::DropTableClusterKey | AlterTableAction::SetOptions { .. } => {}
+        }
+        self.add(table, ObjectAccess::Ddl);
+    }
+
+    fn add_account_mgr_source(&mut self, source: &AccountMgrSource) {
+        let level = match source {
+            AccountMgrSource::Role { role } => {
+                return self.add(ObjectName::Role(role.clone()), ObjectAccess::Read);
+            }
+            AccountMgrSource::Privs { level, .. } | AccountMgrSource::ALL { level } => level,
+        };
+        match level {
+            AccountMgrLevel::Global | AccountMgrLevel::Database(None) => {}
+            AccountMgrLevel::Database(Some(database)) => self.add(
+                ObjectName::Database {
+                    catalog: None,
+                    database: database.clone(),
+                },
+                ObjectAccess::Ddl,
+            ),
+            AccountMgrLevel::Table(database, table) => self.add(
+                ObjectName::Table {
+                    catalog: None,
+                    database: database.clone(),
+                    table: table.clone(),
+                },
+                ObjectAccess::Ddl,
+            ),
+        }
+    }
+
+    fn add_principal(&mut self, principal: &PrincipalIdentity, access: ObjectAccess) {
+        match principal {
+            PrincipalIdentity::User(user) => self.add(user_name(user), access),
+            PrincipalIdentity::Role(role) => self.add(ObjectName::Role(role.clone()), access),
+        }
+    }
+
+    fn add_user_options(&mut self, options: &[UserOptionItem]) {
+        for option in options {
+            match option {
+                UserOptionItem::DefaultRole(role) => {
+                    self.add(ObjectName::Role(role.clone()), ObjectAccess::Read)
+                }
+                UserOptionItem::SetNetworkPolicy(policy) => self.add(
+                    ObjectName::NetworkPolicy(policy.clone()),
+                    ObjectAccess::Read,
+                ),
+                UserOptionItem::TenantSetting(_) | UserOptionItem::UnsetNetworkPolicy => {}
+            }
+        }
+    }
+
+    fn add_udf_definition(&mut self, definition: &UDFDefinition) {
+        if let UDFDefinition::LambdaUDF {
+            parameters,
+            definition,
+        } = definition
+        {
+            let len = self.lambda_params.len();
+            self.lambda_params
+                .extend(parameters.iter().map(|param| param.name.clone()));
+            self.visit_expr(definition);
+            self.lambda_params.truncate(len);
+        }
+    }
+
+    fn add_share_object(&mut self, object: &ShareGrantObjectName, access: ObjectAccess) {
+        let name = match object {
+            ShareGrantObjectName::Database(database) => ObjectName::Database {
+                catalog: None,
+                database: database.clone(),
+            },
+            ShareGrantObjectName::Table(database, table) => ObjectName::Table {
+                catalog: None,
+                database: Some(database.clone()),
+                table: table.clone(),
+            },
+        };
+        self.add(name, access);
+    }
+}
+
+impl<'ast> Visitor<'ast> for ReferencedObjectsCollector {
+    fn visit_statement(&mut self, stmt: &'ast Statement) {
+        use ObjectAccess::*;
+
+        match stmt {
+            Statement::Query(query) => self.visit_query(query),
+            Statement::Explain { query, .. } => self.visit_statement(query),
+            Statement::ExplainAnalyze { query } => self.visit_statement(query),
+            Statement::Copy(copy) => {
+                self.add_copy_unit(&copy.src, Read);
+                self.add_copy_unit(&copy.dst, Write);
+                if let Some(partition_by) = &copy.partition_by {
+                    self.visit_expr(partition_by);
+                }
+                self.add_file_format(copy.file_format.as_ref());
+            }
+            Statement::Call(call) => self.add(ObjectName::Function(call.name.clone()), Read),
+
+            Statement::ShowSettings { .. }
+            | Statement::ShowProcessList
+            | Statement::ShowMetrics
+            | Statement::ShowEngines
+            | Statement::ShowFunctions { .. }
+            | Statement::ShowTableFunctions { .. }
+            | Statement::ShowIndexes
+            | Statement::KillStmt { .. }
+            | Statement::UnSetVariable(_) => {}
+            Statement::SetVariable { value, .. } => self.visit_expr(value),
+            Statement::SetRole { role_name, .. } => {
+                self.add(ObjectName::Role(role_name.clone()), Read)
+            }
+
+            Statement::Insert(insert) => {
+                let table = table_name(&insert.catalog, &insert.database, &insert.table);
+                for column in &insert.columns {
+                    self.add_column(&table, column, Write);
+                }
+                self.add(table, Write);
+                self.add_insert_source(&insert.source);
+            }
+            Statement::Replace(replace) => {
+                let table = table_name(&replace.catalog, &replace.database, &replace.table);
+                for column in &replace.on_conflict_columns {
+                    self.add_column(&table, column, Read);
+                }
+                for column in &replace.columns {
+                    self.add_column(&table, column, Write);
+                }
+                self.add(table, Write);
+                self.add_insert_source(&replace.source);
+            }
+            Statement::MergeInto(merge) => {
+                let table = table_name(&merge.catalog, &merge.database, &merge.table_ident);
+                match &merge.source {
+                    MergeSource::Select { query } => self.visit_query(query),
+                    MergeSource::StreamingV2 { settings, .. } => {
+                        self.add_file_format(Some(settings))
+                    }
+                }
+                self.visit_expr(&merge.join_expr);
+                for option in &merge.merge_options {
+                    match option {
+                        MergeOption::Match(clause) => {
+                            if let Some(selection) = &clause.selection {
+                                self.visit_expr(selection);
+                            }
+                            if let MatchOperation::Update { update_list } = &clause.operation {
+                                for update in update_list {
+                                    self.add_column(&table, &update.name, Write);
+                                    self.visit_expr(&update.expr);
+                                }
+                            }
+                        }
+                        MergeOption::Unmatch(clause) => {
+                            if let Some(selection) = &clause.selection {
+                                self.visit_expr(selection);
+                            }
+                            let insert = &clause.insert_operation;
+                            for column in insert.columns.iter().flatten() {
+                                self.add_column(&table, column, Write);
+                            }
+                            for value in &insert.values {
+                                self.visit_expr(value);
+                            }
+                        }
+                    }
+                }
+                self.add(table, Write);
+            }
+            Statement::Delete {
+                table_reference,
+                selection,
+                ..
+            } => {
+                if let TableReference::Table {
+                    catalog,
+                    database,
+                    table,
+                    ..
+                } = table_reference
+                {
+                    self.add(table_name(catalog, database, table), Write);
+                }
+                if let Some(selection) = selection {
+                    self.visit_expr(selection);
+                }
+            }
+            Statement::Update(update) => {
+                if let TableReference::Table {
+                    catalog,
+                    database,
+                    table,
+                    ..
+                } = &update.table
+                {
+                    let table = table_name(catalog, database, table);
+                    for update in &update.update_list {
+                        self.add_column(&table, &update.name, Write);
+                    }
+                    self.add(table, Write);
+                }
+                for update in &update.update_list {
+                    self.visit_expr(&update.expr);
+                }
+                if let Some(selection) = &update.selection {
+                    self.visit_expr(selection);
+                }
+            }
+
+            Statement::ShowCatalogs(_) => {}
+            Statement::ShowCreateCatalog(stmt) => {
+                self.add(ObjectName::Catalog(stmt.catalog.name.clone()), Read)
+            }
+            Statement::CreateCatalog(stmt) => {
+                self.add(ObjectName::Catalog(stmt.catalog_name.clone()), Ddl)
+            }
+            Statement::DropCatalog(stmt) => {
+                self.add(ObjectName::Catalog(stmt.catalog.name.clone()), Ddl)
+            }
+
+            Statement::ShowDatabases(stmt) => {
+                if let Some(catalog) = &stmt.catalog {
+                    self.add(ObjectName::Catalog(catalog.name.clone()), Read);
+                }
+            }
+            Statement::ShowCreateDatabase(stmt) => {
+                self.add(database_name(&stmt.catalog, &stmt.database), Read)
+            }
+            Statement::CreateDatabase(stmt) => {
+                self.add(database_name(&stmt.catalog, &stmt.database), Ddl);
+                if let Some(share) = &stmt.from_share {
+                    self.add(
+                        ObjectName::Share(format!("{}.{}", share.tenant, share.share_name)),
+                        Read,
+                    );
+                }
+            }
+            Statement::DropDatabase(stmt) => {
+                self.add(database_name(&stmt.catalog, &stmt.database), Ddl)
+            }
+            Statement::UndropDatabase(stmt) => {
+                self.add(database_name(&stmt.catalog, &stmt.database), Ddl)
+            }
+            Statement::AlterDatabase(stmt) => {
+                self.add(database_name(&stmt.catalog, &stmt.database), Ddl);
+                let AlterDatabaseAction::RenameDatabase { new_db } = &stmt.action;
+                self.add(database_name(&stmt.catalog, new_db), Ddl);
+            }
+            Statement::UseDatabase { database } => self.add(database_name(&None, database), Read),
+
+            Statement::ShowTables(stmt) => {
+                if let Some(database) = &stmt.database {
+                    self.add(database_name(&stmt.catalog, database), Read);
+                } else if let Some(catalog) = &stmt.catalog {
+                    self.add(ObjectName::Catalog(catalog.name.clone()), Read);
+                }
+            }
+            Statement::ShowCreateTable(stmt) => {
+                self.add(table_name(&stmt.catalog, &stmt.database, &stmt.table), Read)
+            }
+            Statement::DescribeTable(stmt) => {
+                self.add(table_name(&stmt.catalog, &stmt.database, &stmt.table), Read)
+            }
+            Statement::ShowTablesStatus(stmt) => {
+                if let Some(database) = &stmt.database {
+                    self.add(database_name(&None, database), Read);
+                }
+            }
+            Statement::ShowDropTables(stmt) => {
+                if let Some(database) = &stmt.database {
+                    self.add(database_name(&None, database), Read);
+                }
+            }
+            Statement::AttachTable(stmt) => {
+                self.add(table_name(&stmt.catalog, &stmt.database, &stmt.table), Ddl)
+            }
+            Statement::CreateTable(stmt) => {
+                let table = table_name(&stmt.catalog, &stmt.database, &stmt.table);
+                match &stmt.source {
+                    Some(CreateTableSource::Columns(columns)) => {
+                        for column in columns {
+                            self.add_column_definition(&table, column);
+                        }
+                    }
+                    Some(CreateTableSource::Like {
+                        catalog,
+                        database,
+                        table,
+                    }) => self.add(table_name(catalog, database, table), Read),
+                    None => {}
+                }
+                for expr in &stmt.cluster_by {
+                    self.visit_expr(expr);
+                }
+                if let Some(query) = &stmt.as_query {
+                    self.visit_query(query);
+                }
+                self.add(table, Ddl);
+            }
+            Statement::DropTable(stmt) => {
+                self.add(table_name(&stmt.catalog, &stmt.database, &stmt.table), Ddl)
+            }
+            Statement::UndropTable(stmt) => {
+                self.add(table_name(&stmt.catalog, &stmt.database, &stmt.table), Ddl)
+            }
+            Statement::AlterTable(stmt) => self.add_alter_table(stmt),
+            Statement::RenameTable(stmt) => {
+                self.add(table_name(&stmt.catalog, &stmt.database, &stmt.table), Ddl);
+                self.add(
+                    table_name(&stmt.new_catalog, &stmt.new_database, &stmt.new_table),
+                    Ddl,
+                );
+            }
+            Statement::TruncateTable(stmt) => self.add(
+                table_name(&stmt.catalog, &stmt.database, &stmt.table),
+                Write,
+            ),
+            Statement::OptimizeTable(stmt) => {
+                if let OptimizeTableAction::Purge {
+                    before: Some(point),
+                } = &stmt.action
+                {
+                    self.visit_time_travel_point(point);
+                }
+                self.add(
+                    table_name(&stmt.catalog, &stmt.database, &stmt.table),
+                    Write,
+                )
+            }
+            Statement::VacuumTable(stmt) => self.add(
+                table_name(&stmt.catalog, &stmt.database, &stmt.table),
+                Write,
+            ),
+            Statement::VacuumDropTable(stmt) => {
+                if let Some(database) = &stmt.database {
+                    self.add(database_name(&stmt.catalog, database), Write);
+                }
+            }
+            Statement::AnalyzeTable(stmt) => self.add(
+                table_name(&stmt.catalog, &stmt.database, &stmt.table),
+                Write,
+            ),
+            Statement::ExistsTable(stmt) => {
+                self.add(table_name(&stmt.catalog, &stmt.database, &stmt.table), Read)
+            }
+            Statement::ShowColumns(stmt) => {
+                self.add(table_name(&stmt.catalog, &stmt.database, &stmt.table), Read)
+            }
+
+            Statement::CreateView(stmt) => {
+                let view = ObjectName::View {
+                    catalog: ident_name(&stmt.catalog),
+                    database: ident_name(&stmt.database),
+                    view: stmt.view.name.clone(),
+                };
+                self.add(view, Ddl);
+                self.visit_query(&stmt.query);
+            }
+            Statement::AlterView(stmt) => {
+                let view = ObjectName::View {
+                    catalog: ident_name(&stmt.catalog),
+                    database: ident_name(&stmt.database),
+                    view: stmt.view.name.clone(),
+                };
+                self.add(view, Ddl);
+                self.visit_query(&stmt.query);
+            }
+            Statement::DropView(stmt) => {
+                let view = ObjectName::View {
+                    catalog: ident_name(&stmt.catalog),
+                    database: ident_name(&stmt.database),
+                    view: stmt.view.name.clone(),
+                };
+                self.add(view, Ddl);
+            }
+
+            Statement::CreateIndex(stmt) => {
+                self.add(ObjectName::Index(stmt.index_name.name.clone()), Ddl);
+                self.visit_query(&stmt.query);
+            }
+            Statement::DropIndex(stmt) => self.add(ObjectName::Index(stmt.index.name.clone()), Ddl),
+            Statement::RefreshIndex(stmt) => {
+                self.add(ObjectName::Index(stmt.index.name.clone()), Write)
+            }
+
+            Statement::CreateVirtualColumn(stmt) => {
+                self.add(table_name(&stmt.catalog, &stmt.database, &stmt.table), Ddl);
+                for expr in &stmt.virtual_columns {
+                    self.visit_expr(expr);
+                }
+            }
+            Statement::AlterVirtualColumn(stmt) => {
+                self.add(table_name(&stmt.catalog, &stmt.database, &stmt.table), Ddl);
+                for expr in &stmt.virtual_columns {
+                    self.visit_expr(expr);
+                }
+            }
+            Statement::DropVirtualColumn(stmt) => {
+                self.add(table_name(&stmt.catalog, &stmt.database, &stmt.table), Ddl)
+            }
+            Statement::RefreshVirtualColumn(stmt) => self.add(
+                table_name(&stmt.catalog, &stmt.database, &stmt.table),
+                Write,
+            ),
+
+            Statement::ShowUsers | Statement::ShowRoles => {}
+            Statement::CreateUser(stmt) => {
+                self.add(user_name(&stmt.user), Ddl);
+                self.add_user_options(&stmt.user_options);
+            }
+            Statement::AlterUser(stmt) => {
+                if let Some(user) = &stmt.user {
+                    self.add(user_name(user), Ddl);
+                }
+                self.add_user_options(&stmt.user_options);
+            }
+            Statement::DropUser { user, .. } => self.add(user_name(user), Ddl),
+            Statement::CreateRole { role_name, .. } | Statement::DropRole { role_name, .. } => {
+                self.add(ObjectName::Role(role_name.clone()), Ddl)
+            }
+            Statement::Grant(stmt) => {
+                self.add_account_mgr_source(&stmt.source);
+                self.add_principal(&stmt.principal, Ddl);
+            }
+            Statement::Revoke(stmt) => {
+                self.add_account_mgr_source(&stmt.source);
+                self.add_principal(&stmt.principal, Ddl);
+            }
+            Statement::ShowGrants { principal } => {
+                if let Some(principal) = principal {
+                    self.add_principal(principal, Read);
+                }
+            }
+
+            Statement::CreateUDF(stmt) => {
+                self.add(ObjectName::Function(stmt.udf_name.name.clone()), Ddl);
+                self.add_udf_definition(&stmt.definition);
+            }
+            Statement::AlterUDF(stmt) => {
+                self.add(ObjectName::Function(stmt.udf_name.name.clone()), Ddl);
+                self.add_udf_definition(&stmt.definition);
+            }
+            Statement::DropUDF { udf_name, .. } => {
+                self.add(ObjectName::Function(udf_name.name.clone()), Ddl)
+            }
+
+            Statement::CreateStage(stmt) => {
+                self.add(ObjectName::Stage(stmt.stage_name.clone()), Ddl);
+                self.add_file_format(stmt.file_format_options.as_ref());
+            }
+            Statement::ShowStages => {}
+            Statement::DropStage { stage_name, .. } => {
+                self.add(ObjectName::Stage(stage_name.clone()), Ddl)
+            }
+            Statement::DescribeStage { stage_name } => {
+                self.add(ObjectName::Stage(stage_name.clone()), Read)
+            }
+            Statement::RemoveStage { location, .. } => self.add(stage_name(location), Write),
+            Statement::ListStage { location, .. } => self.add(stage_name(location), Read),
+
+            Statement::CreateFileFormat { name, .. } | Statement::DropFileFormat { name, .. } => {
+                self.add(ObjectName::FileFormat(name.clone()), Ddl)
+            }
+            Statement::ShowFileFormats => {}
+            Statement::Presign(stmt) => {
+                let PresignLocation::StageLocation(location) = &stmt.location;
+                let access = match stmt.action {
+                    PresignAction::Download => Read,
+                    PresignAction::Upload => Write,
+                };
+                self.add(stage_name(location), access);
+            }
+
+            Statement::CreateShareEndpoint(stmt) => {
+                self.add(ObjectName::ShareEndpoint(stmt.endpoint.name.clone()), Ddl)
+            }
+            Statement::ShowShareEndpoint(_) | Statement::ShowShares(_) => {}
+            Statement::DropShareEndpoint(stmt) => {
+                self.add(ObjectName::ShareEndpoint(stmt.endpoint.name.clone()), Ddl)
+            }
+            Statement::CreateShare(stmt) => {
+                self.add(ObjectName::Share(stmt.share.name.clone()), Ddl)
+            }
+            Statement::DropShare(stmt) => self.add(ObjectName::Share(stmt.share.name.clone()), Ddl),
+            Statement::GrantShareObject(stmt) => {
+                self.add(ObjectName::Share(stmt.share.name.clone()), Ddl);
+                self.add_share_object(&stmt.object, Ddl);
+            }
+            Statement::RevokeShareObject(stmt) => {
+                self.add(ObjectName::Share(stmt.share.name.clone()), Ddl);
+                self.add_share_object(&stmt.object, Ddl);
+            }
+            Statement::AlterShareTenants(stmt) => {
+                self.add(ObjectName::Share(stmt.share.name.clone()), Ddl)
+            }
+            Statement::DescShare(stmt) => {
+                self.add(ObjectName::Share(stmt.share.name.clone()), Read)
+            }
+            Statement::ShowObjectGrantPrivileges(stmt) => self.add_share_object(&stmt.object, Read),
+            Statement::ShowGrantsOfShare(stmt) => {
+                self.add(ObjectName::Share(stmt.share_name.clone()), Read)
+            }
+
+            Statement::CreateDatamaskPolicy(stmt) => {
+                self.add(ObjectName::MaskingPolicy(stmt.name.clone()), Ddl);
+                let len = self.lambda_params.len();
+                self.lambda_params
+                    .extend(stmt.policy.args.iter().map(|arg| arg.arg_name.clone()));
+                self.visit_expr(&stmt.policy.body);
+                self.lambda_params.truncate(len);
+            }
+            Statement::DropDatamaskPolicy(stmt) => {
+                self.add(ObjectName::MaskingPolicy(stmt.name.clone()), Ddl)
+            }
+            Statement::DescDatamaskPolicy(stmt) => {
+                self.add(ObjectName::MaskingPolicy(stmt.name.clone()), Read)
+            }
+
+            Statement::CreateNetworkPolicy(stmt) => {
+                self.add(ObjectName::NetworkPolicy(stmt.name.clone()), Ddl)
+            }
+            Statement::AlterNetworkPolicy(stmt) => {
+                self.add(ObjectName::NetworkPolicy(stmt.name.clone()), Ddl)
+            }
+            Statement::DropNetworkPolicy(stmt) => {
+                self.add(ObjectName::NetworkPolicy(stmt.name.clone()), Ddl)
+            }
+            Statement::DescNetworkPolicy(stmt) => {
+                self.add(ObjectName::NetworkPolicy(stmt.name.clone()), Read)
+            }
+            Statement::ShowNetworkPolicies => {}
+        }
+    }
+
+    fn visit_query(&mut self, query: &'ast Query) {
+        let Query {
+            with,
+            body,
+            order_by,
+            limit,
+            offset,
+            ..
+        } = query;
+
+        // A CTE can be referenced by the CTEs after it and the body of the query,
+        // and also by itself if the `WITH` is recursive.
+        if let Some(with) = with {
+            self.ctes.push(vec![]);
+            for cte in &with.ctes {
+                let name = cte.alias.name.normalized_name();
+                if with.recursive {
+                    self.ctes.last_mut().unwrap().push(name);
+                    self.visit_query(&cte.query);
+                } else {
+                    self.visit_query(&cte.query);
+                    self.ctes.last_mut().unwrap().push(name);
+                }
+            }
+        }
+
+        self.visit_set_expr(body);
+        for order_by in order_by {
+            self.visit_order_by(order_by);
+        }
+        for limit in limit {
+            self.visit_expr(limit);
+        }
+        if let Some(offset) = offset {
+            self.visit_expr(offset);
+        }
+
+        if with.is_some() {
+            self.ctes.pop();
+        }
+    }
+
+    fn visit_select_stmt(&mut self, stmt: &'ast SelectStmt) {
+        let SelectStmt {
+            select_list,
+            from,
+            selection,
+            group_by,
+            having,
+            window_list,
+            ..
+        } = stmt;
+
+        for table_ref in from {
+            self.visit_table_reference(table_ref);
+        }
+        for target in select_list {
+            self.visit_select_target(target);
+        }
+        if let Some(selection) = selection {
+            self.visit_expr(selection);
+        }
+        match group_by {
+            Some(GroupBy::Normal(exprs) | GroupBy::Cube(exprs) | GroupBy::Rollup(exprs)) => {
+                for expr in exprs {
+                    self.visit_expr(expr);
+                }
+            }
+            Some(GroupBy::GroupingSets(sets)) => {
+                for expr in sets.iter().flatten() {
+                    self.visit_expr(expr);
+                }
+            }
+            Some(GroupBy::All) | None => {}
+        }
+        if let Some(having) = having {
+            self.visit_expr(having);
+        }
+        for window in window_list.iter().flatten() {
+            self.visit_window_definition(window);
+        }
+    }
+
+    fn visit_table_reference(&mut self, table_ref: &'ast TableReference) {
+        match table_ref {
+            TableReference::Table {
+                catalog,
+                database,
+                table,
+                travel_point,
+                pivot,
+                ..
+            } => {
+                if catalog.is_some() || database.is_some() || !self.is_cte(table) {
+                    self.add(table_name(catalog, database, table), ObjectAccess::Read);
+                }
+                if let Some(travel_point) = travel_point {
+                    self.visit_time_travel_point(travel_point);
+                }
+                if let Some(pivot) = pivot {
+                    self.visit_expr(&pivot.aggregate);
+                    for value in &pivot.values {
+                        self.visit_expr(value);
+                    }
+                }
+            }
+            TableReference::TableFunction {
+                name,
+                params,
+                named_params,
+                ..
+            } => {
+                self.add(ObjectName::Function(name.name.clone()), ObjectAccess::Read);
+                for param in params {
+                    self.visit_expr(param);
+                }
+                for (_, param) in named_params {
+                    self.visit_expr(param);
+                }
+            }
+            TableReference::Subquery { subquery, .. } => self.visit_query(subquery),
+            TableReference::Join { join, .. } => self.visit_join(join),
+            TableReference::Location { location, .. } => {
+                if let FileLocation::Stage(location) = location {
+                    self.add(stage_name(location), ObjectAccess::Read);
+                }
+            }
+        }
+    }
+
+    fn visit_join(&mut self, join: &'ast Join) {
+        self.visit_table_reference(&join.left);
+        self.visit_table_reference(&join.right);
+        if let JoinCondition::On(expr) = &join.condition {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn visit_column_ref(
+        &mut self,
+        _span: Span,
+        database: &'ast Option<Identifier>,
+        table: &'ast Option<Identifier>,
+        column: &'ast ColumnID,
+    ) {
+        if let ColumnID::Name(column) = column {
+            if table.is_none() && self.lambda_params.contains(&column.name) {
+                return;
+            }
+            self.add(
+                ObjectName::Column {
+                    database: ident_name(database),
+                    table: ident_name(table),
+                    column: column.name.clone(),
+                },
+                ObjectAccess::Read,
+            );
+        }
+    }
+
+    fn visit_function_call(
+        &mut self,
+        _span: Span,
+        _distinct: bool,
+        name: &'ast Identifier,
+        args: &'ast [Expr],
+        _params: &'ast [Literal],
+        over: &'ast Option<Window>,
+        lambda: &'ast Option<Lambda>,
+    ) {
+        self.add(ObjectName::Function(name.name.clone()), ObjectAccess::Read);
+        for arg in args {
+            self.visit_expr(arg);
+        }
+        if let Some(over) = over {
+            self.visit_window(over);
+        }
+        if let Some(lambda) = lambda {
+            let len = self.lambda_params.len();
+            self.lambda_params
+                .extend(lambda.params.iter().map(|param| param.name.clone()));
+            self.visit_expr(&lambda.expr);
+            self.lambda_params.truncate(len);
+        }
+    }
+
+    fn visit_in_subquery(
+        &mut self,
+        _span: Span,
+        expr: &'ast Expr,
+        subquery: &'ast Query,
+        _not: bool,
+    ) {
+        self.visit_expr(expr);
+        self.visit_query(subquery);
+    }
+
+    fn visit_exists(&mut self, _span: Span, _not: bool, subquery: &'ast Query) {
+        self.visit_query(subquery);
+    }
+
+    fn visit_subquery(
+        &mut self,
+        _span: Span,
+        _modifier: &'ast Option<SubqueryModifier>,
+        subquery: &'ast Query,
+    ) {
+        self.visit_query(subquery);
+    }
+}
@@ -0,0 +1,881 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+
+use common_exception::Span;
+use ethnum::i256;
+
+use super::walk_expr_children_mut;
+use super::VisitorMut;
+use crate::ast::*;
+
+/// The maximum number of clauses a normal form conversion may produce, the
+/// expression is left as is if the conversion would exceed it.
+const MAX_NORMAL_FORM_CLAUSES: usize = 256;
+
+/// The maximum precision of `Decimal256`.
+const MAX_DECIMAL_PRECISION: u8 = 76;
+
+/// The precision of a `UInt64` when it is mixed with decimals.
+const UINT64_PRECISION: u8 = 20;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NormalForm {
+    /// Conjunctive normal form, an `AND` of `OR`s.
+    Conjunctive,
+    /// Disjunctive normal form, an `OR` of `AND`s.
+    Disjunctive,
+}
+
+/// Simplify the expression in place, see [`Simplifier`].
+pub fn simplify_expr(expr: &mut Expr) {
+    Simplifier::new().visit_expr(expr);
+}
+
+/// Rewrites expressions into a simpler equivalent form:
+///
+/// - Arithmetic, string concatenation and comparisons of literals are folded.
+///   Decimals keep the precision and scale the operation would produce, and are
+///   left untouched if the result doesn't fit.
+/// - `NOT` is pushed down to the leaves and double negations are removed.
+/// - `AND`, `OR` and `XOR` with boolean literals are short-circuited.
+/// - `BETWEEN` is expanded into comparisons, `IN` lists are deduplicated, and
+///   `IN` lists or equalities of the same expression joined by `OR` are merged.
+/// - Optionally, boolean expressions are converted into a [`NormalForm`].
+///
+/// Operations that could fail at runtime, like division by zero or overflow, are
+/// never folded, so that the error is still reported by the server.
+///
+/// It can also be applied to a whole statement as a [`VisitorMut`].
+#[derive(Debug, Clone, Default)]
+pub struct Simplifier {
+    normal_form: Option<NormalForm>,
+}
+
+impl Simplifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_normal_form(mut self, normal_form: NormalForm) -> Self {
+        self.normal_form = Some(normal_form);
+        self
+    }
+
+    fn simplify(&self, expr: Expr) -> Expr {
+        match expr {
+            Expr::UnaryOp {
+                span,
+                op: UnaryOperator::Not,
+                expr,
+            } => self.negate(span, *expr),
+            Expr::UnaryOp {
+                span,
+                op: op @ (UnaryOperator::Plus | UnaryOperator::Minus),
+                expr,
+            } => {
+                let folded = Number::from_expr(&expr)
+                    .map(|n| {
+                        if op == UnaryOperator::Minus {
+                            n.neg()
+                        } else {
+                            n
+                        }
+                    })
+                    .and_then(|n| n.to_expr(span));
+                folded.unwrap_or(Expr::UnaryOp { span, op, expr })
+            }
+            Expr::BinaryOp {
+                span,
+                op: BinaryOperator::And,
+                left,
+                right,
+            } => self.and(span, *left, *right),
+            Expr::BinaryOp {
+                span,
+                op: BinaryOperator::Or,
+                left,
+                right,
+            } => self.or(span, *left, *right),
+            Expr::BinaryOp {
+                span,
+                op,
+                left,
+                right,
+            } => fold_binary(span, &op, &left, &right).unwrap_or(Expr::BinaryOp {
+                span,
+                op,
+                left,
+                right,
+            }),
+            Expr::IsNull { span, expr, not } => match literal_of(&expr) {
+                Some(lit) => bool_literal(span, (lit == Literal::Null) != not),
+                None => Expr::IsNull { span, expr, not },
+            },
+            Expr::IsDistinctFrom {
+                span,
+                left,
+                right,
+                not,
+            } => {
+                let distinct = match (literal_of(&left), literal_of(&right)) {
+                    (Some(Literal::Null), Some(Literal::Null)) => Some(false),
+                    (Some(Literal::Null), Some(_)) | (Some(_), Some(Literal::Null)) => Some(true),
+                    _ => compare(&left, &right).map(|ord| ord != Ordering::Equal),
+                };
+                match distinct {
+                    Some(distinct) => bool_literal(span, distinct != not),
+                    None => Expr::IsDistinctFrom {
+                        span,
+                        left,
+                        right,
+                        not,
+                    },
+                }
+            }
+            Expr::InList {
+                span,
+                expr,
+                list,
+                not,
+            } => self.in_list(span, *expr, list, not),
+            // Only expand `BETWEEN` if the expression is cheap and deterministic to be
+            // evaluated twice.
+            Expr::Between {
+                span,
+                expr,
+                low,
+                high,
+                not,
+            } if is_simple(&expr) => {
+                let (low_op, high_op, join) = if not {
+                    (BinaryOperator::Lt, BinaryOperator::Gt, BinaryOperator::Or)
+                } else {
+                    (
+                        BinaryOperator::Gte,
+                        BinaryOperator::Lte,
+                        BinaryOperator::And,
+                    )
+                };
+                let low = self.simplify(binary(span, low_op, (*expr).clone(), *low));
+                let high = self.simplify(binary(span, high_op, *expr, *high));
+                self.simplify(binary(span, join, low, high))
+            }
+            expr => expr,
+        }
+    }
+
+    /// Build the simplified negation of an already simplified expression.
+    fn negate(&self, span: Span, expr: Expr) -> Expr {
+        match expr {
+            Expr::Literal {
+                span,
+                lit: Literal::Boolean(b),
+            } => bool_literal(span, !b),
+            Expr::Literal {
+                lit: Literal::Null, ..
+            } => expr,
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr,
+                ..
+            } => *expr,
+            Expr::BinaryOp {
+                span,
+                op: BinaryOperator::And,
+                left,
+                right,
+            } => {
+                let left = self.negate(span, *left);
+                let right = self.negate(span, *right);
+                self.or(span, left, right)
+            }
+            Expr::BinaryOp {
+                span,
+                op: BinaryOperator::Or,
+                left,
+                right,
+            } => {
+                let left = self.negate(span, *left);
+                let right = self.negate(span, *right);
+                self.and(span, left, right)
+            }
+            // `NOT a > ANY (...)` is `a <= ALL (...)`, not `a <= ANY (...)`.
+            Expr::BinaryOp {
+                span,
+                op,
+                left,
+                right,
+            } if !is_quantified(&left) && !is_quantified(&right) && op.to_contrary().is_ok() => {
+                Expr::BinaryOp {
+                    span,
+                    op: op.to_contrary().unwrap(),
+                    left,
+                    right,
+                }
+            }
+            Expr::IsNull { span, expr, not } => Expr::IsNull {
+                span,
+                expr,
+                not: !not,
+            },
+            Expr::InList {
+                span,
+                expr,
+                list,
+                not,
+            } => Expr::InList {
+                span,
+                expr,
+                list,
+                not: !not,
+            },
+            Expr::InSubquery {
+                span,
+                expr,
+                subquery,
+                not,
+            } => Expr::InSubquery {
+                span,
+                expr,
+                subquery,
+                not: !not,
+            },
+            Expr::Exists {
+                span,
+                not,
+                subquery,
+            } => Expr::Exists {
+                span,
+                not: !not,
+                subquery,
+            },
+            expr => Expr::UnaryOp {
+                span,
+                op: UnaryOperator::Not,
+                expr: Box::new(expr),
+            },
+        }
+    }
+
+    fn and(&self, span: Span, left: Expr, right: Expr) -> Expr {
+        match (literal_of(&left), literal_of(&right)) {
+            (Some(Literal::Boolean(false)), _) | (_, Some(Literal::Boolean(false))) => {
+                return bool_literal(span, false);
+            }
+            (Some(Literal::Boolean(true)), _) => return right,
+            (_, Some(Literal::Boolean(true))) => return left,
+            _ => (),
+        }
+        if let Some(merged) = self.merge_in_lists(span, &left, &right, true) {
+            return merged;
+        }
+        self.to_normal_form(binary(span, BinaryOperator::And, left, right))
+    }
+
+    fn or(&self, span: Span, left: Expr, right: Expr) -> Expr {
+        match (literal_of(&left), literal_of(&right)) {
+            (Some(Literal::Boolean(true)), _) | (_, Some(Literal::Boolean(true))) => {
+                return bool_literal(span, true);
+            }
+            (Some(Literal::Boolean(false)), _) => return right,
+            (_, Some(Literal::Boolean(false))) => return left,
+            _ => (),
+        }
+        if let Some(merged) = self.merge_in_lists(span, &left, &right, false) {
+            return merged;
+        }
+        self.to_normal_form(binary(span, BinaryOperator::Or, left, right))
+    }
+
+    fn in_list(&self, span: Span, expr: Expr, list: Vec<Expr>, not: bool) -> Expr {
+        let mut items: Vec<Expr> = Vec::with_capacity(list.len());
+        for item in list {
+            if !is_simple(&item) || !items.iter().any(|other| same_expr(other, &item)) {
+                items.push(item);
+            }
+        }
+
+        if literal_of(&expr).is_some() && items.iter().all(|item| literal_of(item).is_some()) {
+            let folded = if literal_of(&expr) == Some(Literal::Null) {
+                Some(Literal::Null)
+            } else {
+                let mut found = false;
+                let mut has_null = false;
+                let mut comparable = true;
+                for item in &items {
+                    if literal_of(item) == Some(Literal::Null) {
+                        has_null = true;
+                    } else {
+                        match compare(&expr, item) {
+                            Some(Ordering::Equal) => found = true,
+                            Some(_) => (),
+                            None => comparable = false,
+                        }
+                    }
+                }
+                if found {
+                    Some(Literal::Boolean(!not))
+                } else if !comparable {
+                    None
+                } else if has_null {
+                    Some(Literal::Null)
+                } else {
+                    Some(Literal::Boolean(not))
+                }
+            };
+            if let Some(lit) = folded {
+                return Expr::Literal { span, lit };
+            }
+        }
+
+        if items.len() == 1 {
+            let op = if not {
+                BinaryOperator::NotEq
+            } else {
+                BinaryOperator::Eq
+            };
+            return binary(span, op, expr, items.pop().unwrap());
+        }
+        Expr::InList {
+            span,
+            expr: Box::new(expr),
+            list: items,
+            not,
+        }
+    }
+
+    /// Merge `a IN (1, 2) OR a = 3` into `a IN (1, 2, 3)`, and likewise
+    /// `a NOT IN (1, 2) AND a <> 3` into `a NOT IN (1, 2, 3)`.
+    fn merge_in_lists(&self, span: Span, left: &Expr, right: &Expr, not: bool) -> Option<Expr> {
+        let (left_expr, left_list) = as_in_list(left, not)?;
+        let (right_expr, right_list) = as_in_list(right, not)?;
+        if !is_simple(left_expr) || !same_expr(left_expr, right_expr) {
+            return None;
+        }
+        let list = left_list.into_iter().chain(right_list).collect();
+        Some(self.in_list(span, left_expr.clone(), list, not))
+    }
+
+    fn to_normal_form(&self, expr: Expr) -> Expr {
+        let (outer, inner) = match self.normal_form {
+            Some(NormalForm::Conjunctive) => (BinaryOperator::And, BinaryOperator::Or),
+            Some(NormalForm::Disjunctive) => (BinaryOperator::Or, BinaryOperator::And),
+            None => return expr,
+        };
+        let span = expr.span();
+        match clauses(&expr, &outer, &inner) {
+            Some(clauses) => clauses
+                .into_iter()
+                .map(|clause| join(span, &inner, clause))
+                .reduce(|left, right| binary(span, outer.clone(), left, right))
+                .unwrap_or(expr),
+            None => expr,
+        }
+    }
+}
+
+impl VisitorMut for Simplifier {
+    fn visit_expr(&mut self, expr: &mut Expr) {
+        walk_expr_children_mut(self, expr);
+        let placeholder = Expr::Literal {
+            span: expr.span(),
+            lit: Literal::Null,
+        };
+        let original = std::mem::replace(expr, placeholder);
+        *expr = self.simplify(original);
+    }
+
+    fn visit_select_stmt(&mut self, stmt: &mut SelectStmt) {
+        let SelectStmt {
+            select_list,
+            from,
+            selection,
+            having,
+            ..
+        } = stmt;
+
+        // The unaliased targets are left as is, folding them would change the names of the
+        // output columns.
+        for target in select_list.iter_mut() {
+            if let SelectTarget::AliasedExpr { alias: Some(_), .. } = target {
+                self.visit_select_target(target);
+            }
+        }
+        for table_ref in from.iter_mut() {
+            self.visit_table_reference(table_ref);
+        }
+        if let Some(selection) = selection {
+            self.visit_expr(selection);
+        }
+        // `GROUP BY` items are left as is, folding `1 + 1` would turn it into a position.
+        if let Some(having) = having {
+            self.visit_expr(having);
+        }
+    }
+
+    // `ORDER BY` items are left as is for the same reason as `GROUP BY`.
+    fn visit_order_by(&mut self, _order_by: &mut OrderByExpr) {}
+}
+
+/// Split an expression into the clauses of the normal form, where `outer` joins
+/// the clauses and `inner` joins the terms of a clause.
+fn clauses(expr: &Expr, outer: &BinaryOperator, inner: &BinaryOperator) -> Option<Vec<Vec<Expr>>> {
+    let result = match expr {
+        Expr::BinaryOp {
+            op, left, right, ..
+        } if op == outer => {
+            let mut clauses = self::clauses(left, outer, inner)?;
+            for clause in self::clauses(right, outer, inner)? {
+                push_clause(&mut clauses, clause);
+            }
+            clauses
+        }
+        Expr::BinaryOp {
+            op, left, right, ..
+        } if op == inner => {
+            let left = self::clauses(left, outer, inner)?;
+            let right = self::clauses(right, outer, inner)?;
+            if left.len() * right.len() > MAX_NORMAL_FORM_CLAUSES {
+                return None;
+            }
+            let mut clauses = vec![];
+            for l in &left {
+                for r in &right {
+                    let mut clause = l.clone();
+                    for term in r {
+                        if !clause.iter().any(|other| same_expr(other, term)) {
+                            clause.push(term.clone());
+                        }
+                    }
+                    push_clause(&mut clauses, clause);
+                }
+            }
+            clauses
+        }
+        _ => vec![vec![expr.clone()]],
+    };
+    (result.len() <= MAX_NORMAL_FORM_CLAUSES).then_some(result)
+}
+
+fn push_clause(clauses: &mut Vec<Vec<Expr>>, clause: Vec<Expr>) {
+    let key = |clause: &[Expr]| {
+        let mut terms = clause
+            .iter()
+            .map(|term| term.to_string())
+            .collect::<Vec<_>>();
+        terms.sort();
+        terms
+    };
+    let clause_key = key(&clause);
+    if !clauses.iter().any(|other| key(other) == clause_key) {
+        clauses.push(clause);
+    }
+}
+
+fn join(span: Span, op: &BinaryOperator, terms: Vec<Expr>) -> Expr {
+    terms
+        .into_iter()
+        .reduce(|left, right| binary(span, op.clone(), left, right))
+        .unwrap()
+}
+
+/// View `a = 1` as `a IN (1)`, or `a <> 1` as `a NOT IN (1)`.
+fn as_in_list(expr: &Expr, not: bool) -> Option<(&Expr, Vec<Expr>)> {
+    match expr {
+        Expr::InList {
+            expr,
+            list,
+            not: list_not,
+            ..
+        } if *list_not == not => Some((expr, list.clone())),
+        Expr::BinaryOp {
+            op, left, right, ..
+        } if *op
+            == if not {
+                BinaryOperator::NotEq
+            } else {
+                BinaryOperator::Eq
+            }
+            && literal_of(left).is_none()
+            && literal_of(right).is_some() =>
+        {
+            Some((left, vec![(**right).clone()]))
+        }
+        _ => None,
+    }
+}
+
+fn fold_binary(span: Span, op: &BinaryOperator, left: &Expr, right: &Expr) -> Option<Expr> {
+    let left_lit = literal_of(left);
+    let right_lit = literal_of(right);
+    let is_null = left_lit == Some(Literal::Null) || right_lit == Some(Literal::Null);
+    // Arithmetic and comparisons with `NULL` are `NULL` whatever the other side is.
+    let propagates_null = matches!(
+        op,
+        BinaryOperator::Plus
+            | BinaryOperator::Minus
+            | BinaryOperator::Multiply
+            | BinaryOperator::Divide
+            | BinaryOperator::Div
+            | BinaryOperator::IntDiv
+            | BinaryOperator::Modulo
+            | BinaryOperator::Gt
+            | BinaryOperator::Lt
+            | BinaryOperator::Gte
+            | BinaryOperator::Lte
+            | BinaryOperator::Eq
+            | BinaryOperator::NotEq
+    );
+    if is_null && propagates_null {
+        return Some(null_literal(span));
+    }
+    let (left_lit, right_lit) = (left_lit?, right_lit?);
+
+    match op {
+        BinaryOperator::Plus
+        | BinaryOperator::Minus
+        | BinaryOperator::Multiply
+        | BinaryOperator::Divide
+        | BinaryOperator::Div
+        | BinaryOperator::IntDiv
+        | BinaryOperator::Modulo => {
+            let left = Number::from_expr(left)?;
+            let right = Number::from_expr(right)?;
+            left.arithmetic(op, right)?.to_expr(span)
+        }
+        BinaryOperator::Gt
+        | BinaryOperator::Lt
+        | BinaryOperator::Gte
+        | BinaryOperator::Lte
+        | BinaryOperator::Eq
+        | BinaryOperator::NotEq => {
+            let ord = compare(left, right)?;
+            let result = match op {
+                BinaryOperator::Gt => ord == Ordering::Greater,
+                BinaryOperator::Lt => ord == Ordering::Less,
+                BinaryOperator::Gte => ord != Ordering::Less,
+                BinaryOperator::Lte => ord != Ordering::Greater,
+                BinaryOperator::Eq => ord == Ordering::Equal,
+                _ => ord != Ordering::Equal,
+            };
+            Some(bool_literal(span, result))
+        }
+        BinaryOperator::StringConcat => match (left_lit, right_lit) {
+            (Literal::String(l), Literal::String(r)) => Some(Expr::Literal {
+                span,
+                lit: Literal::String(l + &r),
+            }),
+            _ if is_null => Some(null_literal(span)),
+            _ => None,
+        },
+        BinaryOperator::Xor => match (left_lit, right_lit) {
+            (Literal::Boolean(l), Literal::Boolean(r)) => Some(bool_literal(span, l != r)),
+            (Literal::Boolean(_) | Literal::Null, Literal::Boolean(_) | Literal::Null) => {
+                Some(null_literal(span))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn compare(left: &Expr, right: &Expr) -> Option<Ordering> {
+    if let (Some(l), Some(r)) = (Number::from_expr(left), Number::from_expr(right)) {
+        return l.compare(&r);
+    }
+    match (literal_of(left)?, literal_of(right)?) {
+        (Literal::String(l), Literal::String(r)) => Some(l.cmp(&r)),
+        (Literal::Boolean(l), Literal::Boolean(r)) => Some(l.cmp(&r)),
+        _ => None,
+    }
+}
+
+/// The constant value of an expression, a negative number is returned as its
+/// absolute value which is enough to tell the kind of the literal.
+fn literal_of(expr: &Expr) -> Option<Literal> {
+    match expr {
+        Expr::Literal {
+            lit: Literal::CurrentTimestamp,
+            ..
+        } => None,
+        Expr::Literal { lit, .. } => Some(lit.clone()),
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+            ..
+        } => Number::from_expr(expr).and_then(|_| literal_of(expr)),
+        _ => None,
+    }
+}
+
+fn is_quantified(expr: &Expr) -> bool {
+    matches!(expr, Expr::Subquery {
+        modifier: Some(_),
+        ..
+    })
+}
+
+/// Whether an expression is cheap and deterministic, so it can be evaluated more than
+/// once or only once.
+fn is_simple(expr: &Expr) -> bool {
+    matches!(expr, Expr::ColumnRef { .. } | Expr::Literal { .. })
+}
+
+fn same_expr(left: &Expr, right: &Expr) -> bool {
+    left.to_string() == right.to_string()
+}
+
+fn binary(span: Span, op: BinaryOperator, left: Expr, right: Expr) -> Expr {
+    Expr::BinaryOp {
+        span,
+        op,
+        left: Box::new(left),
+        right: Box::new(right),
+    }
+}
+
+fn bool_literal(span: Span, value: bool) -> Expr {
+    Expr::Literal {
+        span,
+        lit: Literal::Boolean(value),
+    }
+}
+
+fn null_literal(span: Span) -> Expr {
+    Expr::Literal {
+        span,
+        lit: Literal::Null,
+    }
+}
+
+/// A numeric constant. Literals are always non-negative, negative numbers are
+/// expressed as `-literal` just like the parser does.
+#[derive(Debug, Copy, Clone)]
+enum Number {
+    /// `value * 10 ^ -scale`, `int` tells it comes from `UInt64` literals only.
+    Exact {
+        value: i256,
+        precision: u8,
+        scale: u8,
+        int: bool,
+    },
+    Float(f64),
+}
+
+impl Number {
+    fn from_expr(expr: &Expr) -> Option<Number> {
+        match expr {
+            Expr::Literal {
+                lit: Literal::UInt64(value),
+                ..
+            } => Some(Number::Exact {
+                value: i256::from(*value),
+                precision: UINT64_PRECISION,
+                scale: 0,
+                int: true,
+            }),
+            Expr::Literal {
+                lit:
+                    Literal::Decimal256 {
+                        value,
+                        precision,
+                        scale,
+                    },
+                ..
+            } => Some(Number::Exact {
+                value: *value,
+                precision: *precision,
+                scale: *scale,
+                int: false,
+            }),
+            Expr::Literal {
+                lit: Literal::Float64(value),
+                ..
+            } => Some(Number::Float(*value)),
+            Expr::UnaryOp {
+                op: UnaryOperator::Minus,
+                expr,
+                ..
+            } => Number::from_expr(expr).map(Number::neg),
+            _ => None,
+        }
+    }
+
+    fn to_expr(self, span: Span) -> Option<Expr> {
+        let (lit, negative) = match self {
+            Number::Exact {
+                value,
+                precision,
+                scale,
+                int,
+            } => {
+                let negative = value < i256::ZERO;
+                let abs = if negative { -value } else { value };
+                if int {
+                    if abs > i256::from(u64::MAX) {
+                        return None;
+                    }
+                    (Literal::UInt64(abs.as_u64()), negative)
+                } else {
+                    if scale > precision || abs >= pow10(precision as u32)? {
+                        return None;
+                    }
+                    let lit = Literal::Decimal256 {
+                        value: abs,
+                        precision,
+                        scale,
+                    };
+                    (lit, negative)
+                }
+            }
+            Number::Float(value) if value.is_finite() => {
+                (Literal::Float64(value.abs()), value.is_sign_negative())
+            }
+            Number::Float(_) => return None,
+        };
+        let lit = Expr::Literal { span, lit };
+        Some(if negative {
+            Expr::UnaryOp {
+                span,
+                op: UnaryOperator::Minus,
+                expr: Box::new(lit),
+            }
+        } else {
+            lit
+        })
+    }
+
+    fn neg(self) -> Number {
+        match self {
+            Number::Exact {
+                value,
+                precision,
+                scale,
+                int,
+            } => Number::Exact {
+                value: -value,
+                precision,
+                scale,
+                int,
+            },
+            Number::Float(value) => Number::Float(-value),
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        match self {
+            Number::Exact { value, scale, .. } => value.as_f64() / 10f64.powi(scale as i32),
+            Number::Float(value) => value,
+        }
+    }
+
+    fn arithmetic(self, op: &BinaryOperator, other: Number) -> Option<Number> {
+        match (self, other) {
+            (
+                Number::Exact {
+                    value: l,
+                    precision: lp,
+                    scale: ls,
+                    int: l_int,
+                },
+                Number::Exact {
+                    value: r,
+                    precision: rp,
+                    scale: rs,
+                    int: r_int,
+                },
+            ) => {
+                let int = l_int && r_int;
+                let (value, precision, scale) = match op {
+                    BinaryOperator::Plus | BinaryOperator::Minus => {
+                        let scale = ls.max(rs);
+                        let l = rescale(l, ls, scale)?;
+                        let r = rescale(r, rs, scale)?;
+                        let value = if *op == BinaryOperator::Plus {
+                            l.checked_add(r)?
+                        } else {
+                            l.checked_sub(r)?
+                        };
+                        let integral = (lp - ls).max(rp - rs);
+                        let precision = (integral + scale + 1).min(MAX_DECIMAL_PRECISION);
+                        (value, precision, scale)
+                    }
+                    BinaryOperator::Multiply => {
+                        let scale = ls.checked_add(rs)?;
+                        let precision = lp.saturating_add(rp).min(MAX_DECIMAL_PRECISION);
+                        (l.checked_mul(r)?, precision, scale)
+                    }
+                    BinaryOperator::Div | BinaryOperator::IntDiv if int && r != i256::ZERO => {
+                        (l.checked_div(r)?, UINT64_PRECISION, 0)
+                    }
+                    BinaryOperator::Modulo if int && r != i256::ZERO => {
+                        (l.checked_rem(r)?, UINT64_PRECISION, 0)
+                    }
+                    _ => return None,
+                };
+                Some(Number::Exact {
+                    value,
+                    precision: if int { UINT64_PRECISION } else { precision },
+                    scale,
+                    int,
+                })
+            }
+            (l, r) => {
+                if !matches!(l, Number::Float(_)) && !matches!(r, Number::Float(_)) {
+                    return None;
+                }
+                let (l, r) = (l.to_f64(), r.to_f64());
+                let value = match op {
+                    BinaryOperator::Plus => l + r,
+                    BinaryOperator::Minus => l - r,
+                    BinaryOperator::Multiply => l * r,
+                    BinaryOperator::Divide if r != 0.0 => l / r,
+                    _ => return None,
+                };
+                Some(Number::Float(value))
+            }
+        }
+    }
+
+    fn compare(&self, other: &Number) -> Option<Ordering> {
+        match (*self, *other) {
+            (
+                Number::Exact {
+                    value: l,
+                    scale: ls,
+                    ..
+                },
+                Number::Exact {
+                    value: r,
+                    scale: rs,
+                    ..
+                },
+            ) => {
+                let scale = ls.max(rs);
+                Some(rescale(l, ls, scale)?.cmp(&rescale(r, rs, scale)?))
+            }
+            (l, r) => l.to_f64().partial_cmp(&r.to_f64()),
+        }
+    }
+}
+
+fn pow10(exp: u32) -> Option<i256> {
+    i256::from(10).checked_pow(exp)
+}
+
+fn rescale(value: i256, from: u8, to: u8) -> Option<i256> {
+    value.checked_mul(pow10((to - from) as u32)?)
+}
@@ -12,25 +12,43 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::ControlFlow;
+
 use common_exception::Span;
-use crate::meta::FileFormatOptionsAst;
 use crate::meta::principal_identity::PrincipalIdentity;
 use crate::meta::user_identity::UserIdentity;
 
-use super::walk::walk_cte;
 use super::walk::walk_expr;
 use super::walk::walk_identifier;
-use super::walk::walk_join_condition;
+use super::walk::walk_join;
 use super::walk::walk_query;
+use super::walk::walk_select_stmt;
 use super::walk::walk_select_target;
 use super::walk::walk_set_expr;
 use super::walk::walk_statement;
 use super::walk::walk_table_reference;
+use super::walk::walk_with;
 use super::walk_time_travel_point;
+use super::AstNode;
+use super::Children;
+use super::VisitResult;
 use crate::ast::*;
 use crate::visitors::walk_window_definition;
 
 pub trait Visitor<'ast>: Sized {
+    /// Called by the walk functions before a node and its children are visited.
+    /// `Continue(Children::Skip)` skips the children, and `Break` returns from the
+    /// walk of the node at once, without leaving it. To stop the whole traversal, a
+    /// visitor keeps returning `Break` for the nodes that are entered later, or is
+    /// driven by [`traverse_statement`](super::traverse_statement) which does so.
+    fn enter_node(&mut self, _node: AstNode<'ast>) -> VisitResult {
+        ControlFlow::Continue(Children::Visit)
+    }
+
+    /// Called by the walk functions after the children of a node are visited or
+    /// skipped.
+    fn leave_node(&mut self, _node: AstNode<'ast>) {}
+
     fn visit_expr(&mut self, expr: &'ast Expr) {
         walk_expr(self, expr);
     }
@@ -210,6 +228,8 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_literal(&mut self, _span: Span, _lit: &'ast Literal) {}
 
+    fn visit_placeholder(&mut self, _span: Span) {}
+
     fn visit_count_all(&mut self, _span: Span, window: &'ast Option<Window>) {
         if let Some(window) = window {
             self.visit_window(window);
@@ -537,7 +557,7 @@ pub trait Visitor<'ast>: Sized {
         &mut self,
         _if_not_exists: bool,
         _name: &'ast str,
-        _file_format_options: &'ast FileFormatOptionsAst,
+        _file_format_options: &'ast FileFormatParams,
     ) {
     }
 
@@ -588,10 +608,7 @@ pub trait Visitor<'ast>: Sized {
     fn visit_show_network_policies(&mut self) {}
 
     fn visit_with(&mut self, with: &'ast With) {
-        let With { ctes, .. } = with;
-        for cte in ctes.iter() {
-            walk_cte(self, cte);
-        }
+        walk_with(self, with);
     }
 
     fn visit_set_expr(&mut self, expr: &'ast SetExpr) {
@@ -611,46 +628,7 @@ pub trait Visitor<'ast>: Sized {
     }
 
     fn visit_select_stmt(&mut self, stmt: &'ast SelectStmt) {
-        let SelectStmt {
-            select_list,
-            from,
-            selection,
-            group_by,
-            having,
-            ..
-        } = stmt;
-
-        for target in select_list.iter() {
-            walk_select_target(self, target);
-        }
-
-        for table_ref in from.iter() {
-            walk_table_reference(self, table_ref);
-        }
-
-        if let Some(selection) = selection {
-            walk_expr(self, selection);
-        }
-
-        match group_by {
-            Some(GroupBy::Normal(exprs)) => {
-                for expr in exprs {
-                    walk_expr(self, expr);
-                }
-            }
-            Some(GroupBy::GroupingSets(sets)) => {
-                for set in sets {
-                    for expr in set {
-                        walk_expr(self, expr);
-                    }
-                }
-            }
-            _ => {}
-        }
-
-        if let Some(having) = having {
-            walk_expr(self, having);
-        }
+        walk_select_stmt(self, stmt);
     }
 
     fn visit_select_target(&mut self, target: &'ast SelectTarget) {
@@ -666,17 +644,7 @@ pub trait Visitor<'ast>: Sized {
     }
 
     fn visit_join(&mut self, join: &'ast Join) {
-        let Join {
-            left,
-            right,
-            condition,
-            ..
-        } = join;
-
-        walk_table_reference(self, left);
-        walk_table_reference(self, right);
-
-        walk_join_condition(self, condition);
+        walk_join(self, join);
     }
     fn visit_window_definition(&mut self, window_definition: &'ast WindowDefinition) {
         walk_window_definition(self, window_definition);
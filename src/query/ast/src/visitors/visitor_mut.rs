@@ -12,25 +12,40 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::ControlFlow;
+
 use common_exception::Span;
-use crate::meta::FileFormatOptionsAst;
 use crate::meta::principal_identity::PrincipalIdentity;
 use crate::meta::user_identity::UserIdentity;
 
-use super::walk_mut::walk_cte_mut;
 use super::walk_mut::walk_expr_mut;
 use super::walk_mut::walk_identifier_mut;
-use super::walk_mut::walk_join_condition_mut;
+use super::walk_mut::walk_join_mut;
 use super::walk_mut::walk_query_mut;
+use super::walk_mut::walk_select_stmt_mut;
 use super::walk_mut::walk_select_target_mut;
 use super::walk_mut::walk_set_expr_mut;
 use super::walk_mut::walk_statement_mut;
 use super::walk_mut::walk_table_reference_mut;
+use super::walk_mut::walk_with_mut;
 use super::walk_time_travel_point_mut;
+use super::AstNodeMut;
+use super::Children;
+use super::VisitResult;
 use crate::ast::*;
 use crate::visitors::walk_column_id_mut;
 
 pub trait VisitorMut: Sized {
+    /// The mutable counterpart of [`Visitor::enter_node`](super::Visitor::enter_node),
+    /// the node can be rewritten before its children are visited.
+    fn enter_node(&mut self, _node: AstNodeMut<'_>) -> VisitResult {
+        ControlFlow::Continue(Children::Visit)
+    }
+
+    /// The mutable counterpart of [`Visitor::leave_node`](super::Visitor::leave_node),
+    /// the node can be rewritten after its children are visited.
+    fn leave_node(&mut self, _node: AstNodeMut<'_>) {}
+
     fn visit_expr(&mut self, expr: &mut Expr) {
         walk_expr_mut(self, expr);
     }
@@ -207,6 +222,8 @@ pub trait VisitorMut: Sized {
 
     fn visit_literal(&mut self, _span: Span, _lit: &mut Literal) {}
 
+    fn visit_placeholder(&mut self, _span: Span) {}
+
     fn visit_count_all(&mut self, _span: Span, window: &mut Option<Window>) {
         if let Some(window) = window {
             match window {
@@ -552,7 +569,7 @@ pub trait VisitorMut: Sized {
         &mut self,
         _if_not_exists: bool,
         _name: &mut String,
-        _file_format_options: &mut FileFormatOptionsAst,
+        _file_format_options: &mut FileFormatParams,
     ) {
     }
 
@@ -603,10 +620,7 @@ pub trait VisitorMut: Sized {
     fn visit_show_network_policies(&mut self) {}
 
     fn visit_with(&mut self, with: &mut With) {
-        let With { ctes, .. } = with;
-        for cte in ctes.iter_mut() {
-            walk_cte_mut(self, cte);
-        }
+        walk_with_mut(self, with);
     }
 
     fn visit_set_expr(&mut self, expr: &mut SetExpr) {
@@ -626,46 +640,7 @@ pub trait VisitorMut: Sized {
     }
 
     fn visit_select_stmt(&mut self, stmt: &mut SelectStmt) {
-        let SelectStmt {
-            select_list,
-            from,
-            selection,
-            group_by,
-            having,
-            ..
-        } = stmt;
-
-        for target in select_list.iter_mut() {
-            walk_select_target_mut(self, target);
-        }
-
-        for table_ref in from.iter_mut() {
-            self.visit_table_reference(table_ref);
-        }
-
-        if let Some(selection) = selection {
-            walk_expr_mut(self, selection);
-        }
-
-        match group_by {
-            Some(GroupBy::Normal(exprs)) => {
-                for expr in exprs {
-                    walk_expr_mut(self, expr);
-                }
-            }
-            Some(GroupBy::GroupingSets(sets)) => {
-                for set in sets {
-                    for expr in set {
-                        walk_expr_mut(self, expr);
-                    }
-                }
-            }
-            _ => {}
-        }
-
-        if let Some(having) = having {
-            walk_expr_mut(self, having);
-        }
+        walk_select_stmt_mut(self, stmt);
     }
 
     fn visit_select_target(&mut self, target: &mut SelectTarget) {
@@ -681,16 +656,6 @@ pub trait VisitorMut: Sized {
     }
 
     fn visit_join(&mut self, join: &mut Join) {
-        let Join {
-            left,
-            right,
-            condition,
-            ..
-        } = join;
-
-        self.visit_table_reference(left);
-        self.visit_table_reference(right);
-
-        walk_join_condition_mut(self, condition);
+        walk_join_mut(self, join);
     }
 }
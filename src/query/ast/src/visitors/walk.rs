@@ -12,11 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::ControlFlow;
+
+use super::AstNode;
+use super::Children;
 use super::Visitor;
 use crate::ast::*;
 
+/// Enter a node, walk its children with `walk` unless they are skipped, and leave it.
+/// Nothing else is done if entering the node stops the traversal.
+fn walk_node<'a, V: Visitor<'a>, N: ?Sized>(
+    visitor: &mut V,
+    node: &'a N,
+    as_node: fn(&'a N) -> AstNode<'a>,
+    walk: impl FnOnce(&mut V, &'a N),
+) {
+    match visitor.enter_node(as_node(node)) {
+        ControlFlow::Break(()) => return,
+        ControlFlow::Continue(Children::Visit) => walk(visitor, node),
+        ControlFlow::Continue(Children::Skip) => {}
+    }
+    visitor.leave_node(as_node(node));
+}
+
 pub fn walk_expr<'a, V: Visitor<'a>>(visitor: &mut V, expr: &'a Expr) {
-    match expr {
+    walk_node(visitor, expr, AstNode::Expr, |visitor, expr| match expr {
         Expr::ColumnRef {
             span,
             database,
@@ -85,6 +105,7 @@ pub fn walk_expr<'a, V: Visitor<'a>>(visitor: &mut V, expr: &'a Expr) {
             trim_where,
         } => visitor.visit_trim(*span, expr, trim_where),
         Expr::Literal { span, lit } => visitor.visit_literal(*span, lit),
+        Expr::Placeholder { span } => visitor.visit_placeholder(*span),
         Expr::CountAll { span, window } => visitor.visit_count_all(*span, window),
         Expr::Tuple { span, exprs } => visitor.visit_tuple(*span, exprs),
         Expr::FunctionCall {
@@ -134,7 +155,7 @@ pub fn walk_expr<'a, V: Visitor<'a>>(visitor: &mut V, expr: &'a Expr) {
             unit,
         } => visitor.visit_date_sub(*span, unit, interval, date),
         Expr::DateTrunc { span, unit, date } => visitor.visit_date_trunc(*span, unit, date),
-    }
+    });
 }
 
 pub fn walk_identifier<'a, V: Visitor<'a>>(visitor: &mut V, ident: &'a Identifier) {
@@ -142,135 +163,213 @@ pub fn walk_identifier<'a, V: Visitor<'a>>(visitor: &mut V, ident: &'a Identifie
 }
 
 pub fn walk_query<'a, V: Visitor<'a>>(visitor: &mut V, query: &'a Query) {
-    let Query {
-        with,
-        body,
-        order_by,
-        limit,
-        offset,
-        ..
-    } = query;
+    walk_node(visitor, query, AstNode::Query, |visitor, query| {
+        let Query {
+            with,
+            body,
+            order_by,
+            limit,
+            offset,
+            ..
+        } = query;
 
-    if let Some(with) = with {
-        visitor.visit_with(with);
-    }
-    visitor.visit_set_expr(body);
-    for order_by in order_by {
-        visitor.visit_order_by(order_by);
-    }
-    for limit in limit {
-        visitor.visit_expr(limit);
-    }
-    if let Some(offset) = offset {
-        visitor.visit_expr(offset);
-    }
+        if let Some(with) = with {
+            visitor.visit_with(with);
+        }
+        visitor.visit_set_expr(body);
+        for order_by in order_by {
+            visitor.visit_order_by(order_by);
+        }
+        for limit in limit {
+            visitor.visit_expr(limit);
+        }
+        if let Some(offset) = offset {
+            visitor.visit_expr(offset);
+        }
+    });
 }
 
 pub fn walk_set_expr<'a, V: Visitor<'a>>(visitor: &mut V, set_expr: &'a SetExpr) {
-    match set_expr {
-        SetExpr::Select(select) => {
-            visitor.visit_select_stmt(select);
+    walk_node(
+        visitor,
+        set_expr,
+        AstNode::SetExpr,
+        |visitor, set_expr| match set_expr {
+            SetExpr::Select(select) => {
+                visitor.visit_select_stmt(select);
+            }
+            SetExpr::Query(query) => {
+                visitor.visit_query(query);
+            }
+            SetExpr::SetOperation(op) => {
+                visitor.visit_set_operation(op);
+            }
+            SetExpr::Values { values, .. } => {
+                for row_values in values {
+                    for value in row_values {
+                        visitor.visit_expr(value);
+                    }
+                }
+            }
+        },
+    );
+}
+
+pub fn walk_select_stmt<'a, V: Visitor<'a>>(visitor: &mut V, stmt: &'a SelectStmt) {
+    walk_node(visitor, stmt, AstNode::SelectStmt, |visitor, stmt| {
+        let SelectStmt {
+            select_list,
+            from,
+            selection,
+            group_by,
+            having,
+            ..
+        } = stmt;
+
+        for target in select_list.iter() {
+            walk_select_target(visitor, target);
         }
-        SetExpr::Query(query) => {
-            visitor.visit_query(query);
+
+        for table_ref in from.iter() {
+            walk_table_reference(visitor, table_ref);
         }
-        SetExpr::SetOperation(op) => {
-            visitor.visit_set_operation(op);
+
+        if let Some(selection) = selection {
+            walk_expr(visitor, selection);
         }
-        SetExpr::Values { values, .. } => {
-            for row_values in values {
-                for value in row_values {
-                    visitor.visit_expr(value);
+
+        match group_by {
+            Some(GroupBy::Normal(exprs) | GroupBy::Cube(exprs) | GroupBy::Rollup(exprs)) => {
+                for expr in exprs {
+                    walk_expr(visitor, expr);
+                }
+            }
+            Some(GroupBy::GroupingSets(sets)) => {
+                for set in sets {
+                    for expr in set {
+                        walk_expr(visitor, expr);
+                    }
                 }
             }
+            Some(GroupBy::All) | None => {}
         }
-    }
+
+        if let Some(having) = having {
+            walk_expr(visitor, having);
+        }
+    });
 }
 
 pub fn walk_select_target<'a, V: Visitor<'a>>(visitor: &mut V, target: &'a SelectTarget) {
-    match target {
-        SelectTarget::AliasedExpr { expr, alias } => {
-            visitor.visit_expr(expr);
-            if let Some(alias) = alias {
-                visitor.visit_identifier(alias);
+    walk_node(
+        visitor,
+        target,
+        AstNode::SelectTarget,
+        |visitor, target| match target {
+            SelectTarget::AliasedExpr { expr, alias } => {
+                visitor.visit_expr(expr);
+                if let Some(alias) = alias {
+                    visitor.visit_identifier(alias);
+                }
             }
-        }
-        SelectTarget::QualifiedName {
-            qualified: names,
-            exclude,
-        } => {
-            for indirection in names {
-                match indirection {
-                    Indirection::Identifier(ident) => {
-                        visitor.visit_identifier(ident);
+            SelectTarget::QualifiedName {
+                qualified: names,
+                exclude,
+            } => {
+                for indirection in names {
+                    match indirection {
+                        Indirection::Identifier(ident) => {
+                            visitor.visit_identifier(ident);
+                        }
+                        Indirection::Star(_) => {}
                     }
-                    Indirection::Star(_) => {}
                 }
-            }
-            if let Some(cols) = exclude {
-                for ident in cols.iter() {
-                    visitor.visit_column_id(ident);
+                if let Some(cols) = exclude {
+                    for ident in cols.iter() {
+                        visitor.visit_column_id(ident);
+                    }
                 }
             }
-        }
-    }
+        },
+    );
 }
 
 pub fn walk_table_reference<'a, V: Visitor<'a>>(visitor: &mut V, table_ref: &'a TableReference) {
-    match table_ref {
-        TableReference::Table {
-            catalog,
-            database,
-            table,
-            alias,
-            travel_point,
-            ..
-        } => {
-            if let Some(catalog) = catalog {
-                visitor.visit_identifier(catalog);
-            }
+    walk_node(
+        visitor,
+        table_ref,
+        AstNode::TableReference,
+        |visitor, table_ref| match table_ref {
+            TableReference::Table {
+                catalog,
+                database,
+                table,
+                alias,
+                travel_point,
+                ..
+            } => {
+                if let Some(catalog) = catalog {
+                    visitor.visit_identifier(catalog);
+                }
 
-            if let Some(database) = database {
-                visitor.visit_identifier(database);
-            }
+                if let Some(database) = database {
+                    visitor.visit_identifier(database);
+                }
 
-            visitor.visit_identifier(table);
+                visitor.visit_identifier(table);
 
-            if let Some(alias) = alias {
-                visitor.visit_identifier(&alias.name);
-            }
+                if let Some(alias) = alias {
+                    visitor.visit_identifier(&alias.name);
+                }
 
-            if let Some(travel_point) = travel_point {
-                visitor.visit_time_travel_point(travel_point);
+                if let Some(travel_point) = travel_point {
+                    visitor.visit_time_travel_point(travel_point);
+                }
             }
-        }
-        TableReference::Subquery {
-            subquery, alias, ..
-        } => {
-            visitor.visit_query(subquery);
-            if let Some(alias) = alias {
-                visitor.visit_identifier(&alias.name);
+            TableReference::Subquery {
+                subquery, alias, ..
+            } => {
+                visitor.visit_query(subquery);
+                if let Some(alias) = alias {
+                    visitor.visit_identifier(&alias.name);
+                }
             }
-        }
-        TableReference::TableFunction {
-            name,
-            params,
-            alias,
-            ..
-        } => {
-            visitor.visit_identifier(name);
-            for param in params {
-                visitor.visit_expr(param);
+            TableReference::TableFunction {
+                name,
+                params,
+                alias,
+                ..
+            } => {
+                visitor.visit_identifier(name);
+                for param in params {
+                    visitor.visit_expr(param);
+                }
+                if let Some(alias) = alias {
+                    visitor.visit_identifier(&alias.name);
+                }
             }
-            if let Some(alias) = alias {
-                visitor.visit_identifier(&alias.name);
+            TableReference::Join { join, .. } => {
+                visitor.visit_join(join);
             }
-        }
-        TableReference::Join { join, .. } => {
-            visitor.visit_join(join);
-        }
-        TableReference::Location { .. } => {}
-    }
+            TableReference::Location { .. } => {}
+        },
+    );
+}
+
+pub fn walk_join<'a, V: Visitor<'a>>(visitor: &mut V, join: &'a Join) {
+    walk_node(visitor, join, AstNode::Join, |visitor, join| {
+        let Join {
+            left,
+            right,
+            condition,
+            ..
+        } = join;
+
+        walk_table_reference(visitor, left);
+        walk_table_reference(visitor, right);
+
+        walk_join_condition(visitor, condition);
+    });
 }
 
 pub fn walk_time_travel_point<'a, V: Visitor<'a>>(visitor: &mut V, time: &'a TimeTravelPoint) {
@@ -293,11 +392,20 @@ pub fn walk_join_condition<'a, V: Visitor<'a>>(visitor: &mut V, join_cond: &'a J
     }
 }
 
+pub fn walk_with<'a, V: Visitor<'a>>(visitor: &mut V, with: &'a With) {
+    let With { ctes, .. } = with;
+    for cte in ctes.iter() {
+        walk_cte(visitor, cte);
+    }
+}
+
 pub fn walk_cte<'a, V: Visitor<'a>>(visitor: &mut V, cte: &'a CTE) {
-    let CTE { alias, query, .. } = cte;
+    walk_node(visitor, cte, AstNode::Cte, |visitor, cte| {
+        let CTE { alias, query, .. } = cte;
 
-    visitor.visit_identifier(&alias.name);
-    visitor.visit_query(query);
+        visitor.visit_identifier(&alias.name);
+        visitor.visit_query(query);
+    });
 }
 
 pub fn walk_window_definition<'a, V: Visitor<'a>>(
@@ -330,145 +438,152 @@ pub fn walk_window_definition<'a, V: Visitor<'a>>(
 }
 
 pub fn walk_statement<'a, V: Visitor<'a>>(visitor: &mut V, statement: &'a Statement) {
-    match statement {
-        Statement::Explain { kind, query } => visitor.visit_explain(kind, query),
-        Statement::ExplainAnalyze { query } => visitor.visit_statement(query),
-        Statement::Query(query) => visitor.visit_query(query),
-        Statement::Insert(insert) => visitor.visit_insert(insert),
-        Statement::Replace(replace) => visitor.visit_replace(replace),
-        Statement::MergeInto(merge_into) => visitor.visit_merge_into(merge_into),
-        Statement::Delete {
-            table_reference,
-            selection,
-            ..
-        } => visitor.visit_delete(table_reference, selection),
-        Statement::Update(update) => visitor.visit_update(update),
-        Statement::Copy(stmt) => visitor.visit_copy(stmt),
-        Statement::ShowSettings { like } => visitor.visit_show_settings(like),
-        Statement::ShowProcessList => visitor.visit_show_process_list(),
-        Statement::ShowMetrics => visitor.visit_show_metrics(),
-        Statement::ShowEngines => visitor.visit_show_engines(),
-        Statement::ShowFunctions { limit } => visitor.visit_show_functions(limit),
-        Statement::ShowTableFunctions { limit } => visitor.visit_show_table_functions(limit),
-        Statement::ShowIndexes => visitor.visit_show_indexes(),
-        Statement::KillStmt {
-            kill_target,
-            object_id,
-        } => visitor.visit_kill(kill_target, object_id),
-        Statement::SetVariable {
-            is_global,
-            variable,
-            value,
-        } => visitor.visit_set_variable(*is_global, variable, value),
-        Statement::UnSetVariable(stmt) => visitor.visit_unset_variable(stmt),
-        Statement::SetRole {
-            is_default,
-            role_name,
-        } => visitor.visit_set_role(*is_default, role_name),
-        Statement::ShowCatalogs(stmt) => visitor.visit_show_catalogs(stmt),
-        Statement::ShowCreateCatalog(stmt) => visitor.visit_show_create_catalog(stmt),
-        Statement::CreateCatalog(stmt) => visitor.visit_create_catalog(stmt),
-        Statement::DropCatalog(stmt) => visitor.visit_drop_catalog(stmt),
-        Statement::ShowDatabases(stmt) => visitor.visit_show_databases(stmt),
-        Statement::ShowCreateDatabase(stmt) => visitor.visit_show_create_databases(stmt),
-        Statement::CreateDatabase(stmt) => visitor.visit_create_database(stmt),
-        Statement::DropDatabase(stmt) => visitor.visit_drop_database(stmt),
-        Statement::UndropDatabase(stmt) => visitor.visit_undrop_database(stmt),
-        Statement::AlterDatabase(stmt) => visitor.visit_alter_database(stmt),
-        Statement::UseDatabase { database } => visitor.visit_use_database(database),
-        Statement::ShowTables(stmt) => visitor.visit_show_tables(stmt),
-        Statement::ShowColumns(stmt) => visitor.visit_show_columns(stmt),
-        Statement::ShowCreateTable(stmt) => visitor.visit_show_create_table(stmt),
-        Statement::DescribeTable(stmt) => visitor.visit_describe_table(stmt),
-        Statement::ShowTablesStatus(stmt) => visitor.visit_show_tables_status(stmt),
-        Statement::ShowDropTables(stmt) => visitor.visit_show_drop_tables(stmt),
-        Statement::CreateTable(stmt) => visitor.visit_create_table(stmt),
-        Statement::DropTable(stmt) => visitor.visit_drop_table(stmt),
-        Statement::UndropTable(stmt) => visitor.visit_undrop_table(stmt),
-        Statement::AlterTable(stmt) => visitor.visit_alter_table(stmt),
-        Statement::RenameTable(stmt) => visitor.visit_rename_table(stmt),
-        Statement::TruncateTable(stmt) => visitor.visit_truncate_table(stmt),
-        Statement::OptimizeTable(stmt) => visitor.visit_optimize_table(stmt),
-        Statement::VacuumTable(stmt) => visitor.visit_vacuum_table(stmt),
-        Statement::VacuumDropTable(stmt) => visitor.visit_vacuum_drop_table(stmt),
-        Statement::AnalyzeTable(stmt) => visitor.visit_analyze_table(stmt),
-        Statement::ExistsTable(stmt) => visitor.visit_exists_table(stmt),
-        Statement::CreateView(stmt) => visitor.visit_create_view(stmt),
-        Statement::AlterView(stmt) => visitor.visit_alter_view(stmt),
-        Statement::DropView(stmt) => visitor.visit_drop_view(stmt),
-        Statement::CreateIndex(stmt) => visitor.visit_create_index(stmt),
-        Statement::DropIndex(stmt) => visitor.visit_drop_index(stmt),
-        Statement::RefreshIndex(stmt) => visitor.visit_refresh_index(stmt),
-        Statement::CreateVirtualColumn(stmt) => visitor.visit_create_virtual_column(stmt),
-        Statement::AlterVirtualColumn(stmt) => visitor.visit_alter_virtual_column(stmt),
-        Statement::DropVirtualColumn(stmt) => visitor.visit_drop_virtual_column(stmt),
-        Statement::RefreshVirtualColumn(stmt) => visitor.visit_refresh_virtual_column(stmt),
-        Statement::ShowUsers => visitor.visit_show_users(),
-        Statement::ShowRoles => visitor.visit_show_roles(),
-        Statement::CreateUser(stmt) => visitor.visit_create_user(stmt),
-        Statement::AlterUser(stmt) => visitor.visit_alter_user(stmt),
-        Statement::DropUser { if_exists, user } => visitor.visit_drop_user(*if_exists, user),
-        Statement::CreateRole {
-            if_not_exists,
-            role_name,
-        } => visitor.visit_create_role(*if_not_exists, role_name),
-        Statement::DropRole {
-            if_exists,
-            role_name,
-        } => visitor.visit_drop_role(*if_exists, role_name),
-        Statement::Grant(stmt) => visitor.visit_grant(stmt),
-        Statement::ShowGrants { principal } => visitor.visit_show_grant(principal),
-        Statement::Revoke(stmt) => visitor.visit_revoke(stmt),
-        Statement::CreateUDF(stmt) => visitor.visit_create_udf(stmt),
-        Statement::DropUDF {
-            if_exists,
-            udf_name,
-        } => visitor.visit_drop_udf(*if_exists, udf_name),
-        Statement::AlterUDF(stmt) => visitor.visit_alter_udf(stmt),
-        Statement::ListStage { location, pattern } => visitor.visit_list_stage(location, pattern),
-        Statement::ShowStages => visitor.visit_show_stages(),
-        Statement::DropStage {
-            if_exists,
-            stage_name,
-        } => visitor.visit_drop_stage(*if_exists, stage_name),
-        Statement::CreateStage(stmt) => visitor.visit_create_stage(stmt),
-        Statement::RemoveStage { location, pattern } => {
-            visitor.visit_remove_stage(location, pattern)
-        }
-        Statement::CreateFileFormat {
-            if_not_exists,
-            name,
-            file_format_options,
-        } => visitor.visit_create_file_format(*if_not_exists, name, file_format_options),
-        Statement::DropFileFormat { if_exists, name } => {
-            visitor.visit_drop_file_format(*if_exists, name)
-        }
-        Statement::ShowFileFormats => visitor.visit_show_file_formats(),
-        Statement::DescribeStage { stage_name } => visitor.visit_describe_stage(stage_name),
-        Statement::Call(stmt) => visitor.visit_call(stmt),
-        Statement::Presign(stmt) => visitor.visit_presign(stmt),
-        Statement::CreateShareEndpoint(stmt) => visitor.visit_create_share_endpoint(stmt),
-        Statement::ShowShareEndpoint(stmt) => visitor.visit_show_share_endpoint(stmt),
-        Statement::DropShareEndpoint(stmt) => visitor.visit_drop_share_endpoint(stmt),
-        Statement::CreateShare(stmt) => visitor.visit_create_share(stmt),
-        Statement::DropShare(stmt) => visitor.visit_drop_share(stmt),
-        Statement::GrantShareObject(stmt) => visitor.visit_grant_share_object(stmt),
-        Statement::RevokeShareObject(stmt) => visitor.visit_revoke_share_object(stmt),
-        Statement::AlterShareTenants(stmt) => visitor.visit_alter_share_tenants(stmt),
-        Statement::DescShare(stmt) => visitor.visit_desc_share(stmt),
-        Statement::ShowShares(stmt) => visitor.visit_show_shares(stmt),
-        Statement::ShowObjectGrantPrivileges(stmt) => {
-            visitor.visit_show_object_grant_privileges(stmt)
-        }
-        Statement::ShowGrantsOfShare(stmt) => visitor.visit_show_grants_of_share(stmt),
-        Statement::CreateDatamaskPolicy(stmt) => visitor.visit_create_data_mask_policy(stmt),
-        Statement::DropDatamaskPolicy(stmt) => visitor.visit_drop_data_mask_policy(stmt),
-        Statement::DescDatamaskPolicy(stmt) => visitor.visit_desc_data_mask_policy(stmt),
-        Statement::AttachTable(_) => {}
-        Statement::CreateNetworkPolicy(stmt) => visitor.visit_create_network_policy(stmt),
-        Statement::AlterNetworkPolicy(stmt) => visitor.visit_alter_network_policy(stmt),
-        Statement::DropNetworkPolicy(stmt) => visitor.visit_drop_network_policy(stmt),
-        Statement::DescNetworkPolicy(stmt) => visitor.visit_desc_network_policy(stmt),
-        Statement::ShowNetworkPolicies => visitor.visit_show_network_policies(),
-    }
+    walk_node(
+        visitor,
+        statement,
+        AstNode::Statement,
+        |visitor, statement| match statement {
+            Statement::Explain { kind, query } => visitor.visit_explain(kind, query),
+            Statement::ExplainAnalyze { query } => visitor.visit_statement(query),
+            Statement::Query(query) => visitor.visit_query(query),
+            Statement::Insert(insert) => visitor.visit_insert(insert),
+            Statement::Replace(replace) => visitor.visit_replace(replace),
+            Statement::MergeInto(merge_into) => visitor.visit_merge_into(merge_into),
+            Statement::Delete {
+                table_reference,
+                selection,
+                ..
+            } => visitor.visit_delete(table_reference, selection),
+            Statement::Update(update) => visitor.visit_update(update),
+            Statement::Copy(stmt) => visitor.visit_copy(stmt),
+            Statement::ShowSettings { like } => visitor.visit_show_settings(like),
+            Statement::ShowProcessList => visitor.visit_show_process_list(),
+            Statement::ShowMetrics => visitor.visit_show_metrics(),
+            Statement::ShowEngines => visitor.visit_show_engines(),
+            Statement::ShowFunctions { limit } => visitor.visit_show_functions(limit),
+            Statement::ShowTableFunctions { limit } => visitor.visit_show_table_functions(limit),
+            Statement::ShowIndexes => visitor.visit_show_indexes(),
+            Statement::KillStmt {
+                kill_target,
+                object_id,
+            } => visitor.visit_kill(kill_target, object_id),
+            Statement::SetVariable {
+                is_global,
+                variable,
+                value,
+            } => visitor.visit_set_variable(*is_global, variable, value),
+            Statement::UnSetVariable(stmt) => visitor.visit_unset_variable(stmt),
+            Statement::SetRole {
+                is_default,
+                role_name,
+            } => visitor.visit_set_role(*is_default, role_name),
+            Statement::ShowCatalogs(stmt) => visitor.visit_show_catalogs(stmt),
+            Statement::ShowCreateCatalog(stmt) => visitor.visit_show_create_catalog(stmt),
+            Statement::CreateCatalog(stmt) => visitor.visit_create_catalog(stmt),
+            Statement::DropCatalog(stmt) => visitor.visit_drop_catalog(stmt),
+            Statement::ShowDatabases(stmt) => visitor.visit_show_databases(stmt),
+            Statement::ShowCreateDatabase(stmt) => visitor.visit_show_create_databases(stmt),
+            Statement::CreateDatabase(stmt) => visitor.visit_create_database(stmt),
+            Statement::DropDatabase(stmt) => visitor.visit_drop_database(stmt),
+            Statement::UndropDatabase(stmt) => visitor.visit_undrop_database(stmt),
+            Statement::AlterDatabase(stmt) => visitor.visit_alter_database(stmt),
+            Statement::UseDatabase { database } => visitor.visit_use_database(database),
+            Statement::ShowTables(stmt) => visitor.visit_show_tables(stmt),
+            Statement::ShowColumns(stmt) => visitor.visit_show_columns(stmt),
+            Statement::ShowCreateTable(stmt) => visitor.visit_show_create_table(stmt),
+            Statement::DescribeTable(stmt) => visitor.visit_describe_table(stmt),
+            Statement::ShowTablesStatus(stmt) => visitor.visit_show_tables_status(stmt),
+            Statement::ShowDropTables(stmt) => visitor.visit_show_drop_tables(stmt),
+            Statement::CreateTable(stmt) => visitor.visit_create_table(stmt),
+            Statement::DropTable(stmt) => visitor.visit_drop_table(stmt),
+            Statement::UndropTable(stmt) => visitor.visit_undrop_table(stmt),
+            Statement::AlterTable(stmt) => visitor.visit_alter_table(stmt),
+            Statement::RenameTable(stmt) => visitor.visit_rename_table(stmt),
+            Statement::TruncateTable(stmt) => visitor.visit_truncate_table(stmt),
+            Statement::OptimizeTable(stmt) => visitor.visit_optimize_table(stmt),
+            Statement::VacuumTable(stmt) => visitor.visit_vacuum_table(stmt),
+            Statement::VacuumDropTable(stmt) => visitor.visit_vacuum_drop_table(stmt),
+            Statement::AnalyzeTable(stmt) => visitor.visit_analyze_table(stmt),
+            Statement::ExistsTable(stmt) => visitor.visit_exists_table(stmt),
+            Statement::CreateView(stmt) => visitor.visit_create_view(stmt),
+            Statement::AlterView(stmt) => visitor.visit_alter_view(stmt),
+            Statement::DropView(stmt) => visitor.visit_drop_view(stmt),
+            Statement::CreateIndex(stmt) => visitor.visit_create_index(stmt),
+            Statement::DropIndex(stmt) => visitor.visit_drop_index(stmt),
+            Statement::RefreshIndex(stmt) => visitor.visit_refresh_index(stmt),
+            Statement::CreateVirtualColumn(stmt) => visitor.visit_create_virtual_column(stmt),
+            Statement::AlterVirtualColumn(stmt) => visitor.visit_alter_virtual_column(stmt),
+            Statement::DropVirtualColumn(stmt) => visitor.visit_drop_virtual_column(stmt),
+            Statement::RefreshVirtualColumn(stmt) => visitor.visit_refresh_virtual_column(stmt),
+            Statement::ShowUsers => visitor.visit_show_users(),
+            Statement::ShowRoles => visitor.visit_show_roles(),
+            Statement::CreateUser(stmt) => visitor.visit_create_user(stmt),
+            Statement::AlterUser(stmt) => visitor.visit_alter_user(stmt),
+            Statement::DropUser { if_exists, user } => visitor.visit_drop_user(*if_exists, user),
+            Statement::CreateRole {
+                if_not_exists,
+                role_name,
+            } => visitor.visit_create_role(*if_not_exists, role_name),
+            Statement::DropRole {
+                if_exists,
+                role_name,
+            } => visitor.visit_drop_role(*if_exists, role_name),
+            Statement::Grant(stmt) => visitor.visit_grant(stmt),
+            Statement::ShowGrants { principal } => visitor.visit_show_grant(principal),
+            Statement::Revoke(stmt) => visitor.visit_revoke(stmt),
+            Statement::CreateUDF(stmt) => visitor.visit_create_udf(stmt),
+            Statement::DropUDF {
+                if_exists,
+                udf_name,
+            } => visitor.visit_drop_udf(*if_exists, udf_name),
+            Statement::AlterUDF(stmt) => visitor.visit_alter_udf(stmt),
+            Statement::ListStage { location, pattern } => {
+                visitor.visit_list_stage(location, pattern)
+            }
+            Statement::ShowStages => visitor.visit_show_stages(),
+            Statement::DropStage {
+                if_exists,
+                stage_name,
+            } => visitor.visit_drop_stage(*if_exists, stage_name),
+            Statement::CreateStage(stmt) => visitor.visit_create_stage(stmt),
+            Statement::RemoveStage { location, pattern } => {
+                visitor.visit_remove_stage(location, pattern)
+            }
+            Statement::CreateFileFormat {
+                if_not_exists,
+                name,
+                file_format_options,
+            } => visitor.visit_create_file_format(*if_not_exists, name, file_format_options),
+            Statement::DropFileFormat { if_exists, name } => {
+                visitor.visit_drop_file_format(*if_exists, name)
+            }
+            Statement::ShowFileFormats => visitor.visit_show_file_formats(),
+            Statement::DescribeStage { stage_name } => visitor.visit_describe_stage(stage_name),
+            Statement::Call(stmt) => visitor.visit_call(stmt),
+            Statement::Presign(stmt) => visitor.visit_presign(stmt),
+            Statement::CreateShareEndpoint(stmt) => visitor.visit_create_share_endpoint(stmt),
+            Statement::ShowShareEndpoint(stmt) => visitor.visit_show_share_endpoint(stmt),
+            Statement::DropShareEndpoint(stmt) => visitor.visit_drop_share_endpoint(stmt),
+            Statement::CreateShare(stmt) => visitor.visit_create_share(stmt),
+            Statement::DropShare(stmt) => visitor.visit_drop_share(stmt),
+            Statement::GrantShareObject(stmt) => visitor.visit_grant_share_object(stmt),
+            Statement::RevokeShareObject(stmt) => visitor.visit_revoke_share_object(stmt),
+            Statement::AlterShareTenants(stmt) => visitor.visit_alter_share_tenants(stmt),
+            Statement::DescShare(stmt) => visitor.visit_desc_share(stmt),
+            Statement::ShowShares(stmt) => visitor.visit_show_shares(stmt),
+            Statement::ShowObjectGrantPrivileges(stmt) => {
+                visitor.visit_show_object_grant_privileges(stmt)
+            }
+            Statement::ShowGrantsOfShare(stmt) => visitor.visit_show_grants_of_share(stmt),
+            Statement::CreateDatamaskPolicy(stmt) => visitor.visit_create_data_mask_policy(stmt),
+            Statement::DropDatamaskPolicy(stmt) => visitor.visit_drop_data_mask_policy(stmt),
+            Statement::DescDatamaskPolicy(stmt) => visitor.visit_desc_data_mask_policy(stmt),
+            Statement::AttachTable(_) => {}
+            Statement::CreateNetworkPolicy(stmt) => visitor.visit_create_network_policy(stmt),
+            Statement::AlterNetworkPolicy(stmt) => visitor.visit_alter_network_policy(stmt),
+            Statement::DropNetworkPolicy(stmt) => visitor.visit_drop_network_policy(stmt),
+            Statement::DescNetworkPolicy(stmt) => visitor.visit_desc_network_policy(stmt),
+            Statement::ShowNetworkPolicies => visitor.visit_show_network_policies(),
+        },
+    );
 }
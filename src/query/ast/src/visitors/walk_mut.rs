@@ -12,128 +12,294 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::ControlFlow;
+
 use super::visitor_mut::VisitorMut;
+use super::AstNodeMut;
+use super::Children;
 use crate::ast::*;
 
+/// Enter a node, walk its children with `walk` unless they are skipped, and leave it.
+/// Nothing else is done if entering the node stops the traversal. The node can be
+/// replaced when it is entered or left.
+fn walk_node_mut<V: VisitorMut, N: ?Sized>(
+    visitor: &mut V,
+    node: &mut N,
+    as_node: fn(&mut N) -> AstNodeMut<'_>,
+    walk: impl FnOnce(&mut V, &mut N),
+) {
+    match visitor.enter_node(as_node(node)) {
+        ControlFlow::Break(()) => return,
+        ControlFlow::Continue(Children::Visit) => walk(visitor, node),
+        ControlFlow::Continue(Children::Skip) => {}
+    }
+    visitor.leave_node(as_node(node));
+}
+
 pub fn walk_expr_mut<V: VisitorMut>(visitor: &mut V, expr: &mut Expr) {
+    walk_node_mut(
+        visitor,
+        expr,
+        |expr| AstNodeMut::Expr(expr),
+        |visitor, expr| match expr {
+            Expr::ColumnRef {
+                span,
+                database,
+                table,
+                column,
+            } => visitor.visit_column_ref(*span, database, table, column),
+            Expr::IsNull { span, expr, not } => visitor.visit_is_null(*span, expr, *not),
+            Expr::IsDistinctFrom {
+                span,
+                left,
+                right,
+                not,
+            } => visitor.visit_is_distinct_from(*span, left, right, *not),
+            Expr::InList {
+                span,
+                expr,
+                list,
+                not,
+            } => visitor.visit_in_list(*span, expr, list, *not),
+            Expr::InSubquery {
+                span,
+                expr,
+                subquery,
+                not,
+            } => visitor.visit_in_subquery(*span, expr, subquery, *not),
+            Expr::Between {
+                span,
+                expr,
+                low,
+                high,
+                not,
+            } => visitor.visit_between(*span, expr, low, high, *not),
+            Expr::BinaryOp {
+                span,
+                op,
+                left,
+                right,
+            } => visitor.visit_binary_op(*span, op, left, right),
+            Expr::UnaryOp { span, op, expr } => visitor.visit_unary_op(*span, op, expr),
+            Expr::Cast {
+                span,
+                expr,
+                target_type,
+                pg_style,
+            } => visitor.visit_cast(*span, expr, target_type, *pg_style),
+            Expr::TryCast {
+                span,
+                expr,
+                target_type,
+            } => visitor.visit_try_cast(*span, expr, target_type),
+            Expr::Extract { span, kind, expr } => visitor.visit_extract(*span, kind, expr),
+            Expr::Position {
+                span,
+                substr_expr,
+                str_expr,
+            } => visitor.visit_position(*span, substr_expr, str_expr),
+            Expr::Substring {
+                span,
+                expr,
+                substring_from,
+                substring_for,
+            } => visitor.visit_substring(*span, expr, substring_from, substring_for),
+            Expr::Trim {
+                span,
+                expr,
+                trim_where,
+            } => visitor.visit_trim(*span, expr, trim_where),
+            Expr::Literal { span, lit } => visitor.visit_literal(*span, lit),
+            Expr::Placeholder { span } => visitor.visit_placeholder(*span),
+            Expr::CountAll { span, window } => visitor.visit_count_all(*span, window),
+            Expr::Tuple { span, exprs } => visitor.visit_tuple(*span, exprs),
+            Expr::FunctionCall {
+                span,
+                distinct,
+                name,
+                args,
+                params,
+                window,
+                lambda,
+            } => visitor.visit_function_call(*span, *distinct, name, args, params, window, lambda),
+            Expr::Case {
+                span,
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => visitor.visit_case_when(*span, operand, conditions, results, else_result),
+            Expr::Exists {
+                span,
+                not,
+                subquery,
+            } => visitor.visit_exists(*span, *not, subquery),
+            Expr::Subquery {
+                span,
+                modifier,
+                subquery,
+            } => visitor.visit_subquery(*span, modifier, subquery),
+            Expr::MapAccess {
+                span,
+                expr,
+                accessor,
+            } => visitor.visit_map_access(*span, expr, accessor),
+            Expr::Array { span, exprs } => visitor.visit_array(*span, exprs),
+            Expr::Map { span, kvs } => visitor.visit_map(*span, kvs),
+            Expr::Interval { span, expr, unit } => visitor.visit_interval(*span, expr, unit),
+            Expr::DateAdd {
+                span,
+                unit,
+                interval,
+                date,
+            } => visitor.visit_date_add(*span, unit, interval, date),
+            Expr::DateSub {
+                span,
+                date,
+                interval,
+                unit,
+            } => visitor.visit_date_sub(*span, unit, interval, date),
+            Expr::DateTrunc { span, unit, date } => visitor.visit_date_trunc(*span, unit, date),
+        },
+    );
+}
+
+/// Visit the sub-expressions of `expr` with `visit_expr`, unlike [`walk_expr_mut`]
+/// whose default methods walk them directly. Used by the visitors that rewrite an
+/// expression as a whole in `visit_expr`.
+pub(crate) fn walk_expr_children_mut<V: VisitorMut>(visitor: &mut V, expr: &mut Expr) {
     match expr {
-        Expr::ColumnRef {
-            span,
-            database,
-            table,
-            column,
-        } => visitor.visit_column_ref(*span, database, table, column),
-        Expr::IsNull { span, expr, not } => visitor.visit_is_null(*span, expr, *not),
-        Expr::IsDistinctFrom {
-            span,
-            left,
-            right,
-            not,
-        } => visitor.visit_is_distinct_from(*span, left, right, *not),
-        Expr::InList {
-            span,
-            expr,
-            list,
-            not,
-        } => visitor.visit_in_list(*span, expr, list, *not),
-        Expr::InSubquery {
-            span,
-            expr,
-            subquery,
-            not,
-        } => visitor.visit_in_subquery(*span, expr, subquery, *not),
+        Expr::IsNull { expr, .. }
+        | Expr::UnaryOp { expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::TryCast { expr, .. }
+        | Expr::Extract { expr, .. }
+        | Expr::MapAccess { expr, .. }
+        | Expr::Interval { expr, .. } => visitor.visit_expr(expr),
+        Expr::IsDistinctFrom { left, right, .. } | Expr::BinaryOp { left, right, .. } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        Expr::InList { expr, list, .. } => {
+            visitor.visit_expr(expr);
+            for expr in list.iter_mut() {
+                visitor.visit_expr(expr);
+            }
+        }
+        Expr::InSubquery { expr, subquery, .. } => {
+            visitor.visit_expr(expr);
+            visitor.visit_query(subquery);
+        }
         Expr::Between {
-            span,
-            expr,
-            low,
-            high,
-            not,
-        } => visitor.visit_between(*span, expr, low, high, *not),
-        Expr::BinaryOp {
-            span,
-            op,
-            left,
-            right,
-        } => visitor.visit_binary_op(*span, op, left, right),
-        Expr::UnaryOp { span, op, expr } => visitor.visit_unary_op(*span, op, expr),
-        Expr::Cast {
-            span,
-            expr,
-            target_type,
-            pg_style,
-        } => visitor.visit_cast(*span, expr, target_type, *pg_style),
-        Expr::TryCast {
-            span,
-            expr,
-            target_type,
-        } => visitor.visit_try_cast(*span, expr, target_type),
-        Expr::Extract { span, kind, expr } => visitor.visit_extract(*span, kind, expr),
+            expr, low, high, ..
+        } => {
+            visitor.visit_expr(expr);
+            visitor.visit_expr(low);
+            visitor.visit_expr(high);
+        }
         Expr::Position {
-            span,
             substr_expr,
             str_expr,
-        } => visitor.visit_position(*span, substr_expr, str_expr),
+            ..
+        } => {
+            visitor.visit_expr(substr_expr);
+            visitor.visit_expr(str_expr);
+        }
         Expr::Substring {
-            span,
             expr,
             substring_from,
             substring_for,
-        } => visitor.visit_substring(*span, expr, substring_from, substring_for),
+            ..
+        } => {
+            visitor.visit_expr(expr);
+            visitor.visit_expr(substring_from);
+            if let Some(substring_for) = substring_for {
+                visitor.visit_expr(substring_for);
+            }
+        }
         Expr::Trim {
-            span,
-            expr,
-            trim_where,
-        } => visitor.visit_trim(*span, expr, trim_where),
-        Expr::Literal { span, lit } => visitor.visit_literal(*span, lit),
-        Expr::CountAll { span, window } => visitor.visit_count_all(*span, window),
-        Expr::Tuple { span, exprs } => visitor.visit_tuple(*span, exprs),
+            expr, trim_where, ..
+        } => {
+            visitor.visit_expr(expr);
+            if let Some((_, trim_where_expr)) = trim_where {
+                visitor.visit_expr(trim_where_expr);
+            }
+        }
+        Expr::CountAll { window, .. } => {
+            if let Some(window) = window {
+                walk_window_children_mut(visitor, window);
+            }
+        }
+        Expr::Tuple { exprs, .. } | Expr::Array { exprs, .. } => {
+            for expr in exprs.iter_mut() {
+                visitor.visit_expr(expr);
+            }
+        }
         Expr::FunctionCall {
-            span,
-            distinct,
-            name,
             args,
-            params,
             window,
             lambda,
-        } => visitor.visit_function_call(*span, *distinct, name, args, params, window, lambda),
+            ..
+        } => {
+            for arg in args.iter_mut() {
+                visitor.visit_expr(arg);
+            }
+            if let Some(window) = window {
+                walk_window_children_mut(visitor, window);
+            }
+            if let Some(lambda) = lambda {
+                visitor.visit_expr(&mut lambda.expr);
+            }
+        }
         Expr::Case {
-            span,
             operand,
             conditions,
             results,
             else_result,
-        } => visitor.visit_case_when(*span, operand, conditions, results, else_result),
-        Expr::Exists {
-            span,
-            not,
-            subquery,
-        } => visitor.visit_exists(*span, *not, subquery),
-        Expr::Subquery {
-            span,
-            modifier,
-            subquery,
-        } => visitor.visit_subquery(*span, modifier, subquery),
-        Expr::MapAccess {
-            span,
-            expr,
-            accessor,
-        } => visitor.visit_map_access(*span, expr, accessor),
-        Expr::Array { span, exprs } => visitor.visit_array(*span, exprs),
-        Expr::Map { span, kvs } => visitor.visit_map(*span, kvs),
-        Expr::Interval { span, expr, unit } => visitor.visit_interval(*span, expr, unit),
-        Expr::DateAdd {
-            span,
-            unit,
-            interval,
-            date,
-        } => visitor.visit_date_add(*span, unit, interval, date),
-        Expr::DateSub {
-            span,
-            date,
-            interval,
-            unit,
-        } => visitor.visit_date_sub(*span, unit, interval, date),
-        Expr::DateTrunc { span, unit, date } => visitor.visit_date_trunc(*span, unit, date),
+            ..
+        } => {
+            if let Some(operand) = operand {
+                visitor.visit_expr(operand);
+            }
+            for expr in conditions.iter_mut().chain(results.iter_mut()) {
+                visitor.visit_expr(expr);
+            }
+            if let Some(else_result) = else_result {
+                visitor.visit_expr(else_result);
+            }
+        }
+        Expr::Exists { subquery, .. } | Expr::Subquery { subquery, .. } => {
+            visitor.visit_query(subquery);
+        }
+        Expr::DateAdd { interval, date, .. } | Expr::DateSub { interval, date, .. } => {
+            visitor.visit_expr(date);
+            visitor.visit_expr(interval);
+        }
+        Expr::DateTrunc { date, .. } => visitor.visit_expr(date),
+        Expr::ColumnRef { .. }
+        | Expr::Literal { .. }
+        | Expr::Placeholder { .. }
+        | Expr::Map { .. } => walk_expr_mut(visitor, expr),
+    }
+}
+
+fn walk_window_children_mut<V: VisitorMut>(visitor: &mut V, window: &mut Window) {
+    match window {
+        Window::WindowReference(reference) => {
+            visitor.visit_identifier(&mut reference.window_name);
+        }
+        Window::WindowSpec(spec) => {
+            for expr in spec.partition_by.iter_mut() {
+                visitor.visit_expr(expr);
+            }
+            for order_by in spec.order_by.iter_mut() {
+                visitor.visit_expr(&mut order_by.expr);
+            }
+            if let Some(frame) = &mut spec.window_frame {
+                visitor.visit_frame_bound(&mut frame.start_bound);
+                visitor.visit_frame_bound(&mut frame.end_bound);
+            }
+        }
     }
 }
 
@@ -146,135 +312,228 @@ pub fn walk_column_id_mut<V: VisitorMut>(visitor: &mut V, ident: &mut ColumnID)
 }
 
 pub fn walk_query_mut<V: VisitorMut>(visitor: &mut V, query: &mut Query) {
-    let Query {
-        with,
-        body,
-        order_by,
-        limit,
-        offset,
-        ..
-    } = query;
-
-    if let Some(with) = with {
-        visitor.visit_with(with);
-    }
-    visitor.visit_set_expr(body);
-    for order_by in order_by {
-        visitor.visit_order_by(order_by);
-    }
-    for limit in limit {
-        visitor.visit_expr(limit);
-    }
-    if let Some(offset) = offset {
-        visitor.visit_expr(offset);
-    }
+    walk_node_mut(
+        visitor,
+        query,
+        |query| AstNodeMut::Query(query),
+        |visitor, query| {
+            let Query {
+                with,
+                body,
+                order_by,
+                limit,
+                offset,
+                ..
+            } = query;
+
+            if let Some(with) = with {
+                visitor.visit_with(with);
+            }
+            visitor.visit_set_expr(body);
+            for order_by in order_by {
+                visitor.visit_order_by(order_by);
+            }
+            for limit in limit {
+                visitor.visit_expr(limit);
+            }
+            if let Some(offset) = offset {
+                visitor.visit_expr(offset);
+            }
+        },
+    );
 }
 
 pub fn walk_set_expr_mut<V: VisitorMut>(visitor: &mut V, set_expr: &mut SetExpr) {
-    match set_expr {
-        SetExpr::Select(select) => {
-            visitor.visit_select_stmt(select);
-        }
-        SetExpr::Query(query) => {
-            visitor.visit_query(query);
-        }
-        SetExpr::SetOperation(op) => {
-            visitor.visit_set_operation(op);
-        }
-        SetExpr::Values { values, .. } => {
-            for row_values in values {
-                for value in row_values {
-                    visitor.visit_expr(value);
+    walk_node_mut(
+        visitor,
+        set_expr,
+        |set_expr| AstNodeMut::SetExpr(set_expr),
+        |visitor, set_expr| match set_expr {
+            SetExpr::Select(select) => {
+                visitor.visit_select_stmt(select);
+            }
+            SetExpr::Query(query) => {
+                visitor.visit_query(query);
+            }
+            SetExpr::SetOperation(op) => {
+                visitor.visit_set_operation(op);
+            }
+            SetExpr::Values { values, .. } => {
+                for row_values in values {
+                    for value in row_values {
+                        visitor.visit_expr(value);
+                    }
                 }
             }
-        }
-    }
+        },
+    );
 }
 
-pub fn walk_select_target_mut<V: VisitorMut>(visitor: &mut V, target: &mut SelectTarget) {
-    match target {
-        SelectTarget::AliasedExpr { expr, alias } => {
-            visitor.visit_expr(expr);
-            if let Some(alias) = alias {
-                visitor.visit_identifier(alias);
+pub fn walk_select_stmt_mut<V: VisitorMut>(visitor: &mut V, stmt: &mut SelectStmt) {
+    walk_node_mut(
+        visitor,
+        stmt,
+        |stmt| AstNodeMut::SelectStmt(stmt),
+        |visitor, stmt| {
+            let SelectStmt {
+                select_list,
+                from,
+                selection,
+                group_by,
+                having,
+                ..
+            } = stmt;
+
+            for target in select_list.iter_mut() {
+                walk_select_target_mut(visitor, target);
             }
-        }
-        SelectTarget::QualifiedName {
-            qualified: names,
-            exclude,
-        } => {
-            for indirection in names {
-                match indirection {
-                    Indirection::Identifier(ident) => {
-                        visitor.visit_identifier(ident);
+
+            for table_ref in from.iter_mut() {
+                visitor.visit_table_reference(table_ref);
+            }
+
+            if let Some(selection) = selection {
+                walk_expr_mut(visitor, selection);
+            }
+
+            match group_by {
+                Some(GroupBy::Normal(exprs) | GroupBy::Cube(exprs) | GroupBy::Rollup(exprs)) => {
+                    for expr in exprs {
+                        walk_expr_mut(visitor, expr);
+                    }
+                }
+                Some(GroupBy::GroupingSets(sets)) => {
+                    for set in sets {
+                        for expr in set {
+                            walk_expr_mut(visitor, expr);
+                        }
                     }
-                    Indirection::Star(_) => {}
                 }
+                Some(GroupBy::All) | None => {}
+            }
+
+            if let Some(having) = having {
+                walk_expr_mut(visitor, having);
             }
-            if let Some(cols) = exclude {
-                for ident in cols {
-                    visitor.visit_column_id(ident);
+        },
+    );
+}
+
+pub fn walk_select_target_mut<V: VisitorMut>(visitor: &mut V, target: &mut SelectTarget) {
+    walk_node_mut(
+        visitor,
+        target,
+        |target| AstNodeMut::SelectTarget(target),
+        |visitor, target| match target {
+            SelectTarget::AliasedExpr { expr, alias } => {
+                visitor.visit_expr(expr);
+                if let Some(alias) = alias {
+                    visitor.visit_identifier(alias);
                 }
             }
-        }
-    }
+            SelectTarget::QualifiedName {
+                qualified: names,
+                exclude,
+            } => {
+                for indirection in names {
+                    match indirection {
+                        Indirection::Identifier(ident) => {
+                            visitor.visit_identifier(ident);
+                        }
+                        Indirection::Star(_) => {}
+                    }
+                }
+                if let Some(cols) = exclude {
+                    for ident in cols {
+                        visitor.visit_column_id(ident);
+                    }
+                }
+            }
+        },
+    );
 }
 
 pub fn walk_table_reference_mut<V: VisitorMut>(visitor: &mut V, table_ref: &mut TableReference) {
-    match table_ref {
-        TableReference::Table {
-            catalog,
-            database,
-            table,
-            alias,
-            travel_point,
-            ..
-        } => {
-            if let Some(catalog) = catalog {
-                visitor.visit_identifier(catalog);
-            }
+    walk_node_mut(
+        visitor,
+        table_ref,
+        |table_ref| AstNodeMut::TableReference(table_ref),
+        |visitor, table_ref| match table_ref {
+            TableReference::Table {
+                catalog,
+                database,
+                table,
+                alias,
+                travel_point,
+                ..
+            } => {
+                if let Some(catalog) = catalog {
+                    visitor.visit_identifier(catalog);
+                }
 
-            if let Some(database) = database {
-                visitor.visit_identifier(database);
-            }
+                if let Some(database) = database {
+                    visitor.visit_identifier(database);
+                }
 
-            visitor.visit_identifier(table);
+                visitor.visit_identifier(table);
 
-            if let Some(alias) = alias {
-                visitor.visit_identifier(&mut alias.name);
-            }
+                if let Some(alias) = alias {
+                    visitor.visit_identifier(&mut alias.name);
+                }
 
-            if let Some(travel_point) = travel_point {
-                visitor.visit_time_travel_point(travel_point);
+                if let Some(travel_point) = travel_point {
+                    visitor.visit_time_travel_point(travel_point);
+                }
             }
-        }
-        TableReference::Subquery {
-            subquery, alias, ..
-        } => {
-            visitor.visit_query(subquery);
-            if let Some(alias) = alias {
-                visitor.visit_identifier(&mut alias.name);
+            TableReference::Subquery {
+                subquery, alias, ..
+            } => {
+                visitor.visit_query(subquery);
+                if let Some(alias) = alias {
+                    visitor.visit_identifier(&mut alias.name);
+                }
             }
-        }
-        TableReference::TableFunction {
-            name,
-            params,
-            alias,
-            ..
-        } => {
-            visitor.visit_identifier(name);
-            for param in params {
-                visitor.visit_expr(param);
+            TableReference::TableFunction {
+                name,
+                params,
+                alias,
+                ..
+            } => {
+                visitor.visit_identifier(name);
+                for param in params {
+                    visitor.visit_expr(param);
+                }
+                if let Some(alias) = alias {
+                    visitor.visit_identifier(&mut alias.name);
+                }
             }
-            if let Some(alias) = alias {
-                visitor.visit_identifier(&mut alias.name);
+            TableReference::Join { join, .. } => {
+                visitor.visit_join(join);
             }
-        }
-        TableReference::Join { join, .. } => {
-            visitor.visit_join(join);
-        }
-        TableReference::Location { .. } => {}
-    }
+            TableReference::Location { .. } => {}
+        },
+    );
+}
+
+pub fn walk_join_mut<V: VisitorMut>(visitor: &mut V, join: &mut Join) {
+    walk_node_mut(
+        visitor,
+        join,
+        |join| AstNodeMut::Join(join),
+        |visitor, join| {
+            let Join {
+                left,
+                right,
+                condition,
+                ..
+            } = join;
+
+            visitor.visit_table_reference(left);
+            visitor.visit_table_reference(right);
+
+            walk_join_condition_mut(visitor, condition);
+        },
+    );
 }
 
 pub fn walk_time_travel_point_mut<V: VisitorMut>(visitor: &mut V, time: &mut TimeTravelPoint) {
@@ -297,153 +556,174 @@ pub fn walk_join_condition_mut<V: VisitorMut>(visitor: &mut V, join_cond: &mut J
     }
 }
 
+pub fn walk_with_mut<V: VisitorMut>(visitor: &mut V, with: &mut With) {
+    let With { ctes, .. } = with;
+    for cte in ctes.iter_mut() {
+        walk_cte_mut(visitor, cte);
+    }
+}
+
 pub fn walk_cte_mut<V: VisitorMut>(visitor: &mut V, cte: &mut CTE) {
-    let CTE { alias, query, .. } = cte;
+    walk_node_mut(
+        visitor,
+        cte,
+        |cte| AstNodeMut::Cte(cte),
+        |visitor, cte| {
+            let CTE { alias, query, .. } = cte;
 
-    visitor.visit_identifier(&mut alias.name);
-    visitor.visit_query(query);
+            visitor.visit_identifier(&mut alias.name);
+            visitor.visit_query(query);
+        },
+    );
 }
 
 pub fn walk_statement_mut<V: VisitorMut>(visitor: &mut V, statement: &mut Statement) {
-    match statement {
-        Statement::Explain { kind, query } => visitor.visit_explain(kind, &mut *query),
-        Statement::ExplainAnalyze { query } => visitor.visit_statement(&mut *query),
-        Statement::Query(query) => visitor.visit_query(&mut *query),
-        Statement::Insert(insert) => visitor.visit_insert(insert),
-        Statement::Replace(replace) => visitor.visit_replace(replace),
-        Statement::MergeInto(merge_into) => visitor.visit_merge_into(merge_into),
-        Statement::Delete {
-            table_reference,
-            selection,
-            ..
-        } => visitor.visit_delete(table_reference, selection),
-        Statement::Update(update) => visitor.visit_update(update),
-        Statement::Copy(stmt) => visitor.visit_copy(stmt),
-        Statement::ShowSettings { like } => visitor.visit_show_settings(like),
-        Statement::ShowProcessList => visitor.visit_show_process_list(),
-        Statement::ShowMetrics => visitor.visit_show_metrics(),
-        Statement::ShowEngines => visitor.visit_show_engines(),
-        Statement::ShowFunctions { limit } => visitor.visit_show_functions(limit),
-        Statement::ShowIndexes => visitor.visit_show_indexes(),
-        Statement::ShowTableFunctions { limit } => visitor.visit_show_table_functions(limit),
-        Statement::KillStmt {
-            kill_target,
-            object_id,
-        } => visitor.visit_kill(kill_target, object_id),
-        Statement::SetVariable {
-            is_global,
-            variable,
-            value,
-        } => visitor.visit_set_variable(*is_global, variable, value),
-        Statement::UnSetVariable(stmt) => visitor.visit_unset_variable(stmt),
-        Statement::SetRole {
-            is_default,
-            role_name,
-        } => visitor.visit_set_role(*is_default, role_name),
-        Statement::ShowCatalogs(stmt) => visitor.visit_show_catalogs(stmt),
-        Statement::ShowCreateCatalog(stmt) => visitor.visit_show_create_catalog(stmt),
-        Statement::CreateCatalog(stmt) => visitor.visit_create_catalog(stmt),
-        Statement::DropCatalog(stmt) => visitor.visit_drop_catalog(stmt),
-        Statement::ShowDatabases(stmt) => visitor.visit_show_databases(stmt),
-        Statement::ShowCreateDatabase(stmt) => visitor.visit_show_create_databases(stmt),
-        Statement::CreateDatabase(stmt) => visitor.visit_create_database(stmt),
-        Statement::DropDatabase(stmt) => visitor.visit_drop_database(stmt),
-        Statement::UndropDatabase(stmt) => visitor.visit_undrop_database(stmt),
-        Statement::AlterDatabase(stmt) => visitor.visit_alter_database(stmt),
-        Statement::UseDatabase { database } => visitor.visit_use_database(database),
-        Statement::ShowTables(stmt) => visitor.visit_show_tables(stmt),
-        Statement::ShowColumns(stmt) => visitor.visit_show_columns(stmt),
-        Statement::ShowCreateTable(stmt) => visitor.visit_show_create_table(stmt),
-        Statement::DescribeTable(stmt) => visitor.visit_describe_table(stmt),
-        Statement::ShowTablesStatus(stmt) => visitor.visit_show_tables_status(stmt),
-        Statement::ShowDropTables(stmt) => visitor.visit_show_drop_tables(stmt),
-        Statement::CreateTable(stmt) => visitor.visit_create_table(stmt),
-        Statement::DropTable(stmt) => visitor.visit_drop_table(stmt),
-        Statement::UndropTable(stmt) => visitor.visit_undrop_table(stmt),
-        Statement::AlterTable(stmt) => visitor.visit_alter_table(stmt),
-        Statement::RenameTable(stmt) => visitor.visit_rename_table(stmt),
-        Statement::TruncateTable(stmt) => visitor.visit_truncate_table(stmt),
-        Statement::OptimizeTable(stmt) => visitor.visit_optimize_table(stmt),
-        Statement::VacuumTable(stmt) => visitor.visit_vacuum_table(stmt),
-        Statement::VacuumDropTable(stmt) => visitor.visit_vacuum_drop_table(stmt),
-        Statement::AnalyzeTable(stmt) => visitor.visit_analyze_table(stmt),
-        Statement::ExistsTable(stmt) => visitor.visit_exists_table(stmt),
-        Statement::CreateView(stmt) => visitor.visit_create_view(stmt),
-        Statement::AlterView(stmt) => visitor.visit_alter_view(stmt),
-        Statement::DropView(stmt) => visitor.visit_drop_view(stmt),
-        Statement::CreateIndex(stmt) => visitor.visit_create_index(stmt),
-        Statement::DropIndex(stmt) => visitor.visit_drop_index(stmt),
-        Statement::RefreshIndex(stmt) => visitor.visit_refresh_index(stmt),
-        Statement::CreateVirtualColumn(stmt) => visitor.visit_create_virtual_column(stmt),
-        Statement::AlterVirtualColumn(stmt) => visitor.visit_alter_virtual_column(stmt),
-        Statement::DropVirtualColumn(stmt) => visitor.visit_drop_virtual_column(stmt),
-        Statement::RefreshVirtualColumn(stmt) => visitor.visit_refresh_virtual_column(stmt),
-        Statement::ShowUsers => visitor.visit_show_users(),
-        Statement::ShowRoles => visitor.visit_show_roles(),
-        Statement::CreateUser(stmt) => visitor.visit_create_user(stmt),
-        Statement::AlterUser(stmt) => visitor.visit_alter_user(stmt),
-        Statement::DropUser { if_exists, user } => visitor.visit_drop_user(*if_exists, user),
-        Statement::CreateRole {
-            if_not_exists,
-            role_name,
-        } => visitor.visit_create_role(*if_not_exists, role_name),
-        Statement::DropRole {
-            if_exists,
-            role_name,
-        } => visitor.visit_drop_role(*if_exists, role_name),
-        Statement::Grant(stmt) => visitor.visit_grant(stmt),
-        Statement::ShowGrants { principal } => visitor.visit_show_grant(principal),
-        Statement::Revoke(stmt) => visitor.visit_revoke(stmt),
-        Statement::CreateUDF(stmt) => visitor.visit_create_udf(stmt),
-        Statement::DropUDF {
-            if_exists,
-            udf_name,
-        } => visitor.visit_drop_udf(*if_exists, udf_name),
-        Statement::AlterUDF(stmt) => visitor.visit_alter_udf(stmt),
-        Statement::ListStage { location, pattern } => visitor.visit_list_stage(location, pattern),
-        Statement::ShowStages => visitor.visit_show_stages(),
-        Statement::DropStage {
-            if_exists,
-            stage_name,
-        } => visitor.visit_drop_stage(*if_exists, stage_name),
-        Statement::CreateStage(stmt) => visitor.visit_create_stage(stmt),
-        Statement::RemoveStage { location, pattern } => {
-            visitor.visit_remove_stage(location, pattern)
-        }
-        Statement::DescribeStage { stage_name } => visitor.visit_describe_stage(stage_name),
-        Statement::CreateFileFormat {
-            if_not_exists,
-            name,
-            file_format_options,
-        } => visitor.visit_create_file_format(*if_not_exists, name, file_format_options),
-        Statement::DropFileFormat { if_exists, name } => {
-            visitor.visit_drop_file_format(*if_exists, name)
-        }
-        Statement::ShowFileFormats => visitor.visit_show_file_formats(),
-        Statement::Call(stmt) => visitor.visit_call(stmt),
-        Statement::Presign(stmt) => visitor.visit_presign(stmt),
-        Statement::CreateShareEndpoint(stmt) => visitor.visit_create_share_endpoint(stmt),
-        Statement::ShowShareEndpoint(stmt) => visitor.visit_show_share_endpoint(stmt),
-        Statement::DropShareEndpoint(stmt) => visitor.visit_drop_share_endpoint(stmt),
-        Statement::CreateShare(stmt) => visitor.visit_create_share(stmt),
-        Statement::DropShare(stmt) => visitor.visit_drop_share(stmt),
-        Statement::GrantShareObject(stmt) => visitor.visit_grant_share_object(stmt),
-        Statement::RevokeShareObject(stmt) => visitor.visit_revoke_share_object(stmt),
-        Statement::AlterShareTenants(stmt) => visitor.visit_alter_share_tenants(stmt),
-        Statement::DescShare(stmt) => visitor.visit_desc_share(stmt),
-        Statement::ShowShares(stmt) => visitor.visit_show_shares(stmt),
-        Statement::ShowObjectGrantPrivileges(stmt) => {
-            visitor.visit_show_object_grant_privileges(stmt)
-        }
-        Statement::ShowGrantsOfShare(stmt) => visitor.visit_show_grants_of_share(stmt),
-        Statement::CreateDatamaskPolicy(stmt) => visitor.visit_create_data_mask_policy(stmt),
-        Statement::DropDatamaskPolicy(stmt) => visitor.visit_drop_data_mask_policy(stmt),
-        Statement::DescDatamaskPolicy(stmt) => visitor.visit_desc_data_mask_policy(stmt),
-        Statement::AttachTable(_) => {}
-        Statement::CreateNetworkPolicy(stmt) => visitor.visit_create_network_policy(stmt),
-        Statement::AlterNetworkPolicy(stmt) => visitor.visit_alter_network_policy(stmt),
-        Statement::DropNetworkPolicy(stmt) => visitor.visit_drop_network_policy(stmt),
-        Statement::DescNetworkPolicy(stmt) => visitor.visit_desc_network_policy(stmt),
-        Statement::ShowNetworkPolicies => visitor.visit_show_network_policies(),
-    }
+    walk_node_mut(
+        visitor,
+        statement,
+        |statement| AstNodeMut::Statement(statement),
+        |visitor, statement| match statement {
+            Statement::Explain { kind, query } => visitor.visit_explain(kind, &mut *query),
+            Statement::ExplainAnalyze { query } => visitor.visit_statement(&mut *query),
+            Statement::Query(query) => visitor.visit_query(&mut *query),
+            Statement::Insert(insert) => visitor.visit_insert(insert),
+            Statement::Replace(replace) => visitor.visit_replace(replace),
+            Statement::MergeInto(merge_into) => visitor.visit_merge_into(merge_into),
+            Statement::Delete {
+                table_reference,
+                selection,
+                ..
+            } => visitor.visit_delete(table_reference, selection),
+            Statement::Update(update) => visitor.visit_update(update),
+            Statement::Copy(stmt) => visitor.visit_copy(stmt),
+            Statement::ShowSettings { like } => visitor.visit_show_settings(like),
+            Statement::ShowProcessList => visitor.visit_show_process_list(),
+            Statement::ShowMetrics => visitor.visit_show_metrics(),
+            Statement::ShowEngines => visitor.visit_show_engines(),
+            Statement::ShowFunctions { limit } => visitor.visit_show_functions(limit),
+            Statement::ShowIndexes => visitor.visit_show_indexes(),
+            Statement::ShowTableFunctions { limit } => visitor.visit_show_table_functions(limit),
+            Statement::KillStmt {
+                kill_target,
+                object_id,
+            } => visitor.visit_kill(kill_target, object_id),
+            Statement::SetVariable {
+                is_global,
+                variable,
+                value,
+            } => visitor.visit_set_variable(*is_global, variable, value),
+            Statement::UnSetVariable(stmt) => visitor.visit_unset_variable(stmt),
+            Statement::SetRole {
+                is_default,
+                role_name,
+            } => visitor.visit_set_role(*is_default, role_name),
+            Statement::ShowCatalogs(stmt) => visitor.visit_show_catalogs(stmt),
+            Statement::ShowCreateCatalog(stmt) => visitor.visit_show_create_catalog(stmt),
+            Statement::CreateCatalog(stmt) => visitor.visit_create_catalog(stmt),
+            Statement::DropCatalog(stmt) => visitor.visit_drop_catalog(stmt),
+            Statement::ShowDatabases(stmt) => visitor.visit_show_databases(stmt),
+            Statement::ShowCreateDatabase(stmt) => visitor.visit_show_create_databases(stmt),
+            Statement::CreateDatabase(stmt) => visitor.visit_create_database(stmt),
+            Statement::DropDatabase(stmt) => visitor.visit_drop_database(stmt),
+            Statement::UndropDatabase(stmt) => visitor.visit_undrop_database(stmt),
+            Statement::AlterDatabase(stmt) => visitor.visit_alter_database(stmt),
+            Statement::UseDatabase { database } => visitor.visit_use_database(database),
+            Statement::ShowTables(stmt) => visitor.visit_show_tables(stmt),
+            Statement::ShowColumns(stmt) => visitor.visit_show_columns(stmt),
+            Statement::ShowCreateTable(stmt) => visitor.visit_show_create_table(stmt),
+            Statement::DescribeTable(stmt) => visitor.visit_describe_table(stmt),
+            Statement::ShowTablesStatus(stmt) => visitor.visit_show_tables_status(stmt),
+            Statement::ShowDropTables(stmt) => visitor.visit_show_drop_tables(stmt),
+            Statement::CreateTable(stmt) => visitor.visit_create_table(stmt),
+            Statement::DropTable(stmt) => visitor.visit_drop_table(stmt),
+            Statement::UndropTable(stmt) => visitor.visit_undrop_table(stmt),
+            Statement::AlterTable(stmt) => visitor.visit_alter_table(stmt),
+            Statement::RenameTable(stmt) => visitor.visit_rename_table(stmt),
+            Statement::TruncateTable(stmt) => visitor.visit_truncate_table(stmt),
+            Statement::OptimizeTable(stmt) => visitor.visit_optimize_table(stmt),
+            Statement::VacuumTable(stmt) => visitor.visit_vacuum_table(stmt),
+            Statement::VacuumDropTable(stmt) => visitor.visit_vacuum_drop_table(stmt),
+            Statement::AnalyzeTable(stmt) => visitor.visit_analyze_table(stmt),
+            Statement::ExistsTable(stmt) => visitor.visit_exists_table(stmt),
+            Statement::CreateView(stmt) => visitor.visit_create_view(stmt),
+            Statement::AlterView(stmt) => visitor.visit_alter_view(stmt),
+            Statement::DropView(stmt) => visitor.visit_drop_view(stmt),
+            Statement::CreateIndex(stmt) => visitor.visit_create_index(stmt),
+            Statement::DropIndex(stmt) => visitor.visit_drop_index(stmt),
+            Statement::RefreshIndex(stmt) => visitor.visit_refresh_index(stmt),
+            Statement::CreateVirtualColumn(stmt) => visitor.visit_create_virtual_column(stmt),
+            Statement::AlterVirtualColumn(stmt) => visitor.visit_alter_virtual_column(stmt),
+            Statement::DropVirtualColumn(stmt) => visitor.visit_drop_virtual_column(stmt),
+            Statement::RefreshVirtualColumn(stmt) => visitor.visit_refresh_virtual_column(stmt),
+            Statement::ShowUsers => visitor.visit_show_users(),
+            Statement::ShowRoles => visitor.visit_show_roles(),
+            Statement::CreateUser(stmt) => visitor.visit_create_user(stmt),
+            Statement::AlterUser(stmt) => visitor.visit_alter_user(stmt),
+            Statement::DropUser { if_exists, user } => visitor.visit_drop_user(*if_exists, user),
+            Statement::CreateRole {
+                if_not_exists,
+                role_name,
+            } => visitor.visit_create_role(*if_not_exists, role_name),
+            Statement::DropRole {
+                if_exists,
+                role_name,
+            } => visitor.visit_drop_role(*if_exists, role_name),
+            Statement::Grant(stmt) => visitor.visit_grant(stmt),
+            Statement::ShowGrants { principal } => visitor.visit_show_grant(principal),
+            Statement::Revoke(stmt) => visitor.visit_revoke(stmt),
+            Statement::CreateUDF(stmt) => visitor.visit_create_udf(stmt),
+            Statement::DropUDF {
+                if_exists,
+                udf_name,
+            } => visitor.visit_drop_udf(*if_exists, udf_name),
+            Statement::AlterUDF(stmt) => visitor.visit_alter_udf(stmt),
+            Statement::ListStage { location, pattern } => {
+                visitor.visit_list_stage(location, pattern)
+            }
+            Statement::ShowStages => visitor.visit_show_stages(),
+            Statement::DropStage {
+                if_exists,
+                stage_name,
+            } => visitor.visit_drop_stage(*if_exists, stage_name),
+            Statement::CreateStage(stmt) => visitor.visit_create_stage(stmt),
+            Statement::RemoveStage { location, pattern } => {
+                visitor.visit_remove_stage(location, pattern)
+            }
+            Statement::DescribeStage { stage_name } => visitor.visit_describe_stage(stage_name),
+            Statement::CreateFileFormat {
+                if_not_exists,
+                name,
+                file_format_options,
+            } => visitor.visit_create_file_format(*if_not_exists, name, file_format_options),
+            Statement::DropFileFormat { if_exists, name } => {
+                visitor.visit_drop_file_format(*if_exists, name)
+            }
+            Statement::ShowFileFormats => visitor.visit_show_file_formats(),
+            Statement::Call(stmt) => visitor.visit_call(stmt),
+            Statement::Presign(stmt) => visitor.visit_presign(stmt),
+            Statement::CreateShareEndpoint(stmt) => visitor.visit_create_share_endpoint(stmt),
+            Statement::ShowShareEndpoint(stmt) => visitor.visit_show_share_endpoint(stmt),
+            Statement::DropShareEndpoint(stmt) => visitor.visit_drop_share_endpoint(stmt),
+            Statement::CreateShare(stmt) => visitor.visit_create_share(stmt),
+            Statement::DropShare(stmt) => visitor.visit_drop_share(stmt),
+            Statement::GrantShareObject(stmt) => visitor.visit_grant_share_object(stmt),
+            Statement::RevokeShareObject(stmt) => visitor.visit_revoke_share_object(stmt),
+            Statement::AlterShareTenants(stmt) => visitor.visit_alter_share_tenants(stmt),
+            Statement::DescShare(stmt) => visitor.visit_desc_share(stmt),
+            Statement::ShowShares(stmt) => visitor.visit_show_shares(stmt),
+            Statement::ShowObjectGrantPrivileges(stmt) => {
+                visitor.visit_show_object_grant_privileges(stmt)
+            }
+            Statement::ShowGrantsOfShare(stmt) => visitor.visit_show_grants_of_share(stmt),
+            Statement::CreateDatamaskPolicy(stmt) => visitor.visit_create_data_mask_policy(stmt),
+            Statement::DropDatamaskPolicy(stmt) => visitor.visit_drop_data_mask_policy(stmt),
+            Statement::DescDatamaskPolicy(stmt) => visitor.visit_desc_data_mask_policy(stmt),
+            Statement::AttachTable(_) => {}
+            Statement::CreateNetworkPolicy(stmt) => visitor.visit_create_network_policy(stmt),
+            Statement::AlterNetworkPolicy(stmt) => visitor.visit_alter_network_policy(stmt),
+            Statement::DropNetworkPolicy(stmt) => visitor.visit_drop_network_policy(stmt),
+            Statement::DescNetworkPolicy(stmt) => visitor.visit_desc_network_policy(stmt),
+            Statement::ShowNetworkPolicies => visitor.visit_show_network_policies(),
+        },
+    );
 }
@@ -0,0 +1,91 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::Anonymizer;
+use common_ast::Dialect;
+use common_ast::NameKind;
+use goldenfile::Mint;
+
+#[test]
+fn test_anonymizer() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("anonymizer.txt").unwrap();
+
+    let cases = &[
+        r#"SELECT c.Name, sum(o.amount) AS total FROM sales.customers AS c JOIN sales.orders AS o ON c.id = o.customer_id WHERE o.created >= '2023-06-01' AND c.email LIKE '%@example.com' GROUP BY c.name HAVING total > 12345.67 ORDER BY 2 DESC LIMIT 10"#,
+        r#"WITH recent AS (SELECT * FROM "Orders" WHERE status IN ('paid', 'shipped')) SELECT array_transform(tags, x -> upper(x)), payload:customer.id, count(*) OVER (PARTITION BY region) FROM recent"#,
+        r#"INSERT INTO sales.orders (id, note, amount) VALUES (1, 'It''s urgent', 3.5), (22, 'call 555-0100', 4e3)"#,
+        r#"UPDATE sales.orders SET note = 'refund' WHERE id = 42"#,
+        r#"COPY INTO sales.orders FROM @landing/2023/orders.csv FILE_FORMAT = (TYPE = CSV) PATTERN = '.*[.]csv'"#,
+        r#"CREATE STAGE landing URL = 's3://acme-data/exports/' CONNECTION = (REGION = 'us-east-2' ACCESS_KEY_ID = 'AKIAEXAMPLE' SECRET_ACCESS_KEY = 'secret' ENABLE_VIRTUAL_HOST_STYLE = 'true') COMMENTS = 'customer exports'"#,
+        r#"CREATE TABLE sales.refunds (id BIGINT, reason VARCHAR DEFAULT 'none' COMMENT 'why', amount DECIMAL(10, 2)) CLUSTER BY (id)"#,
+        r#"CREATE USER 'alice'@'%' IDENTIFIED BY 'P@ssw0rd!' WITH DEFAULT_ROLE = 'analyst'"#,
+        r#"ALTER USER 'bob'@'%' IDENTIFIED WITH sha256_password BY 'hunter2'"#,
+        r#"GRANT SELECT ON sales.orders TO ROLE analyst"#,
+        r#"GRANT ROLE analyst TO 'alice'@'%'"#,
+        r#"CREATE FUNCTION score (INT) RETURNS FLOAT LANGUAGE python HANDLER = 'score_handler' ADDRESS = 'http://10.1.2.3:8815'"#,
+        r#"CREATE FUNCTION mask_email AS (email) -> concat('***', substr(email, 4))"#,
+        r#"CREATE MASKING POLICY email_mask AS (val STRING) RETURNS STRING -> CASE WHEN current_role() IN ('admin') THEN val ELSE '********' END COMMENT = 'hide emails'"#,
+        r#"CREATE NETWORK POLICY office ALLOWED_IP_LIST = ('192.168.1.0/24') COMMENT = 'hq'"#,
+        r#"SHOW TABLES FROM sales LIKE 'cust%'"#,
+    ];
+
+    for case in cases {
+        let tokens = tokenize_sql(case).unwrap();
+        let (mut stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+        let mut anonymizer = Anonymizer::new();
+        anonymizer.anonymize(&mut stmt);
+        let anonymized = stmt.to_string();
+
+        // The anonymized statement should still be parseable.
+        let tokens = tokenize_sql(&anonymized).unwrap();
+        parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", case).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        writeln!(file, "{}", anonymized).unwrap();
+        writeln!(file, "---------- Mapping --------").unwrap();
+        write!(file, "{}", anonymizer.map()).unwrap();
+        writeln!(file, "\n").unwrap();
+    }
+}
+
+#[test]
+fn test_anonymizer_consistent_mapping() {
+    let mut anonymizer = Anonymizer::new();
+    let mut anonymize = |sql: &str| {
+        let tokens = tokenize_sql(sql).unwrap();
+        let (mut stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+        anonymizer.anonymize(&mut stmt);
+        stmt.to_string()
+    };
+
+    assert_eq!(
+        anonymize("SELECT Id FROM Users WHERE id = 1"),
+        "SELECT n1 FROM n2 WHERE (n1 = 1)"
+    );
+    assert_eq!(
+        anonymize("SELECT users.id FROM users"),
+        "SELECT n2.n1 FROM n2"
+    );
+
+    let map = anonymizer.map();
+    assert_eq!(map.pseudonym(NameKind::Name, "users"), Some("n2"));
+    assert_eq!(map.original("n1"), Some((NameKind::Name, "id")));
+}
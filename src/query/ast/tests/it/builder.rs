@@ -0,0 +1,156 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::ast::Engine;
+use common_ast::ast::InsertSource;
+use common_ast::ast::InsertStmt;
+use common_ast::ast::SetExpr;
+use common_ast::ast::Statement;
+use common_ast::ast::TypeName;
+use common_ast::builder::*;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::parser::ValuesRowReader;
+use common_ast::Dialect;
+use goldenfile::Mint;
+
+fn parse(sql: &str) -> Statement {
+    let tokens = tokenize_sql(sql).unwrap();
+    let (mut stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+    // Read the rows left in the source into a `VALUES` query like the builder's.
+    if let Statement::Insert(InsertStmt { source, .. }) = &mut stmt {
+        if let InsertSource::Values { start } = source {
+            let values = ValuesRowReader::new(sql, *start, Dialect::PostgreSQL)
+                .map(|row| {
+                    row.unwrap()
+                        .values
+                        .into_iter()
+                        .map(Option::unwrap)
+                        .collect()
+                })
+                .collect();
+            *source = InsertSource::Select {
+                query: Box::new(SetExpr::Values { span: None, values }.into_query()),
+            };
+        }
+    }
+    stmt
+}
+
+#[test]
+fn test_builder() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("builder.txt").unwrap();
+
+    let cases: Vec<Statement> = vec![
+        select([col("a").into(), func("sum", [col("b")]).alias("s")])
+            .from(table(("db", "t")))
+            .where_(col("x").gt(lit(5)))
+            .group_by([col("a")])
+            .order_by([col("s").desc()])
+            .limit(10)
+            .into(),
+        select([star()])
+            .distinct()
+            .from(table("t1").alias("x").left_join(
+                table(("c", "db", "t2")),
+                qualified_col("x", "id").eq(qualified_col("t2", "id")),
+            ))
+            .where_(col("a").is_not_null())
+            .where_(col("b").between(lit(-1), lit(1.5)))
+            .where_(not(col("c").in_list([lit("x"), lit("it's")])))
+            .limit(5)
+            .offset(10)
+            .into(),
+        select([qualified_star("s"), count_all().alias("select")])
+            .with("s", select([col("Order")]).from(table("order")))
+            .from(derived_table(select([col("a")]).from(table("t")), "s"))
+            .where_(exists(
+                select([lit(1)])
+                    .from(table("u"))
+                    .where_(col("u").eq(qualified_col("s", "a"))),
+            ))
+            .group_by([qualified_col("s", "a")])
+            .having(count_all().gt(lit(1)).or(col("a").like(lit("%x"))))
+            .into(),
+        select([col("a")
+            .plus(lit(1))
+            .multiply(col("b").minus(lit(2)))
+            .divide(lit(3))
+            .cast(TypeName::Int64)
+            .alias("v")])
+        .from(table("t").cross_join(table("u")))
+        .where_(col("a").in_subquery(select([col("a")]).from(table("s"))))
+        .where_(col("d").not_eq(subquery(select([func("max", [col("d")])]).from(table("s")))))
+        .into(),
+        insert_into(("db", "t"))
+            .columns(["a", "b"])
+            .values([[lit(1), lit("x")], [lit(2), null()]]),
+        insert_into("t").overwrite().select(
+            select([col("a")])
+                .from(table("s"))
+                .where_(col("a").lte(lit(1))),
+        ),
+        update(table("t"))
+            .set("a", col("a").plus(lit(1)))
+            .set("b", null())
+            .where_(col("c").eq(lit(true)))
+            .into(),
+        delete_from(table(("db", "t")))
+            .where_(col("a").lt(lit(0)))
+            .where_(col("b").is_null())
+            .into(),
+        merge_into(
+            "t1",
+            select([star()]).from(table("t2")),
+            qualified_col("x", "a").eq(qualified_col("t2", "a")),
+        )
+        .alias("x")
+        .when_matched_update(Some(qualified_col("t2", "b").gt(lit(0))), [(
+            "b",
+            qualified_col("t2", "b"),
+        )])
+        .when_matched_delete(None)
+        .when_not_matched_insert(None, ["a", "b"], [
+            qualified_col("t2", "a"),
+            qualified_col("t2", "b"),
+        ])
+        .into(),
+        create_table(("db", "t"))
+            .if_not_exists()
+            .column(column_def("a", TypeName::Int32).not_null())
+            .column(column_def("b", TypeName::String).default_value(lit("x")))
+            .column(
+                column_def("c", TypeName::Float64)
+                    .null()
+                    .with_comment("the c"),
+            )
+            .engine(Engine::Fuse)
+            .cluster_by([col("a")])
+            .option("comment", "events")
+            .into(),
+        create_table("t")
+            .transient()
+            .as_select(select([col("a")]).from(table("s")))
+            .into(),
+    ];
+
+    for stmt in cases {
+        let sql = stmt.to_string();
+        assert_eq!(parse(&sql).to_string(), sql);
+        writeln!(file, "{}", sql).unwrap();
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::ast::Statement;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::Dialect;
+use goldenfile::Mint;
+
+fn parse(sql: &str) -> Statement {
+    let tokens = tokenize_sql(sql).unwrap();
+    let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+    stmt
+}
+
+#[test]
+fn test_classify_statement() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("classification.txt").unwrap();
+
+    let cases = &[
+        r#"SELECT a FROM db.t1 JOIN t2 ON t1.a = t2.a"#,
+        r#"WITH c AS (SELECT * FROM t1) SELECT * FROM c"#,
+        r#"EXPLAIN DELETE FROM t1"#,
+        r#"EXPLAIN ANALYZE SELECT * FROM t1"#,
+        r#"INSERT INTO db.t1 SELECT * FROM t2, db2.v1"#,
+        r#"INSERT INTO Db.T1 SELECT * FROM db.t1, "T1""#,
+        r#"SELECT a FROM t1 GROUP BY CUBE (a, (SELECT max(b) FROM t2))"#,
+        r#"SELECT a FROM t1 GROUP BY ROLLUP ((SELECT max(b) FROM t2))"#,
+        r#"INSERT OVERWRITE t1 VALUES (1)"#,
+        r#"REPLACE INTO t1 ON (a) VALUES (1, 2)"#,
+        r#"MERGE INTO t1 USING (SELECT * FROM t2) ON t1.a = t2.a WHEN MATCHED THEN DELETE WHEN NOT MATCHED THEN INSERT (a) VALUES (t2.a)"#,
+        r#"DELETE FROM t1 WHERE a IN (SELECT a FROM t2)"#,
+        r#"UPDATE db.t1 SET a = a + 1"#,
+        r#"COPY INTO t1 FROM @stage/data.csv"#,
+        r#"COPY INTO t1 FROM @stage/data.csv FORCE = true"#,
+        r#"COPY INTO @stage FROM t1"#,
+        r#"CREATE TABLE IF NOT EXISTS db.t1 (a INT)"#,
+        r#"CREATE TABLE t3 AS SELECT * FROM t1"#,
+        r#"DROP TABLE db.t1"#,
+        r#"ALTER TABLE t1 ADD COLUMN b INT"#,
+        r#"ALTER TABLE t1 SET OPTIONS (a = 'b')"#,
+        r#"RENAME TABLE db.t1 TO db2.t2"#,
+        r#"TRUNCATE TABLE t1"#,
+        r#"OPTIMIZE TABLE t1 COMPACT"#,
+        r#"CREATE VIEW v1 AS SELECT * FROM t1"#,
+        r#"CREATE DATABASE IF NOT EXISTS db"#,
+        r#"DROP DATABASE db"#,
+        r#"CREATE USER 'u' IDENTIFIED BY 'p'"#,
+        r#"DROP USER IF EXISTS 'u'"#,
+        r#"CREATE ROLE r"#,
+        r#"GRANT SELECT, INSERT ON db.t1 TO ROLE r"#,
+        r#"REVOKE ALL ON db.* FROM 'u'"#,
+        r#"GRANT ROLE r TO 'u'"#,
+        r#"CREATE FUNCTION f AS (a) -> a + 1"#,
+        r#"CREATE STAGE s"#,
+        r#"REMOVE @s"#,
+        r#"PRESIGN UPLOAD @s/a.csv"#,
+        r#"SHOW TABLES"#,
+        r#"DESC t1"#,
+        r#"USE db"#,
+        r#"SET max_threads = 1"#,
+        r#"SET GLOBAL max_threads = 1"#,
+        r#"KILL QUERY '1'"#,
+        r#"CALL system$test(1)"#,
+    ];
+
+    for sql in cases {
+        let stmt = parse(sql);
+        let class = stmt.classify();
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", sql).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        writeln!(
+            file,
+            "category: {}, read only: {}, retry safe: {}",
+            class.category, class.read_only, class.retry_safe
+        )
+        .unwrap();
+        for privilege in stmt.required_privileges() {
+            writeln!(file, "{}", privilege).unwrap();
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::parser::complete;
+use common_ast::Dialect;
+use goldenfile::Mint;
+use itertools::Itertools;
+
+/// The number of keywords written for each case.
+const MAX_KEYWORDS: usize = 12;
+
+#[test]
+fn test_complete() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("completion.txt").unwrap();
+
+    // The cursor is at `|`.
+    let cases = vec![
+        ("|", Dialect::PostgreSQL),
+        ("SEL|", Dialect::PostgreSQL),
+        ("SELECT 1; SEL|", Dialect::PostgreSQL),
+        ("SELECT | FROM t", Dialect::PostgreSQL),
+        ("SELECT a FROM |", Dialect::PostgreSQL),
+        ("SELECT a FROM db.|", Dialect::PostgreSQL),
+        ("SELECT a FROM t WH|", Dialect::PostgreSQL),
+        ("SELECT a FROM t |", Dialect::PostgreSQL),
+        ("SELECT a FROM t WHERE NOT |", Dialect::PostgreSQL),
+        ("SELECT a FROM t JOIN |", Dialect::PostgreSQL),
+        ("SELECT t.| FROM db.t", Dialect::PostgreSQL),
+        ("SELECT CAST(a AS |) FROM t", Dialect::PostgreSQL),
+        ("SELECT a FROM t WHERE b = co|", Dialect::PostgreSQL),
+        ("COPY INTO t FROM |", Dialect::PostgreSQL),
+        (
+            "COPY INTO t FROM 's3://bucket/' CONNECTION = (|)",
+            Dialect::PostgreSQL,
+        ),
+        (
+            "COPY INTO t FROM @s FILE_FORMAT = (TYPE = CSV, |)",
+            Dialect::PostgreSQL,
+        ),
+        ("CREATE TABLE t (a INT) |", Dialect::PostgreSQL),
+        (
+            "CREATE TABLE t (a INT) ENGINE = FUSE comp|",
+            Dialect::PostgreSQL,
+        ),
+        ("DROP TABLE |", Dialect::PostgreSQL),
+        ("INSERT INTO | VALUES (1)", Dialect::PostgreSQL),
+        (
+            "WITH c AS (SELECT * FROM u AS x) SELECT | FROM c JOIN db.t ON c.a = t.a, v w",
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT * FROM t AS a WHERE EXISTS (SELECT * FROM u AS b WHERE |)",
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT * FROM (SELECT * FROM u AS b) AS s, t WHERE | AND EXTRACT(YEAR FROM d) = 1",
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT 1; SELECT * FROM t1; SELECT | FROM t2",
+            Dialect::PostgreSQL,
+        ),
+        ("SELECT `a`.| FROM `a`", Dialect::MySQL),
+        ("SELECT 'abc|' FROM t", Dialect::PostgreSQL),
+        ("SELECT a -- comment |\nFROM t", Dialect::PostgreSQL),
+        ("SELECT a /* comment */ | FROM t", Dialect::PostgreSQL),
+    ];
+
+    for (case, dialect) in cases {
+        let cursor = case.find('|').unwrap();
+        let sql = case.replacen('|', "", 1);
+        let completions = complete(&sql, cursor, dialect);
+
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", case).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        writeln!(
+            file,
+            "prefix: {:?} at {:?}",
+            completions.prefix, completions.span
+        )
+        .unwrap();
+        if !completions.qualifier.is_empty() {
+            writeln!(file, "qualifier: {}", completions.qualifier.join(".")).unwrap();
+        }
+        writeln!(
+            file,
+            "keywords: {}{}",
+            completions.keywords.iter().take(MAX_KEYWORDS).join(", "),
+            match completions.keywords.len().checked_sub(MAX_KEYWORDS) {
+                Some(more) if more > 0 => format!(", and {} more", more),
+                _ => String::new(),
+            }
+        )
+        .unwrap();
+        writeln!(file, "token kinds: {:?}", completions.token_kinds).unwrap();
+        writeln!(file, "contexts: {:?}", completions.contexts).unwrap();
+        writeln!(file, "ctes: {:?}", completions.ctes).unwrap();
+        writeln!(file, "aliases: {:?}", completions.aliases).unwrap();
+        writeln!(file, "\n").unwrap();
+    }
+}
@@ -0,0 +1,261 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+use std::ops::ControlFlow;
+
+use common_ast::ast::Expr;
+use common_ast::ast::Literal;
+use common_ast::ast::Query;
+use common_ast::ast::SelectTarget;
+use common_ast::ast::SetExpr;
+use common_ast::ast::Statement;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::traverse_expr;
+use common_ast::traverse_statement;
+use common_ast::traverse_statement_mut;
+use common_ast::walk_statement;
+use common_ast::AstNode;
+use common_ast::AstNodeMut;
+use common_ast::Children;
+use common_ast::Dialect;
+use common_ast::VisitResult;
+use common_ast::Visitor;
+use common_ast::VisitorMut;
+use goldenfile::Mint;
+
+fn parse(sql: &str) -> Statement {
+    let tokens = tokenize_sql(sql).unwrap();
+    let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+    stmt
+}
+
+/// Logs the statements, queries, selects, tables and expressions that are entered,
+/// and skips the subqueries.
+#[derive(Default)]
+struct Tracer {
+    depth: usize,
+    events: Vec<String>,
+}
+
+impl Tracer {
+    fn kind(node: AstNode) -> Option<(&'static str, String)> {
+        match node {
+            AstNode::Statement(stmt) => Some(("statement", stmt.to_string())),
+            AstNode::Query(query) => Some(("query", query.to_string())),
+            AstNode::SelectStmt(stmt) => Some(("select", stmt.to_string())),
+            AstNode::TableReference(table_ref) => Some(("table", table_ref.to_string())),
+            AstNode::Expr(expr) => Some(("expr", expr.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for Tracer {
+    fn enter_node(&mut self, node: AstNode<'ast>) -> VisitResult {
+        if let Some((kind, node)) = Self::kind(node) {
+            self.events
+                .push(format!("{}{kind} {node}", "  ".repeat(self.depth)));
+            self.depth += 1;
+        }
+        if matches!(node, AstNode::Expr(Expr::Subquery { .. })) {
+            ControlFlow::Continue(Children::Skip)
+        } else {
+            ControlFlow::Continue(Children::Visit)
+        }
+    }
+
+    fn leave_node(&mut self, node: AstNode<'ast>) {
+        if Self::kind(node).is_some() {
+            self.depth -= 1;
+        }
+    }
+}
+
+/// Stops at the first subquery and counts the expressions that are entered.
+#[derive(Default)]
+struct SubqueryFinder<'ast> {
+    entered: usize,
+    found: Option<&'ast Query>,
+}
+
+impl<'ast> Visitor<'ast> for SubqueryFinder<'ast> {
+    fn enter_node(&mut self, node: AstNode<'ast>) -> VisitResult {
+        if let AstNode::Expr(expr) = node {
+            self.entered += 1;
+            if let Expr::Subquery { subquery, .. }
+            | Expr::Exists { subquery, .. }
+            | Expr::InSubquery { subquery, .. } = expr
+            {
+                self.found = Some(subquery);
+                return ControlFlow::Break(());
+            }
+        }
+        ControlFlow::Continue(Children::Visit)
+    }
+}
+
+#[test]
+fn test_traverse_statement() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("control-flow.txt").unwrap();
+
+    let cases = &[
+        r#"SELECT a + 1 FROM t1 JOIN (SELECT b FROM t2) AS s ON t1.a = s.b WHERE a > (SELECT max(b) FROM t3)"#,
+        r#"WITH c AS (SELECT 1 AS x) SELECT x FROM c ORDER BY x LIMIT 1"#,
+        r#"INSERT INTO t1 SELECT a FROM t2 WHERE b = 1"#,
+        r#"UPDATE t1 SET a = a + 1 WHERE b IN (SELECT b FROM t2)"#,
+        r#"DELETE FROM t1 WHERE a IS NULL"#,
+        r#"CREATE TABLE t1 (a INT DEFAULT 1 + 1) CLUSTER BY (a)"#,
+        r#"EXPLAIN SELECT 1"#,
+        r#"SELECT a, sum(b) FROM t GROUP BY CUBE (a, c + 1)"#,
+        r#"SELECT a FROM t GROUP BY ROLLUP (a, (SELECT 1))"#,
+    ];
+
+    for sql in cases {
+        let stmt = parse(sql);
+        let mut tracer = Tracer::default();
+        assert_eq!(
+            traverse_statement(&mut tracer, &stmt),
+            ControlFlow::Continue(())
+        );
+        assert_eq!(tracer.depth, 0);
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", sql).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        for event in tracer.events {
+            writeln!(file, "{}", event).unwrap();
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
+
+#[test]
+fn test_traverse_break() {
+    let stmt = parse("SELECT a, b + c FROM t WHERE a IN (SELECT a FROM s WHERE x = 1) AND c = d");
+    let mut finder = SubqueryFinder::default();
+    assert!(traverse_statement(&mut finder, &stmt).is_break());
+    assert_eq!(
+        finder.found.map(ToString::to_string).as_deref(),
+        Some("SELECT a FROM s WHERE (x = 1)")
+    );
+    // `a`, `b + c`, `b`, `c`, the `AND` and the `IN`, but not the expressions
+    // after the subquery.
+    assert_eq!(finder.entered, 6);
+
+    let stmt = parse("SELECT a FROM t WHERE b = 1");
+    let mut finder = SubqueryFinder::default();
+    assert_eq!(
+        traverse_statement(&mut finder, &stmt),
+        ControlFlow::Continue(())
+    );
+    assert_eq!(finder.entered, 4);
+    assert!(finder.found.is_none());
+
+    let Statement::Query(query) = parse("SELECT EXISTS (SELECT 1)") else {
+        unreachable!()
+    };
+    let SetExpr::Select(select) = &query.body else {
+        unreachable!()
+    };
+    let SelectTarget::AliasedExpr { expr, .. } = &select.select_list[0] else {
+        unreachable!()
+    };
+    assert!(traverse_expr(&mut SubqueryFinder::default(), expr).is_break());
+}
+
+/// Replaces the literals with placeholders, and stops at the first `LIMIT`.
+#[derive(Default)]
+struct LiteralReplacer {
+    limit: Option<String>,
+}
+
+impl VisitorMut for LiteralReplacer {
+    fn enter_node(&mut self, node: AstNodeMut<'_>) -> VisitResult {
+        if let AstNodeMut::Query(query) = node {
+            if !query.limit.is_empty() {
+                self.limit = Some(format!("LIMIT in {query}"));
+                return ControlFlow::Break(());
+            }
+        }
+        ControlFlow::Continue(Children::Visit)
+    }
+
+    fn leave_node(&mut self, node: AstNodeMut<'_>) {
+        if let AstNodeMut::Expr(expr) = node {
+            if let Expr::Literal { span, lit } = expr {
+                if !matches!(lit, Literal::Null) {
+                    *expr = Expr::Placeholder { span: *span };
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_traverse_statement_mut() {
+    let mut stmt = parse("UPDATE t SET a = 1, b = NULL WHERE c = 'x' AND d IN (SELECT 2)");
+    assert_eq!(
+        traverse_statement_mut(&mut LiteralReplacer::default(), &mut stmt),
+        ControlFlow::Continue(())
+    );
+    assert_eq!(
+        stmt.to_string(),
+        "UPDATE t SET a = ?, b = NULL WHERE ((c = ?) AND d IN(SELECT ?))"
+    );
+
+    let mut stmt = parse("SELECT 1 UNION ALL (SELECT 2 LIMIT 1)");
+    let mut replacer = LiteralReplacer::default();
+    assert!(traverse_statement_mut(&mut replacer, &mut stmt).is_break());
+    assert_eq!(replacer.limit.as_deref(), Some("LIMIT in SELECT 2 LIMIT 1"));
+    assert_eq!(stmt.to_string(), "SELECT ? UNION ALL (SELECT 2 LIMIT 1)");
+}
+
+/// The hooks of a plain `Visitor` are called by the walk functions too, including
+/// the expressions nested in other expressions and in `GROUP BY CUBE` or `ROLLUP`.
+#[test]
+fn test_visitor_hooks() {
+    #[derive(Default)]
+    struct Counter {
+        exprs: usize,
+        columns: Vec<String>,
+    }
+
+    impl<'ast> Visitor<'ast> for Counter {
+        fn enter_node(&mut self, node: AstNode<'ast>) -> VisitResult {
+            if let AstNode::Expr(expr) = node {
+                self.exprs += 1;
+                if let Expr::ColumnRef { column, .. } = expr {
+                    self.columns.push(column.to_string());
+                }
+            }
+            ControlFlow::Continue(Children::Visit)
+        }
+    }
+
+    let stmt = parse("SELECT a + b * 2 FROM t WHERE c = (SELECT d FROM s)");
+    let mut counter = Counter::default();
+    walk_statement(&mut counter, &stmt);
+    assert_eq!(counter.exprs, 9);
+
+    for (sql, columns) in [
+        ("SELECT 1 FROM t GROUP BY CUBE (a, b)", ["a", "b"]),
+        ("SELECT 1 FROM t GROUP BY ROLLUP (c, d)", ["c", "d"]),
+    ] {
+        let mut counter = Counter::default();
+        walk_statement(&mut counter, &parse(sql));
+        assert_eq!(counter.columns, columns);
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::parser::parse_sql_with_options;
+use common_ast::parser::tokenize_sql;
+use common_ast::ParseOptions;
+use common_ast::SyntaxFeature;
+use common_exception::Result;
+use enumflags2::BitFlags;
+use goldenfile::Mint;
+
+fn parse(sql: &str, options: &ParseOptions) -> Result<String> {
+    let tokens = tokenize_sql(sql)?;
+    Ok(parse_sql_with_options(&tokens, options)?.0.to_string())
+}
+
+#[test]
+fn test_denied_syntax() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("denied_syntax.txt").unwrap();
+
+    let public = ParseOptions::default().deny(BitFlags::all());
+    let cases = vec![
+        "SELECT /*+ SET_VAR(timezone='Asia/Shanghai') */ * FROM t",
+        "INSERT /*+ SET_VAR(timezone='Asia/Shanghai') */ INTO t VALUES (1)",
+        "SELECT * FROM t /*+ not a hint */ WHERE a = 1",
+        "SELECT * FROM @stage/path (FILE_FORMAT => 'csv') AS s",
+        "SELECT * FROM 's3://bucket/path' AS s",
+        "CREATE FUNCTION f AS (a) -> a + 1",
+        "CREATE FUNCTION f (INT) RETURNS INT LANGUAGE python HANDLER = 'f' ADDRESS = 'http://0.0.0.0:8815'",
+        "EXPLAIN SELECT * FROM t",
+        "EXPLAIN ANALYZE SELECT * FROM t",
+        "SET max_threads = 4",
+        "SET GLOBAL max_threads = 4",
+    ];
+
+    for sql in cases {
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", sql).unwrap();
+        let allowed = parse(sql, &ParseOptions::default()).unwrap();
+        match parse(sql, &public) {
+            Ok(output) => {
+                assert_eq!(output, allowed);
+                writeln!(file, "---------- Output ---------").unwrap();
+                writeln!(file, "{}", output).unwrap();
+            }
+            Err(err) => {
+                let span = err.span().unwrap();
+                writeln!(file, "---------- Error ----------").unwrap();
+                writeln!(file, "{}", err.message()).unwrap();
+                writeln!(file, "at {:?}: {}", span, &sql[span.start..span.end]).unwrap();
+            }
+        }
+        writeln!(file, "\n").unwrap();
+    }
+
+    // Syntax is only denied if it's used.
+    let options = ParseOptions::default()
+        .deny(SyntaxFeature::Explain | SyntaxFeature::SetGlobal)
+        .allow(SyntaxFeature::Explain);
+    assert!(options.is_allowed(SyntaxFeature::Explain));
+    assert!(!options.is_allowed(SyntaxFeature::SetGlobal));
+    assert!(parse("EXPLAIN SELECT 1", &options).is_ok());
+    assert!(parse("SET GLOBAL a = 1", &options).is_err());
+}
@@ -0,0 +1,132 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::parser::parse_sql_with_diagnostic;
+use common_ast::parser::parse_sql_with_options;
+use common_ast::parser::tokenize_sql;
+use common_ast::Dialect;
+use common_ast::ParseOptions;
+use common_ast::ParserLimits;
+use common_ast::SyntaxFeature;
+use goldenfile::Mint;
+use regex::Regex;
+
+#[test]
+fn test_diagnostic() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("diagnostic.txt").unwrap();
+
+    let options = ParseOptions {
+        limits: ParserLimits {
+            max_depth: 8,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .deny(SyntaxFeature::Explain);
+    let ansi_escape = Regex::new("\x1b\\[[0-9;]*m").unwrap();
+    let cases = vec![
+        "SELECT * FORM t",
+        "SELECT a, b FROM",
+        "SELECT * FROM t WHERE a =",
+        "SELECT CAST(a AS DECIMAL(1000, 2))",
+        "SELECT * FROM t\nWHERE a IN (1, 2\n  AND b = 'é' FROM",
+        "SELECT 1 2",
+        "SELECT 'unterminated",
+        "SELECT ((((((((((1))))))))))",
+        "EXPLAIN SELECT 1",
+    ];
+
+    for sql in cases {
+        let diag = parse_sql_with_diagnostic(sql, &options).unwrap_err();
+        let text = diag.render_text(sql);
+        let ansi = diag.render_ansi(sql);
+        assert!(ansi.contains("\x1b["));
+        assert_eq!(ansi_escape.replace_all(&ansi, ""), text);
+
+        // The error of the parser is converted from the same diagnostic.
+        if let Ok(tokens) = tokenize_sql(sql) {
+            let err = parse_sql_with_options(&tokens, &options).unwrap_err();
+            let expected = diag.clone().into_error_code(sql);
+            assert_eq!(err.message(), expected.message());
+            assert_eq!(err.span(), expected.span());
+        }
+
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", sql).unwrap();
+        writeln!(file, "---------- Text -----------").unwrap();
+        writeln!(file, "{}", text).unwrap();
+        writeln!(file, "---------- JSON -----------").unwrap();
+        writeln!(
+            file,
+            "{}",
+            serde_json::to_string_pretty(&diag.to_json(sql)).unwrap()
+        )
+        .unwrap();
+        writeln!(file, "\n").unwrap();
+    }
+}
+
+#[test]
+fn test_suggestion() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("suggestion.txt").unwrap();
+
+    let cases = vec![
+        ("SELEC * FROM t", Dialect::PostgreSQL),
+        ("select * form t", Dialect::PostgreSQL),
+        (
+            "SELECT * FROM t PIVOT(sum(a) FOR b = ('x', 'y'))",
+            Dialect::PostgreSQL,
+        ),
+        ("SELECT a b c FROM t", Dialect::PostgreSQL),
+        ("SELECT 1 2", Dialect::PostgreSQL),
+        ("CREATE TABLE t (a INT, from INT)", Dialect::PostgreSQL),
+        ("CREATE TABLE t (a INT, from INT)", Dialect::MySQL),
+        ("CREATE USER u IDENTIFIED BY \"it's\"", Dialect::PostgreSQL),
+        ("CREATE USER u IDENTIFIED BY \"it's\"", Dialect::MySQL),
+        ("SELECT * FROM t WHERE a IN (1, 2 FROM", Dialect::PostgreSQL),
+    ];
+
+    for (sql, dialect) in cases {
+        let options = ParseOptions {
+            dialect,
+            ..Default::default()
+        };
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{} ({:?})", sql, dialect).unwrap();
+        match parse_sql_with_diagnostic(sql, &options) {
+            Ok(_) => {
+                writeln!(file, "---------- Output ---------").unwrap();
+                writeln!(file, "OK").unwrap();
+            }
+            Err(diag) => {
+                writeln!(file, "---------- Error ----------").unwrap();
+                writeln!(file, "{}", diag.message).unwrap();
+                for suggestion in &diag.suggestions {
+                    let fixed = suggestion.apply(sql);
+                    writeln!(file, "---------- Fix ------------").unwrap();
+                    writeln!(file, "{}", suggestion.message).unwrap();
+                    writeln!(file, "{}", fixed).unwrap();
+                    if let Err(err) = parse_sql_with_diagnostic(&fixed, &options) {
+                        writeln!(file, "still fails: {}", err.message).unwrap();
+                    }
+                }
+            }
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::ast::Statement;
+use common_ast::diff;
+use common_ast::diff_with_options;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::ChangeKind;
+use common_ast::Dialect;
+use common_ast::DiffOptions;
+use goldenfile::Mint;
+
+fn parse(sql: &str) -> Statement {
+    let tokens = tokenize_sql(sql).unwrap();
+    let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+    stmt
+}
+
+#[test]
+fn test_diff() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("diff.txt").unwrap();
+
+    let cases = &[
+        (
+            r#"SELECT a, b FROM t WHERE a > 1"#,
+            r#"SELECT a, b, c FROM t WHERE a > 2"#,
+        ),
+        (
+            r#"SELECT a, b, c FROM t1 JOIN t2 ON t1.id = t2.id GROUP BY a, b ORDER BY a"#,
+            r#"SELECT a, x, c FROM t1 LEFT JOIN t2 ON t1.id = t2.id GROUP BY a ORDER BY a DESC LIMIT 10"#,
+        ),
+        (
+            r#"WITH c AS (SELECT 1) SELECT * FROM c UNION ALL SELECT 2"#,
+            r#"WITH c AS (SELECT 2) SELECT * FROM c UNION ALL SELECT 2 WHERE true"#,
+        ),
+        (
+            r#"CREATE VIEW v (a, b) AS SELECT a, b FROM t"#,
+            r#"CREATE VIEW IF NOT EXISTS db.v (a) AS SELECT a FROM t WHERE b IS NOT NULL"#,
+        ),
+        (
+            r#"CREATE FUNCTION f AS (a, b) -> a + b DESC = 'add'"#,
+            r#"CREATE FUNCTION f AS (a, b, c) -> a + b + c"#,
+        ),
+        (
+            r#"CREATE FUNCTION f AS (a) -> a DESC = 'it''s'"#,
+            r#"CREATE FUNCTION f AS (a) -> a DESC = 'its'"#,
+        ),
+        (
+            r#"CREATE TABLE t (a INT NOT NULL, b STRING DEFAULT 'x', c FLOAT) CLUSTER BY (a) comment = 'old'"#,
+            r#"CREATE TABLE t (a BIGINT NOT NULL, c FLOAT, d DATE) CLUSTER BY (a, d) compression = 'zstd'"#,
+        ),
+        (r#"SELECT a FROM t"#, r#"DELETE FROM t"#),
+        (
+            r#"SELECT * FROM t WHERE a = 1"#,
+            r#"select *   from T where a=1"#,
+        ),
+    ];
+
+    for (old, new) in cases {
+        writeln!(file, "---------- Old ----------").unwrap();
+        writeln!(file, "{}", old).unwrap();
+        writeln!(file, "---------- New ----------").unwrap();
+        writeln!(file, "{}", new).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        for change in diff(&parse(old), &parse(new)) {
+            writeln!(file, "{}", change).unwrap();
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
+
+#[test]
+fn test_diff_options() {
+    let old = parse(r#"SELECT "a", CAST(b AS INT) FROM T WHERE c > 1"#);
+    let new = parse(r#"SELECT A, b::INT FROM t WHERE C > 1"#);
+
+    let changes = diff(&old, &new);
+    assert_eq!(changes.len(), 4, "{changes:?}");
+    assert!(changes.iter().all(|c| c.kind == ChangeKind::Changed));
+    assert!(changes[0].old_span.is_some() && changes[0].new_span.is_some());
+
+    let options = DiffOptions {
+        ignore_identifier_case: true,
+        ignore_formatting: false,
+    };
+    let changes = diff_with_options(&old, &new, &options);
+    let paths = changes.iter().map(|c| c.path.as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, [
+        "query.body.select_list[0]",
+        "query.body.select_list[1]"
+    ]);
+
+    let options = DiffOptions {
+        ignore_identifier_case: true,
+        ignore_formatting: true,
+    };
+    assert_eq!(diff_with_options(&old, &new, &options), vec![]);
+}
+
+#[test]
+fn test_diff_spans() {
+    let old = "SELECT a FROM t GROUP BY a WINDOW w AS (PARTITION BY a)";
+    let new = "SELECT a FROM t GROUP BY ROLLUP (a, b) WINDOW w AS (ORDER BY b)";
+    let changes = diff(&parse(old), &parse(new));
+    let spans = changes
+        .iter()
+        .map(|c| {
+            let old_span = c.old_span.map(|span| &old[span.start..span.end]);
+            let new_span = c.new_span.map(|span| &new[span.start..span.end]);
+            (c.path.as_str(), old_span, new_span)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(spans, [
+        ("query.body.group_by", Some("a"), Some("a, b")),
+        (
+            "query.body.window_list",
+            Some("w AS (PARTITION BY a"),
+            Some("w AS (ORDER BY b")
+        ),
+    ]);
+
+    let old = parse("CREATE TABLE t (a INT) comment = 'old'");
+    let new = parse("CREATE TABLE t (a INT)");
+    let changes = diff(&old, &new);
+    assert_eq!(changes.len(), 1, "{changes:?}");
+    assert_eq!(changes[0].path, "table_options.comment");
+    assert!(changes[0].old_span.is_some());
+}
@@ -0,0 +1,111 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::builder::*;
+use common_ast::fingerprint;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::Dialect;
+use common_ast::Fingerprint;
+use goldenfile::Mint;
+
+fn fingerprint_sql(sql: &str) -> Fingerprint {
+    let tokens = tokenize_sql(sql).unwrap();
+    let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+    fingerprint(&stmt)
+}
+
+#[test]
+fn test_fingerprint() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("fingerprint.txt").unwrap();
+
+    let cases = &[
+        r#"SELECT * FROM t WHERE id = 5"#,
+        r#"SELECT /*+ SET_VAR(timezone='Asia/Shanghai') */ a, -1.5, 'x' FROM DB.T1 WHERE a IN (1, 2, 3) AND b NOT IN ('a', -2) ORDER BY 1, a LIMIT 10"#,
+        r#"SELECT a FROM t WHERE (a, b) IN ((1, 2), (3, 4)) OR c IN (d, 1)"#,
+        r#"SELECT "MixedCase", Upper FROM T GROUP BY 1, Upper HAVING count(*) > 10"#,
+        r#"SELECT sum(a) OVER w, CURRENT_TIMESTAMP, {'k': 1}, [1, 2] FROM t WINDOW w AS (PARTITION BY b ORDER BY c ROWS BETWEEN 3 PRECEDING AND CURRENT ROW)"#,
+        r#"INSERT /*+ SET_VAR(max_threads=1) */ INTO t (a, b) VALUES (1, 'x'), (2, 'y')"#,
+        r#"INSERT INTO t VALUES (now(), (1 + 2), [3, 4])"#,
+        r#"INSERT INTO T SELECT * FROM s WHERE x > 0"#,
+        r#"UPDATE t SET a = 1, b = 'x' WHERE id IN (1, 2)"#,
+        r#"DELETE FROM t WHERE created < '2023-01-01'"#,
+        r#"MERGE INTO t1 USING (SELECT * FROM t2) ON t1.id = t2.id WHEN MATCHED AND t2.a > 0 THEN UPDATE SET t1.a = 0 WHEN NOT MATCHED THEN INSERT (id, a) VALUES (t2.id, 1)"#,
+        r#"COPY INTO @unload FROM (SELECT * FROM t WHERE a = 1)"#,
+        r#"EXPLAIN SELECT * FROM t WHERE a BETWEEN 1 AND 10"#,
+    ];
+
+    for case in cases {
+        let fingerprint = fingerprint_sql(case);
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", case).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        writeln!(file, "{}", fingerprint.normalized_sql).unwrap();
+        writeln!(file, "{:016x}", fingerprint.digest64).unwrap();
+        writeln!(file, "{}", fingerprint).unwrap();
+        writeln!(file, "\n").unwrap();
+    }
+}
+
+#[test]
+fn test_fingerprint_equivalence() {
+    let groups: &[&[&str]] = &[
+        &[
+            "SELECT * FROM t WHERE id = 5",
+            "SELECT * FROM t WHERE id = 7",
+            "select * from T where ID = -7 -- comment",
+            "SELECT /*+ SET_VAR(max_threads=1) */ * FROM t WHERE id = 'x'",
+        ],
+        &[
+            "SELECT * FROM t WHERE id IN (1)",
+            "SELECT * FROM t WHERE id IN (1, 2, 3, 4)",
+        ],
+        &[
+            "INSERT INTO t VALUES (1, 2)",
+            "INSERT INTO t VALUES (3, 4), (5, 6)",
+        ],
+    ];
+    for group in groups {
+        let expected = fingerprint_sql(group[0]);
+        for sql in &group[1..] {
+            assert_eq!(fingerprint_sql(sql), expected, "{sql}");
+        }
+    }
+
+    assert_ne!(
+        fingerprint_sql(r#"SELECT * FROM t WHERE id = 5"#),
+        fingerprint_sql(r#"SELECT * FROM "T" WHERE id = 5"#),
+    );
+    assert_ne!(
+        fingerprint_sql("SELECT a FROM t ORDER BY 1"),
+        fingerprint_sql("SELECT a FROM t ORDER BY 2"),
+    );
+    assert_ne!(
+        fingerprint_sql("SELECT {'k': 1}"),
+        fingerprint_sql("SELECT {'k': CURRENT_TIMESTAMP}"),
+    );
+
+    // The digests don't depend on the spans, e.g. of a statement that isn't parsed.
+    let built = select([star()])
+        .from(table("t"))
+        .where_(col("id").eq(lit(7)))
+        .into();
+    assert_eq!(
+        fingerprint(&built),
+        fingerprint_sql("SELECT * FROM t WHERE id = 5")
+    );
+}
@@ -0,0 +1,179 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::ast::ColumnID;
+use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
+use common_ast::ast::Indirection;
+use common_ast::ast::Literal;
+use common_ast::ast::SelectTarget;
+use common_ast::ast::Statement;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::visitors::fold_expr;
+use common_ast::visitors::fold_select_target;
+use common_ast::Dialect;
+use common_ast::Fold;
+use goldenfile::Mint;
+
+fn parse(sql: &str) -> Statement {
+    let tokens = tokenize_sql(sql).unwrap();
+    let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+    stmt
+}
+
+/// Expands the stars into the columns `a` and `b`, replaces `current_user()`
+/// with a literal and lowercases the unquoted identifiers.
+struct Rewriter;
+
+impl Fold for Rewriter {
+    fn fold_select_target(&mut self, target: SelectTarget) -> Vec<SelectTarget> {
+        let target = fold_select_target(self, target);
+        target
+            .into_iter()
+            .flat_map(|target| match target {
+                SelectTarget::QualifiedName {
+                    mut qualified,
+                    exclude: None,
+                } if matches!(qualified.last(), Some(Indirection::Star(_))) => {
+                    qualified.pop();
+                    let table = match qualified.pop() {
+                        Some(Indirection::Identifier(table)) => Some(table),
+                        _ => None,
+                    };
+                    ["a", "b"]
+                        .into_iter()
+                        .map(|column| SelectTarget::AliasedExpr {
+                            expr: Box::new(Expr::ColumnRef {
+                                span: None,
+                                database: None,
+                                table: table.clone(),
+                                column: ColumnID::Name(Identifier::from_name(column)),
+                            }),
+                            alias: None,
+                        })
+                        .collect()
+                }
+                target => vec![target],
+            })
+            .collect()
+    }
+
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        match expr {
+            Expr::FunctionCall { span, name, .. } if name.name == "current_user" => Expr::Literal {
+                span,
+                lit: Literal::String("root".to_string()),
+            },
+            expr => fold_expr(self, expr),
+        }
+    }
+
+    fn fold_identifier(&mut self, ident: Identifier) -> Identifier {
+        if ident.is_quoted() {
+            ident
+        } else {
+            Identifier {
+                name: ident.name.to_lowercase(),
+                ..ident
+            }
+        }
+    }
+}
+
+/// Leaves every node unchanged.
+struct Identity;
+
+impl Fold for Identity {}
+
+#[test]
+fn test_fold() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("fold.txt").unwrap();
+
+    let cases = &[
+        r#"SELECT *, T1.* FROM T1 JOIN T2 ON T1.A = T2.B WHERE "C" = current_user()"#,
+        r#"WITH C AS (SELECT * FROM T) SELECT X, count(*) OVER (PARTITION BY Y ORDER BY Z) FROM C"#,
+        r#"SELECT * EXCLUDE (B) FROM T WHERE A IN (SELECT * FROM S)"#,
+        r#"INSERT INTO T (A, B) SELECT * FROM S"#,
+        r#"UPDATE T SET A = current_user() WHERE B = (SELECT * FROM S)"#,
+        r#"DELETE FROM T WHERE OWNER = current_user()"#,
+        r#"CREATE TABLE T (A VARCHAR DEFAULT current_user()) AS SELECT * FROM S"#,
+        r#"CREATE VIEW V AS SELECT * FROM T"#,
+        r#"MERGE INTO T1 USING (SELECT * FROM T2) ON T1.A = T2.A WHEN MATCHED THEN UPDATE SET T1.B = current_user() WHEN NOT MATCHED THEN INSERT (A) VALUES (T2.A)"#,
+        r#"MERGE INTO T1 AS X USING (SELECT * FROM T2) ON X.A = T2.A WHEN MATCHED AND X.B > 1 THEN DELETE WHEN NOT MATCHED THEN INSERT VALUES (T2.A, current_user())"#,
+        r#"ALTER TABLE T ADD COLUMN C VARCHAR DEFAULT current_user() AFTER B"#,
+        r#"EXPLAIN SELECT * FROM T"#,
+        r#"CREATE FUNCTION F AS (X) -> X = current_user()"#,
+        r#"CREATE MASKING POLICY P AS (VAL STRING) RETURNS STRING -> CASE WHEN current_user() = 'admin' THEN VAL ELSE '***' END"#,
+        r#"SHOW TABLES FROM DB WHERE OWNER = current_user()"#,
+        r#"DROP TABLE DB.T"#,
+        r#"RENAME TABLE DB.T TO DB.T2"#,
+    ];
+
+    for sql in cases {
+        let stmt = Rewriter.fold_statement(parse(sql));
+        let output = stmt.to_string();
+        assert_eq!(parse(&output).to_string(), output);
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", sql).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        writeln!(file, "{}", output).unwrap();
+        writeln!(file, "\n").unwrap();
+    }
+}
+
+#[test]
+fn test_fold_identity() {
+    let cases = &[
+        "SELECT a, t.* EXCLUDE (b) FROM t1 AS t (a, b) LEFT JOIN t2 USING (a) WHERE a BETWEEN 1 AND 2",
+        "SELECT sum(a) OVER w FROM t GROUP BY CUBE (a, b) HAVING count(*) > 1 WINDOW w AS (ORDER BY a ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)",
+        "SELECT CASE WHEN a THEN b ELSE c END, CAST(d AS INT), TRIM(BOTH 'x' FROM e), m['k'] FROM t",
+        "SELECT 1 UNION ALL SELECT 2 ORDER BY 1 LIMIT 1 OFFSET 1",
+        "COPY INTO @s FROM (SELECT a FROM t)",
+        "SET max_threads = 1 + 1",
+        "SHOW TABLES",
+    ];
+
+    for sql in cases {
+        let stmt = parse(sql);
+        let expected = stmt.to_string();
+        assert_eq!(Identity.fold_statement(stmt).to_string(), expected);
+    }
+}
+
+/// A select target that is folded into no target is removed.
+#[test]
+fn test_fold_remove_target() {
+    struct DropHidden;
+
+    impl Fold for DropHidden {
+        fn fold_select_target(&mut self, target: SelectTarget) -> Vec<SelectTarget> {
+            match &target {
+                SelectTarget::AliasedExpr {
+                    alias: Some(alias), ..
+                } if alias.name.starts_with('_') => vec![],
+                _ => fold_select_target(self, target),
+            }
+        }
+    }
+
+    let stmt = parse("SELECT a, b AS _b, (SELECT c AS _c, d FROM s) FROM t");
+    assert_eq!(
+        DropHidden.fold_statement(stmt).to_string(),
+        "SELECT a, (SELECT d FROM s) FROM t"
+    );
+}
@@ -0,0 +1,70 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::parser::highlight;
+use common_ast::Dialect;
+use goldenfile::Mint;
+
+#[test]
+fn test_highlight() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("highlight.txt").unwrap();
+
+    let cases = vec![
+        (
+            "SELECT a, count(*), sum(b) AS total FROM db.t AS x JOIN u USING (id) WHERE x.c = 'str' AND d > 1.5 -- comment",
+            Dialect::PostgreSQL,
+        ),
+        (
+            "WITH c(one) AS (SELECT 1) SELECT c.*, \"date\" FROM c, numbers(10) n",
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT `a`, \"s\", 0x1F FROM `t` /* block */",
+            Dialect::MySQL,
+        ),
+        (
+            "INSERT /*+ SET_VAR(timezone='UTC') */ INTO t (a, b) VALUES (?, $1)",
+            Dialect::PostgreSQL,
+        ),
+        (
+            "COPY INTO t FROM @stage/path; SELECT $1 FROM @stage",
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT a FROM WHERE b; SELECT date FROM t",
+            Dialect::PostgreSQL,
+        ),
+        ("SELECT a, 'unterminated FROM t", Dialect::PostgreSQL),
+        ("SELECT 1 /* unclosed", Dialect::PostgreSQL),
+    ];
+
+    for (sql, dialect) in cases {
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", sql).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        for (span, class) in highlight(sql, dialect) {
+            writeln!(
+                file,
+                "{:<16} {}",
+                format!("{:?}", class),
+                &sql[span.start..span.end]
+            )
+            .unwrap();
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::parser::parse_expr_with_options;
+use common_ast::parser::parse_sql_with_options;
+use common_ast::parser::tokenize_sql;
+use common_ast::ParseOptions;
+use common_ast::ParserLimits;
+use common_exception::Result;
+use goldenfile::Mint;
+
+fn parse(sql: &str, limits: ParserLimits, is_expr: bool) -> Result<String> {
+    let tokens = tokenize_sql(sql)?;
+    let options = ParseOptions {
+        limits,
+        ..Default::default()
+    };
+    if is_expr {
+        Ok(parse_expr_with_options(&tokens, &options)?.to_string())
+    } else {
+        Ok(parse_sql_with_options(&tokens, &options)?.0.to_string())
+    }
+}
+
+/// Describe the limits that differ from the defaults.
+fn describe(limits: &ParserLimits) -> String {
+    if *limits == ParserLimits::recommended() {
+        return "recommended".to_string();
+    }
+    let default = ParserLimits::default();
+    let mut changed = vec![];
+    if limits.max_depth != default.max_depth {
+        changed.push(format!("max_depth: {}", limits.max_depth));
+    }
+    if limits.max_chain_len != default.max_chain_len {
+        changed.push(format!("max_chain_len: {}", limits.max_chain_len));
+    }
+    if limits.max_tokens != default.max_tokens {
+        changed.push(format!("max_tokens: {}", limits.max_tokens));
+    }
+    if limits.max_in_list_len != default.max_in_list_len {
+        changed.push(format!("max_in_list_len: {}", limits.max_in_list_len));
+    }
+    if limits.max_statement_bytes != default.max_statement_bytes {
+        changed.push(format!(
+            "max_statement_bytes: {}",
+            limits.max_statement_bytes
+        ));
+    }
+    if changed.is_empty() {
+        "default".to_string()
+    } else {
+        changed.join(", ")
+    }
+}
+
+#[test]
+fn test_parser_limits() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("limits.txt").unwrap();
+
+    let limits = ParserLimits::default();
+    let recommended = ParserLimits::recommended();
+    let cases = vec![
+        (
+            "SELECT * FROM t WHERE a IN (1, 2, 3)".to_string(),
+            ParserLimits {
+                max_in_list_len: 3,
+                ..limits
+            },
+            false,
+        ),
+        (
+            "SELECT * FROM t WHERE a IN (1, 2, 3)".to_string(),
+            ParserLimits {
+                max_in_list_len: 2,
+                ..limits
+            },
+            false,
+        ),
+        (
+            "SELECT ((a + 1)) FROM (SELECT * FROM t)".to_string(),
+            ParserLimits {
+                max_depth: 8,
+                ..limits
+            },
+            false,
+        ),
+        (
+            format!("SELECT {}1{}", "(".repeat(10000), ")".repeat(10000)),
+            ParserLimits {
+                max_depth: 8,
+                ..limits
+            },
+            false,
+        ),
+        (
+            format!(
+                "SELECT * FROM {}t{}",
+                "(SELECT * FROM ".repeat(10000),
+                ")".repeat(10000)
+            ),
+            ParserLimits {
+                max_depth: 8,
+                ..limits
+            },
+            false,
+        ),
+        (
+            format!(
+                "SELECT {} 1 {}",
+                "CASE WHEN a THEN ".repeat(10000),
+                " END".repeat(10000)
+            ),
+            ParserLimits {
+                max_depth: 8,
+                ..limits
+            },
+            false,
+        ),
+        (
+            vec!["SELECT * FROM t"; 10000].join(" UNION ALL "),
+            recommended,
+            false,
+        ),
+        (
+            format!("SELECT * FROM {}", vec!["t"; 10000].join(" JOIN ")),
+            recommended,
+            false,
+        ),
+        (
+            "a AND b AND c".to_string(),
+            ParserLimits {
+                max_chain_len: 5,
+                ..limits
+            },
+            true,
+        ),
+        (
+            "a AND b AND c AND d".to_string(),
+            ParserLimits {
+                max_chain_len: 5,
+                ..limits
+            },
+            true,
+        ),
+        (format!("{} a", "NOT ".repeat(10000)), recommended, true),
+        (
+            format!("SELECT {}1{}", "(".repeat(100), ")".repeat(100)),
+            recommended,
+            false,
+        ),
+        (
+            "SELECT a, b FROM t".to_string(),
+            ParserLimits {
+                max_tokens: 5,
+                ..limits
+            },
+            false,
+        ),
+        (
+            "SELECT a, b FROM t".to_string(),
+            ParserLimits {
+                max_statement_bytes: 10,
+                ..limits
+            },
+            false,
+        ),
+    ];
+
+    for (sql, limits, is_expr) in cases {
+        writeln!(file, "---------- Input ----------").unwrap();
+        if sql.len() > 80 {
+            writeln!(file, "{}... ({} bytes)", &sql[..80], sql.len()).unwrap();
+        } else {
+            writeln!(file, "{}", sql).unwrap();
+        }
+        writeln!(file, "---------- Limits ---------").unwrap();
+        writeln!(file, "{}", describe(&limits)).unwrap();
+        match parse(&sql, limits, is_expr) {
+            Ok(output) => {
+                writeln!(file, "---------- Output ---------").unwrap();
+                writeln!(file, "{}", output).unwrap();
+            }
+            Err(err) => {
+                let span = err.span().unwrap();
+                writeln!(file, "---------- Error ----------").unwrap();
+                writeln!(file, "{}", err.message()).unwrap();
+                writeln!(
+                    file,
+                    "at {:?}: {}",
+                    span,
+                    &sql[span.start..span.end.min(span.start + 80)]
+                )
+                .unwrap();
+            }
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
+
+/// Nothing is limited by default, so long chains and deep nesting are parsed as
+/// long as the stack is big enough.
+#[test]
+fn test_default_limits() {
+    let cases = [
+        format!("SELECT * FROM t WHERE {}", vec!["a = 1"; 2100].join(" OR ")),
+        format!(
+            "SELECT * FROM t WHERE {}1{}",
+            "a IN (SELECT a FROM t WHERE a = ".repeat(64),
+            ")".repeat(64)
+        ),
+    ];
+    std::thread::Builder::new()
+        .stack_size(256 << 20)
+        .spawn(move || {
+            for sql in &cases {
+                assert!(parse(sql, ParserLimits::default(), false).is_ok());
+                assert!(parse(sql, ParserLimits::recommended(), false).is_err());
+            }
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
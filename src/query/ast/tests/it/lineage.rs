@@ -0,0 +1,91 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::column_lineage;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::Dialect;
+use common_ast::SchemaProvider;
+use goldenfile::Mint;
+
+struct TestSchema;
+
+impl SchemaProvider for TestSchema {
+    fn table_columns(
+        &self,
+        _catalog: Option<&str>,
+        _database: Option<&str>,
+        table: &str,
+    ) -> Option<Vec<String>> {
+        let columns: &[&str] = match table {
+            "s" => &["x", "y", "z"],
+            "t" => &["a", "b"],
+            _ => return None,
+        };
+        Some(columns.iter().map(|c| c.to_string()).collect())
+    }
+}
+
+#[test]
+fn test_column_lineage() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("lineage.txt").unwrap();
+
+    let cases = &[
+        r#"insert into t(a,b) select x+y, z from s"#,
+        r#"insert into t select * from s"#,
+        r#"insert into db.t2 select s1.x, s2.y from s1 join s2 on s1.id = s2.id"#,
+        r#"insert into t2 select x, y from s1, s2"#,
+        r#"create table t2 as select a as c, upper(b) from (select x + 1 as a, z as b from s) sub"#,
+        r#"create view v (c1, c2) as with cte (m, n) as (select x, y from s) select m, n * 2 from cte"#,
+        r#"select x from s union all select a from t"#,
+        r#"select z, sum(x) from s group by z"#,
+        r#"select x + y as k, count(*) from s group by k"#,
+        r#"select z, count(*) from s group by 1"#,
+        r#"select z, y, sum(x) from s group by rollup (z, y)"#,
+        r#"insert into t(a, b) select x from s"#,
+        r#"insert into t(a, b) select * from s"#,
+        r#"insert into t(a, b) select x, s.* exclude (x, y) from s"#,
+        r#"select rank() over (partition by x order by y), sum(z) over w from s window w as (partition by x)"#,
+        r#"select x, (select max(a) from t where t.b = s.y) as m from s where exists (select * from t)"#,
+        r#"select * exclude y from s, (select a from t) t"#,
+        r#"select t.* from t, s"#,
+        r#"select $1, $2 from @my_stage"#,
+        r#"with recursive r (n) as (select x from s union all select n + 1 from r) select n from r"#,
+        r#"merge into t using (select * from s) on t.a = x when matched then update set b = z when not matched then insert (a, b) values (x, y)"#,
+        r#"delete from t where a = 1"#,
+    ];
+
+    for case in cases {
+        let tokens = tokenize_sql(case).unwrap();
+        let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", case).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        match column_lineage(&stmt, Some(&TestSchema)) {
+            Ok(Some(lineage)) => write!(file, "{}", lineage).unwrap(),
+            Ok(None) => writeln!(file, "None").unwrap(),
+            Err(err) => writeln!(file, "Error: {}", err).unwrap(),
+        }
+        writeln!(file, "---------- Without Schema ---------").unwrap();
+        match column_lineage(&stmt, None) {
+            Ok(Some(lineage)) => write!(file, "{}", lineage).unwrap(),
+            Ok(None) => writeln!(file, "None").unwrap(),
+            Err(err) => writeln!(file, "Error: {}", err).unwrap(),
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
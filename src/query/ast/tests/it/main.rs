@@ -14,6 +14,24 @@
 
 #![allow(clippy::uninlined_format_args)]
 
+mod anonymizer;
+mod builder;
+mod classification;
+mod completion;
+mod control_flow;
 mod decimal;
+mod denied_syntax;
+mod diagnostic;
+mod diff;
+mod fingerprint;
+mod fold;
+mod highlight;
+mod limits;
+mod lineage;
+mod migration;
+mod params;
 mod parser;
+mod referenced_objects;
+mod script;
+mod simplifier;
 mod token;
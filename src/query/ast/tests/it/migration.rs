@@ -0,0 +1,151 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use common_ast::ast::CreateTableStmt;
+use common_ast::ast::Statement;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::plan_table_migration;
+use common_ast::Dialect;
+use common_ast::MigrationOptions;
+use goldenfile::Mint;
+
+fn parse(sql: &str) -> Statement {
+    let tokens = tokenize_sql(sql).unwrap();
+    let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+    stmt
+}
+
+fn parse_create_table(sql: &str) -> CreateTableStmt {
+    match parse(sql) {
+        Statement::CreateTable(stmt) => stmt,
+        stmt => panic!("not a CREATE TABLE: {stmt}"),
+    }
+}
+
+#[test]
+fn test_plan_table_migration() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("migration.txt").unwrap();
+
+    let renames = |pairs: &[(&str, &str)]| {
+        pairs
+            .iter()
+            .map(|(old, new)| (old.to_string(), new.to_string()))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let cases = &[
+        (
+            r#"CREATE TABLE t (a INT, b STRING)"#,
+            r#"CREATE TABLE t (id BIGINT, a INT, c FLOAT DEFAULT 1.5, b STRING, d DATE NULL)"#,
+            MigrationOptions::default(),
+        ),
+        (
+            r#"CREATE TABLE t (a INT, b STRING, c INT)"#,
+            r#"CREATE TABLE t (a INT, c INT)"#,
+            MigrationOptions::default(),
+        ),
+        (
+            r#"CREATE TABLE db.t (a INT, b STRING, c INT) CLUSTER BY (a)"#,
+            r#"CREATE TABLE db.t (a BIGINT, c INT NULL DEFAULT 0 COMMENT 'count')"#,
+            MigrationOptions {
+                allow_destructive: true,
+                renames: BTreeMap::new(),
+            },
+        ),
+        (
+            r#"CREATE TABLE t (a INT, old_name STRING) CLUSTER BY (a)"#,
+            r#"CREATE TABLE t (a INT NULL, new_name STRING) CLUSTER BY (a, new_name)"#,
+            MigrationOptions {
+                allow_destructive: false,
+                renames: renames(&[("old_name", "new_name")]),
+            },
+        ),
+        (
+            r#"CREATE TABLE t (a INT, b INT) CLUSTER BY (b)"#,
+            r#"CREATE TABLE t (a BIGINT, c INT) CLUSTER BY (a)"#,
+            MigrationOptions {
+                allow_destructive: true,
+                renames: BTreeMap::new(),
+            },
+        ),
+        (
+            r#"CREATE TABLE t (a INT, b STRING) CLUSTER BY (a)"#,
+            r#"CREATE TABLE t (a BIGINT, b STRING COMMENT 'it''s b') CLUSTER BY (a) comment = 'it''s t'"#,
+            MigrationOptions {
+                allow_destructive: true,
+                renames: BTreeMap::new(),
+            },
+        ),
+        (
+            r#"CREATE TABLE t (a INT) CLUSTER BY (a) comment = 'old' bloom_index_columns = 'a'"#,
+            r#"CREATE TABLE t2 (A INT) comment = 'new' bloom_index_columns = 'a' compression = 'zstd'"#,
+            MigrationOptions::default(),
+        ),
+        (
+            r#"CREATE TABLE t (a INT, b INT AS (a + 1) STORED)"#,
+            r#"CREATE TABLE t (a INT, b INT)"#,
+            MigrationOptions::default(),
+        ),
+        (
+            r#"CREATE TABLE t (a INT, b INT AS (a + 1) STORED)"#,
+            r#"CREATE TABLE t (a INT, b INT AS (a + 2) STORED)"#,
+            MigrationOptions::default(),
+        ),
+        (
+            r#"CREATE TABLE t (a INT) comment = 'old'"#,
+            r#"CREATE TABLE t (a INT)"#,
+            MigrationOptions::default(),
+        ),
+        (
+            r#"CREATE TABLE t (a INT)"#,
+            r#"CREATE TABLE t (b INT)"#,
+            MigrationOptions {
+                allow_destructive: false,
+                renames: renames(&[("a", "c")]),
+            },
+        ),
+        (
+            r#"CREATE TABLE t (a INT)"#,
+            r#"CREATE TRANSIENT TABLE t (a INT)"#,
+            MigrationOptions::default(),
+        ),
+    ];
+
+    for (old, new, options) in cases {
+        writeln!(file, "---------- Old ----------").unwrap();
+        writeln!(file, "{}", old).unwrap();
+        writeln!(file, "---------- New ----------").unwrap();
+        writeln!(file, "{}", new).unwrap();
+        if options.allow_destructive || !options.renames.is_empty() {
+            writeln!(file, "---------- Options ------").unwrap();
+            writeln!(file, "{:?}", options).unwrap();
+        }
+        writeln!(file, "---------- Output ---------").unwrap();
+        match plan_table_migration(&parse_create_table(old), &parse_create_table(new), options) {
+            Ok(stmts) => {
+                for stmt in stmts {
+                    let sql = stmt.to_string();
+                    assert_eq!(parse(&sql).to_string(), sql);
+                    writeln!(file, "{};", sql).unwrap();
+                }
+            }
+            Err(err) => writeln!(file, "Error: {}", err).unwrap(),
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
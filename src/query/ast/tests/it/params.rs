@@ -0,0 +1,244 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use common_ast::ast::Expr;
+use common_ast::ast::Literal;
+use common_ast::ast::SelectTarget;
+use common_ast::ast::SetExpr;
+use common_ast::ast::Statement;
+use common_ast::ast::TypeName;
+use common_ast::bind_params;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::BindError;
+use common_ast::Dialect;
+use common_ast::ParamValue;
+use common_ast::Params;
+use ethnum::i256;
+use goldenfile::Mint;
+
+fn bind(sql: &str, params: &Params, dialect: Dialect) -> Result<String, BindError> {
+    let tokens = tokenize_sql(sql).unwrap();
+    let (stmt, _) = parse_sql(&tokens, dialect).unwrap();
+    let bound = bind_params(stmt, params, dialect)?.to_string();
+
+    // The bound statement is valid SQL that displays the same.
+    let tokens = tokenize_sql(&bound).unwrap();
+    let (stmt, _) = parse_sql(&tokens, dialect).unwrap();
+    assert_eq!(stmt.to_string(), bound);
+
+    Ok(bound)
+}
+
+#[test]
+fn test_bind_params() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("params.txt").unwrap();
+
+    let named = |values: Vec<(&str, ParamValue)>| {
+        Params::Named(
+            values
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    };
+    let cases = vec![
+        (
+            "SELECT * FROM t WHERE a = ? AND b IN (?, ?) LIMIT ?",
+            Params::Positional(vec![
+                ParamValue::Int(-1),
+                ParamValue::String("it's \\ 'quoted'".to_string()),
+                ParamValue::Null,
+                ParamValue::UInt(10),
+            ]),
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT ?, ?, ?, ?, ?",
+            Params::Positional(vec![
+                ParamValue::Decimal {
+                    value: i256::from(-12345),
+                    scale: 2,
+                },
+                ParamValue::Decimal {
+                    value: i256::from(5),
+                    scale: 3,
+                },
+                ParamValue::Float(-1.5),
+                ParamValue::Float(f64::NAN),
+                ParamValue::Boolean(true),
+            ]),
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT * FROM t WHERE d = ? AND ts > ? AND f < ?",
+            Params::Positional(vec![
+                ParamValue::Date("2023-06-01".to_string()),
+                ParamValue::Timestamp("2023-06-01 12:00:00.123456".to_string()),
+                ParamValue::Float(f64::INFINITY),
+            ]),
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT * FROM t WHERE d = ? AND ts > ? AND f < ?",
+            Params::Positional(vec![
+                ParamValue::Date("2023-06-01".to_string()),
+                ParamValue::Timestamp("2023-06-01 12:00:00.123456".to_string()),
+                ParamValue::Float(f64::INFINITY),
+            ]),
+            Dialect::MySQL,
+        ),
+        (
+            "SELECT ?, ?, ?, ?",
+            Params::Positional(vec![
+                ParamValue::Float(1.0),
+                ParamValue::Float(1e300),
+                ParamValue::Float(-0.0),
+                ParamValue::Float(1e-7),
+            ]),
+            Dialect::MySQL,
+        ),
+        (
+            "SELECT CASE WHEN a = ? THEN ? WHEN b = ? THEN ? ELSE ? END FROM t",
+            Params::Positional((1..=5).map(ParamValue::UInt).collect()),
+            Dialect::PostgreSQL,
+        ),
+        (
+            "INSERT INTO t (a, b, c) SELECT ?, '?', ? UNION ALL SELECT ? /* ? */, 'x', ?",
+            Params::Positional(vec![
+                ParamValue::Int(1),
+                ParamValue::String("a'b".to_string()),
+                ParamValue::Int(2),
+                ParamValue::Binary(vec![0xde, 0xad, 0xbe, 0xef]),
+            ]),
+            Dialect::PostgreSQL,
+        ),
+        (
+            "UPDATE t SET a = $2 WHERE b = $1 OR c = $1",
+            Params::Positional(vec![
+                ParamValue::String("x".to_string()),
+                ParamValue::Binary(vec![0, 255]),
+            ]),
+            Dialect::PostgreSQL,
+        ),
+        (
+            "DELETE FROM t WHERE a = $1 AND b > $2",
+            named(vec![
+                ("$1", ParamValue::Int(7)),
+                ("$2", ParamValue::Date("2023-01-01".to_string())),
+            ]),
+            Dialect::MySQL,
+        ),
+        (
+            "SELECT * FROM t WHERE a = ? AND b = ?",
+            Params::Positional(vec![ParamValue::Int(1)]),
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT * FROM t WHERE a = $3",
+            Params::Positional(vec![ParamValue::Int(1)]),
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT * FROM t WHERE a = ? AND b = $1",
+            Params::Positional(vec![ParamValue::Int(1), ParamValue::Int(2)]),
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT * FROM t WHERE a = ?",
+            named(vec![("$1", ParamValue::Int(1))]),
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT * FROM t WHERE a = $1 AND b = $2",
+            named(vec![("$1", ParamValue::Int(1))]),
+            Dialect::PostgreSQL,
+        ),
+        (
+            "SELECT * FROM t WHERE a = $1",
+            named(vec![("$1", ParamValue::Int(1)), ("$2", ParamValue::Int(2))]),
+            Dialect::PostgreSQL,
+        ),
+    ];
+
+    for (sql, params, dialect) in cases {
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", sql).unwrap();
+        writeln!(file, "---------- Params ---------").unwrap();
+        writeln!(file, "{:?} {:?}", dialect, params).unwrap();
+        match bind(sql, &params, dialect) {
+            Ok(bound) => {
+                writeln!(file, "---------- Output ---------").unwrap();
+                writeln!(file, "{}", bound).unwrap();
+            }
+            Err(err) => {
+                writeln!(file, "---------- Error ----------").unwrap();
+                writeln!(file, "{}", err).unwrap();
+            }
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
+
+/// The bound floats read back as the same `Float64` values, sign of zero included.
+#[test]
+fn test_bind_float_round_trip() {
+    for value in [
+        1.0,
+        -1.5,
+        1e300,
+        -1e-300,
+        0.1,
+        -0.0,
+        0.0,
+        f64::MAX,
+        f64::NEG_INFINITY,
+    ] {
+        for dialect in [Dialect::PostgreSQL, Dialect::MySQL] {
+            let params = Params::Positional(vec![ParamValue::Float(value)]);
+            let bound = bind("SELECT ?", &params, dialect).unwrap();
+            let tokens = tokenize_sql(&bound).unwrap();
+            let (stmt, _) = parse_sql(&tokens, dialect).unwrap();
+            let Statement::Query(query) = stmt else {
+                unreachable!()
+            };
+            let SetExpr::Select(select) = &query.body else {
+                unreachable!()
+            };
+            let SelectTarget::AliasedExpr { expr, .. } = &select.select_list[0] else {
+                unreachable!()
+            };
+            let Expr::Cast {
+                expr, target_type, ..
+            } = &**expr
+            else {
+                panic!("{bound} is not a cast");
+            };
+            assert_eq!(*target_type, TypeName::Float64, "{bound}");
+            let Expr::Literal {
+                lit: Literal::String(text),
+                ..
+            } = &**expr
+            else {
+                panic!("{bound} is not a cast of a string");
+            };
+            let parsed = text.parse::<f64>().unwrap();
+            assert_eq!(parsed.to_bits(), value.to_bits(), "{bound}");
+        }
+    }
+}
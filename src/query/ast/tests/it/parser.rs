@@ -14,6 +14,8 @@
 
 use std::io::Write;
 
+use common_ast::ast::InsertSource;
+use common_ast::ast::Statement;
 use common_ast::display_parser_error;
 use common_ast::parser::expr::*;
 use common_ast::parser::parse_sql;
@@ -22,10 +24,12 @@ use common_ast::parser::quote::quote_ident;
 use common_ast::parser::quote::unquote_ident;
 use common_ast::parser::token::*;
 use common_ast::parser::tokenize_sql;
+use common_ast::parser::ValuesRowReader;
 use common_ast::rule;
 use common_ast::Backtrace;
 use common_ast::Dialect;
 use common_ast::Input;
+use common_ast::ParseState;
 use common_exception::Result;
 use goldenfile::Mint;
 use nom::Parser;
@@ -34,9 +38,10 @@ macro_rules! run_parser {
     ($file:expr, $parser:expr, $source:expr $(,)*) => {
         let tokens = Tokenizer::new($source).collect::<Result<Vec<_>>>().unwrap();
         let backtrace = Backtrace::new();
+        let state = ParseState::default();
         let parser = $parser;
         let mut parser = rule! { #parser ~ &EOI };
-        match parser.parse(Input(&tokens, Dialect::PostgreSQL, &backtrace)) {
+        match parser.parse(Input(&tokens, Dialect::PostgreSQL, &backtrace, &state)) {
             Ok((i, (output, _))) => {
                 assert_eq!(i[0].kind, TokenKind::EOI);
                 writeln!($file, "---------- Input ----------").unwrap();
@@ -171,6 +176,12 @@ fn test_statement() {
         r#"CREATE STAGE ~"#,
         r#"CREATE STAGE IF NOT EXISTS test_stage url='s3://load/files/' credentials=(aws_key_id='1a2b3c' aws_secret_key='4x5y6z') file_format=(type = CSV compression = GZIP record_delimiter=',')"#,
         r#"CREATE STAGE IF NOT EXISTS test_stage url='azblob://load/files/' connection=(account_name='1a2b3c' account_key='4x5y6z') file_format=(type = CSV compression = GZIP record_delimiter=',')"#,
+        r#"CREATE STAGE IF NOT EXISTS test_stage url='gcs://load/files/' connection=(credential='c2VjcmV0')"#,
+        r#"CREATE STAGE IF NOT EXISTS test_stage url='oss://load/files/' connection=(endpoint_url='https://oss-cn-beijing.aliyuncs.com' access_key_id='1a2b3c' access_key_secret='4x5y6z')"#,
+        r#"CREATE STAGE IF NOT EXISTS test_stage url='hdfs://load/files/' connection=(name_node='hdfs://127.0.0.1:9000')"#,
+        r#"COPY INTO t1 FROM 's3://load/files/' CONNECTION = (allow_anonymous='true')"#,
+        r#"SELECT * FROM 'oss://load/files/' (ENDPOINT_URL => 'https://oss-cn-beijing.aliyuncs.com')"#,
+        r#"SELECT * FROM 's3://load/files/' (ALLOW_ANONYMOUS => 'true', PATTERN => '.*[.]csv')"#,
         r#"DROP STAGE abc"#,
         r#"DROP STAGE ~"#,
         r#"list @stage_a;"#,
@@ -443,6 +454,17 @@ fn test_statement() {
         r#"SELECT c1 FROM 's3://test/bucket' (ENDPOINT_URL => 'xxx', PATTERN => '*.parquet') t;"#,
         r#"CREATE FILE FORMAT my_csv
             type = CSV field_delimiter = ',' record_delimiter = '\n' skip_header = 1;"#,
+        r#"CREATE FILE FORMAT IF NOT EXISTS my_orc TYPE = ORC"#,
+        r#"CREATE STAGE my_stage FILE_FORMAT = (TYPE = XML, ROW_TAG = 'item', COMPRESSION = AUTO)"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (FORMAT_NAME = 'my_csv')"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (SKIP_HEADER = 1, FIELD_DELIMITER = '|')"#,
+        r#"CREATE STAGE my_stage FILE_FORMAT = ()"#,
+        r#"COPY INTO t1 FROM @my_stage VALIDATION_MODE = RETURN_10_ROWS ON_ERROR = SKIP_FILE_10%"#,
+        r#"COPY INTO t1 FROM @my_stage VALIDATION_MODE = 'return_all_errors' ON_ERROR = 'skip_file_3'"#,
+        r#"CREATE STAGE my_stage ON_ERROR = continue VALIDATION_MODE = RETURN_ERRORS"#,
+        r#"COPY INTO t1 (a, b) FROM (SELECT $1, $3::INT FROM @my_stage/path t) FILES = ('f1.csv', 'f2.csv') FILE_FORMAT = (TYPE = CSV)"#,
+        r#"COPY INTO t1 FROM (SELECT a, b FROM t2)"#,
+        r#"COPY INTO @my_stage/unload FROM (SELECT * FROM t1) PARTITION BY 'date=' || to_string(d) FILE_FORMAT = (TYPE = PARQUET) MAX_FILE_SIZE = 1000000 HEADER = true INCLUDE_QUERY_ID = true"#,
         r#"SHOW FILE FORMATS"#,
         r#"DROP FILE FORMAT my_csv"#,
         r#"SELECT * FROM t GROUP BY GROUPING SETS (a, b, c, d)"#,
@@ -460,6 +482,8 @@ fn test_statement() {
         r#"REFRESH VIRTUAL COLUMN FOR t"#,
         r#"CREATE NETWORK POLICY mypolicy ALLOWED_IP_LIST=('192.168.10.0/24') BLOCKED_IP_LIST=('192.168.10.99') COMMENT='test'"#,
         r#"ALTER NETWORK POLICY mypolicy SET ALLOWED_IP_LIST=('192.168.10.0/24','192.168.255.1') BLOCKED_IP_LIST=('192.168.1.99') COMMENT='test'"#,
+        r#"CREATE FUNCTION f (INT) RETURNS INT LANGUAGE python HANDLER = 'it''s' ADDRESS = 'http://0.0.0.0:8815/?a=''b'''"#,
+        r#"CREATE FUNCTION f AS (a) -> a DESC = 'it''s'"#,
         "--各环节转各环节转各环节转各环节转各\n  select 34343",
         "-- 96477300355	31379974136	3.074486292973661\nselect 34343",
         "-- xxxxx\n  select 34343;",
@@ -516,6 +540,13 @@ fn test_statement_error() {
         r#"REVOKE SELECT, CREATE ON * TO 'test-grant';"#,
         r#"COPY INTO mytable FROM 's3://bucket' CREDENTIAL = ();"#,
         r#"COPY INTO mytable FROM @mystage CREDENTIALS = ();"#,
+        r#"COPY INTO mytable FROM 's3://bucket' CONNECTION = (aws_key_id='1a2b3c' account_key='4x5y6z');"#,
+        r#"COPY INTO mytable FROM 's3://bucket' CONNECTION = (aws_key_id='1a2b3c');"#,
+        r#"CREATE STAGE test_stage url='azblob://load/files/' connection=(account_name='1a2b3c' aws_key_id='4x5y6z')"#,
+        r#"CREATE STAGE test_stage url='oss://load/files/' connection=(access_key_id='1a2b3c' access_key_secret='4x5y6z')"#,
+        r#"CREATE STAGE test_stage url='ftp://load/files/'"#,
+        r#"SELECT * FROM 'oss://load/files/'"#,
+        r#"SELECT * FROM 's3://load/files/' (ENDPOINT_URL => 'https://s3.amazonaws.com', NAME_NODE => 'hdfs://127.0.0.1:9000')"#,
         r#"CALL system$test"#,
         r#"CALL system$test(a"#,
         r#"show settings ilike 'enable%'"#,
@@ -539,6 +570,21 @@ fn test_statement_error() {
         r#"copy into t1 from "" FILE_FORMAT = (TYPE ="#,
         r#"copy into t1 from "" FILE_FORMAT = (TYPE ="#,
         r#"COPY INTO t1 FROM "" PATTERN = '.*[.]csv' FILE_FORMAT = (type = TSV field_delimiter = '\t' skip_headerx = 0);"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (TYPE = CSV, FIELD_DELIMTER = ',')"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (TYPE = CSV, FIELD_DELIMITER = '||')"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (TYPE = NDJSON, QUOTE = '"')"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (TYPE = CSV, COMPRESSION = LZ4)"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (TYPE = CSV, QUOTE = '"', QUOTE = '`')"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (TYPE = CSV, SKIP_HEADER = 1"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (TYPE = CSV 1)"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (SKIP_HEADER = -1)"#,
+        r#"CREATE FILE FORMAT my_csv SKIP_HEADER = 1"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (FORMAT_NAME = 'my_csv', TYPE = CSV)"#,
+        r#"COPY INTO t1 FROM @my_stage ON_ERROR = 'skipfile_10%'"#,
+        r#"COPY INTO t1 FROM @my_stage ON_ERROR = SKIP_FILE_200%"#,
+        r#"COPY INTO t1 FROM @my_stage VALIDATION_MODE = RETURN_ROWS"#,
+        r#"CREATE STAGE my_stage ON_ERROR = skip_file_0"#,
+        r#"COPY INTO t1 FROM @my_stage HEADER = true"#,
     ];
 
     for case in cases {
@@ -714,6 +760,7 @@ fn test_expr() {
         r#"COUNT() OVER (ORDER BY hire_date ROWS 3 PRECEDING)"#,
         r#"ARRAY_APPLY([1,2,3], x -> x + 1)"#,
         r#"ARRAY_FILTER(col, y -> y % 2 = 0)"#,
+        r#"a = ? AND b IN (?, ?)"#,
     ];
 
     for case in cases {
@@ -767,3 +814,71 @@ fn test_quote() {
         assert_eq!(unquoted, *input, "unquote({}) got {}", quoted, unquoted);
     }
 }
+
+#[test]
+fn test_values_rows() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("values.txt").unwrap();
+
+    let cases = &[
+        r#"(1, 'a'), (2, 'b')"#,
+        r#"(1, 2);   "#,
+        r#"(?, 1 + 2, [1, (2)]), (now(), ?),
+           (-1, {'k': 'v'}, (1, 2));"#,
+        r#"(1, 2) (3, 4)"#,
+        r#"(1, 2),
+           (3, 4 +),
+           (5, 6)"#,
+        r#"(1, 2), (3, (4)"#,
+        r#"1, 2"#,
+    ];
+    // The rows of the statements are read from the source, after `VALUES`.
+    let stmt_cases = &[
+        r#"INSERT INTO t VALUES (1, 'a'), (2, 'b');"#,
+        r#"REPLACE INTO t ON (a) VALUES (1, ?), (2, 'x');"#,
+        r#"INSERT /*+ SET_VAR(timezone = 'UTC') */ INTO t (a, b) VALUES (1, (2))"#,
+    ];
+
+    let cases = cases
+        .iter()
+        .map(|case| (*case, 0))
+        .chain(stmt_cases.iter().map(|sql| {
+            let tokens = tokenize_sql(sql).unwrap();
+            // The rows are not tokenized.
+            assert_eq!(tokens[tokens.len() - 2].kind, TokenKind::VALUES);
+            let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+            let source = match stmt {
+                Statement::Insert(insert) => insert.source,
+                Statement::Replace(replace) => replace.source,
+                _ => unreachable!(),
+            };
+            let InsertSource::Values { start } = source else {
+                unreachable!()
+            };
+            (*sql, start)
+        }));
+    for (case, start) in cases {
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", case).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        for row in ValuesRowReader::new(case, start, Dialect::PostgreSQL) {
+            match row {
+                Ok(row) => {
+                    let values = row
+                        .values
+                        .iter()
+                        .map(|v| match v {
+                            Some(expr) => expr.to_string(),
+                            None => "?".to_string(),
+                        })
+                        .collect::<Vec<_>>();
+                    writeln!(file, "{} {:?}: ({})", row.row, row.span, values.join(", ")).unwrap();
+                }
+                Err(err) => {
+                    writeln!(file, "{}", err.message().trim_end()).unwrap();
+                }
+            }
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
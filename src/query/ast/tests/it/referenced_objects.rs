@@ -0,0 +1,62 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::referenced_objects;
+use common_ast::Dialect;
+use goldenfile::Mint;
+
+#[test]
+fn test_referenced_objects() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("referenced-objects.txt").unwrap();
+
+    let cases = &[
+        r#"SELECT a, sum(b) FROM db.t1 JOIN t2 ON t1.id = t2.id WHERE c IN (SELECT c FROM t3) GROUP BY a"#,
+        r#"WITH t1 AS (SELECT * FROM t1), t2 AS (SELECT * FROM t1) SELECT * FROM t2, db.t1"#,
+        r#"WITH RECURSIVE r AS (SELECT 1 AS n UNION ALL SELECT n + 1 FROM r) SELECT * FROM r"#,
+        r#"WITH t AS (SELECT * FROM s) SELECT * FROM T, "T""#,
+        r#"SELECT a FROM t GROUP BY CUBE ((SELECT x FROM secret))"#,
+        r#"SELECT a FROM t GROUP BY ROLLUP ((SELECT x FROM secret))"#,
+        r#"SELECT (WITH c AS (SELECT 1) SELECT * FROM c), array_transform(arr, x -> x + 1) FROM c"#,
+        r#"SELECT $1 FROM @my_stage/path/ t, generate_series(1, 10)"#,
+        r#"INSERT INTO db.t1 (a, b) SELECT a, b FROM t2"#,
+        r#"INSERT INTO t1 FILE_FORMAT = (FORMAT_NAME = 'my_csv') ON_ERROR = continue"#,
+        r#"MERGE INTO t1 USING (SELECT * FROM t2) ON t1.id = t2.id WHEN MATCHED THEN UPDATE SET t1.a = t2.a WHEN NOT MATCHED THEN INSERT (id, a) VALUES (t2.id, t2.a)"#,
+        r#"UPDATE t1 SET a = a + 1 WHERE b = (SELECT max(b) FROM t2)"#,
+        r#"DELETE FROM t1 WHERE a > 1"#,
+        r#"COPY INTO t1 FROM @my_stage FILE_FORMAT = (FORMAT_NAME = 'my_csv')"#,
+        r#"COPY INTO @my_stage/unload/ FROM (SELECT * FROM t1)"#,
+        r#"CREATE TABLE t3 AS SELECT * FROM t1"#,
+        r#"ALTER TABLE db.t1 RENAME COLUMN a TO b"#,
+        r#"CREATE VIEW v1 AS SELECT a FROM t1"#,
+        r#"GRANT SELECT ON db.t1 TO ROLE analyst"#,
+        r#"CREATE FUNCTION add_one AS (x) -> x + 1"#,
+        r#"REMOVE @my_stage/path/"#,
+        r#"EXPLAIN SELECT * FROM t1"#,
+    ];
+
+    for case in cases {
+        let tokens = tokenize_sql(case).unwrap();
+        let (stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", case).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        write!(file, "{}", referenced_objects(&stmt)).unwrap();
+        writeln!(file, "\n").unwrap();
+    }
+}
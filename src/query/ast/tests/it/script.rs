@@ -0,0 +1,57 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::parser::split_statements;
+use common_ast::ParseOptions;
+use goldenfile::Mint;
+
+#[test]
+fn test_split_statements() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("script.txt").unwrap();
+
+    let options = ParseOptions::default();
+    let cases = vec![
+        "",
+        " ; ;",
+        "SELECT 1; SELECT 2;",
+        "SELECT 1;\n\nSELECT a -- the column\nFROM t",
+        "SELECT ';' FROM t; INSERT INTO t VALUES (1, ';')",
+        "SELECT 1;\nSELECT * FORM t;\nSELECT 3",
+        "SELECT 1; SELECT 'unterminated; SELECT 3;\n",
+    ];
+
+    for script in cases {
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", script).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        for statement in split_statements(script) {
+            writeln!(
+                file,
+                "{:?} {:?} (comments: {})",
+                statement.span,
+                &script[statement.span.clone()],
+                statement.has_comments
+            )
+            .unwrap();
+            if let Err(mut diag) = statement.parse(script, &options) {
+                diag.shift(statement.span.start);
+                writeln!(file, "{}", diag.render_text(script)).unwrap();
+            }
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
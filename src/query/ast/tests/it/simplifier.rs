@@ -0,0 +1,114 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_ast::parser::parse_expr;
+use common_ast::parser::parse_sql;
+use common_ast::parser::tokenize_sql;
+use common_ast::simplify_expr;
+use common_ast::Dialect;
+use common_ast::NormalForm;
+use common_ast::Simplifier;
+use common_ast::VisitorMut;
+use goldenfile::Mint;
+
+#[test]
+fn test_simplifier() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("simplifier.txt").unwrap();
+
+    let cases = &[
+        r#"1 + 2 > x AND true"#,
+        r#"1 + 2 * 3 - 10"#,
+        r#"- -5 + +1"#,
+        r#"7 DIV 2 + 7 % 3 + 7 // 0"#,
+        r#"1.50 + 1"#,
+        r#"0.1 * 0.25 - 1"#,
+        r#"1.5 = 1.50 AND 2 > 1.99"#,
+        r#"18446744073709551615 + 1"#,
+        r#"1 / 2"#,
+        r#"'abc' || 'def' = 'abcdef'"#,
+        r#"'a' < 'b' OR x"#,
+        r#"NULL + 1 > x"#,
+        r#"x AND NULL AND FALSE"#,
+        r#"NULL OR x OR TRUE"#,
+        r#"NOT NOT (a > 1)"#,
+        r#"NOT (a > 1 AND b LIKE 'x%')"#,
+        r#"NOT (a IS NULL OR b IN (1, 2) OR NOT c)"#,
+        r#"NOT EXISTS (SELECT 1) AND NOT a = ANY (SELECT b FROM t)"#,
+        r#"a BETWEEN 1 AND 1 + 9"#,
+        r#"a NOT BETWEEN 1 AND 10"#,
+        r#"5 BETWEEN 1 AND 10"#,
+        r#"rand() BETWEEN 0.1 AND 0.5"#,
+        r#"a IN (1, 2, 1) OR a = 3 OR a IN (4)"#,
+        r#"a NOT IN (1) AND a <> 2"#,
+        r#"a IN (rand(), rand())"#,
+        r#"rand() IN (1, 2) OR rand() = 3"#,
+        r#"2 IN (1, 2) AND 3 NOT IN (1, NULL)"#,
+        r#"NULL IS NULL AND 1 IS NOT NULL AND 1 IS DISTINCT FROM NULL"#,
+        r#"TRUE XOR FALSE"#,
+    ];
+
+    for case in cases {
+        let tokens = tokenize_sql(case).unwrap();
+        let mut expr = parse_expr(&tokens, Dialect::PostgreSQL).unwrap();
+        simplify_expr(&mut expr);
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", case).unwrap();
+        writeln!(file, "---------- Output ---------").unwrap();
+        writeln!(file, "{}", expr).unwrap();
+        writeln!(file, "\n").unwrap();
+    }
+}
+
+#[test]
+fn test_simplifier_normal_form() {
+    let mut mint = Mint::new("tests/it/testdata");
+    let mut file = mint.new_goldenfile("simplifier-normal-form.txt").unwrap();
+
+    let cases = &[
+        r#"a OR (b AND c)"#,
+        r#"(a AND b) OR (c AND d)"#,
+        r#"NOT (a OR b) OR c"#,
+        r#"(a OR b) AND (b OR a) AND TRUE"#,
+    ];
+
+    for case in cases {
+        writeln!(file, "---------- Input ----------").unwrap();
+        writeln!(file, "{}", case).unwrap();
+        for normal_form in [NormalForm::Conjunctive, NormalForm::Disjunctive] {
+            let tokens = tokenize_sql(case).unwrap();
+            let mut expr = parse_expr(&tokens, Dialect::PostgreSQL).unwrap();
+            Simplifier::new()
+                .with_normal_form(normal_form)
+                .visit_expr(&mut expr);
+            writeln!(file, "---------- {:?} ----------", normal_form).unwrap();
+            writeln!(file, "{}", expr).unwrap();
+        }
+        writeln!(file, "\n").unwrap();
+    }
+}
+
+#[test]
+fn test_simplifier_statement() {
+    let sql = "SELECT 1 + 1, 1 + 1 AS two FROM t WHERE NOT (a < 2 * 5) AND TRUE";
+    let tokens = tokenize_sql(sql).unwrap();
+    let (mut stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL).unwrap();
+    Simplifier::new().visit_statement(&mut stmt);
+    assert_eq!(
+        stmt.to_string(),
+        "SELECT (1 + 1), 2 AS two FROM t WHERE (a >= 10)"
+    );
+}